@@ -1,8 +1,10 @@
-use std::path::PathBuf;
-use std::sync::mpsc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 
 use eframe::egui;
-use tv_core::{MappedFile, ByteHistogram};
+use tv_core::{FileSource, MappedFile, ByteAnomaly, ByteHistogram, xy2d};
+use tv_ui::file_dialog::{self, RememberDirectory};
 use tv_ui::{
     AppState, HexPanel, MinimapPanel, PerfState, PerfWindow,
     FileInfoWindow, SearchWindow, SignaturesWindow,
@@ -13,15 +15,31 @@ use tv_ui::{
     XRefsState, XRefsWindow,
     BookmarksState, BookmarksWindow,
     ScriptState, ScriptWindow,
+    ThemeWindow,
+    SectionsWindow, StructureWindow, TextViewState, TextViewWindow, XorState, XorWindow,
+    BundleArtifact, BundleState, BundleWindow,
+    Command, CommandPalette, CommandPaletteState, KeyBindings, PaletteEntry,
+    ShortcutsState, ShortcutsWindow, NotesWindow,
+    OverviewAction, OverviewFile, OverviewWindow,
     WorkspaceManager,
+    command_palette::{self, DIFF_REGION_SHORTCUTS, ESCAPE_SHORTCUT, PALETTE_SHORTCUT, WORKSPACE_SHORTCUTS},
     session::{Session, SESSION_EXTENSION},
+    state::{FileTab, GpuJob, SearchBackend},
 };
 
 fn main() -> eframe::Result<()> {
     env_logger::init();
 
-    // CLI argument: open file or session directly
-    let initial_file: Option<PathBuf> = std::env::args_os().nth(1).map(PathBuf::from);
+    // CLI arguments: open file or session directly, `--read-only` locks edit mode off
+    let mut read_only = false;
+    let mut initial_file: Option<PathBuf> = None;
+    for arg in std::env::args_os().skip(1) {
+        if arg == "--read-only" {
+            read_only = true;
+        } else if initial_file.is_none() {
+            initial_file = Some(PathBuf::from(arg));
+        }
+    }
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -36,10 +54,14 @@ fn main() -> eframe::Result<()> {
         options,
         Box::new(move |_cc| {
             let mut app = TitanViewApp::default();
+            app.perf.gpu_memory_budget = file_dialog::gpu_memory_budget();
+            tv_gpu::set_memory_budget(app.perf.gpu_memory_budget);
             if let Some(path) = initial_file {
                 // Check if it's a session file
                 if path.extension().and_then(|e| e.to_str()) == Some(SESSION_EXTENSION) {
                     app.load_session(path);
+                } else if read_only {
+                    app.open_file_read_only(path);
                 } else {
                     app.open_file(path);
                 }
@@ -66,16 +88,48 @@ struct ClassifyChunk {
     total_blocks: usize,
 }
 
+/// Failure of a background GPU job, shown in the status bar.
+struct WorkerError {
+    job: GpuJob,
+    /// File the job ran on, named in the message unless it is the active file.
+    path: PathBuf,
+    message: String,
+}
+
+/// Reports failures of one background job to the app (and the log).
+#[derive(Clone)]
+struct ErrorReporter {
+    tx: mpsc::Sender<WorkerError>,
+    job: GpuJob,
+    path: PathBuf,
+}
+
+impl ErrorReporter {
+    fn report(&self, message: impl Into<String>) {
+        let message = message.into();
+        log::error!("{} failed: {}", self.job.label(), message);
+        let _ = self.tx.send(WorkerError { job: self.job, path: self.path.clone(), message });
+    }
+}
+
 /// Result from a pattern search.
 struct SearchResult {
     offsets: Vec<u64>,
+    /// Number of matches (more than `offsets` when the GPU result buffer filled up).
+    total: u64,
     duration_ms: f64,
+    /// Scanner that actually ran.
+    backend: SearchBackend,
+    /// `SearchState::generation` the search was started for.
+    generation: u64,
 }
 
 /// Progressive chunk from deep scan.
 struct DeepScanChunk {
     /// Signatures found in this chunk.
     signatures: Vec<tv_ui::state::SignatureHit>,
+    /// Exact carved size of each signature, when known (parallel to `signatures`).
+    extents: Vec<Option<u64>>,
     /// Progress: bytes scanned so far.
     bytes_scanned: u64,
     /// Total bytes to scan.
@@ -90,6 +144,10 @@ struct DeepScanChunk {
 struct HilbertResult {
     pixels: Vec<u32>,
     duration_ms: f64,
+    /// Why the entropy shown is not the full result, in entropy mode.
+    entropy_warning: Option<String>,
+    /// Whether that entropy was still incomplete, so regenerating later helps.
+    entropy_incomplete: bool,
 }
 
 /// Result from diff computation.
@@ -97,42 +155,352 @@ struct DiffResult {
     offsets: Vec<u64>,
     total_count: u64,
     duration_ms: f64,
+    error: Option<String>,
+}
+
+/// Message from the batch carve thread.
+enum CarveMessage {
+    /// (hits processed, total hits).
+    Progress(usize, usize),
+    /// Manifest path and (carved files, manifest rows), or an error.
+    Done(Result<(PathBuf, usize, usize), String>),
+}
+
+/// Message from the file hashing thread.
+enum HashMessage {
+    /// Fraction of the file hashed.
+    Progress(f32),
+    Done(tv_core::FileDigests),
 }
 
 /// Result from histogram computation.
 struct HistogramResult {
     histogram: ByteHistogram,
+    /// Flagged byte values with their first/last absolute offsets.
+    anomalies: Vec<(ByteAnomaly, Option<(u64, u64)>)>,
+    /// Length of the range the histogram was sampled from, if it was sampled.
+    sampled_from: Option<u64>,
     file_size: u64,
     offset: u64,
 }
 
-/// Convert (x, y) coordinates to Hilbert curve index.
-fn xy2d(n: u32, x: u32, y: u32) -> u64 {
-    let mut rx: u32;
-    let mut ry: u32;
-    let mut d: u64 = 0;
-    let mut s: u32 = n / 2;
-    let mut px = x;
-    let mut py = y;
+/// Chunk size of GPU signature scans (progressive results, bounded memory).
+const SIGNATURE_SCAN_CHUNK: u64 = tv_ui::state::DEFAULT_DEEP_SCAN_CHUNK_MB * 1024 * 1024;
+
+/// Bytes analyzed after a signature to find its carved size for nesting.
+const NESTING_ANALYZE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Bytes searched for the end of each quick-scan hit when the file is opened.
+const QUICK_CARVE_ANALYZE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Scan the `chunk_size` bytes of `file` starting at `offset` for `signatures`.
+///
+/// Only matches starting in the chunk are returned; the scan reads past its end
+/// by the longest pattern, so matches on a boundary are reported exactly once.
+fn scan_signature_chunk(
+    ctx: &tv_gpu::GpuContext,
+    file: &MappedFile,
+    signatures: &[&tv_core::signatures::Signature],
+    offset: u64,
+    chunk_size: u64,
+) -> anyhow::Result<Vec<tv_ui::state::SignatureHit>> {
+    if signatures.is_empty() {
+        return Ok(vec![]);
+    }
+    let patterns: Vec<&[u8]> = signatures.iter().map(|s| s.magic).collect();
+
+    let data = file.slice(tv_core::FileRegion::new(0, file.len()));
+    let region = tv_core::FileRegion::new(offset, chunk_size);
+
+    // Run GPU multi-pattern scan on this chunk
+    let chunk_matches = ctx.scan_multi_pattern_region(data, region, &patterns)
+        .map_err(|e| anyhow::anyhow!("signature scan at offset 0x{:X}: {}", offset, e))?;
+
+    Ok(chunk_matches
+        .into_iter()
+        .map(|m| {
+            let sig = &signatures[m.pattern_idx as usize];
+            tv_ui::state::SignatureHit {
+                offset: m.offset,
+                name: sig.name.to_string(),
+                magic: sig.magic.to_vec(),
+            }
+        })
+        .collect())
+}
+
+/// Exact carved size of the file embedded at a signature hit, if known.
+///
+/// GIF sizes come from the last trailer byte in the analyzed window, which
+/// would swallow everything after the image, so they never act as a parent.
+fn signature_extent(file: &MappedFile, hit: &tv_ui::state::SignatureHit) -> Option<u64> {
+    if hit.name.starts_with("GIF") {
+        return None;
+    }
+    let analyze_len = NESTING_ANALYZE_BYTES.min(file.len().saturating_sub(hit.offset));
+    let data = file.slice(tv_core::FileRegion::new(hit.offset, analyze_len));
+    let carve = tv_core::analyze_carve_size(&hit.name, data, analyze_len);
+    carve.size.filter(|_| carve.size_exact)
+}
+
+/// Carve estimate (extension, embedded size) of a quick-scan hit.
+fn quick_scan_carve(file: &MappedFile, hit: &tv_ui::state::SignatureHit) -> tv_core::CarveInfo {
+    let analyze_len = QUICK_CARVE_ANALYZE_BYTES.min(file.len().saturating_sub(hit.offset));
+    let data = file.slice(tv_core::FileRegion::new(hit.offset, analyze_len));
+    tv_core::analyze_carve_size(&hit.name, data, analyze_len)
+}
+
+/// Carve and hash `hits` of the file opened from `source` into `dir` and
+/// write the manifest. Returns the manifest path and (carved files, manifest rows).
+fn run_batch_carve(
+    source: &FileSource,
+    hits: &[(u64, String)],
+    dir: &Path,
+    tx: &mpsc::Sender<CarveMessage>,
+) -> Result<(PathBuf, usize, usize), String> {
+    let file = source.open().map_err(|e| e.to_string())?;
+    let entries = tv_core::carve_and_hash(&file, hits, dir, |done, total| {
+        let _ = tx.send(CarveMessage::Progress(done, total));
+    }).map_err(|e| e.to_string())?;
+
+    let manifest = dir.join(tv_core::carve::MANIFEST_FILE_NAME);
+    std::fs::write(&manifest, tv_core::manifest_csv(&entries))
+        .map_err(|e| format!("failed to write {}: {}", manifest.display(), e))?;
+    let carved = entries.iter().filter(|e| e.output.is_some()).count();
+    Ok((manifest, carved, entries.len()))
+}
+
+/// Scan a whole file for all known signatures, sorted by offset.
+fn scan_all_signatures(ctx: &tv_gpu::GpuContext, file: &MappedFile) -> anyhow::Result<Vec<tv_ui::state::SignatureHit>> {
+    let signatures: Vec<_> = tv_core::signatures::SIGNATURES.iter().collect();
+    let mut hits = Vec::new();
+    let mut offset = 0;
+    while offset < file.len() {
+        hits.extend(scan_signature_chunk(ctx, file, &signatures, offset, SIGNATURE_SCAN_CHUNK)?);
+        offset += SIGNATURE_SCAN_CHUNK;
+    }
+    hits.sort_by_key(|h| h.offset);
+    Ok(hits)
+}
+
+/// One progress readout for the running operations: the least advanced one
+/// gates the bar, and the label names all of them.
+fn combined_progress(ops: &[(&str, f32)]) -> Option<(String, f32)> {
+    let fraction = ops.iter().map(|&(_, f)| f).min_by(f32::total_cmp)?;
+    let label = ops.iter().map(|&(name, _)| name).collect::<Vec<_>>().join(" + ");
+    Some((label, fraction.clamp(0.0, 1.0)))
+}
+
+/// Fraction of `total_blocks` covered up to the end of the chunk at
+/// `start_block`.
+fn chunk_progress(start_block: usize, len: usize, total_blocks: usize) -> f32 {
+    ((start_block + len) as f32 / total_blocks.max(1) as f32).min(1.0)
+}
+
+/// Bytes sent to the GPU per entropy / classification dispatch (~64 MB:
+/// larger chunks mean fewer dispatches, smaller ones finer progress).
+const ANALYSIS_CHUNK_BYTES: u64 = 64 * 1024 * 1024;
+
+/// One chunk of blocks handed to the analysis shaders.
+struct AnalysisChunk<'a> {
+    /// First block covered by the chunk.
+    start_block: usize,
+    /// Bytes of every block, or of one block per stride when sampling.
+    data: &'a [u8],
+    stride: usize,
+    total_blocks: usize,
+}
 
-    while s > 0 {
-        rx = if (px & s) > 0 { 1 } else { 0 };
-        ry = if (py & s) > 0 { 1 } else { 0 };
-        d += (s as u64) * (s as u64) * (((3 * rx) ^ ry) as u64);
+impl AnalysisChunk<'_> {
+    /// Per-block values for the whole chunk from the values of `data`.
+    fn expand<T: Copy>(&self, values: Vec<T>) -> Vec<T> {
+        if self.stride > 1 {
+            tv_ui::state::expand_samples(&values, self.stride, self.start_block, self.total_blocks)
+        } else {
+            values
+        }
+    }
+}
 
-        // Rotate quadrant
-        if ry == 0 {
-            if rx == 1 {
-                px = s - 1 - px;
-                py = s - 1 - py;
+/// Walk `file` in analysis chunks of `block_size`-byte blocks, gathering one
+/// block in `stride` when sampling, until `dispatch` returns false. Returns
+/// the number of blocks covered.
+fn for_each_analysis_chunk(
+    file: &MappedFile,
+    block_size: u64,
+    stride: usize,
+    mut dispatch: impl FnMut(&AnalysisChunk) -> bool,
+) -> usize {
+    let file_len = file.len();
+    let total_blocks = file_len.div_ceil(block_size) as usize;
+    // Sampled mode gathers one block per stride into a buffer of the same size
+    let samples_per_chunk = (ANALYSIS_CHUNK_BYTES / block_size) as usize;
+    let mut sample_buf = Vec::new();
+    let mut block_offset: usize = 0;
+
+    while block_offset < total_blocks {
+        let offset = block_offset as u64 * block_size;
+        let (data, blocks) = if stride <= 1 {
+            let chunk_len = ANALYSIS_CHUNK_BYTES.min(file_len - offset);
+            (file.slice(tv_core::FileRegion::new(offset, chunk_len)), chunk_len.div_ceil(block_size) as usize)
+        } else {
+            let chunk_blocks = (samples_per_chunk * stride).min(total_blocks - block_offset);
+            sample_buf.clear();
+            for block in (block_offset..block_offset + chunk_blocks).step_by(stride) {
+                let block_start = block as u64 * block_size;
+                let len = block_size.min(file_len - block_start);
+                sample_buf.extend_from_slice(file.slice(tv_core::FileRegion::new(block_start, len)));
             }
-            std::mem::swap(&mut px, &mut py);
+            (&sample_buf[..], chunk_blocks)
+        };
+
+        let chunk = AnalysisChunk { start_block: block_offset, data, stride, total_blocks };
+        if !dispatch(&chunk) {
+            break;
+        }
+        block_offset += blocks;
+    }
+    block_offset
+}
+
+/// Size of the minimap image in an analysis bundle (width, height).
+const BUNDLE_MINIMAP_SIZE: (u32, u32) = (64, 1024);
+
+/// Ask for a destination and write an exported text file.
+fn export_text(text: &str, file_name: &str, filter: &str, extensions: &[&str]) {
+    if let Some(path) = file_dialog::new()
+        .set_file_name(file_name)
+        .add_filter(filter, extensions)
+        .save_file_remembered()
+    {
+        if let Err(e) = std::fs::write(&path, text) {
+            log::error!("Export failed: {}", e);
+        } else {
+            log::info!("Exported {} to {}", file_name, path.display());
         }
+    }
+}
+
+/// Merge an entropy chunk into the per-block entropy. Returns true if any block was written.
+fn accumulate_entropy(entropy: &mut Option<Vec<f32>>, chunk: &EntropyChunk) -> bool {
+    tv_ui::state::merge_block_chunk(
+        entropy.get_or_insert_with(Vec::new),
+        chunk.start_block,
+        &chunk.values,
+        chunk.total_blocks,
+        tv_ui::state::PENDING_ENTROPY,
+    )
+}
+
+/// Merge a classification chunk into the per-block classes. Returns true if any block was written.
+fn accumulate_classification(classification: &mut Option<Vec<u8>>, chunk: &ClassifyChunk) -> bool {
+    tv_ui::state::merge_block_chunk(
+        classification.get_or_insert_with(Vec::new),
+        chunk.start_block,
+        &chunk.values,
+        chunk.total_blocks,
+        tv_ui::state::PENDING_CLASS,
+    )
+}
+
+/// Average entropy of a finished pass, `None` for an empty file.
+fn entropy_stats(entropy: &[f32]) -> Option<tv_ui::state::EntropyStats> {
+    if entropy.is_empty() {
+        return None;
+    }
+    let sum: f32 = entropy.iter().sum();
+    Some(tv_ui::state::EntropyStats { avg: sum / entropy.len() as f32, block_count: entropy.len() })
+}
+
+/// Number of blocks per class of a finished pass (unknown ids count as high entropy).
+fn class_counts(classification: &[u8]) -> [u32; 5] {
+    let mut counts = [0u32; 5];
+    for &c in classification {
+        counts[(c as usize).min(4)] += 1;
+    }
+    counts
+}
+
+/// Merge a deep scan chunk into the scan state. On the final chunk the scan is
+/// finished (nesting and filtered view built); returns true then.
+fn accumulate_deep_scan(scan: &mut tv_ui::state::DeepScanState, chunk: DeepScanChunk) -> bool {
+    scan.bytes_scanned = scan.bytes_scanned.max(chunk.bytes_scanned);
+    scan.total_bytes = chunk.total_bytes;
+
+    if !chunk.signatures.is_empty() {
+        let results = scan.results.get_or_insert_with(Vec::new);
+        // Keep extents parallel to results even if a chunk sends too few or too many
+        scan.extents.resize(results.len(), None);
+        let mut extents = chunk.extents;
+        extents.resize(chunk.signatures.len(), None);
+        results.extend(chunk.signatures);
+        scan.extents.extend(extents);
+    }
 
-        s /= 2;
+    if chunk.is_final {
+        scan.bytes_scanned = scan.total_bytes;
+        scan.duration_ms = chunk.duration_ms;
+        scan.scanning = false;
+        scan.compute_nesting();
+        scan.rebuild_filtered_indices();
     }
+    chunk.is_final
+}
+
+/// Background jobs of a file tab, kept running while the tab is parked.
+struct TabJobs {
+    entropy_rx: Option<mpsc::Receiver<EntropyChunk>>,
+    computing_entropy: bool,
+    entropy_progress: f32,
+    classify_rx: Option<mpsc::Receiver<ClassifyChunk>>,
+    computing_classification: bool,
+    classify_progress: f32,
+    search_rx: Option<mpsc::Receiver<SearchResult>>,
+    deep_scan_rx: Option<mpsc::Receiver<DeepScanChunk>>,
+    carve_rx: Option<mpsc::Receiver<CarveMessage>>,
+    file_histogram_rx: Option<mpsc::Receiver<ByteHistogram>>,
+    file_hash_rx: Option<mpsc::Receiver<HashMessage>>,
+}
+
+/// An open file that is not in the active tab.
+struct ParkedTab {
+    tab: FileTab,
+    jobs: TabJobs,
+}
+
+/// What a close confirmation closes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CloseRequest {
+    /// The tab at this index.
+    Tab(usize),
+    /// Every tab, back to the landing page.
+    Session,
+}
 
-    d
+/// Entropy of each cell of a Hilbert map with `total_pixels` cells, in curve
+/// order. Cell `d` covers bytes `d * bpp..(d + 1) * bpp` with
+/// `bpp = file_len / total_pixels` (at least 1), as in the shader, and gets
+/// the mean of the `block_size` entropy blocks it overlaps, weighted by
+/// overlap. Cells past the end of `entropy` get 0.
+fn hilbert_cell_entropy(entropy: &[f32], block_size: u64, file_len: u64, total_pixels: u64) -> Vec<f32> {
+    let block_size = block_size.max(1);
+    let bytes_per_pixel = (file_len / total_pixels.max(1)).max(1);
+    let cells = (file_len / bytes_per_pixel).min(total_pixels);
+    (0..cells)
+        .map(|cell| {
+            let start = cell * bytes_per_pixel;
+            let end = start + bytes_per_pixel;
+            let mut sum = 0.0f64;
+            let mut block = start / block_size;
+            while block * block_size < end {
+                let Some(&value) = entropy.get(block as usize) else { break };
+                let overlap = end.min((block + 1) * block_size) - start.max(block * block_size);
+                sum += value as f64 * overlap as f64;
+                block += 1;
+            }
+            (sum / bytes_per_pixel as f64) as f32
+        })
+        .collect()
 }
 
 struct TitanViewApp {
@@ -141,18 +509,38 @@ struct TitanViewApp {
     entropy_rx: Option<mpsc::Receiver<EntropyChunk>>,
     /// Whether entropy computation is in progress.
     computing_entropy: bool,
+    /// Fraction of blocks with entropy received so far.
+    entropy_progress: f32,
     /// Receiver for progressive classification results from GPU thread.
     classify_rx: Option<mpsc::Receiver<ClassifyChunk>>,
     /// Whether classification computation is in progress.
     computing_classification: bool,
+    /// Fraction of blocks classified so far.
+    classify_progress: f32,
     /// Receiver for search results from GPU thread.
     search_rx: Option<mpsc::Receiver<SearchResult>>,
     /// Receiver for progressive deep scan chunks from GPU thread.
     deep_scan_rx: Option<mpsc::Receiver<DeepScanChunk>>,
+    /// Receiver for batch carve progress.
+    carve_rx: Option<mpsc::Receiver<CarveMessage>>,
     /// Pending file from drag & drop (processed next frame).
     pending_drop: Option<PathBuf>,
+    /// Other files of a multi-file drop, offered as tabs or diff file B.
+    dropped_extra: Vec<PathBuf>,
+    /// Last closed file, offered as diff file B.
+    previous_file: Option<PathBuf>,
+    /// Diff file B chooser visible (F6 with open tabs or a previous file).
+    choosing_diff_b: bool,
     /// Performance monitoring state.
     perf: PerfState,
+    /// Receiver for the GPU limits probe (started when the Performance window opens).
+    gpu_limits_rx: Option<mpsc::Receiver<Result<tv_ui::GpuLimitsInfo, String>>>,
+    /// Failures of background GPU jobs (senders are cloned into the workers).
+    worker_error_tx: mpsc::Sender<WorkerError>,
+    worker_error_rx: mpsc::Receiver<WorkerError>,
+    /// Cloned into each GPU worker: more than one reference means a job
+    /// still holds the GPU, even one whose tab is in the background.
+    gpu_workers: Arc<()>,
     // --- Floating window visibility ---
     /// File Info window visible (F1).
     show_file_info: bool,
@@ -174,6 +562,22 @@ struct TitanViewApp {
     show_bookmarks: bool,
     /// Minimap panel visible.
     show_minimap: bool,
+    /// Theme editor visible.
+    show_theme: bool,
+    /// Keyboard shortcuts window visible (F12).
+    show_shortcuts: bool,
+    /// Notes scratchpad visible.
+    show_notes: bool,
+    /// Multi-file overview (minimaps of all tabs) visible.
+    show_overview: bool,
+    /// XOR brute-force window visible.
+    show_xor: bool,
+    /// Text view window visible.
+    show_text_view: bool,
+    /// Section layout window visible.
+    show_sections: bool,
+    /// PNG / ZIP structure window visible.
+    show_structure: bool,
     /// Hilbert visualization state.
     hilbert: HilbertState,
     /// Disassembly state.
@@ -182,6 +586,12 @@ struct TitanViewApp {
     inspector: InspectorState,
     /// Histogram state.
     histogram: HistogramState,
+    /// XOR brute-force state.
+    xor: XorState,
+    /// Text view state.
+    text_view: TextViewState,
+    /// Export bundle window state.
+    bundle: BundleState,
     /// XRefs state.
     xrefs: XRefsState,
     /// Bookmarks state.
@@ -192,10 +602,20 @@ struct TitanViewApp {
     show_script: bool,
     /// Receiver for diff computation results.
     diff_rx: Option<mpsc::Receiver<DiffResult>>,
+    /// Receiver for the signature comparison between diff files A and B.
+    signature_diff_rx: Option<mpsc::Receiver<tv_ui::state::SignatureDiff>>,
+    /// Receiver for the entropy of diff file B (entropy comparison).
+    entropy_diff_rx: Option<mpsc::Receiver<Vec<f32>>>,
     /// Receiver for Hilbert texture computation.
     hilbert_rx: Option<mpsc::Receiver<HilbertResult>>,
     /// Receiver for histogram computation.
     histogram_rx: Option<mpsc::Receiver<HistogramResult>>,
+    /// Receiver for the full-file byte histogram shown in File Info.
+    file_histogram_rx: Option<mpsc::Receiver<ByteHistogram>>,
+    /// Receiver for the digests of the file shown in File Info.
+    file_hash_rx: Option<mpsc::Receiver<HashMessage>>,
+    /// Receiver for minimap checksum strips: (is file B, path, block CRC-32s).
+    checksum_rx: Option<mpsc::Receiver<(bool, PathBuf, Vec<u32>)>>,
     // --- Session management ---
     /// Current session path (if saved/loaded).
     session_path: Option<PathBuf>,
@@ -205,20 +625,44 @@ struct TitanViewApp {
     session_status: Option<(String, bool)>, // (message, is_error)
     /// Workspace manager for contextual analysis environments.
     workspaces: WorkspaceManager,
+    /// Command palette (Ctrl+P).
+    palette: CommandPaletteState,
+    /// Registered shortcuts as rebound by the user.
+    key_bindings: KeyBindings,
+    /// Keyboard shortcuts window state.
+    shortcuts: ShortcutsState,
+    // --- File tabs ---
+    /// Open files in tab order. The active tab's slot is `None`: its state lives in `state`.
+    tabs: Vec<Option<ParkedTab>>,
+    /// Index of the active tab.
+    active_tab: usize,
+    /// Close waiting for the user to confirm discarding unsaved changes.
+    close_confirm: Option<CloseRequest>,
 }
 
 impl Default for TitanViewApp {
     fn default() -> Self {
+        let (worker_error_tx, worker_error_rx) = mpsc::channel();
         Self {
             state: AppState::default(),
             entropy_rx: None,
             computing_entropy: false,
+            entropy_progress: 0.0,
             classify_rx: None,
             computing_classification: false,
+            classify_progress: 0.0,
             search_rx: None,
             deep_scan_rx: None,
+            carve_rx: None,
             pending_drop: None,
+            dropped_extra: Vec::new(),
+            previous_file: None,
+            choosing_diff_b: false,
             perf: PerfState::default(),
+            gpu_limits_rx: None,
+            worker_error_tx,
+            worker_error_rx,
+            gpu_workers: Arc::new(()),
             // Windows hidden by default, except minimap
             show_file_info: false,
             show_search: false,
@@ -230,21 +674,43 @@ impl Default for TitanViewApp {
             show_xrefs: false,
             show_bookmarks: false,
             show_minimap: true,
+            show_theme: false,
+            show_shortcuts: false,
+            show_notes: false,
+            show_overview: false,
+            show_xor: false,
+            show_text_view: false,
+            show_sections: false,
+            show_structure: false,
             hilbert: HilbertState::default(),
             hilbert_rx: None,
             histogram_rx: None,
+            file_histogram_rx: None,
+            file_hash_rx: None,
+            checksum_rx: None,
             disasm: DisasmState::default(),
             inspector: InspectorState::default(),
             histogram: HistogramState::default(),
+            xor: XorState::default(),
+            text_view: TextViewState::default(),
+            bundle: BundleState::default(),
             xrefs: XRefsState::default(),
             bookmarks: BookmarksState::default(),
             script: ScriptState::new(),
             show_script: false,
             diff_rx: None,
+            signature_diff_rx: None,
+            entropy_diff_rx: None,
             session_path: None,
             session_modified: false,
             session_status: None,
             workspaces: WorkspaceManager::new(),
+            palette: CommandPaletteState::default(),
+            key_bindings: KeyBindings::from_config(&file_dialog::key_bindings()),
+            shortcuts: ShortcutsState::default(),
+            tabs: Vec::new(),
+            active_tab: 0,
+            close_confirm: None,
         }
     }
 }
@@ -287,16 +753,35 @@ impl TitanViewApp {
 
     /// Reset the app to its initial landing page state.
     fn reset_to_landing(&mut self) {
+        if let Some(path) = self.state.file.as_ref().and_then(|f| f.disk_path()) {
+            self.previous_file = Some(path.to_path_buf());
+        }
         // Reset all state to defaults
+        self.state.file_hash_cancel.store(true, Ordering::Relaxed);
+        self.state.search.cancel.store(true, Ordering::Relaxed);
+        // Hash lists are shared by all files
+        let hash_db = std::mem::take(&mut self.state.hash_db);
+        let hash_db_status = self.state.hash_db_status.take();
         self.state = AppState::default();
+        self.state.hash_db = hash_db;
+        self.state.hash_db_status = hash_db_status;
         self.entropy_rx = None;
         self.computing_entropy = false;
+        self.file_histogram_rx = None;
+        self.file_hash_rx = None;
+        self.checksum_rx = None;
         self.classify_rx = None;
         self.computing_classification = false;
         self.search_rx = None;
         self.deep_scan_rx = None;
+        self.carve_rx = None;
         self.pending_drop = None;
+        self.dropped_extra.clear();
+        self.choosing_diff_b = false;
+        // Device limits don't change between files
+        let gpu_limits = self.perf.gpu_limits.take();
         self.perf = PerfState::default();
+        self.perf.gpu_limits = gpu_limits;
 
         // Reset window visibility to defaults
         self.show_file_info = false;
@@ -309,6 +794,12 @@ impl TitanViewApp {
         self.show_xrefs = false;
         self.show_bookmarks = false;
         self.show_script = false;
+        self.show_xor = false;
+        self.show_text_view = false;
+        self.show_sections = false;
+        self.show_structure = false;
+        self.show_notes = false;
+        self.show_overview = false;
         self.show_minimap = true;
 
         // Reset analysis state
@@ -317,10 +808,13 @@ impl TitanViewApp {
         self.disasm = DisasmState::default();
         self.inspector = InspectorState::default();
         self.histogram = HistogramState::default();
+        self.xor = XorState::default();
         self.xrefs = XRefsState::default();
         self.bookmarks = BookmarksState::default();
         self.script = ScriptState::new();
         self.diff_rx = None;
+        self.signature_diff_rx = None;
+        self.entropy_diff_rx = None;
 
         // Reset session state
         self.session_path = None;
@@ -330,66 +824,597 @@ impl TitanViewApp {
         // Reset workspace to default
         self.workspaces = WorkspaceManager::new();
 
+        // Close all tabs
+        self.tabs.clear();
+        self.active_tab = 0;
+        self.close_confirm = None;
+
         log::info!("Session closed, returned to landing page");
     }
 }
 
 impl TitanViewApp {
     fn open_file(&mut self, path: PathBuf) {
-        match MappedFile::open(&path) {
-            Ok(mapped) => {
-                let file_len = mapped.len();
-                log::info!("Opened: {} ({} bytes)", path.display(), file_len);
-                self.state.viewport = tv_core::ViewPort::new(0, 4096);
-                self.state.entropy = None;
-                self.state.cached_entropy_stats = None;
-                self.computing_entropy = false;
-                self.entropy_rx = None;
-                self.state.classification = None;
-                self.state.cached_class_counts = None;
-                self.computing_classification = false;
-                self.classify_rx = None;
-                self.state.search = tv_ui::state::SearchState::default();
-                self.search_rx = None;
-                self.state.goto_open = false;
-                self.state.signatures = None;
-                self.state.deep_scan = tv_ui::state::DeepScanState::default();
-                self.deep_scan_rx = None;
-                self.disasm.invalidate();
-                self.hilbert.invalidate();
-                self.histogram.clear();
-                self.xrefs.clear();
-                self.bookmarks.clear();
-                self.state.edit.clear(); // Clear edit mode when opening new file
-                self.state.file = Some(tv_ui::state::LoadedFile { path: path.clone(), mapped });
-
-                // Detect signatures in the first 1 MB (fast CPU scan)
-                if let Some(ref f) = self.state.file {
-                    let scan_len = (1024 * 1024).min(file_len) as u64;
-                    let scan_data = f.mapped.slice(tv_core::FileRegion::new(0, scan_len));
-                    let hits = tv_core::signatures::detect_signatures(scan_data, scan_data.len());
-                    let sig_hits: Vec<tv_ui::state::SignatureHit> = hits.into_iter().map(|h| {
-                        tv_ui::state::SignatureHit {
-                            offset: h.offset,
-                            name: h.name.to_string(),
-                            magic: scan_data[h.offset as usize..(h.offset as usize + h.magic_len).min(scan_data.len())].to_vec(),
+        // Already open in another tab: switch to it
+        let open_in = |t: &Option<ParkedTab>| t.as_ref().is_some_and(|p| p.tab.file.disk_path() == Some(path.as_path()));
+        if let Some(index) = self.tabs.iter().position(open_in) {
+            self.switch_tab(index);
+            return;
+        }
+
+        if let Err(msg) = file_dialog::check_openable(&path) {
+            log::error!("{}", msg);
+            self.session_status = Some((msg, true));
+            return;
+        }
+
+        match tv_ui::state::LoadedFile::open(path.clone()) {
+            Ok(file) => {
+                file_dialog::remember(&path);
+                self.show_loaded_file(file);
+            }
+            Err(e) => {
+                log::error!("Failed to open file: {}", e);
+                self.session_status = Some((format!("Failed to open {}: {}", path.display(), e), true));
+            }
+        }
+    }
+
+    /// Make `file` the active file, keeping the current one open in its own
+    /// tab, and start its analysis.
+    fn show_loaded_file(&mut self, file: tv_ui::state::LoadedFile) {
+        if self.session_status.as_ref().is_some_and(|(_, is_error)| *is_error) {
+            self.session_status = None;
+        }
+        let search_backend = self.state.search.backend;
+
+        // Keep the current file open in its own tab
+        if self.state.file.as_ref().is_some_and(|f| f.path != file.path) {
+            self.tabs[self.active_tab] = self.park_active_tab();
+            self.tabs.push(None);
+            self.active_tab = self.tabs.len() - 1;
+        } else if self.tabs.is_empty() {
+            self.tabs.push(None);
+            self.active_tab = 0;
+        }
+
+        let file_len = file.mapped.len();
+        let disk_path = file.disk_path().map(Path::to_path_buf);
+        log::info!("Opened: {} ({} bytes)", file.path.display(), file_len);
+        self.state.viewport = tv_core::ViewPort::new(0, 4096);
+        self.state.entropy = None;
+        self.state.cached_entropy_stats = None;
+        self.computing_entropy = false;
+        self.entropy_rx = None;
+        self.state.classification = None;
+        self.state.classification_block_size = None;
+        self.state.classification_request = None;
+        self.state.cached_class_counts = None;
+        self.state.byte_histogram = None;
+        self.state.file_digests = None;
+        self.state.file_metadata = disk_path.as_deref().and_then(|path| {
+            tv_core::FileMetadata::read(path).map_err(|e| log::warn!("{:#}", e)).ok()
+        });
+        self.state.nav_back.clear();
+        self.computing_classification = false;
+        self.classify_rx = None;
+        self.state.search.cancel.store(true, Ordering::Relaxed);
+        self.state.search = tv_ui::state::SearchState {
+            backend: search_backend,
+            ..Default::default()
+        };
+        self.search_rx = None;
+        self.state.goto_open = false;
+        self.state.signatures = None;
+        self.state.signature_carves.clear();
+        self.state.sections = None;
+        self.state.structure = None;
+        // Keep the scan options, drop the results
+        self.state.deep_scan = tv_ui::state::DeepScanState {
+            scan_category: self.state.deep_scan.scan_category,
+            scan_chunk_mb: self.state.deep_scan.scan_chunk_mb,
+            ..Default::default()
+        };
+        self.deep_scan_rx = None;
+        self.carve_rx = None;
+        self.disasm.invalidate();
+        self.hilbert.invalidate();
+        self.histogram.clear();
+        self.xor.clear();
+        self.xrefs.clear();
+        self.bookmarks.clear();
+        self.state.edit.clear(); // Clear edit mode when opening new file
+        self.state.edit.read_only = false;
+        self.state.file = Some(file);
+
+        // Detect signatures in the first 1 MB (fast CPU scan)
+        if let Some(ref f) = self.state.file {
+            let scan_len = (1024 * 1024).min(file_len) as u64;
+            let scan_data = f.mapped.slice(tv_core::FileRegion::new(0, scan_len));
+            let hits = tv_core::signatures::detect_signatures(scan_data, scan_data.len());
+            let sig_hits: Vec<tv_ui::state::SignatureHit> = hits.into_iter().map(|h| {
+                tv_ui::state::SignatureHit {
+                    offset: h.offset,
+                    name: h.name.to_string(),
+                    magic: scan_data[h.offset as usize..(h.offset as usize + h.magic_len).min(scan_data.len())].to_vec(),
+                }
+            }).collect();
+            log::info!("Detected {} signatures", sig_hits.len());
+            // Pre-select the matching structure template for known formats
+            if self.inspector.auto_select_for_signatures(&sig_hits, scan_data) {
+                self.inspector.apply(scan_data);
+            }
+            self.state.signature_carves = sig_hits.iter().map(|hit| quick_scan_carve(&f.mapped, hit)).collect();
+            self.state.signatures = if sig_hits.is_empty() { None } else { Some(sig_hits) };
+        }
+
+        // Text files in an unexpected encoding (BOM or content heuristics)
+        if let Some(ref f) = self.state.file {
+            let sample = f.mapped.slice_at(0, tv_core::encoding::DETECT_SAMPLE_BYTES as u64);
+            self.state.text_encoding = tv_core::encoding::detect(sample);
+        }
+
+        // Section layout of executables (headers only, cheap)
+        if let Some(ref f) = self.state.file {
+            self.state.sections = tv_core::parse_sections(f.mapped.slice(tv_core::FileRegion::new(0, f.mapped.len())));
+        }
+
+        // PNG chunks / ZIP entries, from the quick-scan hit at offset 0
+        if let Some(ref f) = self.state.file {
+            let format = self.state.signatures.iter().flatten()
+                .filter(|hit| hit.offset == 0)
+                .find_map(|hit| tv_core::ContainerFormat::from_signature(&hit.name));
+            self.state.structure = format.and_then(|format| {
+                tv_core::parse_structure(format, f.mapped.slice(tv_core::FileRegion::new(0, f.mapped.len())))
+            });
+        }
+
+        // Load the annotation project (if any) so labels and comments show up right away
+        if let Some(ref file) = self.state.file {
+            self.bookmarks.ensure_project(file);
+        }
+        if let Some(ref project) = self.bookmarks.project {
+            self.disasm.load_comments(project);
+        }
+
+        // Queue background entropy computation (huge files ask for the mode first)
+        self.state.analysis_mode = tv_ui::state::AnalysisMode::Full;
+        self.state.analysis_prompt = file_len > tv_ui::state::LARGE_FILE_THRESHOLD;
+        self.state.analysis_request =
+            (!self.state.analysis_prompt).then_some(tv_ui::state::AnalysisMode::Full);
+        self.launch_file_histogram();
+        // Hashing reads the whole file; without a hash list it waits for File Info
+        if !self.state.hash_db.is_empty() {
+            self.launch_file_hash();
+        }
+
+        // Check for existing session file and offer to load
+        if let Some(path) = disk_path.filter(|path| Session::exists_for(path)) {
+            log::info!("Session file found for {}", path.display());
+            // Auto-load will happen if user explicitly opens .titan file
+        }
+    }
+
+    /// Open a file with edit mode locked off, so it can't be written to.
+    fn open_file_read_only(&mut self, path: PathBuf) {
+        self.open_file(path.clone());
+        if self.state.file.as_ref().is_some_and(|f| f.path == path) && !self.state.edit.set_read_only() {
+            self.session_status = Some(("Save or undo pending edits before locking the file read-only".to_string(), true));
+        }
+    }
+
+    /// Decode hex or Base64 text on the clipboard and open the bytes as an
+    /// in-memory file (read-only, as there is nowhere to save edits).
+    fn open_clipboard(&mut self) {
+        let Some(text) = tv_ui::clipboard::read_text() else {
+            self.session_status = Some(("Clipboard holds no text".to_string(), true));
+            return;
+        };
+        match tv_core::decode_pasted(&text) {
+            Ok((data, encoding)) => {
+                let len = data.len();
+                // Numbered like `<clipboard 1>`, unlike any open file
+                let open = self.tabs.iter().flatten().map(|p| &p.tab.file.path).chain(self.state.file.iter().map(|f| &f.path));
+                let label = (1..)
+                    .map(|n| format!("<clipboard {}>", n))
+                    .find(|label| !open.clone().any(|path| path.as_os_str() == label.as_str()))
+                    .expect("unbounded range");
+                self.show_loaded_file(tv_ui::state::LoadedFile::from_memory(label, data));
+                self.state.edit.set_read_only();
+                self.session_status = Some((format!("Opened {} bytes decoded from {}", len, encoding.label()), false));
+            }
+            Err(msg) => self.session_status = Some((msg, true)),
+        }
+    }
+
+    /// Open the first of the dropped files; the others are offered as tabs or diff file B.
+    /// Items without a local path (e.g. URLs) and folders are rejected with a status message.
+    fn handle_drop(&mut self, dropped: Vec<Option<PathBuf>>) {
+        let dropped_count = dropped.len();
+        let mut errors = Vec::new();
+        let mut paths = Vec::new();
+        for path in dropped.into_iter().flatten() {
+            match file_dialog::check_openable(&path) {
+                Ok(()) => paths.push(path),
+                Err(msg) => errors.push(msg),
+            }
+        }
+        if paths.len() + errors.len() < dropped_count {
+            errors.push("Dropped item is not a local file".to_string());
+        }
+
+        let mut paths = paths.into_iter();
+        if let Some(first) = paths.next() {
+            self.open_file(first);
+        }
+        self.dropped_extra = paths.collect();
+
+        if let Some(msg) = errors.first() {
+            log::warn!("Rejected drop: {}", errors.join("; "));
+            let msg = if errors.len() > 1 { format!("{} (+{} more)", msg, errors.len() - 1) } else { msg.clone() };
+            self.session_status = Some((msg, true));
+        }
+    }
+
+    /// Offer the remaining files of a multi-file drop as tabs or diff file B.
+    fn show_dropped_files_prompt(&mut self, ctx: &egui::Context) {
+        if self.dropped_extra.is_empty() {
+            return;
+        }
+
+        let mut open_tab = None;
+        let mut diff_b = None;
+        let mut open_all = false;
+        let mut dismiss = false;
+        egui::Window::new("Dropped Files")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("{} more file(s) were dropped:", self.dropped_extra.len()));
+                egui::Grid::new("dropped_files_grid").num_columns(3).show(ui, |ui| {
+                    for (i, path) in self.dropped_extra.iter().enumerate() {
+                        ui.label(path.file_name().and_then(|n| n.to_str()).unwrap_or("?"))
+                            .on_hover_text(path.display().to_string());
+                        if ui.button("Open tab").clicked() {
+                            open_tab = Some(i);
                         }
-                    }).collect();
-                    log::info!("Detected {} signatures", sig_hits.len());
-                    self.state.signatures = if sig_hits.is_empty() { None } else { Some(sig_hits) };
+                        if ui.add_enabled(self.state.has_file(), egui::Button::new("Diff as file B")).clicked() {
+                            diff_b = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    open_all = ui.button("Open all as tabs").clicked();
+                    dismiss = ui.button("Dismiss").clicked();
+                });
+            });
+
+        if let Some(i) = diff_b {
+            let path = self.dropped_extra.remove(i);
+            self.open_diff_file_b(path);
+        } else if let Some(i) = open_tab {
+            let path = self.dropped_extra.remove(i);
+            self.open_file(path);
+        } else if open_all {
+            let active = self.active_tab;
+            for path in std::mem::take(&mut self.dropped_extra) {
+                self.open_file(path);
+            }
+            self.switch_tab(active);
+        } else if dismiss {
+            self.dropped_extra.clear();
+        }
+    }
+
+    /// Move the active file (state, annotations and background jobs) out of the view.
+    fn park_active_tab(&mut self) -> Option<ParkedTab> {
+        let mut tab = self.state.take_tab()?;
+        tab.project = self.bookmarks.project.take();
+        tab.project_path = self.bookmarks.project_path.take();
+        tab.project_modified = self.bookmarks.modified;
+        self.bookmarks.clear();
+
+        let jobs = TabJobs {
+            entropy_rx: self.entropy_rx.take(),
+            computing_entropy: std::mem::take(&mut self.computing_entropy),
+            entropy_progress: std::mem::take(&mut self.entropy_progress),
+            classify_rx: self.classify_rx.take(),
+            computing_classification: std::mem::take(&mut self.computing_classification),
+            classify_progress: std::mem::take(&mut self.classify_progress),
+            search_rx: self.search_rx.take(),
+            deep_scan_rx: self.deep_scan_rx.take(),
+            carve_rx: self.carve_rx.take(),
+            file_histogram_rx: self.file_histogram_rx.take(),
+            file_hash_rx: self.file_hash_rx.take(),
+        };
+        self.invalidate_file_views();
+        Some(ParkedTab { tab, jobs })
+    }
+
+    /// Make a parked tab the active one.
+    fn activate_parked_tab(&mut self, parked: ParkedTab) {
+        let ParkedTab { mut tab, jobs } = parked;
+        self.bookmarks.clear();
+        self.bookmarks.project = tab.project.take();
+        self.bookmarks.project_path = tab.project_path.take();
+        self.bookmarks.modified = tab.project_modified;
+        self.state.restore_tab(tab);
+
+        self.entropy_rx = jobs.entropy_rx;
+        self.computing_entropy = jobs.computing_entropy;
+        self.entropy_progress = jobs.entropy_progress;
+        self.classify_rx = jobs.classify_rx;
+        self.computing_classification = jobs.computing_classification;
+        self.classify_progress = jobs.classify_progress;
+        self.search_rx = jobs.search_rx;
+        self.deep_scan_rx = jobs.deep_scan_rx;
+        self.carve_rx = jobs.carve_rx;
+        self.file_histogram_rx = jobs.file_histogram_rx;
+        self.file_hash_rx = jobs.file_hash_rx;
+
+        self.invalidate_file_views();
+        if let Some(ref project) = self.bookmarks.project {
+            self.disasm.load_comments(project);
+        }
+        // Sessions belong to a single file
+        self.session_path = None;
+    }
+
+    /// Drop views derived from the active file (rebuilt on demand for the next one).
+    fn invalidate_file_views(&mut self) {
+        self.disasm.invalidate();
+        self.disasm.restore_offset = None;
+        self.hilbert.invalidate();
+        self.hilbert_rx = None;
+        self.histogram.clear();
+        self.histogram_rx = None;
+        self.xor.clear();
+        self.xrefs.clear();
+        // Diff results compare the previous file A; a running diff would
+        // deliver them to the next one
+        self.state.diff.clear();
+        self.diff_rx = None;
+        self.signature_diff_rx = None;
+        self.entropy_diff_rx = None;
+    }
+
+    /// Switch to the tab at `index`.
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab {
+            return;
+        }
+        let Some(target) = self.tabs.get_mut(index).and_then(Option::take) else {
+            return;
+        };
+        self.tabs[self.active_tab] = self.park_active_tab();
+        self.active_tab = index;
+        self.activate_parked_tab(target);
+    }
+
+    /// Unsaved byte edits and annotations of the tab at `index`.
+    fn tab_unsaved_changes(&self, index: usize) -> (bool, bool) {
+        match self.tabs.get(index) {
+            Some(Some(parked)) => (parked.tab.edit.has_changes(), parked.tab.project_modified),
+            Some(None) => (self.state.edit.has_changes(), self.bookmarks.modified),
+            None => (false, false),
+        }
+    }
+
+    /// Close a tab or the session, first asking when that discards unsaved
+    /// edits or annotations.
+    fn request_close(&mut self, request: CloseRequest) {
+        let tabs = match request {
+            CloseRequest::Tab(index) => index..index + 1,
+            CloseRequest::Session => 0..self.tabs.len(),
+        };
+        if tabs.into_iter().any(|i| self.tab_unsaved_changes(i) != (false, false)) {
+            self.close_confirm = Some(request);
+        } else {
+            self.close_confirmed(request);
+        }
+    }
+
+    fn close_confirmed(&mut self, request: CloseRequest) {
+        match request {
+            CloseRequest::Tab(index) => self.close_tab(index),
+            CloseRequest::Session => self.reset_to_landing(),
+        }
+    }
+
+    /// Ask before a close drops unsaved edits or annotations.
+    fn show_close_confirm(&mut self, ctx: &egui::Context) {
+        let Some(request) = self.close_confirm else { return };
+        let message = match request {
+            CloseRequest::Tab(index) => {
+                let name = match self.tabs.get(index) {
+                    Some(Some(parked)) => parked.tab.name(),
+                    _ => self.state.file_name(),
+                };
+                let unsaved = match self.tab_unsaved_changes(index) {
+                    (true, true) => "byte edits and annotations",
+                    (true, false) => "byte edits",
+                    _ => "annotations",
+                };
+                format!("{} has unsaved {}.", name, unsaved)
+            }
+            CloseRequest::Session => "Some open files have unsaved byte edits or annotations.".to_string(),
+        };
+
+        let mut close = false;
+        let mut cancel = false;
+        egui::Window::new("Unsaved Changes")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(message);
+                ui.label("Close anyway and discard them?");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    close = ui.button("Discard and Close").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if close {
+            self.close_confirm = None;
+            self.close_confirmed(request);
+        } else if cancel {
+            self.close_confirm = None;
+        }
+    }
+
+    /// Close the tab at `index`. Closing the last tab returns to the landing page.
+    fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        let closed = match self.tabs[index] {
+            Some(ref parked) => parked.tab.file.disk_path().map(Path::to_path_buf),
+            None => self.state.file.as_ref().and_then(|f| f.disk_path()).map(Path::to_path_buf),
+        };
+        if closed.is_some() {
+            self.previous_file = closed;
+        }
+        if index == self.active_tab {
+            if self.tabs.len() == 1 {
+                self.reset_to_landing();
+                return;
+            }
+            let next = if index + 1 < self.tabs.len() { index + 1 } else { index - 1 };
+            self.switch_tab(next);
+        }
+        if let Some(parked) = self.tabs.remove(index) {
+            parked.tab.file_hash_cancel.store(true, Ordering::Relaxed);
+            parked.tab.search.cancel.store(true, Ordering::Relaxed);
+        }
+        if self.active_tab > index {
+            self.active_tab -= 1;
+        }
+    }
+
+    /// Minimaps of all open files side by side; a click activates that file.
+    fn show_file_overview(&mut self, ctx: &egui::Context) {
+        if !self.show_overview {
+            return;
+        }
+        let state = &mut self.state;
+        let mut files: Vec<OverviewFile> = Vec::with_capacity(self.tabs.len());
+        let mut active = Some((&mut state.minimap_cache, state.file.as_ref()));
+        for slot in self.tabs.iter_mut() {
+            match slot {
+                Some(parked) => files.push(OverviewFile {
+                    name: parked.tab.file.path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
+                    file_len: parked.tab.file.mapped.len(),
+                    active: false,
+                    cache: &mut parked.tab.minimap_cache,
+                    entropy: parked.tab.entropy.as_deref(),
+                    classification: parked.tab.classification.as_deref(),
+                }),
+                None => {
+                    let Some((cache, Some(file))) = active.take() else { continue };
+                    files.push(OverviewFile {
+                        name: file.path.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
+                        file_len: file.mapped.len(),
+                        active: true,
+                        cache,
+                        entropy: state.entropy.as_deref(),
+                        classification: state.classification.as_deref(),
+                    });
+                }
+            }
+        }
+
+        match OverviewWindow::show(ctx, &mut files, &state.theme, &mut self.show_overview) {
+            Some(OverviewAction::Activate { index, offset }) => {
+                self.switch_tab(index);
+                self.state.jump_to_offset(offset);
+            }
+            Some(OverviewAction::AddFiles) => {
+                for path in file_dialog::new().pick_files().unwrap_or_default() {
+                    self.open_file(path);
                 }
+            }
+            None => {}
+        }
+    }
+
+    /// Paths of the files on disk open in other tabs.
+    fn other_tab_paths(&self) -> Vec<PathBuf> {
+        self.tabs.iter().flatten().filter_map(|p| p.tab.file.disk_path()).map(Path::to_path_buf).collect()
+    }
 
-                // Launch background entropy computation
-                self.launch_entropy_compute(&path, file_len);
+    /// Files offered as diff file B: other tabs, then the last closed file.
+    fn diff_b_candidates(&self) -> Vec<PathBuf> {
+        let mut paths = self.other_tab_paths();
+        if let Some(ref previous) = self.previous_file {
+            let is_current = self.state.file.as_ref().is_some_and(|f| &f.path == previous);
+            if !is_current && !paths.contains(previous) && previous.is_file() {
+                paths.push(previous.clone());
+            }
+        }
+        paths
+    }
+
+    /// Pick diff file B from the open files and the last closed file.
+    fn show_diff_b_chooser(&mut self, ctx: &egui::Context) {
+        if !self.choosing_diff_b {
+            return;
+        }
 
-                // Check for existing session file and offer to load
-                if Session::exists_for(&path) {
-                    log::info!("Session file found for {}", path.display());
-                    // Auto-load will happen if user explicitly opens .titan file
+        let mut chosen = None;
+        let mut browse = false;
+        let mut open = true;
+        egui::Window::new("Choose File B")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Compare {} with:", self.state.file_name()));
+                for path in self.diff_b_candidates() {
+                    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                    let hint = if self.previous_file.as_ref() == Some(&path) { " (previous)" } else { "" };
+                    if ui.button(format!("{}{}", name, hint)).on_hover_text(path.display().to_string()).clicked() {
+                        chosen = Some(path);
+                    }
                 }
+                ui.separator();
+                browse = ui.button("Browse...").clicked();
+            });
+
+        if browse {
+            chosen = file_dialog::new().pick_file_remembered();
+        }
+        if let Some(path) = chosen {
+            self.open_diff_file_b(path);
+        }
+        if browse || self.state.diff.file_b.is_some() || !open {
+            self.choosing_diff_b = false;
+        }
+    }
+
+    /// Load file B for the binary diff and enter diff mode.
+    fn open_diff_file_b(&mut self, path: PathBuf) {
+        if let Err(msg) = file_dialog::check_openable(&path) {
+            self.session_status = Some((msg, true));
+            return;
+        }
+        match tv_ui::state::LoadedFile::open(path.clone()) {
+            Ok(file) => {
+                self.state.diff.file_b = Some(file);
+                self.state.diff.viewport_b = tv_core::ViewPort::new(0, 4096);
+                self.state.diff.sync_scroll = true;
+                self.state.diff.active = true;
+                self.state.diff.clear();
+                // A comparison against the previous file B is stale now
+                self.signature_diff_rx = None;
             }
             Err(e) => {
-                log::error!("Failed to open file: {}", e);
+                self.session_status = Some((format!("Failed to open {}: {}", path.display(), e), true));
             }
         }
     }
@@ -402,12 +1427,14 @@ impl TitanViewApp {
         session.update_timestamp();
 
         // File path
-        if let Some(ref file) = self.state.file {
-            session.file_path = Some(file.path.clone());
-        }
+        session.file_path = self.state.file.as_ref().and_then(|f| f.disk_path()).map(Path::to_path_buf);
 
         // Viewport
         session.viewport.offset = self.state.viewport.start;
+        session.viewport.class_tint = format!("{:?}", self.state.class_tint);
+        session.viewport.font_size = self.state.hex_font_size;
+        session.theme = Some(self.state.theme.clone());
+        session.notes = self.state.notes.clone();
 
         // Window visibility
         session.windows = WindowStates {
@@ -464,19 +1491,28 @@ impl TitanViewApp {
 
         // Search state
         session.search.query = self.state.search.query_text.clone();
-        if let Some(ref results) = self.state.search.results {
+        session.search.alignment = self.state.search.alignment;
+        if let Some(results) = self.state.search.results.as_ref().filter(|_| self.state.search.live_scope.is_none()) {
             session.search.results = results.clone();
         }
         session.search.selected_index = self.state.search.selected_result;
 
         // Disasm state
-        session.disasm.address = self.state.viewport.start; // Use viewport offset
+        session.disasm.address = self.disasm.result.as_ref()
+            .map_or(self.state.viewport.start, |r| r.base_address);
         session.disasm.architecture = format!("{:?}", self.disasm.arch);
+        session.disasm.auto_detect = self.disasm.auto_detect;
         session.disasm.instruction_count = self.disasm.max_instructions;
+        session.disasm.show_bytes = self.disasm.show_bytes;
+        session.disasm.recursive = self.disasm.recursive;
+        let mut comments: Vec<CommentEntry> = self.disasm.instruction_comments.iter()
+            .map(|(&address, text)| CommentEntry { address, text: text.clone() })
+            .collect();
+        comments.sort_by_key(|c| c.address);
+        session.disasm.comments = comments;
 
         // Hilbert state
-        session.hilbert.mode = format!("{:?}", self.hilbert.mode);
-        session.hilbert.order = 9; // Default order
+        self.hilbert.save_session(&mut session.hilbert);
         session.hilbert.offset = self.state.viewport.start;
 
         // Histogram state
@@ -501,6 +1537,17 @@ impl TitanViewApp {
 
         // Restore viewport
         self.state.viewport.start = session.viewport.offset;
+        if let Some(tint) = tv_ui::state::ClassTint::from_name(&session.viewport.class_tint) {
+            self.state.class_tint = tint;
+        }
+        if session.viewport.font_size > 0.0 {
+            self.state.set_hex_font_size(session.viewport.font_size);
+        }
+        if let Some(ref theme) = session.theme {
+            self.state.theme = theme.clone();
+            self.state.minimap_cache.invalidate();
+        }
+        self.state.notes = session.notes.clone();
 
         // Restore window visibility
         self.show_file_info = session.windows.file_info.visible;
@@ -518,7 +1565,7 @@ impl TitanViewApp {
         // Restore bookmarks and labels
         if !session.bookmarks.is_empty() || !session.labels.is_empty() {
             if let Some(ref file) = self.state.file {
-                self.bookmarks.ensure_project(&file.path, file.mapped.len());
+                self.bookmarks.ensure_project(file);
             }
             if let Some(ref mut project) = self.bookmarks.project {
                 for bookmark in &session.bookmarks {
@@ -557,10 +1604,14 @@ impl TitanViewApp {
         }
         self.inspector.offset = session.inspector.offset;
         self.inspector.offset_text = format!("0x{:X}", session.inspector.offset);
+        if let Some(file) = &self.state.file {
+            self.inspector.clamp_offset(file.mapped.len());
+        }
         self.inspector.auto_detect = session.inspector.auto_detect;
 
         // Restore search state
         self.state.search.query_text = session.search.query.clone();
+        self.state.search.alignment = session.search.alignment;
         if !session.search.results.is_empty() {
             self.state.search.results = Some(session.search.results.clone());
             self.state.search.selected_result = session.search.selected_index;
@@ -569,11 +1620,26 @@ impl TitanViewApp {
 
         // Restore disasm state
         self.disasm.max_instructions = session.disasm.instruction_count;
-        // Architecture is auto-detected, don't override
+        self.disasm.auto_detect = session.disasm.auto_detect;
+        if !session.disasm.auto_detect {
+            match tv_core::Architecture::from_name(&session.disasm.architecture) {
+                Some(arch) => self.disasm.arch = arch,
+                None => {
+                    log::warn!("Unknown architecture in session: {}", session.disasm.architecture);
+                    self.disasm.auto_detect = true;
+                }
+            }
+        }
+        self.disasm.show_bytes = session.disasm.show_bytes;
+        self.disasm.recursive = session.disasm.recursive;
+        self.disasm.restore_offset = Some(session.disasm.address);
+        self.disasm.invalidate();
+        for comment in &session.disasm.comments {
+            self.disasm.set_comment(comment.address, &comment.text);
+        }
 
         // Restore hilbert state
-        // Mode is restored via format string match
-        self.hilbert.texture_size = 512; // Default size
+        self.hilbert.restore_session(&session.hilbert);
 
         // Restore histogram state
         self.histogram.log_scale = session.histogram.log_scale;
@@ -589,14 +1655,14 @@ impl TitanViewApp {
 
         let path = if let Some(ref p) = self.session_path {
             p.clone()
-        } else if let Some(ref file) = self.state.file {
-            Session::session_path_for(&file.path)
+        } else if let Some(file_path) = self.state.file.as_ref().and_then(|f| f.disk_path()) {
+            Session::session_path_for(file_path)
         } else {
             // Prompt for path
-            if let Some(path) = rfd::FileDialog::new()
+            if let Some(path) = file_dialog::new()
                 .add_filter("TitanView Session", &[SESSION_EXTENSION])
                 .set_file_name("session.titan")
-                .save_file()
+                .save_file_remembered()
             {
                 path
             } else {
@@ -622,10 +1688,10 @@ impl TitanViewApp {
     fn save_session_as(&mut self) {
         let session = self.capture_session();
 
-        if let Some(path) = rfd::FileDialog::new()
+        if let Some(path) = file_dialog::new()
             .add_filter("TitanView Session", &[SESSION_EXTENSION])
             .set_file_name("session.titan")
-            .save_file()
+            .save_file_remembered()
         {
             match session.save(&path) {
                 Ok(()) => {
@@ -657,7 +1723,397 @@ impl TitanViewApp {
         }
     }
 
-    fn launch_entropy_compute(&mut self, path: &PathBuf, file_len: u64) {
+    /// Whether there is data for `artifact` in an analysis bundle.
+    fn bundle_artifact_available(&self, artifact: BundleArtifact) -> bool {
+        match artifact {
+            BundleArtifact::Report | BundleArtifact::Session => self.state.has_file(),
+            BundleArtifact::SearchResults => self.command_available(Command::ExportSearchResults),
+            BundleArtifact::Signatures => self.command_available(Command::ExportSignatures),
+            BundleArtifact::Disassembly => self.command_available(Command::ExportDisassembly),
+            BundleArtifact::Histogram => self.state.byte_histogram.is_some(),
+            BundleArtifact::Hilbert => self.hilbert.texture.is_some(),
+            BundleArtifact::Minimap => self.state.entropy.as_ref().is_some_and(|e| !e.is_empty()),
+        }
+    }
+
+    /// Contents of a bundle file, `None` if there is nothing to export.
+    fn bundle_artifact_bytes(&mut self, artifact: BundleArtifact) -> Option<Vec<u8>> {
+        let text = match artifact {
+            BundleArtifact::Report => tv_ui::export::export_json(&self.state),
+            BundleArtifact::SearchResults => tv_ui::export::export_search_csv(&self.state),
+            BundleArtifact::Signatures => tv_ui::export::export_signatures_csv(&self.state),
+            BundleArtifact::Histogram => tv_ui::export::export_histogram_csv(self.state.byte_histogram.as_ref()?),
+            BundleArtifact::Disassembly => {
+                let result = self.disasm.result.as_ref()?;
+                tv_ui::export::export_disasm_text(result, &self.disasm.instruction_comments)
+            }
+            BundleArtifact::Session => self.capture_session().to_json().ok()?,
+            BundleArtifact::Hilbert => return self.hilbert.png(),
+            BundleArtifact::Minimap => {
+                let (width, height) = BUNDLE_MINIMAP_SIZE;
+                return MinimapPanel::png(&mut self.state, width, height);
+            }
+        };
+        Some(text.into_bytes())
+    }
+
+    /// Ask for a folder and write the chosen artifacts to a bundle folder in it.
+    fn export_bundle(&mut self, artifacts: &[BundleArtifact]) {
+        let Some(parent) = file_dialog::new().pick_folder_remembered() else { return };
+        let dir = tv_ui::bundle_dir(&parent, self.state.file_name());
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            self.session_status = Some((format!("Bundle export failed: {}", e), true));
+            return;
+        }
+
+        let mut failed = Vec::new();
+        for &artifact in artifacts {
+            let written = self.bundle_artifact_bytes(artifact)
+                .ok_or_else(|| "no data".to_string())
+                .and_then(|bytes| std::fs::write(dir.join(artifact.file_name()), bytes).map_err(|e| e.to_string()));
+            if let Err(e) = written {
+                log::error!("Bundle export of {} failed: {}", artifact.file_name(), e);
+                failed.push(artifact.file_name());
+            }
+        }
+
+        self.session_status = Some(if failed.is_empty() {
+            log::info!("Exported {} file(s) to {}", artifacts.len(), dir.display());
+            (format!("Exported {} file(s) to {}", artifacts.len(), dir.display()), false)
+        } else {
+            (format!("Bundle export failed for {}", failed.join(", ")), true)
+        });
+    }
+
+    /// Whether the active file can be saved in a session: in-memory bytes
+    /// have no path to reopen them from.
+    fn session_savable(&self) -> bool {
+        self.state.file.as_ref().is_some_and(|f| f.disk_path().is_some())
+    }
+
+    /// Whether `command` can run now (e.g. exports need their results).
+    fn command_available(&self, command: Command) -> bool {
+        let has_file = self.state.has_file();
+        match command {
+            Command::SaveSession | Command::SaveSessionAs => self.session_savable(),
+            Command::CloseSession
+            | Command::ExportReport | Command::ExportBundle | Command::GotoOffset | Command::ToggleDiff => has_file,
+            Command::ExportSearchResults => self.state.search.results.is_some(),
+            Command::ExportDisassembly => self.disasm.result.is_some(),
+            Command::ExportSignatures => {
+                self.state.signatures.is_some() || self.state.deep_scan.results.is_some()
+            }
+            Command::DeepScan => has_file && !self.state.deep_scan.scanning,
+            Command::Workspace(index) => index < self.workspaces.workspaces.len(),
+            _ => true,
+        }
+    }
+
+    /// Run a command from a shortcut, the palette or a menu.
+    fn execute_command(&mut self, ctx: &egui::Context, command: Command) {
+        if !self.command_available(command) {
+            return;
+        }
+        match command {
+            Command::OpenFile => {
+                if let Some(path) = file_dialog::new().pick_file_remembered() {
+                    // Check if it's a session file
+                    if path.extension().and_then(|e| e.to_str()) == Some(SESSION_EXTENSION) {
+                        self.load_session(path);
+                    } else {
+                        self.open_file(path);
+                    }
+                }
+            }
+            Command::OpenClipboard => self.open_clipboard(),
+            Command::OpenSession => {
+                if let Some(path) = file_dialog::new()
+                    .add_filter("TitanView Session", &[SESSION_EXTENSION])
+                    .pick_file_remembered()
+                {
+                    self.load_session(path);
+                }
+            }
+            Command::SaveSession => self.save_session(),
+            Command::SaveSessionAs => self.save_session_as(),
+            Command::CloseSession => self.request_close(CloseRequest::Session),
+            Command::ExportReport => {
+                let json = tv_ui::export::export_json(&self.state);
+                export_text(&json, "report.json", "JSON", &["json"]);
+            }
+            Command::ExportSearchResults => {
+                let csv = tv_ui::export::export_search_csv(&self.state);
+                export_text(&csv, "search_results.csv", "CSV", &["csv"]);
+            }
+            Command::ExportDisassembly => {
+                if let Some(ref result) = self.disasm.result {
+                    let text = tv_ui::export::export_disasm_text(result, &self.disasm.instruction_comments);
+                    export_text(&text, "disassembly.asm", "Assembly", &["asm", "txt"]);
+                }
+            }
+            Command::ExportSignatures => {
+                let csv = tv_ui::export::export_signatures_csv(&self.state);
+                export_text(&csv, "signatures.csv", "CSV", &["csv"]);
+            }
+            Command::ExportBundle => self.bundle.open = true,
+            Command::GotoOffset => self.state.open_goto(),
+            Command::Search => self.show_search = !self.show_search,
+            Command::DeepScan => {
+                self.state.deep_scan.clear_results();
+                self.state.deep_scan.scanning = true;
+                self.show_signatures = true;
+            }
+            // Diff mode (if file B is loaded, toggle; otherwise choose file B)
+            Command::ToggleDiff => {
+                if self.state.diff.file_b.is_some() {
+                    self.state.diff.active = !self.state.diff.active;
+                } else if !self.diff_b_candidates().is_empty() {
+                    self.choosing_diff_b = true;
+                } else if let Some(path) = file_dialog::new().pick_file_remembered() {
+                    self.open_diff_file_b(path);
+                }
+            }
+            Command::ToggleFileInfo => self.show_file_info = !self.show_file_info,
+            Command::ToggleSignatures => self.show_signatures = !self.show_signatures,
+            Command::TogglePerformance => self.perf.visible = !self.perf.visible,
+            Command::ToggleHilbert => self.show_hilbert = !self.show_hilbert,
+            Command::ToggleDisassembly => self.show_disasm = !self.show_disasm,
+            Command::ToggleInspector => self.show_inspector = !self.show_inspector,
+            Command::ToggleHistogram => self.show_histogram = !self.show_histogram,
+            Command::ToggleXrefs => {
+                self.show_xrefs = !self.show_xrefs;
+                // Build XRefs from current disassembly if available
+                if self.show_xrefs && self.xrefs.table.is_none() {
+                    if let Some(ref result) = self.disasm.result {
+                        self.xrefs.build_from_instructions(&result.instructions);
+                    }
+                }
+            }
+            Command::ToggleBookmarks => self.show_bookmarks = !self.show_bookmarks,
+            Command::ToggleScript => self.show_script = !self.show_script,
+            Command::ToggleMinimap => self.show_minimap = !self.show_minimap,
+            Command::ToggleXor => self.show_xor = !self.show_xor,
+            Command::ToggleTextView => self.show_text_view = !self.show_text_view,
+            Command::ToggleSections => self.show_sections = !self.show_sections,
+            Command::ToggleStructure => self.show_structure = !self.show_structure,
+            Command::ToggleTheme => self.show_theme = !self.show_theme,
+            Command::ToggleShortcuts => self.show_shortcuts = !self.show_shortcuts,
+            Command::ToggleNotes => self.show_notes = !self.show_notes,
+            Command::ToggleOverview => self.show_overview = !self.show_overview,
+            Command::Workspace(index) => self.apply_workspace(index),
+        }
+        ctx.request_repaint();
+    }
+
+    /// Palette rows: the available registered commands, then the workspaces.
+    fn palette_entries(&self, ctx: &egui::Context) -> Vec<PaletteEntry> {
+        let mut entries = PaletteEntry::registered(ctx, &self.key_bindings, |command| self.command_available(command));
+        for (index, ws) in self.workspaces.workspaces.iter().enumerate() {
+            let shortcut = ws.shortcut.map(|n| format!("Ctrl+{}", n)).unwrap_or_default();
+            entries.push(PaletteEntry::new(
+                Command::Workspace(index),
+                format!("Workspace: {} {}", ws.icon, ws.name),
+                shortcut,
+            ));
+        }
+        entries
+    }
+
+    /// GPU operation running for the active file, if any.
+    fn running_gpu_job(&self) -> Option<GpuJob> {
+        if self.computing_entropy || self.computing_classification {
+            Some(GpuJob::Analysis)
+        } else if self.search_rx.is_some() && self.state.search.backend == SearchBackend::Gpu {
+            Some(GpuJob::Search)
+        } else if self.deep_scan_rx.is_some() {
+            Some(GpuJob::DeepScan)
+        } else if self.hilbert_rx.is_some() {
+            Some(GpuJob::Hilbert)
+        } else if self.diff_rx.is_some() {
+            Some(GpuJob::Diff)
+        } else if self.signature_diff_rx.is_some() {
+            Some(GpuJob::SignatureDiff)
+        } else if self.entropy_diff_rx.is_some() {
+            Some(GpuJob::EntropyDiff)
+        } else {
+            None
+        }
+    }
+
+    /// Running operations that report progress, with their completion.
+    fn running_operations(&self) -> Vec<(&'static str, f32)> {
+        let mut ops = Vec::new();
+        if self.computing_entropy {
+            ops.push(("Entropy", self.entropy_progress));
+        }
+        if self.computing_classification {
+            ops.push(("Classification", self.classify_progress));
+        }
+        let deep_scan = &self.state.deep_scan;
+        if deep_scan.scanning && deep_scan.total_bytes > 0 {
+            ops.push(("Deep scan", deep_scan.bytes_scanned as f32 / deep_scan.total_bytes as f32));
+        }
+        ops
+    }
+
+    /// Completion of the running operations that report progress, if any.
+    fn operation_progress(&self) -> Option<(String, f32)> {
+        combined_progress(&self.running_operations())
+    }
+
+    /// Whether a `job` may start now: the GPU is idle, or (for analysis) busy
+    /// with the analysis it replaces. Jobs of background tabs keep running,
+    /// so the GPU is only idle once their workers have returned too.
+    fn gpu_available_for(&self, job: GpuJob) -> bool {
+        match self.running_gpu_job() {
+            Some(busy) => busy == job && job == GpuJob::Analysis,
+            None => !self.gpu_workers_running(),
+        }
+    }
+
+    /// Whether any GPU worker (of any tab) is still running.
+    fn gpu_workers_running(&self) -> bool {
+        Arc::strong_count(&self.gpu_workers) > 1
+    }
+
+    /// GPU operation started by a background tab, if one may still be running.
+    fn parked_gpu_job(&self) -> Option<GpuJob> {
+        if !self.gpu_workers_running() {
+            return None;
+        }
+        self.tabs.iter().flatten().find_map(|parked| {
+            let jobs = &parked.jobs;
+            if jobs.computing_entropy || jobs.computing_classification {
+                Some(GpuJob::Analysis)
+            } else if jobs.search_rx.is_some() && parked.tab.search.backend == SearchBackend::Gpu {
+                Some(GpuJob::Search)
+            } else {
+                jobs.deep_scan_rx.is_some().then_some(GpuJob::DeepScan)
+            }
+        })
+    }
+
+    /// Restart entropy and classification of the current file with `mode`.
+    fn relaunch_entropy_compute(&mut self, mode: tv_ui::state::AnalysisMode) {
+        if !self.state.has_file() {
+            return;
+        }
+        self.state.analysis_mode = mode;
+        self.state.analysis_prompt = false;
+        self.state.entropy = None;
+        self.state.cached_entropy_stats = None;
+        self.state.classification = None;
+        self.state.classification_block_size = None;
+        self.state.classification_request = None;
+        self.state.cached_class_counts = None;
+        self.state.minimap_cache.invalidate();
+        self.launch_entropy_compute();
+    }
+
+    /// Run a GPU job on its own thread, holding the GPU until it returns.
+    fn spawn_gpu_worker(&self, work: impl FnOnce() + Send + 'static) {
+        let gpu_workers = Arc::clone(&self.gpu_workers);
+        std::thread::spawn(move || {
+            let _gpu_worker = gpu_workers;
+            work();
+        });
+    }
+
+    /// Reporter for failures of a `job` worker running on `path`.
+    fn error_reporter(&self, job: GpuJob, path: &Path) -> ErrorReporter {
+        ErrorReporter { tx: self.worker_error_tx.clone(), job, path: path.to_path_buf() }
+    }
+
+    /// Show background job failures and GPU memory budget warnings in the
+    /// status bar. The job's own poller resets its progress state once the
+    /// worker hangs up.
+    fn poll_worker_errors(&mut self) {
+        if let Some(warning) = tv_gpu::take_budget_warning() {
+            self.session_status = Some((warning, true));
+        }
+        while let Ok(error) = self.worker_error_rx.try_recv() {
+            let label = error.job.label();
+            let label = label[..1].to_uppercase() + &label[1..];
+            let active = self.state.file.as_ref().is_some_and(|f| f.path == error.path);
+            let message = if active {
+                format!("{} failed: {}", label, error.message)
+            } else {
+                let name = error.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                format!("{} of {} failed: {}", label, name, error.message)
+            };
+            self.session_status = Some((message, true));
+        }
+    }
+
+    /// Recompute only the classification of the current file at `block_size`,
+    /// keeping the entropy. Uses the same chunking and sampling as the full
+    /// analysis; results arrive through `classify_rx`.
+    fn launch_classification_compute(&mut self, block_size: u64) {
+        let (path, source) = match &self.state.file {
+            Some(f) => (f.path.clone(), f.source()),
+            None => return,
+        };
+        let file_len = self.state.file_len();
+        self.state.classification = None;
+        self.state.classification_block_size = Some(block_size);
+        self.state.cached_class_counts = None;
+        self.state.minimap_cache.invalidate();
+        if file_len == 0 {
+            self.state.classification = Some(vec![]);
+            return;
+        }
+
+        let (classify_tx, classify_rx) = mpsc::channel();
+        self.classify_rx = Some(classify_rx);
+        self.computing_classification = true;
+        self.classify_progress = 0.0;
+        let stride = self.state.analysis_mode.stride() as usize;
+        let errors = self.error_reporter(GpuJob::Analysis, &path);
+
+        self.spawn_gpu_worker(move || {
+            let ctx = match pollster::block_on(tv_gpu::GpuContext::new()) {
+                Ok(ctx) => ctx,
+                Err(e) => {
+                    errors.report(format!("GPU init failed: {}", e));
+                    return;
+                }
+            };
+            let file = match source.open() {
+                Ok(f) => f,
+                Err(e) => {
+                    errors.report(format!("Failed to reopen the file: {}", e));
+                    return;
+                }
+            };
+
+            let total_blocks = file_len.div_ceil(block_size) as usize;
+            log::info!("Reclassifying with block size {} ({} blocks)", block_size, total_blocks);
+            let blocks = for_each_analysis_chunk(&file, block_size, stride, |chunk| {
+                match ctx.compute_classification(chunk.data, block_size as u32) {
+                    Ok(classes) => classify_tx
+                        .send(ClassifyChunk {
+                            start_block: chunk.start_block,
+                            values: chunk.expand(classes),
+                            total_blocks: chunk.total_blocks,
+                        })
+                        .is_ok(),
+                    Err(e) => {
+                        let offset = chunk.start_block as u64 * block_size;
+                        errors.report(format!("Classification at offset 0x{:X}: {}", offset, e));
+                        false
+                    }
+                }
+            });
+            log::info!("Classification complete: {} blocks", blocks);
+        });
+    }
+
+    fn launch_entropy_compute(&mut self) {
+        let (path, source) = match &self.state.file {
+            Some(f) => (f.path.clone(), f.source()),
+            None => return,
+        };
+        let file_len = self.state.file_len();
         if file_len == 0 {
             self.state.entropy = Some(vec![]);
             self.state.classification = Some(vec![]);
@@ -670,96 +2126,76 @@ impl TitanViewApp {
         self.classify_rx = Some(classify_rx);
         self.computing_entropy = true;
         self.computing_classification = true;
+        self.entropy_progress = 0.0;
+        self.classify_progress = 0.0;
 
-        let path = path.clone();
+        let stride = self.state.analysis_mode.stride() as usize;
+        let errors = self.error_reporter(GpuJob::Analysis, &path);
 
-        std::thread::spawn(move || {
+        self.spawn_gpu_worker(move || {
             // Init GPU on this thread
             let ctx = match pollster::block_on(tv_gpu::GpuContext::new()) {
                 Ok(ctx) => ctx,
                 Err(e) => {
-                    log::error!("GPU init failed: {}", e);
+                    errors.report(format!("GPU init failed: {}", e));
                     return;
                 }
             };
 
             // Open a separate mmap for this thread (MappedFile is not Send)
-            let file = match MappedFile::open(&path) {
+            let file = match source.open() {
                 Ok(f) => f,
                 Err(e) => {
-                    log::error!("Failed to reopen file for GPU: {}", e);
+                    errors.report(format!("Failed to reopen the file: {}", e));
                     return;
                 }
             };
 
-            // Adaptive block size based on file size:
-            // - Small files (<64MB): 256 bytes (high resolution)
-            // - Medium files (<1GB): 1KB
-            // - Large files (<4GB): 4KB
-            // - Very large files (>4GB): 16KB
-            // This keeps total blocks under ~1M for reasonable performance
-            let block_size: u64 = if file_len < 64 * 1024 * 1024 {
-                256
-            } else if file_len < 1024 * 1024 * 1024 {
-                1024
-            } else if file_len < 4 * 1024 * 1024 * 1024 {
-                4096
-            } else {
-                16384
-            };
+            // Adaptive block size keeps total blocks under ~1M for reasonable performance
+            let block_size = tv_ui::state::entropy_block_size(file_len);
             let total_blocks = file_len.div_ceil(block_size) as usize;
             log::info!("Using block size {} for {} blocks", block_size, total_blocks);
 
-            // Process in chunks of ~64 MB to report progress (larger chunks = fewer GPU dispatches)
-            let bytes_per_chunk: u64 = 64 * 1024 * 1024;
-            let mut offset: u64 = 0;
-            let mut block_offset: usize = 0;
-
-            while offset < file_len {
-                let chunk_len = bytes_per_chunk.min(file_len - offset);
-                let chunk_data = file.slice(tv_core::FileRegion::new(offset, chunk_len));
+            if stride > 1 {
+                log::info!("Sampling one block in {} ({} samples)", stride, total_blocks.div_ceil(stride));
+            }
 
+            let blocks = for_each_analysis_chunk(&file, block_size, stride, |chunk| {
+                let offset = chunk.start_block as u64 * block_size;
                 // Dispatch 1: Entropy
-                match ctx.compute_entropy(chunk_data, block_size as u32) {
-                    Ok(values) => {
-                        let num_values = values.len();
-                        if entropy_tx.send(EntropyChunk {
-                            start_block: block_offset,
-                            values,
-                            total_blocks,
-                        }).is_err() {
-                            return;
-                        }
-
-                        // Dispatch 2: Classification (same chunk)
-                        match ctx.compute_classification(chunk_data, block_size as u32) {
-                            Ok(classes) => {
-                                if classify_tx.send(ClassifyChunk {
-                                    start_block: block_offset,
-                                    values: classes,
-                                    total_blocks,
-                                }).is_err() {
-                                    return;
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("GPU classification failed at offset {}: {}", offset, e);
-                                return;
-                            }
-                        }
-
-                        block_offset += num_values;
-                    }
+                let values = match ctx.compute_entropy(chunk.data, block_size as u32) {
+                    Ok(values) => chunk.expand(values),
                     Err(e) => {
-                        log::error!("GPU entropy failed at offset {}: {}", offset, e);
-                        return;
+                        errors.report(format!("Entropy at offset 0x{:X}: {}", offset, e));
+                        return false;
                     }
+                };
+                let sent = entropy_tx.send(EntropyChunk {
+                    start_block: chunk.start_block,
+                    values,
+                    total_blocks: chunk.total_blocks,
+                });
+                if sent.is_err() {
+                    return false;
                 }
 
-                offset += chunk_len;
-            }
+                // Dispatch 2: Classification (same chunk)
+                match ctx.compute_classification(chunk.data, block_size as u32) {
+                    Ok(classes) => classify_tx
+                        .send(ClassifyChunk {
+                            start_block: chunk.start_block,
+                            values: chunk.expand(classes),
+                            total_blocks: chunk.total_blocks,
+                        })
+                        .is_ok(),
+                    Err(e) => {
+                        errors.report(format!("Classification at offset 0x{:X}: {}", offset, e));
+                        false
+                    }
+                }
+            });
 
-            log::info!("Entropy + classification complete: {} blocks", block_offset);
+            log::info!("Entropy + classification complete: {} blocks", blocks);
         });
     }
 
@@ -769,52 +2205,124 @@ impl TitanViewApp {
             Some(p) => p.clone(),
             None => return,
         };
-        let path = match &self.state.file {
-            Some(f) => f.path.clone(),
+        let (path, source) = match &self.state.file {
+            Some(f) => (f.path.clone(), f.source()),
             None => return,
         };
-        let file_len = self.state.file_len();
+        let backend = self.state.search.backend;
+        let generation = self.state.search.generation;
+        let errors = self.error_reporter(GpuJob::Search, &path);
+        self.state.search.cancel.store(true, Ordering::Relaxed);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.state.search.cancel = Arc::clone(&cancel);
 
         let (tx, rx) = mpsc::channel();
         self.search_rx = Some(rx);
 
-        std::thread::spawn(move || {
+        let search = move || {
             let start_time = std::time::Instant::now();
 
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                Self::run_search(path, file_len, pattern)
+                Self::run_search(&source, pattern, backend, &cancel, generation)
             }));
 
-            let duration_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+            // Searches that did not finish report the time until they stopped
+            let stopped = SearchResult {
+                offsets: vec![],
+                total: 0,
+                duration_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+                backend,
+                generation,
+            };
 
-            let offsets = match result {
-                Ok(Ok(offsets)) => offsets,
+            // A cancelled search still reports back, under its stale generation
+            let result = match result {
+                Ok(Ok(Some(found))) => found,
+                Ok(Ok(None)) => {
+                    log::info!("Search cancelled");
+                    stopped
+                }
                 Ok(Err(e)) => {
-                    log::error!("Pattern search failed: {}", e);
-                    vec![]
+                    errors.report(e.to_string());
+                    stopped
                 }
                 Err(_) => {
-                    log::error!("Pattern search panicked");
-                    vec![]
+                    errors.report("the search panicked");
+                    stopped
                 }
             };
 
-            log::info!("Parallel CPU search: {} matches in {:.1}ms", offsets.len(), duration_ms);
-            let _ = tx.send(SearchResult { offsets, duration_ms });
-        });
+            log::info!("{} search: {} matches in {:.1}ms", result.backend.label(), result.total, result.duration_ms);
+            let _ = tx.send(result);
+        };
+        if backend == SearchBackend::Gpu {
+            self.spawn_gpu_worker(search);
+        } else {
+            std::thread::spawn(search);
+        }
     }
 
-    /// Run the actual search using parallel CPU scanner (SIMD + rayon).
-    /// This is 5-20x faster than GPU for single patterns due to no PCIe overhead.
-    fn run_search(path: PathBuf, _file_len: u64, pattern: Vec<u8>) -> anyhow::Result<Vec<u64>> {
-        let file = MappedFile::open(&path)
+    /// Run the actual search with the requested scanner.
+    ///
+    /// The parallel CPU scanner (SIMD + rayon) is usually 5-20x faster than the
+    /// GPU for single patterns due to no PCIe overhead. The GPU falls back to the
+    /// CPU if it can't be initialized or the pattern is too long for the shader.
+    /// `cancel` is checked between 64 MB chunks.
+    /// Returns the matches for `generation`, or `None` if cancelled. The
+    /// duration leaves out opening the file and creating the GPU context, so
+    /// GPU and CPU times compare the scans alone.
+    fn run_search(
+        source: &FileSource,
+        pattern: Vec<u8>,
+        backend: SearchBackend,
+        cancel: &AtomicBool,
+        generation: u64,
+    ) -> anyhow::Result<Option<SearchResult>> {
+        let file = source.open()
             .map_err(|e| anyhow::anyhow!("Failed to reopen file for search: {}", e))?;
-
-        // Get the full file as a slice and run parallel SIMD search
         let data = file.slice(tv_core::FileRegion::new(0, file.len()));
-        let offsets = tv_core::scan_pattern_parallel(data, &pattern);
 
-        Ok(offsets)
+        let mut gpu = None;
+        if backend == SearchBackend::Gpu && pattern.len() <= tv_gpu::MAX_GPU_PATTERN_LEN {
+            match pollster::block_on(tv_gpu::GpuContext::new()) {
+                Ok(ctx) => gpu = Some(ctx),
+                Err(e) => log::warn!("GPU init for search failed, using CPU: {}", e),
+            }
+        }
+
+        let scan_start = std::time::Instant::now();
+
+        // Each chunk starts `pattern.len() - 1` bytes early; a match starting
+        // there doesn't fit in the previous chunk, so none is found twice
+        let bytes_per_chunk = 64 * 1024 * 1024;
+        let mut offsets = Vec::new();
+        let mut total = 0u64;
+        for chunk_start in (0..data.len()).step_by(bytes_per_chunk) {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            let start = chunk_start.saturating_sub(pattern.len().saturating_sub(1));
+            let end = (chunk_start + bytes_per_chunk).min(data.len());
+            let chunk = &data[start..end];
+            let (found, count) = match &gpu {
+                Some(ctx) => {
+                    let scan = ctx.scan_pattern(chunk, &pattern)?;
+                    (scan.offsets, scan.total)
+                }
+                // Parallel SIMD search
+                None => {
+                    let found = tv_core::scan_pattern_parallel(chunk, &pattern);
+                    let count = found.len() as u64;
+                    (found, count)
+                }
+            };
+            total += count;
+            offsets.extend(found.into_iter().map(|o| start as u64 + o));
+        }
+
+        let duration_ms = scan_start.elapsed().as_secs_f64() * 1000.0;
+        let backend = if gpu.is_some() { SearchBackend::Gpu } else { SearchBackend::Cpu };
+        Ok(Some(SearchResult { offsets, total, duration_ms, backend, generation }))
     }
 
     /// Poll search results channel.
@@ -825,9 +2333,16 @@ impl TitanViewApp {
         };
 
         match rx.try_recv() {
+            // Superseded by an edited query: drop it so the next search can start
+            Ok(result) if result.generation != self.state.search.generation => {
+                self.search_rx = None;
+            }
             Ok(result) => {
                 self.state.search.search_duration_ms = Some(result.duration_ms);
-                self.state.search.results = Some(result.offsets);
+                self.state.search.used_backend = Some(result.backend);
+                self.state.search.total_matches =
+                    (result.total > result.offsets.len() as u64).then_some(result.total);
+                self.state.search.results = Some(self.state.search.apply_alignment(result.offsets));
                 self.state.search.searching = false;
                 self.state.search.rebuild_highlights();
                 self.search_rx = None;
@@ -850,17 +2365,9 @@ impl TitanViewApp {
         let mut got_any = false;
         while let Ok(chunk) = rx.try_recv() {
             got_any = true;
-            let classification = self.state.classification.get_or_insert_with(|| {
-                vec![0u8; chunk.total_blocks]
-            });
-
-            if classification.len() < chunk.total_blocks {
-                classification.resize(chunk.total_blocks, 3); // default Binary
-            }
-
-            let end = (chunk.start_block + chunk.values.len()).min(classification.len());
-            classification[chunk.start_block..end]
-                .copy_from_slice(&chunk.values[..end - chunk.start_block]);
+            accumulate_classification(&mut self.state.classification, &chunk);
+            self.classify_progress = self.classify_progress
+                .max(chunk_progress(chunk.start_block, chunk.values.len(), chunk.total_blocks));
         }
 
         // Invalidate minimap cache when new classification data arrives
@@ -875,11 +2382,7 @@ impl TitanViewApp {
                     log::info!("Classification computation finished");
                     // Cache classification counts to avoid recomputing every frame
                     if let Some(ref classification) = self.state.classification {
-                        let mut counts = [0u32; 5];
-                        for &c in classification.iter() {
-                            counts[(c as usize).min(4)] += 1;
-                        }
-                        self.state.cached_class_counts = Some(counts);
+                        self.state.cached_class_counts = Some(class_counts(classification));
                     }
                 }
                 _ => {}
@@ -898,18 +2401,9 @@ impl TitanViewApp {
         let mut got_any = false;
         while let Ok(chunk) = rx.try_recv() {
             got_any = true;
-            let entropy = self.state.entropy.get_or_insert_with(|| {
-                vec![0.0f32; chunk.total_blocks]
-            });
-
-            // Ensure vec is large enough
-            if entropy.len() < chunk.total_blocks {
-                entropy.resize(chunk.total_blocks, 0.0);
-            }
-
-            let end = (chunk.start_block + chunk.values.len()).min(entropy.len());
-            entropy[chunk.start_block..end]
-                .copy_from_slice(&chunk.values[..end - chunk.start_block]);
+            accumulate_entropy(&mut self.state.entropy, &chunk);
+            self.entropy_progress = self.entropy_progress
+                .max(chunk_progress(chunk.start_block, chunk.values.len(), chunk.total_blocks));
         }
 
         // Invalidate minimap cache when new entropy data arrives
@@ -926,13 +2420,8 @@ impl TitanViewApp {
                     log::info!("Entropy computation finished");
                     // Cache entropy stats to avoid recomputing every frame
                     if let Some(ref entropy) = self.state.entropy {
-                        if !entropy.is_empty() {
-                            let sum: f32 = entropy.iter().sum();
-                            let avg = sum / entropy.len() as f32;
-                            self.state.cached_entropy_stats = Some(tv_ui::state::EntropyStats {
-                                avg,
-                                block_count: entropy.len(),
-                            });
+                        if let Some(stats) = entropy_stats(entropy) {
+                            self.state.cached_entropy_stats = Some(stats);
                         }
                     }
                 }
@@ -944,107 +2433,81 @@ impl TitanViewApp {
     /// Launch GPU deep scan (multi-pattern signature detection on full file).
     /// Processes file in 64MB chunks and streams results progressively.
     fn launch_deep_scan(&mut self) {
-        let path = match &self.state.file {
-            Some(f) => f.path.clone(),
+        let (path, source) = match &self.state.file {
+            Some(f) => (f.path.clone(), f.source()),
             None => return,
         };
         let file_len = self.state.file_len();
+        let signatures = self.state.deep_scan.scan_category.signatures();
+        let chunk_size = self.state.deep_scan.scan_chunk_bytes();
 
         // Initialize progress tracking
         self.state.deep_scan.bytes_scanned = 0;
         self.state.deep_scan.total_bytes = file_len;
+        self.state.deep_scan.clear_results();
         self.state.deep_scan.results = Some(Vec::new()); // Start with empty vec
 
+        let errors = self.error_reporter(GpuJob::DeepScan, &path);
+
         let (tx, rx) = mpsc::channel();
         self.deep_scan_rx = Some(rx);
 
-        std::thread::spawn(move || {
+        self.spawn_gpu_worker(move || {
             let start_time = std::time::Instant::now();
+            // Ends the scan early (on failure) with an empty final chunk
+            let finish = || {
+                let _ = tx.send(DeepScanChunk {
+                    signatures: vec![],
+                    extents: vec![],
+                    bytes_scanned: file_len,
+                    total_bytes: file_len,
+                    is_final: true,
+                    duration_ms: Some(start_time.elapsed().as_secs_f64() * 1000.0),
+                });
+            };
 
             // Init GPU on worker thread
             let ctx = match pollster::block_on(tv_gpu::GpuContext::new()) {
                 Ok(ctx) => ctx,
                 Err(e) => {
-                    log::error!("GPU init for deep scan failed: {}", e);
-                    let _ = tx.send(DeepScanChunk {
-                        signatures: vec![],
-                        bytes_scanned: file_len,
-                        total_bytes: file_len,
-                        is_final: true,
-                        duration_ms: Some(start_time.elapsed().as_secs_f64() * 1000.0),
-                    });
+                    errors.report(format!("GPU init failed: {}", e));
+                    finish();
                     return;
                 }
             };
 
-            let file = match MappedFile::open(&path) {
+            let file = match source.open() {
                 Ok(f) => f,
                 Err(e) => {
-                    log::error!("Failed to reopen file for deep scan: {}", e);
-                    let _ = tx.send(DeepScanChunk {
-                        signatures: vec![],
-                        bytes_scanned: file_len,
-                        total_bytes: file_len,
-                        is_final: true,
-                        duration_ms: Some(start_time.elapsed().as_secs_f64() * 1000.0),
-                    });
+                    errors.report(format!("Failed to reopen the file: {}", e));
+                    finish();
                     return;
                 }
             };
 
-            // Build pattern list from all signatures
-            let signatures = tv_core::signatures::SIGNATURES;
-            let patterns: Vec<&[u8]> = signatures.iter().map(|s| s.magic).collect();
-            let max_pattern_len = patterns.iter().map(|p| p.len()).max().unwrap_or(0) as u64;
-
-            // Process in 64MB chunks for progressive results and lower memory
-            const CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+            // Process in chunks for progressive results and lower memory
             let mut offset: u64 = 0;
             let mut total_found = 0usize;
 
             while offset < file_len {
-                // Overlap chunks by max pattern length to catch matches at boundaries
-                let overlap = if offset > 0 { max_pattern_len.saturating_sub(1) } else { 0 };
-                let chunk_start = offset.saturating_sub(overlap);
-                let chunk_len = CHUNK_SIZE.min(file_len - chunk_start);
-                let chunk_data = file.slice(tv_core::FileRegion::new(chunk_start, chunk_len));
-
-                // Run GPU multi-pattern scan on this chunk
-                let chunk_matches = match ctx.scan_multi_pattern(chunk_data, &patterns) {
-                    Ok(m) => m,
+                let chunk_sigs = match scan_signature_chunk(&ctx, &file, &signatures, offset, chunk_size) {
+                    Ok(hits) => hits,
                     Err(e) => {
-                        log::error!("Deep scan chunk failed at offset {}: {}", offset, e);
-                        vec![]
+                        errors.report(e.to_string());
+                        finish();
+                        return;
                     }
                 };
-
-                // Convert matches to SignatureHits, adjusting offsets for chunk position
-                // Filter out duplicates from overlap region (only keep matches >= offset)
-                let chunk_sigs: Vec<tv_ui::state::SignatureHit> = chunk_matches
-                    .into_iter()
-                    .filter_map(|m| {
-                        let absolute_offset = chunk_start + m.offset;
-                        // Only include if this match starts at or after our non-overlap region
-                        if absolute_offset >= offset || offset == 0 {
-                            let sig = &signatures[m.pattern_idx as usize];
-                            Some(tv_ui::state::SignatureHit {
-                                offset: absolute_offset,
-                                name: sig.name.to_string(),
-                                magic: sig.magic.to_vec(),
-                            })
-                        } else {
-                            None // Duplicate from overlap
-                        }
-                    })
-                    .collect();
+                let extents = chunk_sigs.iter().map(|sig| signature_extent(&file, sig)).collect();
 
                 total_found += chunk_sigs.len();
-                let bytes_done = (offset + CHUNK_SIZE).min(file_len);
+                let bytes_done = (offset + chunk_size).min(file_len);
                 let is_final = bytes_done >= file_len;
 
                 // Send chunk results
                 if tx.send(DeepScanChunk {
                     signatures: chunk_sigs,
+                    extents,
                     bytes_scanned: bytes_done,
                     total_bytes: file_len,
                     is_final,
@@ -1057,31 +2520,108 @@ impl TitanViewApp {
                     return; // Receiver dropped
                 }
 
-                offset += CHUNK_SIZE;
+                offset += chunk_size;
             }
 
             log::info!("GPU deep scan complete: {} signatures", total_found);
         });
     }
 
+    /// Carve and hash every deep scan hit into `dir` in background.
+    fn launch_batch_carve(&mut self, dir: PathBuf) {
+        let source = match &self.state.file {
+            Some(f) => f.source(),
+            None => return,
+        };
+        let hits: Vec<(u64, String)> = match &self.state.deep_scan.results {
+            Some(results) => results.iter().map(|h| (h.offset, h.name.clone())).collect(),
+            None => return,
+        };
+        self.state.deep_scan.carve_progress = Some((0, hits.len()));
+
+        let (tx, rx) = mpsc::channel();
+        self.carve_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let result = run_batch_carve(&source, &hits, &dir, &tx);
+            let _ = tx.send(CarveMessage::Done(result));
+        });
+    }
+
+    /// Poll batch carve progress.
+    fn poll_batch_carve(&mut self) {
+        let rx = match &self.carve_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok(CarveMessage::Progress(done, total)) => {
+                    self.state.deep_scan.carve_progress = Some((done, total));
+                }
+                Ok(CarveMessage::Done(result)) => {
+                    self.state.deep_scan.carve_status = Some(match result {
+                        Ok((manifest, carved, rows)) => {
+                            log::info!("Carved {} files, manifest {}", carved, manifest.display());
+                            let skipped = rows - carved;
+                            (format!("Carved {} file(s), {} skipped. Manifest: {}", carved, skipped, manifest.display()), false)
+                        }
+                        Err(e) => {
+                            log::error!("Batch carve failed: {}", e);
+                            (format!("Carve failed: {}", e), true)
+                        }
+                    });
+                    self.state.deep_scan.carve_progress = None;
+                    self.carve_rx = None;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.state.deep_scan.carve_progress = None;
+                    self.carve_rx = None;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
+        }
+    }
+
     /// Launch Hilbert texture computation in background.
     fn launch_hilbert_compute(&mut self) {
-        let path = match &self.state.file {
-            Some(f) => f.path.clone(),
+        let (path, source) = match &self.state.file {
+            Some(f) => (f.path.clone(), f.source()),
             None => return,
         };
         let file_len = self.state.file_len();
         let texture_size = self.hilbert.texture_size;
         let mode = self.hilbert.mode.as_u32();
 
-        // Clone entropy and classification data if available
-        let entropy = self.state.entropy.clone();
-        let classification = self.state.classification.clone();
+        // Entropy mode gets one value per cell, from the blocks each cell covers
+        let total_pixels = (texture_size * texture_size) as u64;
+        let (entropy_warning, entropy_incomplete) = (self.hilbert.mode == tv_ui::HilbertMode::Entropy)
+            .then(|| self.entropy_warning())
+            .flatten()
+            .map_or((None, false), |(warning, incomplete)| (Some(warning), incomplete));
+        let entropy = self.state.entropy.as_deref()
+            .filter(|_| self.hilbert.mode == tv_ui::HilbertMode::Entropy)
+            .map(|entropy| {
+                let block_size = tv_ui::state::entropy_block_size(file_len);
+                hilbert_cell_entropy(entropy, block_size, file_len, total_pixels)
+            });
+        // The shader expects one class per entropy block
+        let classification = match (&self.state.classification, &self.state.entropy) {
+            (Some(classes), Some(entropy)) if self.state.classification_block_size.is_some() => {
+                Some(tv_ui::state::resample_classes(classes, entropy.len()))
+            }
+            (classes, _) => classes.clone(),
+        };
+
+        let errors = self.error_reporter(GpuJob::Hilbert, &path);
 
         let (tx, rx) = mpsc::channel();
         self.hilbert_rx = Some(rx);
 
-        std::thread::spawn(move || {
+        self.spawn_gpu_worker(move || {
             let start_time = std::time::Instant::now();
 
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -1091,11 +2631,9 @@ impl TitanViewApp {
 
                 // Pre-sample bytes for Byte Value (mode 2) or Bit Density (mode 3) using Hilbert mapping
                 let sampled_bytes = if mode == 2 || mode == 3 {
-                    let file = MappedFile::open(&path)
+                    let file = source.open()
                         .map_err(|e| anyhow::anyhow!("Failed to open file: {}", e))?;
 
-                    let total_pixels = (texture_size * texture_size) as u64;
-
                     if mode == 3 {
                         // Bit density mode: each pixel = 1 bit
                         // Sample bits from file (with downsampling if file is large)
@@ -1162,21 +2700,44 @@ impl TitanViewApp {
 
             let pixels = match result {
                 Ok(Ok(p)) => p,
+                // Dropping `tx` without a result ends the computation
                 Ok(Err(e)) => {
-                    log::error!("Hilbert computation failed: {}", e);
-                    vec![]
+                    errors.report(e.to_string());
+                    return;
                 }
                 Err(_) => {
-                    log::error!("Hilbert computation panicked");
-                    vec![]
+                    errors.report("the computation panicked");
+                    return;
                 }
             };
 
             log::info!("Hilbert texture computed: {}x{} in {:.1}ms", texture_size, texture_size, duration_ms);
-            let _ = tx.send(HilbertResult { pixels, duration_ms });
+            let _ = tx.send(HilbertResult { pixels, duration_ms, entropy_warning, entropy_incomplete });
         });
     }
 
+    /// Why the current entropy does not cover the whole file, if it doesn't,
+    /// and whether that is because it is incomplete (as opposed to sampled).
+    fn entropy_warning(&self) -> Option<(String, bool)> {
+        let Some(entropy) = &self.state.entropy else {
+            return Some(("Entropy not computed yet".to_string(), true));
+        };
+        let file_len = self.state.file_len();
+        let blocks = file_len.div_ceil(tv_ui::state::entropy_block_size(file_len));
+        if self.computing_entropy || (entropy.len() as u64) < blocks {
+            let progress = if self.computing_entropy {
+                self.entropy_progress
+            } else {
+                entropy.len() as f32 / blocks as f32
+            };
+            Some((format!("Entropy incomplete ({:.0}% of blocks)", progress * 100.0), true))
+        } else if self.state.analysis_mode.is_sampled() {
+            Some((format!("Entropy sampled (1 block in {})", self.state.analysis_mode.stride()), false))
+        } else {
+            None
+        }
+    }
+
     /// Poll Hilbert computation results.
     fn poll_hilbert(&mut self) {
         let rx = match &self.hilbert_rx {
@@ -1188,6 +2749,8 @@ impl TitanViewApp {
             Ok(result) => {
                 self.hilbert.pending_pixels = Some(result.pixels);
                 self.hilbert.compute_time_ms = Some(result.duration_ms);
+                self.hilbert.entropy_warning = result.entropy_warning;
+                self.hilbert.entropy_incomplete = result.entropy_incomplete;
                 self.hilbert_rx = None;
             }
             Err(mpsc::TryRecvError::Disconnected) => {
@@ -1198,6 +2761,44 @@ impl TitanViewApp {
         }
     }
 
+    /// Query the GPU device limits in the background for the Performance window.
+    fn launch_gpu_limits_probe(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        self.gpu_limits_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let result = pollster::block_on(tv_gpu::GpuContext::new())
+                .map(|ctx| tv_ui::GpuLimitsInfo {
+                    adapter: ctx.adapter_name.clone(),
+                    max_buffer_size: ctx.limits.max_buffer_size,
+                    max_storage_binding: ctx.limits.max_storage_buffer_binding_size as u64,
+                    max_workgroups_per_dimension: ctx.limits.max_compute_workgroups_per_dimension,
+                })
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Poll the GPU limits probe.
+    fn poll_gpu_limits(&mut self) {
+        let rx = match &self.gpu_limits_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.perf.gpu_limits = Some(result);
+                self.gpu_limits_rx = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.perf.gpu_limits = Some(Err("GPU probe stopped unexpectedly".to_string()));
+                self.gpu_limits_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
     /// Launch diff computation on GPU.
     fn launch_diff_compute(&mut self) {
         let file_a = match &self.state.file {
@@ -1209,39 +2810,42 @@ impl TitanViewApp {
             None => return,
         };
 
+        let max_diffs = self.state.diff.max_diffs;
+        let count_only = self.state.diff.count_only;
+
         let (tx, rx) = mpsc::channel();
         self.diff_rx = Some(rx);
 
-        std::thread::spawn(move || {
+        self.spawn_gpu_worker(move || {
             let start_time = std::time::Instant::now();
 
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 let ctx = pollster::block_on(tv_gpu::GpuContext::new())
                     .map_err(|e| anyhow::anyhow!("GPU init failed: {}", e))?;
 
-                // Compute diff with GPU, limit to 100k differences
-                ctx.compute_diff(&file_a, &file_b, 100_000)
+                if count_only {
+                    Ok((vec![], ctx.count_diff(&file_a, &file_b)?))
+                } else {
+                    ctx.compute_diff_counted(&file_a, &file_b, max_diffs)
+                }
             }));
 
             let duration_ms = start_time.elapsed().as_secs_f64() * 1000.0;
 
-            let (offsets, total_count) = match result {
-                Ok(Ok(diffs)) => {
-                    let count = diffs.len() as u64;
-                    (diffs, count)
-                }
+            let ((offsets, total_count), error) = match result {
+                Ok(Ok(diffs)) => (diffs, None),
                 Ok(Err(e)) => {
                     log::error!("Diff computation failed: {}", e);
-                    (vec![], 0)
+                    ((vec![], 0), Some(e.to_string()))
                 }
                 Err(_) => {
                     log::error!("Diff computation panicked");
-                    (vec![], 0)
+                    ((vec![], 0), Some("GPU diff panicked".to_string()))
                 }
             };
 
             log::info!("Diff computed: {} differences in {:.1} ms", total_count, duration_ms);
-            let _ = tx.send(DiffResult { offsets, total_count, duration_ms });
+            let _ = tx.send(DiffResult { offsets, total_count, duration_ms, error });
         });
     }
 
@@ -1254,11 +2858,14 @@ impl TitanViewApp {
 
         match rx.try_recv() {
             Ok(result) => {
+                if let Some(e) = result.error {
+                    self.session_status = Some((format!("Diff failed: {}", e), true));
+                }
+                self.state.diff.selected_diff = if result.offsets.is_empty() { None } else { Some(0) };
                 self.state.diff.diff_offsets = Some(result.offsets);
                 self.state.diff.diff_count = result.total_count;
                 self.state.diff.compute_time_ms = Some(result.duration_ms);
                 self.state.diff.computing = false;
-                self.state.diff.selected_diff = if result.total_count > 0 { Some(0) } else { None };
                 self.diff_rx = None;
             }
             Err(mpsc::TryRecvError::Disconnected) => {
@@ -1269,6 +2876,178 @@ impl TitanViewApp {
         }
     }
 
+    /// Deep scan both diff files and compare their signatures in background.
+    fn launch_signature_diff(&mut self) {
+        let (Some(file_a), Some(file_b)) = (&self.state.file, &self.state.diff.file_b) else {
+            self.state.diff.signature_diff_computing = false;
+            return;
+        };
+        let (source_a, source_b) = (file_a.source(), file_b.source());
+
+        let errors = self.error_reporter(GpuJob::SignatureDiff, &file_a.path);
+
+        let (tx, rx) = mpsc::channel();
+        self.signature_diff_rx = Some(rx);
+
+        self.spawn_gpu_worker(move || {
+            let ctx = match pollster::block_on(tv_gpu::GpuContext::new()) {
+                Ok(ctx) => ctx,
+                Err(e) => {
+                    errors.report(format!("GPU init failed: {}", e));
+                    return;
+                }
+            };
+            let (file_a, file_b) = match (source_a.open(), source_b.open()) {
+                (Ok(a), Ok(b)) => (a, b),
+                (Err(e), _) | (_, Err(e)) => {
+                    errors.report(format!("Failed to reopen the files: {}", e));
+                    return;
+                }
+            };
+
+            let (hits_a, hits_b) = match (scan_all_signatures(&ctx, &file_a), scan_all_signatures(&ctx, &file_b)) {
+                (Ok(a), Ok(b)) => (a, b),
+                (Err(e), _) | (_, Err(e)) => {
+                    errors.report(e.to_string());
+                    return;
+                }
+            };
+            let diff = tv_ui::state::diff_signatures(&hits_a, &hits_b, tv_ui::state::SIGNATURE_MATCH_TOLERANCE);
+            log::info!(
+                "Signature comparison: {} only in A, {} moved, {} only in B",
+                diff.only_in_a.len(), diff.moved.len(), diff.only_in_b.len()
+            );
+            let _ = tx.send(diff);
+        });
+    }
+
+    /// Compute the entropy of diff file B on the GPU, at the block size of
+    /// file A's analysis so both series line up block for block.
+    fn launch_entropy_diff(&mut self) {
+        let (Some(file_a), Some(file_b)) = (&self.state.file, &self.state.diff.file_b) else {
+            self.state.diff.entropy_b_computing = false;
+            return;
+        };
+        let block_size = tv_ui::state::entropy_block_size(file_a.mapped.len());
+        let source_b = file_b.source();
+
+        let errors = self.error_reporter(GpuJob::EntropyDiff, &file_a.path);
+
+        let (tx, rx) = mpsc::channel();
+        self.entropy_diff_rx = Some(rx);
+
+        self.spawn_gpu_worker(move || {
+            let ctx = match pollster::block_on(tv_gpu::GpuContext::new()) {
+                Ok(ctx) => ctx,
+                Err(e) => {
+                    errors.report(format!("GPU init failed: {}", e));
+                    return;
+                }
+            };
+            let file = match source_b.open() {
+                Ok(f) => f,
+                Err(e) => {
+                    errors.report(format!("Failed to reopen file B: {}", e));
+                    return;
+                }
+            };
+
+            // Same ~64 MB chunks as the main entropy pass (whole blocks per chunk)
+            let chunk_len = 64 * 1024 * 1024 / block_size * block_size;
+            let mut entropy = Vec::with_capacity(file.len().div_ceil(block_size) as usize);
+            let mut offset = 0;
+            while offset < file.len() {
+                let data = file.slice(tv_core::FileRegion::new(offset, chunk_len));
+                match ctx.compute_entropy(data, block_size as u32) {
+                    Ok(values) => entropy.extend(values),
+                    Err(e) => {
+                        errors.report(format!("Entropy of file B: {}", e));
+                        return;
+                    }
+                }
+                offset += chunk_len;
+            }
+            let _ = tx.send(entropy);
+        });
+    }
+
+    /// Poll the entropy of diff file B.
+    fn poll_entropy_diff(&mut self) {
+        let rx = match &self.entropy_diff_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        match rx.try_recv() {
+            Ok(entropy) => {
+                // Dropped if file B was closed in the meantime
+                if self.state.diff.entropy_b_computing {
+                    self.state.diff.entropy_b = Some(entropy);
+                    self.state.diff.show_entropy_diff = true;
+                }
+                self.state.diff.entropy_b_computing = false;
+                self.entropy_diff_rx = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.state.diff.entropy_b_computing = false;
+                self.entropy_diff_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Poll the signature comparison result.
+    fn poll_signature_diff(&mut self) {
+        // File B was closed; its comparison is no longer wanted
+        if self.state.diff.file_b.is_none() {
+            self.signature_diff_rx = None;
+            return;
+        }
+        let rx = match &self.signature_diff_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        match rx.try_recv() {
+            Ok(diff) => {
+                // Dropped if the comparison was cleared in the meantime
+                if self.state.diff.signature_diff_computing {
+                    self.state.diff.signature_diff = Some(diff);
+                    self.state.diff.show_signature_diff = true;
+                }
+                self.state.diff.signature_diff_computing = false;
+                self.signature_diff_rx = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.state.diff.signature_diff_computing = false;
+                self.signature_diff_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Label strings referenced by data xrefs in the current XRef table.
+    fn analyze_strings(&mut self) {
+        let (Some(file), Some(table)) = (&self.state.file, &self.xrefs.table) else {
+            return;
+        };
+        self.bookmarks.ensure_project(file);
+        let Some(project) = self.bookmarks.project.as_mut() else {
+            return;
+        };
+
+        let data = file.mapped.slice(tv_core::FileRegion::new(0, file.mapped.len()));
+        let labels = tv_core::label_referenced_strings(table, data, project);
+        let count = labels.len();
+        for label in labels {
+            project.add_label(label);
+        }
+        if count > 0 {
+            self.bookmarks.modified = true;
+        }
+        self.xrefs.analysis_status = Some(format!("{} string label(s) created", count));
+    }
+
     /// Launch histogram computation in background thread.
     fn launch_histogram(&mut self) {
         let file = match &self.state.file {
@@ -1280,23 +3059,28 @@ impl TitanViewApp {
         };
 
         let file_len = file.mapped.len();
-        let path = file.path.clone();
+        let source = file.source();
 
         // Determine what region to analyze based on scope
-        let (start, len) = match self.histogram.scope {
-            tv_ui::HistogramScope::FullFile => {
-                (0u64, file_len.min(self.histogram.max_bytes as u64))
+        let region = match self.histogram.scope {
+            tv_ui::HistogramScope::FullFile | tv_ui::HistogramScope::Selection => {
+                tv_core::FileRegion::new(0, file_len)
             }
             tv_ui::HistogramScope::Viewport => {
                 let vp_start = self.state.viewport.start;
                 let vp_size = (self.state.viewport.visible_bytes as u64)
                     .min(file_len - vp_start.min(file_len));
-                (vp_start, vp_size.min(self.histogram.max_bytes as u64))
-            }
-            tv_ui::HistogramScope::Selection => {
-                (0u64, file_len.min(self.histogram.max_bytes as u64))
+                tv_core::FileRegion::new(vp_start, vp_size)
             }
         };
+        // Over `max_bytes`: blocks spread across the range, or just its prefix
+        let max_bytes = self.histogram.max_bytes as u64;
+        let regions = if self.histogram.sampled {
+            tv_core::histogram::sample_regions(region, max_bytes)
+        } else {
+            vec![tv_core::FileRegion::new(region.offset, region.length.min(max_bytes))]
+        };
+        let sampled_from = (regions.len() > 1).then_some(region.length);
 
         let cached_file_size = self.histogram.cached_file_size();
         let cached_offset = self.histogram.cached_offset();
@@ -1305,16 +3089,34 @@ impl TitanViewApp {
         self.histogram_rx = Some(rx);
 
         std::thread::spawn(move || {
-            let file = match MappedFile::open(&path) {
+            let file = match source.open() {
                 Ok(f) => f,
                 Err(_) => return,
             };
 
-            let data = file.slice(tv_core::FileRegion::new(start, len));
-            let histogram = ByteHistogram::from_data(data);
+            let mut histogram = ByteHistogram::new();
+            for &region in &regions {
+                histogram.merge(&ByteHistogram::from_data(file.slice(region)));
+            }
+            // First and last occurrence among the bytes read
+            let anomalies = tv_core::histogram::anomalies(&histogram)
+                .into_iter()
+                .map(|a| {
+                    let mut ranges = regions.iter().filter_map(|&region| {
+                        tv_core::histogram::occurrence_range(file.slice(region), a.byte)
+                            .map(|(first, last)| (region.offset + first, region.offset + last))
+                    });
+                    let range = ranges.next().map(|first| {
+                        ranges.fold(first, |(start, _), (_, last)| (start, last))
+                    });
+                    (a, range)
+                })
+                .collect();
 
             let _ = tx.send(HistogramResult {
                 histogram,
+                anomalies,
+                sampled_from,
                 file_size: cached_file_size,
                 offset: cached_offset,
             });
@@ -1331,13 +3133,152 @@ impl TitanViewApp {
         match rx.try_recv() {
             Ok(result) => {
                 self.histogram.set_result(result.histogram, result.file_size, result.offset);
+                self.histogram.anomalies = result.anomalies;
+                self.histogram.sampled_from = result.sampled_from;
                 self.histogram_rx = None;
             }
             Err(mpsc::TryRecvError::Disconnected) => {
                 self.histogram.computing = false;
                 self.histogram_rx = None;
             }
-            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Compute the full-file byte histogram in the background (File Info sparkline).
+    fn launch_file_histogram(&mut self) {
+        let Some(source) = self.state.file.as_ref().map(|f| f.source()) else { return };
+        let (tx, rx) = mpsc::channel();
+        self.file_histogram_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let file = match source.open() {
+                Ok(f) => f,
+                Err(_) => return,
+            };
+            let data = file.slice(tv_core::FileRegion::new(0, file.len()));
+            let _ = tx.send(ByteHistogram::from_data(data));
+        });
+    }
+
+    /// Poll the full-file byte histogram.
+    fn poll_file_histogram(&mut self) {
+        let rx = match &self.file_histogram_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        match rx.try_recv() {
+            Ok(histogram) => {
+                self.state.byte_histogram = Some(histogram);
+                self.file_histogram_rx = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.file_histogram_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Hash the file with MD5, SHA-1 and SHA-256 in the background (File Info
+    /// and known-hash check), cancelling any hash still running.
+    fn launch_file_hash(&mut self) {
+        let Some((path, source)) = self.state.file.as_ref().map(|f| (f.path.clone(), f.source())) else { return };
+        self.state.file_hash_cancel.store(true, Ordering::Relaxed);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.state.file_hash_cancel = Arc::clone(&cancel);
+        self.state.file_digests = None;
+        self.state.file_hash_progress = Some(0.0);
+
+        let (tx, rx) = mpsc::channel();
+        self.file_hash_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let file = match source.open() {
+                Ok(f) => f,
+                Err(_) => return,
+            };
+            let total = file.len().max(1);
+            let data = file.slice(tv_core::FileRegion::new(0, file.len()));
+            let digests = tv_core::digest_data(data, tv_core::DIGEST_CHUNK, &cancel, |done| {
+                let _ = tx.send(HashMessage::Progress(done as f32 / total as f32));
+            });
+            match digests {
+                Some(digests) => {
+                    let _ = tx.send(HashMessage::Done(digests));
+                }
+                None => log::info!("Hashing cancelled: {}", path.display()),
+            }
+        });
+    }
+
+    /// Poll the file digests.
+    fn poll_file_hash(&mut self) {
+        let rx = match &self.file_hash_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok(HashMessage::Progress(fraction)) => {
+                    self.state.file_hash_progress = Some(fraction);
+                }
+                Ok(HashMessage::Done(digests)) => {
+                    self.state.file_digests = Some(digests);
+                    self.state.file_hash_progress = None;
+                    self.file_hash_rx = None;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.state.file_hash_progress = None;
+                    self.file_hash_rx = None;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
+        }
+    }
+
+    /// Compute the block checksums of file A and/or diff file B in the background.
+    fn launch_block_checksums(&mut self, files: Vec<(bool, PathBuf, FileSource)>) {
+        let (tx, rx) = mpsc::channel();
+        self.checksum_rx = Some(rx);
+        self.state.checksums.computing = true;
+
+        std::thread::spawn(move || {
+            for (is_b, path, source) in files {
+                // An unreadable file gets an empty series so it isn't retried every frame
+                let sums = match source.open() {
+                    Ok(file) => tv_core::compute_block_checksums(
+                        file.slice(tv_core::FileRegion::new(0, file.len())),
+                        tv_ui::state::CHECKSUM_BLOCK_SIZE,
+                    ),
+                    Err(_) => Vec::new(),
+                };
+                if tx.send((is_b, path, sums)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Poll the minimap checksum strips.
+    fn poll_block_checksums(&mut self) {
+        let Some(rx) = &self.checksum_rx else {
+            return;
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok((is_b, path, sums)) => self.state.checksums.set(is_b, path, sums),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.checksum_rx = None;
+                    self.state.checksums.computing = false;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
         }
     }
 
@@ -1352,25 +3293,12 @@ impl TitanViewApp {
         loop {
             match rx.try_recv() {
                 Ok(chunk) => {
-                    // Update progress
-                    self.state.deep_scan.bytes_scanned = chunk.bytes_scanned;
-                    self.state.deep_scan.total_bytes = chunk.total_bytes;
-
-                    // Accumulate signatures
-                    if !chunk.signatures.is_empty() {
-                        let results = self.state.deep_scan.results.get_or_insert_with(Vec::new);
-                        results.extend(chunk.signatures);
-                    }
-
-                    // Check if final chunk
-                    if chunk.is_final {
-                        self.state.deep_scan.duration_ms = chunk.duration_ms;
-                        self.state.deep_scan.scanning = false;
+                    if accumulate_deep_scan(&mut self.state.deep_scan, chunk) {
                         self.deep_scan_rx = None;
                         log::info!(
                             "Deep scan finished: {} signatures in {:.1}ms",
                             self.state.deep_scan.results.as_ref().map_or(0, |r| r.len()),
-                            chunk.duration_ms.unwrap_or(0.0)
+                            self.state.deep_scan.duration_ms.unwrap_or(0.0)
                         );
                         return;
                     }
@@ -1389,6 +3317,140 @@ impl TitanViewApp {
         }
     }
 
+    /// Three-column list of signatures only in A, moved, and only in B.
+    fn show_signature_diff(ui: &mut egui::Ui, state: &mut AppState) {
+        let Some(diff) = state.diff.signature_diff.as_ref() else {
+            return;
+        };
+        let mut goto_a: Option<u64> = None;
+        let mut goto_b: Option<u64> = None;
+
+        ui.separator();
+        ui.weak(format!("{} signature(s) unchanged", diff.unchanged));
+        ui.columns(3, |columns| {
+            columns[0].label(egui::RichText::new(format!("Only in A ({})", diff.only_in_a.len())).strong());
+            columns[1].label(egui::RichText::new(format!("Moved ({})", diff.moved.len())).strong());
+            columns[2].label(egui::RichText::new(format!("Only in B ({})", diff.only_in_b.len())).strong());
+
+            egui::ScrollArea::vertical().id_salt("sig_diff_a").max_height(150.0).show(&mut columns[0], |ui| {
+                for hit in &diff.only_in_a {
+                    if ui.link(format!("0x{:08X}  {}", hit.offset, hit.name)).clicked() {
+                        goto_a = Some(hit.offset);
+                    }
+                }
+            });
+            egui::ScrollArea::vertical().id_salt("sig_diff_moved").max_height(150.0).show(&mut columns[1], |ui| {
+                for moved in &diff.moved {
+                    let text = format!("{}  0x{:X} -> 0x{:X}", moved.name, moved.offset_a, moved.offset_b);
+                    if ui.link(text).clicked() {
+                        goto_a = Some(moved.offset_a);
+                        goto_b = Some(moved.offset_b);
+                    }
+                }
+            });
+            egui::ScrollArea::vertical().id_salt("sig_diff_b").max_height(150.0).show(&mut columns[2], |ui| {
+                for hit in &diff.only_in_b {
+                    if ui.link(format!("0x{:08X}  {}", hit.offset, hit.name)).clicked() {
+                        goto_b = Some(hit.offset);
+                    }
+                }
+            });
+        });
+
+        // Independent offsets in A and B need unsynced scrolling
+        if goto_a.is_some() && goto_b.is_some() {
+            state.diff.sync_scroll = false;
+        }
+        if let Some(offset) = goto_a {
+            state.viewport.start = (offset / 16) * 16;
+            state.diff.scroll_offset = 0.0;
+        }
+        if let Some(offset) = goto_b {
+            state.diff.viewport_b.start = (offset / 16) * 16;
+            if state.diff.sync_scroll {
+                state.viewport.start = state.diff.viewport_b.start;
+            }
+            state.diff.scroll_offset = 0.0;
+        }
+    }
+
+    /// Per-block entropy of A and B on a shared offset axis, with their
+    /// absolute difference as bars. Clicking jumps to the offset.
+    fn show_entropy_diff(ui: &mut egui::Ui, state: &mut AppState) {
+        const GRAPH_HEIGHT: f32 = 120.0;
+        let color_a = egui::Color32::from_rgb(100, 200, 100);
+        let color_b = egui::Color32::from_rgb(200, 100, 100);
+        let color_diff = egui::Color32::from_rgba_unmultiplied(230, 200, 80, 120);
+
+        ui.separator();
+        let (Some(entropy_a), Some(entropy_b)) = (state.entropy.as_deref(), state.diff.entropy_b.as_deref()) else {
+            ui.weak("Waiting for the entropy analysis of A...");
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.colored_label(color_a, "— A");
+            ui.colored_label(color_b, "— B");
+            ui.colored_label(color_diff, "▮ |A − B|");
+            ui.weak("bits/byte, 0 – 8");
+        });
+
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width(), GRAPH_HEIGHT),
+            egui::Sense::click(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::from_gray(25));
+
+        let blocks = entropy_a.len().max(entropy_b.len());
+        let columns = (rect.width() as usize).max(1);
+        let col_a = tv_ui::state::downsample_max(entropy_a, blocks, columns);
+        let col_b = tv_ui::state::downsample_max(entropy_b, blocks, columns);
+        let y = |v: f32| rect.bottom() - (v / 8.0).clamp(0.0, 1.0) * rect.height();
+        let x = |col: usize| rect.left() + col as f32 * rect.width() / columns as f32;
+
+        for (col, (a, b)) in col_a.iter().zip(&col_b).enumerate() {
+            if let (Some(a), Some(b)) = (a, b) {
+                let diff = (a - b).abs();
+                if diff > 0.0 {
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(egui::pos2(x(col), y(diff)), egui::pos2(x(col + 1), rect.bottom())),
+                        0.0,
+                        color_diff,
+                    );
+                }
+            }
+        }
+        for (series, color) in [(&col_a, color_a), (&col_b, color_b)] {
+            let points: Vec<egui::Pos2> = series
+                .iter()
+                .enumerate()
+                .filter_map(|(col, v)| v.map(|v| egui::pos2(x(col) + 0.5, y(v))))
+                .collect();
+            painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+        }
+
+        let block_size = tv_ui::state::entropy_block_size(state.file_len());
+        let hovered = response.hover_pos().map(|pos| {
+            let col = (((pos.x - rect.left()) / rect.width() * columns as f32) as usize).min(columns - 1);
+            (col, (col * blocks / columns) as u64 * block_size)
+        });
+        if response.clicked() {
+            if let Some((_, offset)) = hovered {
+                state.viewport.start = (offset / 16) * 16;
+                state.diff.scroll_offset = 0.0;
+            }
+        }
+        if let Some((col, offset)) = hovered {
+            let fmt = |v: Option<f32>| v.map_or("--".to_string(), |v| format!("{:.2}", v));
+            let (a, b) = (col_a[col], col_b[col]);
+            let diff = a.zip(b).map(|(a, b)| (a - b).abs());
+            response.on_hover_text(format!(
+                "0x{:X}\nA: {}  B: {}  |A − B|: {}\nClick to jump",
+                offset, fmt(a), fmt(b), fmt(diff)
+            ));
+        }
+    }
+
     /// Show split view for binary diff comparison.
     fn show_diff_split_view(ui: &mut egui::Ui, state: &mut AppState) {
         // Diff toolbar
@@ -1416,44 +3478,79 @@ impl TitanViewApp {
             // Compute diff button
             if state.diff.computing {
                 ui.spinner();
-                ui.label("Computing...");
+                ui.label(state.gpu_job_text(GpuJob::Diff, "Computing..."));
             } else {
                 if ui.button("Compute Diff").clicked() {
                     state.diff.computing = true;
                 }
 
+                ui.add_enabled(
+                    !state.diff.count_only,
+                    egui::DragValue::new(&mut state.diff.max_diffs)
+                        .range(1..=10_000_000)
+                        .speed(1000.0)
+                        .prefix("max "),
+                )
+                .on_hover_text("Maximum number of diff offsets to collect");
+                ui.checkbox(&mut state.diff.count_only, "Count all")
+                    .on_hover_text("Only count differing bytes (no offsets, no navigation)");
+
                 if let Some(count) = state.diff.diff_offsets.as_ref().map(|v| v.len()) {
                     let total = state.diff.diff_count;
-                    if total > count as u64 {
-                        ui.label(format!("{} diffs (showing {})", total, count));
+                    if count == 0 && total > 0 {
+                        ui.label(format!("{} diffs (counted only)", total));
+                    } else if total > count as u64 {
+                        ui.label(format!("{} diffs (showing first {})", total, count));
                     } else {
                         ui.label(format!("{} diffs", count));
                     }
 
-                    // Navigation
-                    let sel = state.diff.selected_diff.unwrap_or(0);
-                    if ui.small_button("<").on_hover_text("Previous diff").clicked() && sel > 0 {
-                        state.diff.selected_diff = Some(sel - 1);
-                        if let Some(offsets) = &state.diff.diff_offsets {
-                            if let Some(&offset) = offsets.get(sel - 1) {
-                                state.viewport.start = (offset / 16) * 16;
-                                state.diff.scroll_offset = 0.0;
+                    // Navigation (only when offsets were collected)
+                    if count > 0 {
+                        let sel = state.diff.selected_diff.unwrap_or(0);
+                        if ui.small_button("<").on_hover_text("Previous diff (Ctrl+Up: previous region)").clicked() && sel > 0 {
+                            state.diff.selected_diff = Some(sel - 1);
+                            if let Some(offsets) = &state.diff.diff_offsets {
+                                if let Some(&offset) = offsets.get(sel - 1) {
+                                    state.viewport.start = (offset / 16) * 16;
+                                    state.diff.scroll_offset = 0.0;
+                                    state.diff.selected_offset = Some(offset);
+                                }
                             }
                         }
-                    }
-                    ui.label(format!("{}/{}", sel + 1, count));
-                    if ui.small_button(">").on_hover_text("Next diff").clicked() && sel + 1 < count {
-                        state.diff.selected_diff = Some(sel + 1);
-                        if let Some(offsets) = &state.diff.diff_offsets {
-                            if let Some(&offset) = offsets.get(sel + 1) {
-                                state.viewport.start = (offset / 16) * 16;
-                                state.diff.scroll_offset = 0.0;
+                        ui.label(format!("{}/{}", sel + 1, count));
+                        if ui.small_button(">").on_hover_text("Next diff (Ctrl+Down: next region)").clicked() && sel + 1 < count {
+                            state.diff.selected_diff = Some(sel + 1);
+                            if let Some(offsets) = &state.diff.diff_offsets {
+                                if let Some(&offset) = offsets.get(sel + 1) {
+                                    state.viewport.start = (offset / 16) * 16;
+                                    state.diff.scroll_offset = 0.0;
+                                    state.diff.selected_offset = Some(offset);
+                                }
                             }
                         }
                     }
                 }
             }
 
+            // Selected byte in both files
+            if let Some(offset) = state.diff.selected_offset {
+                ui.separator();
+                let (a, b) = state.diff_bytes_at(offset);
+                let fmt_byte = |v: Option<u8>| v.map_or("--".to_string(), |v| format!("{:02X}", v));
+                let color = if a == b {
+                    egui::Color32::from_rgb(180, 180, 180)
+                } else {
+                    egui::Color32::from_rgb(255, 100, 100)
+                };
+                ui.label(egui::RichText::new(format!("0x{:X}", offset)).monospace());
+                ui.label(
+                    egui::RichText::new(format!("A: {}  B: {}", fmt_byte(a), fmt_byte(b)))
+                        .monospace()
+                        .color(color),
+                );
+            }
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("Close Diff").clicked() {
                     state.diff.active = false;
@@ -1462,11 +3559,65 @@ impl TitanViewApp {
                 if ui.button("Close B").clicked() {
                     state.diff.close_file_b();
                 }
+                ui.separator();
+                if state.diff.signature_diff_computing {
+                    ui.spinner();
+                    ui.label(state.gpu_job_text(GpuJob::SignatureDiff, "Scanning signatures..."));
+                } else if state.diff.signature_diff.is_some() {
+                    ui.toggle_value(&mut state.diff.show_signature_diff, "Signature changes");
+                    if ui.small_button("Rescan").clicked() {
+                        state.diff.signature_diff_computing = true;
+                    }
+                } else if ui.button("Compare Signatures")
+                    .on_hover_text("Deep scan both files and list added, moved and removed signatures")
+                    .clicked()
+                {
+                    state.diff.signature_diff_computing = true;
+                }
+                ui.separator();
+                if state.diff.entropy_b_computing {
+                    ui.spinner();
+                    ui.label(state.gpu_job_text(GpuJob::EntropyDiff, "Computing entropy of B..."));
+                } else if state.diff.entropy_b.is_some() {
+                    ui.toggle_value(&mut state.diff.show_entropy_diff, "Entropy profile");
+                } else if ui.button("Compare Entropy")
+                    .on_hover_text("Overlay the per-block entropy of both files to spot added encrypted or compressed regions")
+                    .clicked()
+                {
+                    state.diff.entropy_b_computing = true;
+                }
             });
         });
 
+        if state.diff.show_signature_diff {
+            Self::show_signature_diff(ui, state);
+        }
+        if state.diff.show_entropy_diff {
+            Self::show_entropy_diff(ui, state);
+        }
+
         ui.separator();
 
+        // Ctrl+Down / Ctrl+Up: next / previous diff region, centered in the panes
+        let [previous_region, next_region] = DIFF_REGION_SHORTCUTS;
+        let step = ui.input(|i| {
+            if command_palette::shortcut_pressed(i, &next_region) {
+                Some(true)
+            } else if command_palette::shortcut_pressed(i, &previous_region) {
+                Some(false)
+            } else {
+                None
+            }
+        });
+        if let Some((first, _)) = step.and_then(|forward| state.diff.step_region(forward)) {
+            let rows_above = HexPanel::rows_in(ui.available_height(), state.hex_font_size) / 2;
+            state.viewport.start = (first / 16).saturating_sub(rows_above) * 16;
+            state.diff.scroll_offset = 0.0;
+            if !state.diff.sync_scroll {
+                state.diff.viewport_b.start = state.viewport.start;
+            }
+        }
+
         // Split view with synchronized scrolling
         let scroll_offset = state.diff.scroll_offset;
         let mut new_scroll_a = scroll_offset;
@@ -1499,172 +3650,167 @@ impl eframe::App for TitanViewApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Update performance metrics
         self.perf.begin_frame();
+        if self.perf.visible && self.perf.gpu_limits.is_none() && self.gpu_limits_rx.is_none() {
+            self.launch_gpu_limits_probe();
+        }
+        self.poll_gpu_limits();
+        if self.perf.visible {
+            self.perf.gpu_memory = tv_gpu::memory_usage().into_iter()
+                .map(|op| tv_ui::GpuMemoryInfo {
+                    operation: op.operation.to_string(),
+                    last_bytes: op.last_bytes,
+                    peak_bytes: op.peak_bytes,
+                    over_budget: op.over_budget,
+                })
+                .collect();
+        }
 
         // Handle drag & drop
-        ctx.input(|i| {
-            if !i.raw.dropped_files.is_empty() {
-                if let Some(path) = i.raw.dropped_files[0].path.clone() {
-                    self.pending_drop = Some(path);
-                }
-            }
-        });
+        let dropped: Vec<Option<PathBuf>> =
+            ctx.input(|i| i.raw.dropped_files.iter().map(|f| f.path.clone()).collect());
+        if !dropped.is_empty() {
+            self.handle_drop(dropped);
+        }
         if let Some(path) = self.pending_drop.take() {
             self.open_file(path);
         }
 
         // Poll background results
+        self.poll_worker_errors();
         self.poll_entropy();
         self.poll_classification();
         self.poll_search();
         self.poll_deep_scan();
+        self.poll_batch_carve();
         self.poll_hilbert();
         self.poll_diff();
+        self.poll_signature_diff();
+        self.poll_entropy_diff();
         self.poll_histogram();
+        self.poll_file_histogram();
+        self.poll_file_hash();
+        self.poll_block_checksums();
 
-        // Check if search was requested by the UI
-        if self.state.search.searching && self.search_rx.is_none() {
+        // Check if search was requested by the UI (only GPU searches wait for the GPU)
+        if self.state.search.searching && self.search_rx.is_none()
+            && (self.state.search.backend == SearchBackend::Cpu || self.gpu_available_for(GpuJob::Search))
+        {
             self.launch_search();
         }
 
+        // Start requested GPU work one operation at a time; the others stay
+        // requested and start once the GPU is free
+        if self.state.analysis_request.is_some() && self.gpu_available_for(GpuJob::Analysis) {
+            if let Some(mode) = self.state.analysis_request.take() {
+                self.relaunch_entropy_compute(mode);
+            }
+        }
+        // A classification recompute replaces the classification pass of a
+        // running analysis, so it waits for the analysis to finish
+        if self.state.classification_request.is_some()
+            && !self.computing_entropy
+            && !self.computing_classification
+            && self.gpu_available_for(GpuJob::Analysis)
+        {
+            if let Some(block_size) = self.state.classification_request.take() {
+                self.launch_classification_compute(block_size);
+            }
+        }
+
         // Check if histogram computation was requested
         if self.histogram.computing && self.histogram_rx.is_none() {
             self.launch_histogram();
         }
 
         // Check if deep scan was requested by the UI
-        if self.state.deep_scan.scanning && self.deep_scan_rx.is_none() {
+        if self.state.deep_scan.scanning && self.deep_scan_rx.is_none() && self.gpu_available_for(GpuJob::DeepScan) {
             self.launch_deep_scan();
         }
+        if let Some(dir) = self.state.deep_scan.carve_request.take() {
+            if self.carve_rx.is_none() {
+                self.launch_batch_carve(dir);
+            }
+        }
 
-        // Check if Hilbert computation was requested
-        if self.hilbert.computing && self.hilbert_rx.is_none() {
+        // Check if Hilbert computation was requested (entropy mode also
+        // waits here for a running analysis, which holds the GPU)
+        if self.hilbert.computing && self.hilbert_rx.is_none() && self.gpu_available_for(GpuJob::Hilbert) {
             self.launch_hilbert_compute();
         }
 
         // Check if diff computation was requested
-        if self.state.diff.computing && self.diff_rx.is_none() {
+        if self.state.diff.computing && self.diff_rx.is_none() && self.gpu_available_for(GpuJob::Diff) {
             self.launch_diff_compute();
         }
+        if self.state.diff.signature_diff_computing && self.signature_diff_rx.is_none()
+            && self.gpu_available_for(GpuJob::SignatureDiff)
+        {
+            self.launch_signature_diff();
+        }
+        if self.state.diff.entropy_b_computing && self.entropy_diff_rx.is_none()
+            && self.gpu_available_for(GpuJob::EntropyDiff)
+        {
+            self.launch_entropy_diff();
+        }
+        // Checksum strip: (re)compute for the files currently shown
+        if self.state.checksums.enabled && self.checksum_rx.is_none() {
+            let missing = self.state.checksums.missing(
+                self.state.file.as_ref().map(|f| f.path.as_path()),
+                self.state.diff.file_b.as_ref().map(|f| f.path.as_path()),
+            );
+            if !missing.is_empty() {
+                let files = missing.into_iter()
+                    .filter_map(|(is_b, path)| {
+                        let file = if is_b { self.state.diff.file_b.as_ref() } else { self.state.file.as_ref() };
+                        file.map(|f| (is_b, path, f.source()))
+                    })
+                    .collect();
+                self.launch_block_checksums(files);
+            }
+        }
+        self.state.gpu_job = self.running_gpu_job().or_else(|| self.parked_gpu_job());
 
         // Request repaint while computing or when any floating window needs updates
         if self.computing_entropy || self.computing_classification
             || self.state.search.searching || self.state.deep_scan.scanning
+            || self.state.deep_scan.carve_progress.is_some()
+            || self.state.file_hash_progress.is_some()
             || self.hilbert.computing || self.state.diff.computing
-            || self.histogram.computing || self.perf.visible {
+            || self.state.diff.signature_diff_computing || self.state.diff.entropy_b_computing
+            || self.histogram.computing || self.state.checksums.computing || self.perf.visible
+            || self.gpu_workers_running() {
             ctx.request_repaint();
         }
 
-        // Handle keyboard shortcuts
+        // Handle keyboard shortcuts: registered commands, then the palette itself
+        // (not while the shortcuts window waits for a new binding)
+        let capturing = self.shortcuts.capturing.is_some();
+        let (commands, open_palette) = ctx.input(|i| (
+            command_palette::pressed_commands(i, &self.key_bindings),
+            command_palette::shortcut_pressed(i, &PALETTE_SHORTCUT),
+        ));
+        let (mut commands, open_palette) = if capturing { (Vec::new(), false) } else { (commands, open_palette) };
+        if ctx.wants_keyboard_input() {
+            commands.retain(Command::works_while_typing);
+        }
+        for command in commands {
+            self.execute_command(ctx, command);
+        }
+        if open_palette {
+            self.palette.open();
+        }
         ctx.input(|i| {
-            // F1: File Info
-            if i.key_pressed(egui::Key::F1) {
-                self.show_file_info = !self.show_file_info;
-            }
-            // F2: Signatures
-            if i.key_pressed(egui::Key::F2) {
-                self.show_signatures = !self.show_signatures;
-            }
-            // F3: Performance
-            if i.key_pressed(egui::Key::F3) {
-                self.perf.visible = !self.perf.visible;
-            }
-            // F4: Hilbert Curve
-            if i.key_pressed(egui::Key::F4) {
-                self.show_hilbert = !self.show_hilbert;
-            }
-            // F5: Disassembly
-            if i.key_pressed(egui::Key::F5) {
-                self.show_disasm = !self.show_disasm;
-            }
-            // F6: Diff mode (if file B is loaded, toggle; otherwise open file dialog)
-            if i.key_pressed(egui::Key::F6) {
-                if self.state.diff.file_b.is_some() {
-                    self.state.diff.active = !self.state.diff.active;
-                } else if self.state.has_file() {
-                    // Open file B dialog
-                    if let Some(path) = rfd::FileDialog::new().pick_file() {
-                        if let Ok(mapped) = tv_core::MappedFile::open(&path) {
-                            self.state.diff.file_b = Some(tv_ui::state::LoadedFile { path, mapped });
-                            self.state.diff.viewport_b = tv_core::ViewPort::new(0, 4096);
-                            self.state.diff.sync_scroll = true;
-                            self.state.diff.active = true;
-                            self.state.diff.clear();
-                        }
-                    }
-                }
-            }
-            // F7: Structure Inspector
-            if i.key_pressed(egui::Key::F7) {
-                self.show_inspector = !self.show_inspector;
-            }
-
-            // F8: Byte Histogram
-            if i.key_pressed(egui::Key::F8) {
-                self.show_histogram = !self.show_histogram;
-            }
-
-            // F9: Cross-References
-            if i.key_pressed(egui::Key::F9) {
-                self.show_xrefs = !self.show_xrefs;
-                // Build XRefs from current disassembly if available
-                if self.show_xrefs && self.xrefs.table.is_none() {
-                    if let Some(ref result) = self.disasm.result {
-                        self.xrefs.build_from_instructions(&result.instructions);
-                    }
-                }
-            }
-
-            // F10: Bookmarks & Labels
-            if i.key_pressed(egui::Key::F10) {
-                self.show_bookmarks = !self.show_bookmarks;
-            }
-            // F11: Script Console
-            if i.key_pressed(egui::Key::F11) {
-                self.show_script = !self.show_script;
-            }
-            // Ctrl+F: Search
-            if i.modifiers.ctrl && i.key_pressed(egui::Key::F) {
-                self.show_search = !self.show_search;
-            }
-            // Ctrl+S: Save Session
-            if i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::S) {
-                if self.state.has_file() {
-                    self.save_session();
-                }
-            }
-            // Ctrl+Shift+S: Save Session As
-            if i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::S) {
-                if self.state.has_file() {
-                    self.save_session_as();
-                }
-            }
-            // Ctrl+O: Open file (standard shortcut)
-            if i.modifiers.ctrl && i.key_pressed(egui::Key::O) {
-                if let Some(path) = rfd::FileDialog::new().pick_file() {
-                    // Check if it's a session file
-                    if path.extension().and_then(|e| e.to_str()) == Some(SESSION_EXTENSION) {
-                        self.load_session(path);
-                    } else {
-                        self.open_file(path);
-                    }
-                }
-            }
             // Workspace shortcuts: Ctrl+1 through Ctrl+5
-            for (key, num) in [
-                (egui::Key::Num1, 1u8),
-                (egui::Key::Num2, 2u8),
-                (egui::Key::Num3, 3u8),
-                (egui::Key::Num4, 4u8),
-                (egui::Key::Num5, 5u8),
-            ] {
-                if i.modifiers.ctrl && i.key_pressed(key) {
+            for (shortcut, num) in WORKSPACE_SHORTCUTS.iter().zip(1u8..) {
+                if command_palette::shortcut_pressed(i, shortcut) {
                     if let Some(idx) = self.workspaces.find_by_shortcut(num) {
                         self.apply_workspace(idx);
                     }
                 }
             }
-            // Escape: Close all floating windows and diff mode (except minimap)
-            if i.key_pressed(egui::Key::Escape) {
+            // Escape: Close all floating windows and diff mode (except minimap);
+            // the palette closes itself first
+            if command_palette::shortcut_pressed(i, &ESCAPE_SHORTCUT) && !self.palette.open && !capturing {
                 self.show_file_info = false;
                 self.show_search = false;
                 self.show_signatures = false;
@@ -1673,6 +3819,7 @@ impl eframe::App for TitanViewApp {
                 self.show_inspector = false;
                 self.show_script = false;
                 self.state.diff.active = false;
+                self.choosing_diff_b = false;
                 self.perf.visible = false;
             }
         });
@@ -1683,19 +3830,35 @@ impl eframe::App for TitanViewApp {
                 // File menu
                 ui.menu_button("File", |ui| {
                     if ui.button("Open...  (Ctrl+O)").clicked() {
-                        if let Some(path) = rfd::FileDialog::new().pick_file() {
+                        if let Some(path) = file_dialog::new().pick_file_remembered() {
                             self.open_file(path);
                         }
                         ui.close_menu();
                     }
+                    if ui.button("Open Read-Only...")
+                        .on_hover_text("Edit mode stays disabled (evidence integrity)")
+                        .clicked()
+                    {
+                        if let Some(path) = file_dialog::new().pick_file_remembered() {
+                            self.open_file_read_only(path);
+                        }
+                        ui.close_menu();
+                    }
+                    if ui.button("New from Clipboard")
+                        .on_hover_text("Open hex or Base64 text from the clipboard as an in-memory file")
+                        .clicked()
+                    {
+                        self.execute_command(ctx, Command::OpenClipboard);
+                        ui.close_menu();
+                    }
 
                     ui.separator();
 
                     // Session management
                     if ui.button("Open Session...").clicked() {
-                        if let Some(path) = rfd::FileDialog::new()
+                        if let Some(path) = file_dialog::new()
                             .add_filter("TitanView Session", &[SESSION_EXTENSION])
-                            .pick_file()
+                            .pick_file_remembered()
                         {
                             self.load_session(path);
                         }
@@ -1707,12 +3870,20 @@ impl eframe::App for TitanViewApp {
                     } else {
                         "Save Session    (Ctrl+S)"
                     };
-                    if ui.add_enabled(self.state.has_file(), egui::Button::new(save_label)).clicked() {
+                    let savable = self.session_savable();
+                    let unsavable_hint = "In-memory files can't be saved in a session";
+                    if ui.add_enabled(savable, egui::Button::new(save_label))
+                        .on_disabled_hover_text(unsavable_hint)
+                        .clicked()
+                    {
                         self.save_session();
                         ui.close_menu();
                     }
 
-                    if ui.add_enabled(self.state.has_file(), egui::Button::new("Save Session As...  (Ctrl+Shift+S)")).clicked() {
+                    if ui.add_enabled(savable, egui::Button::new("Save Session As...  (Ctrl+Shift+S)"))
+                        .on_disabled_hover_text(unsavable_hint)
+                        .clicked()
+                    {
                         self.save_session_as();
                         ui.close_menu();
                     }
@@ -1721,53 +3892,20 @@ impl eframe::App for TitanViewApp {
                         ui.separator();
 
                         if ui.button("Close Session").clicked() {
-                            self.reset_to_landing();
+                            self.request_close(CloseRequest::Session);
                             ui.close_menu();
                         }
 
                         ui.menu_button("Export", |ui| {
-                            if ui.button("Analysis report (JSON)").clicked() {
-                                let json = tv_ui::export::export_json(&self.state);
-                                if let Some(path) = rfd::FileDialog::new()
-                                    .set_file_name("report.json")
-                                    .add_filter("JSON", &["json"])
-                                    .save_file()
-                                {
-                                    if let Err(e) = std::fs::write(&path, &json) {
-                                        log::error!("Export failed: {}", e);
-                                    } else {
-                                        log::info!("Exported JSON to {}", path.display());
-                                    }
-                                }
-                                ui.close_menu();
-                            }
-                            if self.state.search.results.is_some() {
-                                if ui.button("Search results (CSV)").clicked() {
-                                    let csv = tv_ui::export::export_search_csv(&self.state);
-                                    if let Some(path) = rfd::FileDialog::new()
-                                        .set_file_name("search_results.csv")
-                                        .add_filter("CSV", &["csv"])
-                                        .save_file()
-                                    {
-                                        if let Err(e) = std::fs::write(&path, &csv) {
-                                            log::error!("Export failed: {}", e);
-                                        }
-                                    }
-                                    ui.close_menu();
-                                }
-                            }
-                            if self.state.signatures.is_some() || self.state.deep_scan.results.is_some() {
-                                if ui.button("Signatures (CSV)").clicked() {
-                                    let csv = tv_ui::export::export_signatures_csv(&self.state);
-                                    if let Some(path) = rfd::FileDialog::new()
-                                        .set_file_name("signatures.csv")
-                                        .add_filter("CSV", &["csv"])
-                                        .save_file()
-                                    {
-                                        if let Err(e) = std::fs::write(&path, &csv) {
-                                            log::error!("Export failed: {}", e);
-                                        }
-                                    }
+                            for (command, label) in [
+                                (Command::ExportReport, "Analysis report (JSON)"),
+                                (Command::ExportSearchResults, "Search results (CSV)"),
+                                (Command::ExportDisassembly, "Disassembly (text)"),
+                                (Command::ExportSignatures, "Signatures (CSV)"),
+                                (Command::ExportBundle, "Analysis bundle..."),
+                            ] {
+                                if self.command_available(command) && ui.button(label).clicked() {
+                                    self.execute_command(ctx, command);
                                     ui.close_menu();
                                 }
                             }
@@ -1788,6 +3926,12 @@ impl eframe::App for TitanViewApp {
                     if ui.checkbox(&mut self.show_minimap, "Minimap").clicked() {
                         ui.close_menu();
                     }
+                    if ui.checkbox(&mut self.state.checksums.enabled, "Minimap Checksum Strip")
+                        .on_hover_text("Per-block CRC-32; blocks that differ from diff file B are red")
+                        .clicked()
+                    {
+                        ui.close_menu();
+                    }
                     if ui.checkbox(&mut self.show_hilbert, "Hilbert Curve  (F4)").clicked() {
                         ui.close_menu();
                     }
@@ -1809,6 +3953,24 @@ impl eframe::App for TitanViewApp {
                     if ui.checkbox(&mut self.show_script, "Script Console    (F11)").clicked() {
                         ui.close_menu();
                     }
+                    if ui.checkbox(&mut self.show_xor, "XOR Brute Force").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_text_view, "Text View").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_sections, "Sections").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_structure, "Structure").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_notes, "Notes").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_overview, "File Overview").clicked() {
+                        ui.close_menu();
+                    }
                     // Diff controls
                     if self.state.diff.file_b.is_some() {
                         if ui.checkbox(&mut self.state.diff.active, "Binary Diff  (F6)").clicked() {
@@ -1816,19 +3978,36 @@ impl eframe::App for TitanViewApp {
                         }
                     } else if self.state.has_file() {
                         if ui.button("Open File B for Diff  (F6)").clicked() {
-                            if let Some(path) = rfd::FileDialog::new().pick_file() {
-                                if let Ok(mapped) = tv_core::MappedFile::open(&path) {
-                                    self.state.diff.file_b = Some(tv_ui::state::LoadedFile { path, mapped });
-                                    self.state.diff.viewport_b = tv_core::ViewPort::new(0, 4096);
-                                    self.state.diff.sync_scroll = true;
-                                    self.state.diff.active = true;
-                                    self.state.diff.clear();
-                                }
+                            if let Some(path) = file_dialog::new().pick_file_remembered() {
+                                self.open_diff_file_b(path);
                             }
                             ui.close_menu();
                         }
+                        // Compare against a file open in another tab or the last closed one
+                        for path in self.diff_b_candidates() {
+                            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                            let label = if self.previous_file.as_ref() == Some(&path) {
+                                format!("Diff with previous: {}", name)
+                            } else {
+                                format!("Diff with {}", name)
+                            };
+                            if ui.button(label).on_hover_text(path.display().to_string()).clicked() {
+                                self.open_diff_file_b(path);
+                                ui.close_menu();
+                            }
+                        }
                     }
                     ui.separator();
+                    if ui.button("Command Palette...  (Ctrl+P)").clicked() {
+                        self.palette.open();
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_shortcuts, "Keyboard Shortcuts...  (F12)").clicked() {
+                        ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_theme, "Theme...").clicked() {
+                        ui.close_menu();
+                    }
                     if ui.checkbox(&mut self.perf.visible, "Performance  (F3)").clicked() {
                         ui.close_menu();
                     }
@@ -1890,11 +4069,28 @@ impl eframe::App for TitanViewApp {
                     self.apply_workspace(idx);
                 }
 
-                // Show computation status
-                if self.computing_entropy || self.computing_classification {
+                // Show which GPU operation is running, with its completion when known
+                if let Some(job) = self.state.gpu_job {
                     ui.separator();
-                    ui.spinner();
-                    ui.weak("Analyzing...");
+                    let ops = self.running_operations();
+                    match combined_progress(&ops) {
+                        // Several jobs share one bar, gated by the least advanced
+                        Some((label, fraction)) if ops.len() > 1 => {
+                            ui.add(egui::ProgressBar::new(fraction)
+                                .desired_width(120.0)
+                                .text(format!("GPU: {} jobs {:.0}%", ops.len(), fraction * 100.0)))
+                                .on_hover_text(label);
+                        }
+                        Some((_, fraction)) => {
+                            ui.add(egui::ProgressBar::new(fraction)
+                                .desired_width(120.0)
+                                .text(format!("GPU: {} {:.0}%", job.label(), fraction * 100.0)));
+                        }
+                        None => {
+                            ui.spinner();
+                            ui.weak(format!("GPU: {}", job.label()));
+                        }
+                    }
                 }
 
                 // Right side: file name + FPS
@@ -1911,21 +4107,130 @@ impl eframe::App for TitanViewApp {
             });
         });
 
+        // File tabs
+        if !self.tabs.is_empty() {
+            let mut switch_to: Option<usize> = None;
+            let mut close: Option<usize> = None;
+            egui::TopBottomPanel::top("file_tabs").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for (index, slot) in self.tabs.iter().enumerate() {
+                        let name = match slot {
+                            Some(parked) => parked.tab.name(),
+                            None => self.state.file_name(),
+                        };
+                        if ui.selectable_label(index == self.active_tab, name).clicked() {
+                            switch_to = Some(index);
+                        }
+                        if ui.small_button("x").on_hover_text("Close tab").clicked() {
+                            close = Some(index);
+                        }
+                        ui.separator();
+                    }
+                });
+            });
+            if let Some(index) = switch_to {
+                self.switch_tab(index);
+            }
+            if let Some(index) = close {
+                self.request_close(CloseRequest::Tab(index));
+            }
+        }
+        self.show_file_overview(ctx);
+
         // --- Floating Windows ---
         FileInfoWindow::show(ctx, &mut self.state, &mut self.show_file_info);
+        if std::mem::take(&mut self.state.file_hash_request) {
+            self.launch_file_hash();
+        }
+        FileInfoWindow::show_analysis_prompt(ctx, &mut self.state);
+        self.show_dropped_files_prompt(ctx);
+        self.show_close_confirm(ctx);
+        self.show_diff_b_chooser(ctx);
+        if std::mem::take(&mut self.state.search_window_request) {
+            self.show_search = true;
+        }
         SearchWindow::show(ctx, &mut self.state, &mut self.show_search);
         SignaturesWindow::show(ctx, &mut self.state, &mut self.show_signatures);
         HilbertWindow::show(ctx, &mut self.state, &mut self.hilbert, &mut self.show_hilbert);
-        DisasmWindow::show(ctx, &mut self.state, &mut self.disasm, &mut self.show_disasm);
+        if let Some((offset, arch)) = self.state.disasm_request.take() {
+            self.disasm.open_at(offset, arch);
+            self.show_disasm = true;
+        }
+        DisasmWindow::show(ctx, &mut self.state, &mut self.disasm, self.bookmarks.project.as_ref(), &mut self.show_disasm);
+        if std::mem::take(&mut self.disasm.comments_modified) {
+            // Instruction comments are stored as project comments (both keyed by file offset)
+            if let Some(ref file) = self.state.file {
+                self.bookmarks.ensure_project(file);
+            }
+            if let Some(ref mut project) = self.bookmarks.project {
+                self.disasm.store_comments(project);
+                self.bookmarks.modified = true;
+            }
+            self.session_modified = true;
+        }
         StructInspector::show(ctx, &mut self.state, &mut self.inspector, &mut self.show_inspector);
         HistogramWindow::show(ctx, &mut self.state, &mut self.histogram, &mut self.show_histogram);
+        XorWindow::show(ctx, &mut self.state, &mut self.xor, &mut self.show_xor);
+        if let Some(encoding) = self.state.text_view_request.take() {
+            self.text_view.encoding = encoding;
+            self.show_text_view = true;
+        }
+        if let Some(offset) = TextViewWindow::show(ctx, &self.state, &mut self.text_view, &mut self.show_text_view) {
+            self.state.jump_to_offset(offset);
+            self.text_view.hold(self.state.viewport.start);
+        }
+        SectionsWindow::show(ctx, &mut self.state, &mut self.show_sections);
+        if std::mem::take(&mut self.state.structure_window_request) {
+            self.show_structure = true;
+        }
+        StructureWindow::show(ctx, &mut self.state, &mut self.show_structure);
+        if self.bundle.open {
+            let available: Vec<BundleArtifact> = BundleArtifact::ALL
+                .into_iter()
+                .filter(|&artifact| self.bundle_artifact_available(artifact))
+                .collect();
+            if let Some(artifacts) = BundleWindow::show(ctx, &mut self.bundle, &available) {
+                self.export_bundle(&artifacts);
+            }
+        }
         XRefsWindow::show(ctx, &mut self.state, &mut self.xrefs, &mut self.show_xrefs);
+        if std::mem::take(&mut self.xrefs.analyze_strings_requested) {
+            self.analyze_strings();
+        }
         BookmarksWindow::show(ctx, &mut self.state, &mut self.bookmarks, &mut self.show_bookmarks);
         ScriptWindow::show(ctx, &mut self.state, &mut self.script, &mut self.show_script);
-        PerfWindow::show(ctx, &mut self.perf);
+        match PerfWindow::show(ctx, &mut self.perf) {
+            Some(tv_ui::PerfAction::BudgetChanged) => {
+                tv_gpu::set_memory_budget(self.perf.gpu_memory_budget);
+                file_dialog::save_gpu_memory_budget(self.perf.gpu_memory_budget);
+            }
+            Some(tv_ui::PerfAction::ResetGpuMemory) => tv_gpu::reset_memory_usage(),
+            None => {}
+        }
+        if ThemeWindow::show(ctx, &mut self.state, &mut self.show_theme) {
+            self.session_modified = true;
+        }
+        if NotesWindow::show(ctx, &mut self.state.notes, &mut self.show_notes) {
+            self.session_modified = true;
+        }
+        if ShortcutsWindow::show(ctx, &mut self.key_bindings, &mut self.shortcuts, &mut self.show_shortcuts) {
+            file_dialog::save_key_bindings(self.key_bindings.to_config());
+        }
+        if self.palette.open {
+            let entries = self.palette_entries(ctx);
+            if let Some(command) = CommandPalette::show(ctx, &mut self.palette, &entries) {
+                self.execute_command(ctx, command);
+            }
+        }
 
-        // Update inspector highlights in state
-        self.state.inspector_highlights = self.inspector.highlight_offsets();
+        // Update inspector highlights in state (only while the inspector is open)
+        if self.show_inspector {
+            self.state.inspector_highlights = self.inspector.highlight_offsets();
+            self.state.inspector_focus = self.inspector.focused_range();
+        } else if !self.state.inspector_highlights.is_empty() || self.state.inspector_focus.is_some() {
+            self.state.inspector_highlights.clear();
+            self.state.inspector_focus = None;
+        }
 
         // Right panel: minimap (only when file loaded and enabled)
         if self.state.has_file() && self.show_minimap {
@@ -1952,11 +4257,69 @@ impl eframe::App for TitanViewApp {
 
                 // File info
                 if self.state.has_file() {
+                    if self.state.edit.read_only {
+                        ui.label(egui::RichText::new("READ-ONLY").color(egui::Color32::from_rgb(100, 180, 255)).strong())
+                            .on_hover_text("Opened read-only: editing is disabled");
+                    }
                     ui.label(format!("{} | {} bytes | Offset: 0x{:X}",
                         self.state.file_name(),
                         self.state.file_len(),
                         self.state.viewport.start
                     ));
+                    if ui.small_button("Copy").on_hover_text("Copy offset  (Ctrl+Shift+G)").clicked() {
+                        let text = self.state.offset_clipboard_text();
+                        self.session_status = Some((format!("Copied {}", text), false));
+                        ctx.copy_text(text);
+                    }
+                }
+
+                // Selected range readout
+                if let (Some(sel), Some(stats)) = (self.state.selection, self.state.selection_stats()) {
+                    ui.separator();
+                    let pct = stats.most_common_count as f64 * 100.0 / stats.total.max(1) as f64;
+                    let mut text = format!(
+                        "Sel 0x{:X}–0x{:X} ({} bytes) · entropy {:.2} · most common 0x{:02X} ({:.1}%)",
+                        sel.start(), sel.end(), sel.length(), stats.entropy, stats.most_common, pct
+                    );
+                    if stats.total < sel.length() {
+                        text.push_str(&format!(" (first {} MB)", stats.total / (1024 * 1024)));
+                    }
+                    ui.label(text);
+                }
+                if let Some((_, msg, mismatch)) = self.state.clipboard_compare.as_ref()
+                    .filter(|(compared, _, _)| self.state.selection == Some(*compared))
+                {
+                    let color = if *mismatch {
+                        egui::Color32::from_rgb(255, 100, 100)
+                    } else {
+                        egui::Color32::from_rgb(100, 200, 100)
+                    };
+                    ui.label(egui::RichText::new(msg).color(color));
+                }
+                if let Some(&(_, period)) = self.state.selection_period.as_ref()
+                    .filter(|(examined, _)| self.state.selection == Some(*examined))
+                {
+                    match period {
+                        Some(period) => {
+                            ui.label(format!("Repeats every {} bytes", period));
+                            if ui.small_button("Use as inspector stride")
+                                .on_hover_text("Step the structure inspector through the records")
+                                .clicked()
+                            {
+                                self.inspector.stride = period as u64;
+                                if let Some(sel) = self.state.selection {
+                                    self.inspector.offset = sel.start();
+                                    self.inspector.offset_text = format!("0x{:X}", sel.start());
+                                    self.inspector.offset_error = None;
+                                    self.inspector.base_signature = None;
+                                }
+                                self.show_inspector = true;
+                            }
+                        }
+                        None => {
+                            ui.label(egui::RichText::new("No repeat period found").weak());
+                        }
+                    }
                 }
 
                 // Status message
@@ -1973,9 +4336,10 @@ impl eframe::App for TitanViewApp {
                 // Spacer
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     // Computation progress
-                    if self.computing_entropy || self.computing_classification {
-                        ui.spinner();
-                        ui.weak("Analyzing...");
+                    if let Some((label, fraction)) = self.operation_progress() {
+                        ui.add(egui::ProgressBar::new(fraction)
+                            .desired_width(220.0)
+                            .text(format!("{} {:.0}%", label, fraction * 100.0)));
                     }
                 });
             });
@@ -1995,7 +4359,7 @@ impl eframe::App for TitanViewApp {
                     ui.add_space(32.0);
 
                     if ui.button("Open File...").clicked() {
-                        if let Some(path) = rfd::FileDialog::new().pick_file() {
+                        if let Some(path) = file_dialog::new().pick_file_remembered() {
                             self.pending_drop = Some(path);
                         }
                     }
@@ -2003,9 +4367,15 @@ impl eframe::App for TitanViewApp {
                     ui.add_space(16.0);
                     ui.weak("or drag & drop a file");
 
+                    // Last open error, so a failed drop isn't silent
+                    if let Some((msg, true)) = &self.session_status {
+                        ui.add_space(8.0);
+                        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), msg);
+                    }
+
                     ui.add_space(32.0);
 
-                    // Keyboard shortcuts help
+                    // Keyboard shortcuts help (the full list is in the shortcuts window)
                     ui.group(|ui| {
                         ui.label("Keyboard Shortcuts");
                         ui.separator();
@@ -2013,29 +4383,17 @@ impl eframe::App for TitanViewApp {
                             .num_columns(2)
                             .spacing([20.0, 4.0])
                             .show(ui, |ui| {
-                                ui.code("F1");
-                                ui.label("File Info");
-                                ui.end_row();
-
-                                ui.code("F2");
-                                ui.label("Signatures");
-                                ui.end_row();
-
-                                ui.code("F3");
-                                ui.label("Performance");
-                                ui.end_row();
-
-                                ui.code("Ctrl+F");
-                                ui.label("Search");
-                                ui.end_row();
-
-                                ui.code("Ctrl+G");
-                                ui.label("Go to offset");
+                                ui.code(ctx.format_shortcut(&PALETTE_SHORTCUT));
+                                ui.label("Command palette");
                                 ui.end_row();
 
-                                ui.code("Escape");
-                                ui.label("Close windows");
-                                ui.end_row();
+                                for command in [Command::OpenFile, Command::Search, Command::GotoOffset, Command::ToggleShortcuts] {
+                                    let Some(info) = command_palette::COMMANDS.iter().find(|info| info.command == command) else { continue };
+                                    let Some(shortcut) = self.key_bindings.shortcut(info) else { continue };
+                                    ui.code(ctx.format_shortcut(&shortcut));
+                                    ui.label(command_palette::split_name(info.name).1);
+                                    ui.end_row();
+                                }
                             });
                     });
                 });
@@ -2048,3 +4406,98 @@ impl eframe::App for TitanViewApp {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tv_ui::state::{DeepScanState, SignatureHit, PENDING_CLASS};
+
+    fn hit(offset: u64, name: &str) -> SignatureHit {
+        SignatureHit { offset, name: name.to_string(), magic: vec![] }
+    }
+
+    fn deep_chunk(hits: Vec<SignatureHit>, extents: Vec<Option<u64>>, scanned: u64, is_final: bool) -> DeepScanChunk {
+        DeepScanChunk {
+            signatures: hits,
+            extents,
+            bytes_scanned: scanned,
+            total_bytes: 300,
+            is_final,
+            duration_ms: is_final.then_some(12.5),
+        }
+    }
+
+    #[test]
+    fn entropy_chunks_out_of_order() {
+        let mut entropy = None;
+        let late = EntropyChunk { start_block: 2, values: vec![8.0, 8.0], total_blocks: 4 };
+        let early = EntropyChunk { start_block: 0, values: vec![1.0, 3.0], total_blocks: 4 };
+        assert!(accumulate_entropy(&mut entropy, &late));
+        assert!(accumulate_entropy(&mut entropy, &early));
+        // Duplicate chunk past the end is ignored
+        assert!(!accumulate_entropy(&mut entropy, &EntropyChunk { start_block: 4, values: vec![5.0], total_blocks: 4 }));
+
+        let entropy = entropy.unwrap();
+        assert_eq!(entropy, vec![1.0, 3.0, 8.0, 8.0]);
+        let stats = entropy_stats(&entropy).unwrap();
+        assert_eq!((stats.avg, stats.block_count), (5.0, 4));
+        assert!(entropy_stats(&[]).is_none());
+    }
+
+    #[test]
+    fn hilbert_cells_average_the_blocks_they_cover() {
+        // Four cells of two 256-byte blocks each
+        let entropy = [1.0, 3.0, 8.0, 8.0, 0.0, 2.0, 4.0, 6.0];
+        assert_eq!(hilbert_cell_entropy(&entropy, 256, 2048, 4), vec![2.0, 8.0, 1.0, 5.0]);
+        // Cells smaller than a block repeat its value
+        assert_eq!(hilbert_cell_entropy(&[2.0, 6.0], 1024, 2048, 4), vec![2.0, 2.0, 6.0, 6.0]);
+        // Unaligned cells are weighted by overlap; missing blocks count as 0
+        assert_eq!(hilbert_cell_entropy(&[3.0, 6.0], 100, 300, 2), vec![4.0, 2.0]);
+        // A small file fills only its first cells
+        assert_eq!(hilbert_cell_entropy(&[5.0], 256, 3, 16), vec![5.0; 3]);
+    }
+
+    #[test]
+    fn classification_short_vector_and_counts() {
+        // Vector left short by an earlier pass grows with the pending class
+        let mut classification = Some(vec![1u8]);
+        let chunk = ClassifyChunk { start_block: 3, values: vec![4, 4, 4], total_blocks: 5 };
+        assert!(accumulate_classification(&mut classification, &chunk));
+        let classification = classification.unwrap();
+        assert_eq!(classification, vec![1, PENDING_CLASS, PENDING_CLASS, 4, 4]);
+        assert_eq!(class_counts(&classification), [2, 1, 0, 0, 2]);
+        assert_eq!(class_counts(&[9]), [0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn deep_scan_chunks_and_final_chunk() {
+        let mut scan = DeepScanState { scanning: true, ..Default::default() };
+        // Second chunk arrives first, with a missing extent
+        let second = deep_chunk(vec![hit(200, "PNG"), hit(250, "ZIP")], vec![Some(10)], 200, false);
+        assert!(!accumulate_deep_scan(&mut scan, second));
+        let first = deep_chunk(vec![hit(0, "ZIP")], vec![Some(300), Some(1)], 100, false);
+        assert!(!accumulate_deep_scan(&mut scan, first));
+        assert_eq!(scan.bytes_scanned, 200, "progress never goes backwards");
+        assert!(scan.scanning);
+        assert_eq!(scan.extents, vec![Some(10), None, Some(300)]);
+
+        assert!(accumulate_deep_scan(&mut scan, deep_chunk(vec![], vec![], 300, true)));
+        assert!(!scan.scanning);
+        assert_eq!(scan.bytes_scanned, 300);
+        assert_eq!(scan.duration_ms, Some(12.5));
+        assert_eq!(scan.results.as_ref().unwrap().len(), 3);
+        assert_eq!(scan.parents, vec![Some(2), Some(2), None], "hits inside the ZIP are nested");
+    }
+
+    #[test]
+    fn progress_of_combined_operations() {
+        assert_eq!(combined_progress(&[]), None);
+        assert_eq!(
+            combined_progress(&[("Entropy", 0.5), ("Deep scan", 0.25)]),
+            Some(("Entropy + Deep scan".to_string(), 0.25))
+        );
+        assert_eq!(chunk_progress(0, 4, 16), 0.25);
+        assert_eq!(chunk_progress(12, 8, 16), 1.0);
+        assert_eq!(chunk_progress(0, 0, 0), 0.0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Headless analysis facade for embedding `tv_core` in other tools.
+//!
+//! [`Analysis`] opens a file once (memory-mapped) and runs the analyses behind
+//! the TitanView panels, returning plain data. Everything here is the CPU
+//! implementation (parallel with rayon); the GPU paths live in `tv-gpu`, so
+//! depending on `tv_core` alone pulls in neither wgpu nor egui.
+//!
+//! ```no_run
+//! use tv_core::{Analysis, DEFAULT_BLOCK_SIZE};
+//!
+//! let analysis = Analysis::from_path("firmware.bin".as_ref())?;
+//! let entropy = analysis.entropy_blocks(DEFAULT_BLOCK_SIZE);
+//! for hit in analysis.detect_signatures() {
+//!     println!("0x{:X} {}", hit.offset, hit.name);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::checksum::compute_block_checksums;
+use crate::classify::classify_blocks_cpu;
+use crate::entropy::compute_entropy_cpu;
+use crate::histogram::ByteHistogram;
+use crate::mapped_file::MappedFile;
+use crate::signatures::{detect_signatures, SignatureMatch};
+use crate::strings::{find_strings, FoundString};
+use crate::types::{BlockClass, FileRegion};
+
+/// Block size used by the GUI for entropy and classification.
+pub const DEFAULT_BLOCK_SIZE: usize = 256;
+
+/// A file opened for headless analysis.
+pub struct Analysis {
+    path: PathBuf,
+    file: MappedFile,
+}
+
+impl Analysis {
+    /// Open and memory-map the file at `path`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        Ok(Self { path: path.to_path_buf(), file: MappedFile::open(path)? })
+    }
+
+    /// Path the file was opened from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// File size in bytes.
+    pub fn len(&self) -> u64 {
+        self.file.len()
+    }
+
+    /// Returns true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.file.is_empty()
+    }
+
+    /// The whole file contents (zero-copy).
+    pub fn data(&self) -> &[u8] {
+        self.file.slice(FileRegion::new(0, self.file.len()))
+    }
+
+    /// Shannon entropy (0.0 - 8.0) of each `block_size` block.
+    pub fn entropy_blocks(&self, block_size: usize) -> Vec<f32> {
+        compute_entropy_cpu(self.data(), block_size)
+    }
+
+    /// Content class of each `block_size` block.
+    pub fn classify(&self, block_size: usize) -> Vec<BlockClass> {
+        classify_blocks_cpu(self.data(), block_size)
+    }
+
+    /// Known file signatures anywhere in the file, sorted by offset.
+    pub fn detect_signatures(&self) -> Vec<SignatureMatch> {
+        let data = self.data();
+        detect_signatures(data, data.len())
+    }
+
+    /// Byte value distribution of the whole file.
+    pub fn histogram(&self) -> ByteHistogram {
+        ByteHistogram::from_data(self.data())
+    }
+
+    /// Printable ASCII strings of at least `min_len` characters.
+    pub fn strings(&self, min_len: usize) -> Vec<FoundString> {
+        find_strings(self.data(), min_len)
+    }
+
+    /// CRC-32 of each `block_size` block.
+    pub fn block_checksums(&self, block_size: usize) -> Vec<u32> {
+        compute_block_checksums(self.data(), block_size)
+    }
+
+    /// Lowercase hex SHA-256 of the file.
+    pub fn sha256(&self) -> String {
+        crate::carve::sha256_hex(self.data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn analyze_file_headlessly() {
+        let mut data = vec![0u8; 512];
+        data[..8].copy_from_slice(b"\x89PNG\r\n\x1a\n");
+        data[256..272].copy_from_slice(b"Hello, analysis!");
+        let mut f = NamedTempFile::new().expect("failed to create temp file");
+        f.write_all(&data).expect("failed to write fixture");
+
+        let analysis = Analysis::from_path(f.path()).unwrap();
+        assert_eq!(analysis.len(), 512);
+        assert_eq!(analysis.path(), f.path());
+
+        let entropy = analysis.entropy_blocks(DEFAULT_BLOCK_SIZE);
+        assert_eq!(entropy.len(), 2);
+        assert_eq!(analysis.classify(DEFAULT_BLOCK_SIZE).len(), 2);
+        assert!(analysis.detect_signatures().iter().any(|m| m.offset == 0 && m.name.contains("PNG")));
+        assert_eq!(analysis.histogram().total, 512);
+        assert_eq!(analysis.strings(4), vec![FoundString { offset: 256, text: "Hello, analysis!".to_string() }]);
+        assert_eq!(analysis.block_checksums(DEFAULT_BLOCK_SIZE).len(), 2);
+        assert_eq!(analysis.sha256().len(), 64);
+
+        assert!(Analysis::from_path(Path::new("/nonexistent/titanview")).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Built-in structure templates for common file formats.
 
-use crate::templates::{StructTemplate, FieldType, PrimitiveType};
+use crate::checksum::ChecksumAlgo;
+use crate::templates::{StructTemplate, FieldType, FieldValue, FieldRange, PrimitiveType};
 use std::collections::HashMap;
 
 /// Get all built-in templates.
@@ -25,6 +26,36 @@ pub fn get_template(name: &str) -> Option<StructTemplate> {
     builtin_templates().into_iter().find(|t| t.name == name)
 }
 
+/// Signature names (as reported by `signatures::detect_signatures`) and the
+/// builtin template describing that format's header.
+pub const SIGNATURE_TEMPLATES: &[(&str, &str)] = &[
+    ("ELF", "ELF64 Header"),
+    ("PE/COFF (MZ)", "DOS Header (MZ)"),
+    ("ZIP/JAR/APK/DOCX", "ZIP Local Header"),
+    ("PNG", "PNG Header"),
+    ("JPEG", "JPEG Header"),
+    ("GIF87a", "GIF Header"),
+    ("GIF89a", "GIF Header"),
+    ("BMP", "BMP Header"),
+    ("WAV", "WAV Header"),
+    ("tar (ustar)", "TAR Header"),
+];
+
+/// Get the builtin template name for a detected signature.
+///
+/// `header` is the start of the data the signature belongs to; it picks the
+/// ELF class and only maps RIFF signatures to the WAV template for WAVE files.
+pub fn template_for_signature(signature: &str, header: &[u8]) -> Option<&'static str> {
+    match signature {
+        "ELF" if header.get(4) == Some(&1) => Some("ELF32 Header"),
+        "WAV" | "WebP" | "AVI" => (header.get(8..12) == Some(b"WAVE")).then_some("WAV Header"),
+        _ => SIGNATURE_TEMPLATES
+            .iter()
+            .find(|(sig, _)| *sig == signature)
+            .map(|(_, template)| *template),
+    }
+}
+
 /// ELF64 file header.
 pub fn elf64_header() -> StructTemplate {
     let mut e_type_values = HashMap::new();
@@ -175,6 +206,7 @@ pub fn png_header() -> StructTemplate {
         .description("PNG image file header and IHDR chunk")
         .field("signature", FieldType::Magic(vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]))
         .field_desc("ihdr_length", FieldType::Primitive(PrimitiveType::U32), "IHDR chunk length (big-endian)")
+        .expect(FieldValue::Unsigned(13))
         .field("ihdr_type", FieldType::Magic(vec![b'I', b'H', b'D', b'R']))
         .little_endian(false)  // PNG uses big-endian
         .field_desc("width", FieldType::Primitive(PrimitiveType::U32), "Image width in pixels")
@@ -184,6 +216,11 @@ pub fn png_header() -> StructTemplate {
         .field("compression", FieldType::Primitive(PrimitiveType::U8))
         .field("filter", FieldType::Primitive(PrimitiveType::U8))
         .field_desc("interlace", FieldType::Primitive(PrimitiveType::U8), "0=none, 1=Adam7")
+        .field_desc(
+            "ihdr_crc",
+            FieldType::Computed { algo: ChecksumAlgo::Crc32, over: FieldRange::new(12, 29) },
+            "CRC32 over chunk type and data",
+        )
         .build()
 }
 
@@ -335,4 +372,31 @@ mod tests {
         assert!(template.is_some());
         assert_eq!(template.unwrap().name, "ELF64 Header");
     }
+
+    #[test]
+    fn test_signature_templates_exist() {
+        for (sig, template) in SIGNATURE_TEMPLATES {
+            assert!(get_template(template).is_some(), "{} maps to unknown template {}", sig, template);
+        }
+    }
+
+    #[test]
+    fn test_template_for_signature() {
+        let elf64 = b"\x7fELF\x02\x01\x01";
+        let elf32 = b"\x7fELF\x01\x01\x01";
+        assert_eq!(template_for_signature("ELF", elf64), Some("ELF64 Header"));
+        assert_eq!(template_for_signature("ELF", elf32), Some("ELF32 Header"));
+        assert_eq!(template_for_signature("PNG", &[]), Some("PNG Header"));
+        assert_eq!(template_for_signature("WebP", b"RIFF\x00\x00\x00\x00WAVEfmt "), Some("WAV Header"));
+        assert_eq!(template_for_signature("WAV", b"RIFF\x00\x00\x00\x00AVI LIST"), None);
+        assert_eq!(template_for_signature("PDF", b"%PDF-1.7"), None);
+    }
+
+    #[test]
+    fn test_png_header_crc_verifies() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0DIHDR\x00\x00\x00\x01\x00\x00\x00\x01\x08\x06\x00\x00\x00\x1F\x15\xC4\x89";
+        let template = png_header();
+        let result = crate::templates::apply_template(&template, png, 0);
+        assert_eq!(result.error_count(), 0, "{:?}", result.status);
+    }
 }
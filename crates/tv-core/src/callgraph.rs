@@ -0,0 +1,328 @@
+//! Call graph analysis across detected functions.
+//!
+//! Splits a linear disassembly into functions (entry point, call targets and
+//! any extra known entries) and connects them with the static `call`
+//! relationships found in each function body.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::cfg::{layered_layout, CfgInstruction};
+
+/// Width of a function node when rendered.
+pub const CALL_NODE_WIDTH: f32 = 160.0;
+/// Height of a function node when rendered.
+pub const CALL_NODE_HEIGHT: f32 = 36.0;
+
+/// A function in the call graph.
+#[derive(Debug, Clone)]
+pub struct FunctionNode {
+    /// Start address of the function.
+    pub address: u64,
+    /// End address (exclusive). Equal to `address` for external functions.
+    pub end: u64,
+    /// Number of instructions in the function body.
+    pub instruction_count: usize,
+    /// Called from the disassembly but lies outside of it (no body).
+    pub external: bool,
+    /// Number of call sites targeting this function.
+    pub calls_in: usize,
+    /// Number of call sites inside this function.
+    pub calls_out: usize,
+    /// Layout layer.
+    pub layer: usize,
+    /// Computed layout X position.
+    pub layout_x: f32,
+    /// Computed layout Y position.
+    pub layout_y: f32,
+}
+
+impl FunctionNode {
+    fn new(address: u64, external: bool) -> Self {
+        Self {
+            address,
+            end: address,
+            instruction_count: 0,
+            external,
+            calls_in: 0,
+            calls_out: 0,
+            layer: 0,
+            layout_x: 0.0,
+            layout_y: 0.0,
+        }
+    }
+}
+
+/// A caller -> callee relationship.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    /// Calling function.
+    pub from: u64,
+    /// Called function.
+    pub to: u64,
+    /// Number of call instructions making this call.
+    pub call_sites: usize,
+}
+
+/// Graph of functions connected by calls.
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Functions indexed by start address.
+    pub functions: BTreeMap<u64, FunctionNode>,
+    /// Call edges, sorted by (from, to).
+    pub edges: Vec<CallEdge>,
+    /// Computed layers for layout.
+    pub layers: Vec<Vec<u64>>,
+}
+
+impl CallGraph {
+    /// Build a call graph from a list of disassembled instructions.
+    ///
+    /// Function starts are the first instruction, every static call target that
+    /// lands on an instruction, and any of `extra_entries` (e.g. function
+    /// labels) that do. Each function extends to the next function start.
+    /// Call targets outside the disassembly become external nodes.
+    pub fn build(instructions: &[CfgInstruction], extra_entries: &[u64]) -> Self {
+        let mut graph = Self::default();
+        if instructions.is_empty() {
+            return graph;
+        }
+
+        let mut sorted: Vec<&CfgInstruction> = instructions.iter().collect();
+        sorted.sort_by_key(|i| i.address);
+        let insn_addrs: BTreeSet<u64> = sorted.iter().map(|i| i.address).collect();
+
+        // Step 1: Function starts
+        let mut starts: BTreeSet<u64> = BTreeSet::new();
+        starts.insert(sorted[0].address);
+        for &addr in extra_entries {
+            if insn_addrs.contains(&addr) {
+                starts.insert(addr);
+            }
+        }
+        for instr in &sorted {
+            if instr.is_call() {
+                if let Some(target) = instr.target_address() {
+                    if insn_addrs.contains(&target) {
+                        starts.insert(target);
+                    }
+                }
+            }
+        }
+        for &start in &starts {
+            graph.functions.insert(start, FunctionNode::new(start, false));
+        }
+
+        // Step 2: Function bodies and calls
+        let mut edge_counts: BTreeMap<(u64, u64), usize> = BTreeMap::new();
+        let mut current = sorted[0].address;
+        for instr in &sorted {
+            if starts.contains(&instr.address) {
+                current = instr.address;
+            }
+            let func = graph.functions.get_mut(&current).unwrap();
+            func.instruction_count += 1;
+            func.end = instr.address + instr.size as u64;
+
+            if instr.is_call() {
+                if let Some(target) = instr.target_address() {
+                    *edge_counts.entry((current, target)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        for (&(from, to), &call_sites) in &edge_counts {
+            graph.functions
+                .entry(to)
+                .or_insert_with(|| FunctionNode::new(to, true))
+                .calls_in += call_sites;
+            if let Some(caller) = graph.functions.get_mut(&from) {
+                caller.calls_out += call_sites;
+            }
+            graph.edges.push(CallEdge { from, to, call_sites });
+        }
+
+        graph.compute_layout();
+        graph
+    }
+
+    /// Get the function whose body contains `address`.
+    pub fn function_containing(&self, address: u64) -> Option<&FunctionNode> {
+        self.functions
+            .range(..=address)
+            .next_back()
+            .map(|(_, f)| f)
+            .filter(|f| !f.external && address < f.end)
+    }
+
+    /// Get the instructions making up a function's body.
+    ///
+    /// `instructions` must be sorted by address (as disassembly output is).
+    pub fn function_instructions<'a>(
+        &self,
+        address: u64,
+        instructions: &'a [CfgInstruction],
+    ) -> &'a [CfgInstruction] {
+        match self.functions.get(&address) {
+            Some(f) if !f.external => {
+                let start = instructions.partition_point(|i| i.address < f.address);
+                let end = instructions.partition_point(|i| i.address < f.end);
+                &instructions[start..end]
+            }
+            _ => &[],
+        }
+    }
+
+    /// Functions called by `address`.
+    pub fn callees(&self, address: u64) -> impl Iterator<Item = &CallEdge> {
+        self.edges.iter().filter(move |e| e.from == address)
+    }
+
+    /// Functions calling `address`.
+    pub fn callers(&self, address: u64) -> impl Iterator<Item = &CallEdge> {
+        self.edges.iter().filter(move |e| e.to == address)
+    }
+
+    /// Compute hierarchical layout for the graph.
+    ///
+    /// Functions that nobody calls are the roots; if every function is part of
+    /// a cycle, the lowest address is used instead.
+    fn compute_layout(&mut self) {
+        let mut successors: HashMap<u64, Vec<u64>> =
+            self.functions.keys().map(|&a| (a, Vec::new())).collect();
+        let mut predecessors: HashMap<u64, Vec<u64>> = HashMap::new();
+        for edge in &self.edges {
+            if edge.from != edge.to {
+                successors.entry(edge.from).or_default().push(edge.to);
+                predecessors.entry(edge.to).or_default().push(edge.from);
+            }
+        }
+
+        let mut roots: Vec<u64> = self.functions.keys()
+            .copied()
+            .filter(|a| !predecessors.contains_key(a))
+            .collect();
+        if roots.is_empty() {
+            roots.extend(self.functions.keys().next().copied());
+        }
+
+        let layout = layered_layout(&roots, &successors, &predecessors, |_| CALL_NODE_WIDTH);
+        for (&addr, node) in &layout.nodes {
+            if let Some(func) = self.functions.get_mut(&addr) {
+                func.layer = node.layer;
+                func.layout_x = node.x;
+                func.layout_y = node.y;
+            }
+        }
+        self.layers = layout.layers;
+    }
+
+    /// Get the bounding box of the graph.
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        if self.functions.is_empty() {
+            return (0.0, 0.0, 100.0, 100.0);
+        }
+
+        let mut min_x = f32::MAX;
+        let mut min_y = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut max_y = f32::MIN;
+
+        for func in self.functions.values() {
+            min_x = min_x.min(func.layout_x);
+            min_y = min_y.min(func.layout_y);
+            max_x = max_x.max(func.layout_x + CALL_NODE_WIDTH);
+            max_y = max_y.max(func.layout_y + CALL_NODE_HEIGHT);
+        }
+
+        (min_x, min_y, max_x, max_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_instr(addr: u64, size: u8, mnemonic: &str, operands: &str) -> CfgInstruction {
+        CfgInstruction {
+            address: addr,
+            size,
+            mnemonic: mnemonic.to_string(),
+            operands: operands.to_string(),
+            bytes: vec![0; size as usize],
+        }
+    }
+
+    fn sample() -> Vec<CfgInstruction> {
+        vec![
+            // main: calls f twice, g once, and an external function
+            make_instr(0x00, 5, "call", "0x20"),
+            make_instr(0x05, 5, "call", "0x20"),
+            make_instr(0x0A, 5, "call", "0x30"),
+            make_instr(0x0F, 5, "call", "0x1000"),
+            make_instr(0x14, 1, "ret", ""),
+            // f: calls g
+            make_instr(0x20, 5, "call", "0x30"),
+            make_instr(0x25, 1, "ret", ""),
+            // g
+            make_instr(0x30, 3, "mov", "eax, 1"),
+            make_instr(0x33, 1, "ret", ""),
+            // h: only known through a label
+            make_instr(0x40, 1, "ret", ""),
+        ]
+    }
+
+    #[test]
+    fn test_call_graph_functions_and_edges() {
+        let graph = CallGraph::build(&sample(), &[0x40]);
+
+        let addrs: Vec<u64> = graph.functions.keys().copied().collect();
+        assert_eq!(addrs, vec![0x00, 0x20, 0x30, 0x40, 0x1000]);
+
+        let main = &graph.functions[&0x00];
+        assert_eq!(main.instruction_count, 5);
+        assert_eq!(main.end, 0x15);
+        assert_eq!(main.calls_out, 4);
+        assert_eq!(main.calls_in, 0);
+
+        let g = &graph.functions[&0x30];
+        assert_eq!(g.calls_in, 2);
+        assert_eq!(g.calls_out, 0);
+        assert_eq!(g.end, 0x34, "function ends before the next detected start");
+
+        assert!(graph.functions[&0x1000].external);
+        assert_eq!(graph.functions[&0x40].instruction_count, 1);
+
+        assert_eq!(
+            graph.edges[0],
+            CallEdge { from: 0x00, to: 0x20, call_sites: 2 }
+        );
+        assert_eq!(graph.callees(0x00).count(), 3);
+        assert_eq!(graph.callers(0x30).count(), 2);
+    }
+
+    #[test]
+    fn test_call_graph_layout_and_lookup() {
+        let instrs = sample();
+        let graph = CallGraph::build(&instrs, &[]);
+
+        assert_eq!(graph.functions[&0x00].layer, 0);
+        assert_eq!(graph.functions[&0x20].layer, 1);
+        assert!(graph.functions[&0x30].layer >= 1);
+
+        assert_eq!(graph.function_containing(0x25).map(|f| f.address), Some(0x20));
+        assert!(graph.function_containing(0x1000).is_none());
+        // Without the label, h is folded into g's body
+        assert_eq!(graph.function_containing(0x40).map(|f| f.address), Some(0x30));
+
+        let body = graph.function_instructions(0x20, &instrs);
+        assert_eq!(body.len(), 2);
+        assert!(graph.function_instructions(0x1000, &instrs).is_empty());
+    }
+
+    #[test]
+    fn test_call_graph_empty() {
+        let graph = CallGraph::build(&[], &[]);
+        assert!(graph.functions.is_empty());
+        assert!(graph.edges.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+//! Batch carving of embedded files with SHA-256 hashing and a CSV manifest.
+//!
+//! Only hits whose size is known exactly (from headers or end markers) are
+//! carved; each carved file is hashed and listed in the manifest, and hits
+//! that cannot be carved are listed with a note instead.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::mapped_file::MappedFile;
+use crate::signatures::analyze_carve_size;
+use crate::types::FileRegion;
+
+/// Bytes analyzed after each hit to find its size.
+pub const CARVE_ANALYZE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// File name of the manifest written next to the carved files.
+pub const MANIFEST_FILE_NAME: &str = "manifest.csv";
+
+/// One manifest row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CarveEntry {
+    /// Row number in the manifest.
+    pub index: usize,
+    /// Offset of the hit in the source file.
+    pub offset: u64,
+    /// Signature name.
+    pub name: String,
+    /// Carved size in bytes.
+    pub size: u64,
+    /// SHA-256 of the carved bytes (lowercase hex), if carved.
+    pub sha256: Option<String>,
+    /// Output file name, if carved.
+    pub output: Option<String>,
+    /// Why the hit was skipped, empty if carved.
+    pub note: String,
+}
+
+/// Lowercase hex SHA-256 of `data`.
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Carve every hit with an exact size into `out_dir`, hashing each file.
+///
+/// `hits` are (offset, signature name). `progress` is called with
+/// (hits processed, total hits) after each hit. Hits of unknown size are left
+/// out; zero-size hits and hits extending past the end of the file are kept in
+/// the result with a note. Returns the manifest rows.
+pub fn carve_and_hash(
+    file: &MappedFile,
+    hits: &[(u64, String)],
+    out_dir: &Path,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<Vec<CarveEntry>> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+
+    let file_len = file.len();
+    let mut entries = Vec::new();
+
+    for (i, (offset, name)) in hits.iter().enumerate() {
+        let offset = *offset;
+        let analyze_len = CARVE_ANALYZE_BYTES.min(file_len.saturating_sub(offset));
+        let carve = analyze_carve_size(name, file.slice(FileRegion::new(offset, analyze_len)), analyze_len);
+
+        if let (Some(size), true) = (carve.size, carve.size_exact) {
+            let mut entry = CarveEntry {
+                index: entries.len(),
+                offset,
+                name: name.clone(),
+                size,
+                sha256: None,
+                output: None,
+                note: String::new(),
+            };
+
+            if size == 0 {
+                entry.note = "skipped: zero size".to_string();
+            } else if offset.saturating_add(size) > file_len {
+                entry.note = format!(
+                    "skipped: extends past end of file ({} bytes available)",
+                    file_len.saturating_sub(offset)
+                );
+            } else {
+                let data = file.slice(FileRegion::new(offset, size));
+                let output = format!("{:04}_0x{:X}.{}", entry.index, offset, carve.extension);
+                match std::fs::write(out_dir.join(&output), data) {
+                    Ok(()) => {
+                        entry.sha256 = Some(sha256_hex(data));
+                        entry.output = Some(output);
+                    }
+                    Err(e) => entry.note = format!("write failed: {}", e),
+                }
+            }
+            entries.push(entry);
+        }
+
+        progress(i + 1, hits.len());
+    }
+
+    Ok(entries)
+}
+
+/// Render manifest rows as CSV (with a header line).
+pub fn manifest_csv(entries: &[CarveEntry]) -> String {
+    let mut csv = String::from("index,offset,name,size,sha256,output,note\n");
+    for e in entries {
+        let _ = writeln!(
+            csv,
+            "{},0x{:X},{},{},{},{},{}",
+            e.index,
+            e.offset,
+            csv_field(&e.name),
+            e.size,
+            e.sha256.as_deref().unwrap_or(""),
+            csv_field(e.output.as_deref().unwrap_or("")),
+            csv_field(&e.note),
+        );
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_sha256_known_values() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_carve_and_hash() {
+        // BMP (size in header) at 0x10, a BMP claiming more than the file, and a
+        // zero-size BMP; the JPEG without an end marker has no exact size.
+        let mut data = vec![0u8; 0x100];
+        data[0x10..0x16].copy_from_slice(b"BM\x20\x00\x00\x00");
+        data[0x40..0x46].copy_from_slice(b"BM\x00\x10\x00\x00");
+        data[0x60..0x66].copy_from_slice(b"BM\x00\x00\x00\x00");
+        data[0x80..0x83].copy_from_slice(&[0xFF, 0xD8, 0xFF]);
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(&data).unwrap();
+        f.flush().unwrap();
+        let file = MappedFile::open(f.path()).unwrap();
+
+        let hits: Vec<(u64, String)> = [(0x10, "BMP"), (0x40, "BMP"), (0x60, "BMP"), (0x80, "JPEG")]
+            .iter()
+            .map(|&(o, n)| (o, n.to_string()))
+            .collect();
+        let dir = tempfile::tempdir().unwrap();
+        let mut calls = Vec::new();
+        let entries = carve_and_hash(&file, &hits, dir.path(), |done, total| calls.push((done, total))).unwrap();
+
+        assert_eq!(calls.last(), Some(&(4, 4)));
+        assert_eq!(entries.len(), 3);
+
+        let carved = &entries[0];
+        assert_eq!(carved.size, 0x20);
+        assert_eq!(carved.output.as_deref(), Some("0000_0x10.bmp"));
+        let written = std::fs::read(dir.path().join("0000_0x10.bmp")).unwrap();
+        assert_eq!(written, &data[0x10..0x30]);
+        assert_eq!(carved.sha256.as_deref(), Some(sha256_hex(&written).as_str()));
+
+        assert!(entries[1].output.is_none());
+        assert!(entries[1].note.contains("past end of file"));
+        assert_eq!(entries[2].note, "skipped: zero size");
+
+        let csv = manifest_csv(&entries);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "index,offset,name,size,sha256,output,note");
+        assert!(lines[1].starts_with("0,0x10,BMP,32,"));
+        assert!(lines[1].ends_with(",0000_0x10.bmp,"));
+        assert_eq!(lines[3], "2,0x60,BMP,0,,,skipped: zero size");
+        assert_eq!(csv_field("a,b \"c\""), "\"a,b \"\"c\"\"\"");
+    }
+}
@@ -461,153 +461,164 @@ impl ControlFlowGraph {
             return;
         }
 
-        // Step 1: Assign layers using BFS from entry
-        self.assign_layers();
+        let successors: HashMap<u64, Vec<u64>> = self.blocks.iter()
+            .map(|(&addr, b)| (addr, b.successors.clone()))
+            .collect();
+        let predecessors: HashMap<u64, Vec<u64>> = self.blocks.iter()
+            .map(|(&addr, b)| (addr, b.predecessors.clone()))
+            .collect();
+        let blocks = &self.blocks;
+        let layout = layered_layout(&[self.entry], &successors, &predecessors, |addr| {
+            blocks[&addr].render_width()
+        });
+
+        for (&addr, node) in &layout.nodes {
+            if let Some(block) = self.blocks.get_mut(&addr) {
+                block.layer = node.layer;
+                block.layout_x = node.x;
+                block.layout_y = node.y;
+            }
+        }
+        self.layers = layout.layers;
+    }
+
+    /// Get the bounding box of the graph.
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        if self.blocks.is_empty() {
+            return (0.0, 0.0, 100.0, 100.0);
+        }
 
-        // Step 2: Order nodes within layers
-        self.order_within_layers();
+        let mut min_x = f32::MAX;
+        let mut min_y = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut max_y = f32::MIN;
+
+        for block in self.blocks.values() {
+            min_x = min_x.min(block.layout_x);
+            min_y = min_y.min(block.layout_y);
+            max_x = max_x.max(block.layout_x + block.render_width());
+            max_y = max_y.max(block.layout_y + block.render_height());
+        }
 
-        // Step 3: Assign X/Y coordinates
-        self.assign_coordinates();
+        (min_x, min_y, max_x, max_y)
     }
+}
 
-    /// Assign layers using BFS (Coffman-Graham style).
-    fn assign_layers(&mut self) {
-        let mut visited: HashSet<u64> = HashSet::new();
-        let mut queue: VecDeque<(u64, usize)> = VecDeque::new();
-        let mut max_layer: HashMap<u64, usize> = HashMap::new();
+/// Position of one node in a [`LayeredLayout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayoutNode {
+    pub x: f32,
+    pub y: f32,
+    pub layer: usize,
+}
 
-        // Start from entry
-        queue.push_back((self.entry, 0));
+/// Result of [`layered_layout`].
+#[derive(Debug, Clone, Default)]
+pub struct LayeredLayout {
+    /// Node positions keyed by node id.
+    pub nodes: HashMap<u64, LayoutNode>,
+    /// Node ids per layer, ordered left to right.
+    pub layers: Vec<Vec<u64>>,
+}
 
-        while let Some((addr, layer)) = queue.pop_front() {
-            if !self.blocks.contains_key(&addr) {
-                continue;
-            }
+/// Hierarchical layout for an arbitrary directed graph.
+///
+/// Every node must be a key of `successors` (with an empty list if it has no
+/// outgoing edges). Layers are assigned by BFS from `roots`; nodes not
+/// reachable from any root go to layer 0. Within a layer, nodes are ordered by
+/// the average position of their predecessors to reduce edge crossings, then
+/// spaced horizontally using `width`.
+pub fn layered_layout(
+    roots: &[u64],
+    successors: &HashMap<u64, Vec<u64>>,
+    predecessors: &HashMap<u64, Vec<u64>>,
+    width: impl Fn(u64) -> f32,
+) -> LayeredLayout {
+    if successors.is_empty() {
+        return LayeredLayout::default();
+    }
 
-            // Track maximum layer for each node (handles back edges)
-            let current_max = max_layer.entry(addr).or_insert(0);
-            if layer > *current_max {
-                *current_max = layer;
-            }
+    // Step 1: Assign layers using BFS (Coffman-Graham style)
+    let mut visited: HashSet<u64> = HashSet::new();
+    let mut queue: VecDeque<(u64, usize)> = roots.iter().map(|&r| (r, 0)).collect();
+    let mut max_layer: HashMap<u64, usize> = HashMap::new();
 
-            if visited.contains(&addr) {
-                continue;
-            }
-            visited.insert(addr);
+    while let Some((addr, layer)) = queue.pop_front() {
+        let succs = match successors.get(&addr) {
+            Some(s) => s,
+            None => continue,
+        };
 
-            // Add successors
-            if let Some(block) = self.blocks.get(&addr) {
-                for &succ in &block.successors {
-                    queue.push_back((succ, layer + 1));
-                }
-            }
+        // Track maximum layer for each node (handles back edges)
+        let current_max = max_layer.entry(addr).or_insert(0);
+        if layer > *current_max {
+            *current_max = layer;
         }
 
-        // Also process unreachable blocks
-        for &addr in self.blocks.keys() {
-            if !visited.contains(&addr) {
-                max_layer.insert(addr, 0);
-            }
+        if !visited.insert(addr) {
+            continue;
         }
 
-        // Apply layers to blocks
-        for (&addr, &layer) in &max_layer {
-            if let Some(block) = self.blocks.get_mut(&addr) {
-                block.layer = layer;
-            }
+        for &succ in succs {
+            queue.push_back((succ, layer + 1));
         }
+    }
 
-        // Build layer list
-        let max_layer_num = max_layer.values().copied().max().unwrap_or(0);
-        self.layers = vec![Vec::new(); max_layer_num + 1];
-        for (&addr, &layer) in &max_layer {
-            self.layers[layer].push(addr);
+    // Also process unreachable nodes
+    for &addr in successors.keys() {
+        if !visited.contains(&addr) {
+            max_layer.insert(addr, 0);
         }
     }
 
-    /// Order nodes within each layer to minimize edge crossings.
-    fn order_within_layers(&mut self) {
-        // Simple heuristic: sort by predecessor positions
-        for layer_idx in 1..self.layers.len() {
-            let prev_layer = &self.layers[layer_idx - 1];
-            let prev_positions: HashMap<u64, usize> = prev_layer
-                .iter()
-                .enumerate()
-                .map(|(i, &addr)| (addr, i))
-                .collect();
-
-            // Sort current layer by average predecessor position
-            let mut layer_with_scores: Vec<(u64, f32)> = self.layers[layer_idx]
-                .iter()
-                .map(|&addr| {
-                    let block = &self.blocks[&addr];
-                    let avg_pos = if block.predecessors.is_empty() {
-                        0.0
-                    } else {
-                        let sum: usize = block.predecessors.iter()
-                            .filter_map(|&p| prev_positions.get(&p))
-                            .sum();
-                        let count = block.predecessors.iter()
-                            .filter(|&p| prev_positions.contains_key(p))
-                            .count();
-                        if count > 0 { sum as f32 / count as f32 } else { 0.0 }
-                    };
-                    (addr, avg_pos)
-                })
-                .collect();
-
-            layer_with_scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-            self.layers[layer_idx] = layer_with_scores.into_iter().map(|(addr, _)| addr).collect();
-        }
+    let max_layer_num = max_layer.values().copied().max().unwrap_or(0);
+    let mut layers = vec![Vec::new(); max_layer_num + 1];
+    for (&addr, &layer) in &max_layer {
+        layers[layer].push(addr);
     }
 
-    /// Assign X/Y coordinates based on layers.
-    fn assign_coordinates(&mut self) {
-        let layer_spacing = 150.0;
-        let node_spacing = 50.0;
+    // Step 2: Order nodes within layers by average predecessor position
+    for layer_idx in 1..layers.len() {
+        let prev_positions: HashMap<u64, usize> = layers[layer_idx - 1]
+            .iter()
+            .enumerate()
+            .map(|(i, &addr)| (addr, i))
+            .collect();
+
+        let mut layer_with_scores: Vec<(u64, f32)> = layers[layer_idx]
+            .iter()
+            .map(|&addr| {
+                let preds = predecessors.get(&addr).map(Vec::as_slice).unwrap_or(&[]);
+                let sum: usize = preds.iter().filter_map(|p| prev_positions.get(p)).sum();
+                let count = preds.iter().filter(|p| prev_positions.contains_key(p)).count();
+                let avg_pos = if count > 0 { sum as f32 / count as f32 } else { 0.0 };
+                (addr, avg_pos)
+            })
+            .collect();
+
+        layer_with_scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        layers[layer_idx] = layer_with_scores.into_iter().map(|(addr, _)| addr).collect();
+    }
 
-        for (layer_idx, layer) in self.layers.iter().enumerate() {
-            let y = layer_idx as f32 * layer_spacing;
+    // Step 3: Assign X/Y coordinates based on layers
+    let layer_spacing = 150.0;
+    let node_spacing = 50.0;
+    let mut nodes = HashMap::with_capacity(max_layer.len());
 
-            // Calculate total width of this layer
-            let total_width: f32 = layer.iter()
-                .map(|&addr| self.blocks[&addr].render_width() + node_spacing)
-                .sum();
+    for (layer_idx, layer) in layers.iter().enumerate() {
+        let y = layer_idx as f32 * layer_spacing;
 
-            // Center the layer
-            let mut x = -total_width / 2.0;
+        // Center the layer
+        let total_width: f32 = layer.iter().map(|&addr| width(addr) + node_spacing).sum();
+        let mut x = -total_width / 2.0;
 
-            for &addr in layer {
-                if let Some(block) = self.blocks.get_mut(&addr) {
-                    block.layout_x = x;
-                    block.layout_y = y;
-                    x += block.render_width() + node_spacing;
-                }
-            }
+        for &addr in layer {
+            nodes.insert(addr, LayoutNode { x, y, layer: layer_idx });
+            x += width(addr) + node_spacing;
         }
     }
 
-    /// Get the bounding box of the graph.
-    pub fn bounds(&self) -> (f32, f32, f32, f32) {
-        if self.blocks.is_empty() {
-            return (0.0, 0.0, 100.0, 100.0);
-        }
-
-        let mut min_x = f32::MAX;
-        let mut min_y = f32::MAX;
-        let mut max_x = f32::MIN;
-        let mut max_y = f32::MIN;
-
-        for block in self.blocks.values() {
-            min_x = min_x.min(block.layout_x);
-            min_y = min_y.min(block.layout_y);
-            max_x = max_x.max(block.layout_x + block.render_width());
-            max_y = max_y.max(block.layout_y + block.render_height());
-        }
-
-        (min_x, min_y, max_x, max_y)
-    }
+    LayeredLayout { nodes, layers }
 }
 
 #[cfg(test)]
@@ -735,4 +746,31 @@ mod tests {
         let instr6 = make_instr(0, 6, "jmp", "[rip + 0x100]");
         assert_eq!(instr6.target_address(), None);
     }
+
+    #[test]
+    fn test_layered_layout_generic_graph() {
+        // a -> b, a -> c, b -> d, c -> d, plus an isolated node e
+        let mut succ: HashMap<u64, Vec<u64>> = HashMap::new();
+        succ.insert(1, vec![2, 3]);
+        succ.insert(2, vec![4]);
+        succ.insert(3, vec![4]);
+        succ.insert(4, vec![]);
+        succ.insert(5, vec![]);
+        let mut pred: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (&from, tos) in &succ {
+            for &to in tos {
+                pred.entry(to).or_default().push(from);
+            }
+        }
+
+        let layout = layered_layout(&[1], &succ, &pred, |_| 100.0);
+        assert_eq!(layout.nodes.len(), 5);
+        assert_eq!(layout.nodes[&1].layer, 0);
+        assert_eq!(layout.nodes[&2].layer, 1);
+        assert_eq!(layout.nodes[&3].layer, 1);
+        assert_eq!(layout.nodes[&4].layer, 2);
+        assert_eq!(layout.nodes[&5].layer, 0, "unreachable nodes go to layer 0");
+        assert!(layout.nodes[&4].y > layout.nodes[&2].y);
+        assert_ne!(layout.nodes[&2].x, layout.nodes[&3].x);
+    }
 }
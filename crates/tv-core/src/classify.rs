@@ -1,3 +1,5 @@
+use rayon::prelude::*;
+
 use crate::BlockClass;
 
 /// Classify each block of `block_size` bytes in `data` using CPU heuristics.
@@ -8,18 +10,14 @@ use crate::BlockClass;
 /// 3. **ASCII** — more than 90% of bytes are printable ASCII (0x20..=0x7E) or whitespace (tab/CR/LF)
 /// 4. **UTF-8** — at least one multi-byte lead byte (0xC0..=0xF7) and entropy < 5.0
 /// 5. **Binary** — everything else
+///
+/// Blocks are independent and classified in parallel with rayon.
 pub fn classify_blocks_cpu(data: &[u8], block_size: usize) -> Vec<BlockClass> {
     if data.is_empty() || block_size == 0 {
         return vec![];
     }
 
-    let mut results = Vec::new();
-
-    for chunk in data.chunks(block_size) {
-        results.push(classify_single_block(chunk));
-    }
-
-    results
+    data.par_chunks(block_size).map(classify_single_block).collect()
 }
 
 fn classify_single_block(block: &[u8]) -> BlockClass {
@@ -203,6 +201,27 @@ mod tests {
         assert_eq!(result[2], BlockClass::HighEntropy);
     }
 
+    #[test]
+    fn classify_parallel_matches_serial() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0xC1A5);
+        // Runs of random, zero, text and small-alphabet blocks so every class shows up
+        let mut data = Vec::new();
+        for i in 0..2048 {
+            let len = rng.gen_range(1..=512);
+            data.extend((0..len).map(|_| match i % 4 {
+                0 => rng.gen::<u8>(),
+                1 => 0,
+                2 => rng.gen_range(b' '..=b'~'),
+                _ => rng.gen_range(0xC0..0xC4),
+            }));
+        }
+
+        let serial: Vec<BlockClass> = data.chunks(256).map(classify_single_block).collect();
+        assert_eq!(classify_blocks_cpu(&data, 256), serial);
+    }
+
     #[test]
     fn classify_partial_last_block() {
         // 300 bytes = 1 full block + 1 partial (44 bytes)
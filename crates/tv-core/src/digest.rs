@@ -0,0 +1,91 @@
+//! Whole-file digests (MD5, SHA-1, SHA-256) in one pass.
+//!
+//! The file is read once in chunks; each chunk feeds the three hashers in
+//! parallel. Hashing reports progress after every chunk and stops early when
+//! its cancel flag is set, so multi-gigabyte files can be hashed in the
+//! background.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// Bytes hashed between progress reports and cancel checks.
+pub const DIGEST_CHUNK: usize = 16 * 1024 * 1024;
+
+/// Lowercase hex digests of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDigests {
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+}
+
+impl FileDigests {
+    /// (algorithm name, hex digest) pairs, shortest first.
+    pub fn named(&self) -> [(&'static str, &str); 3] {
+        [("MD5", &self.md5), ("SHA-1", &self.sha1), ("SHA-256", &self.sha256)]
+    }
+}
+
+/// Hash `data` with MD5, SHA-1 and SHA-256 in `chunk_size` steps.
+///
+/// `progress` is called with the bytes hashed so far after each chunk.
+/// Returns `None` if `cancel` was set before the last chunk.
+pub fn digest_data(
+    data: &[u8],
+    chunk_size: usize,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> Option<FileDigests> {
+    let (mut md5, mut sha1, mut sha256) = (Md5::new(), Sha1::new(), Sha256::new());
+    let mut done = 0u64;
+
+    for chunk in data.chunks(chunk_size.max(1)) {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        rayon::join(
+            || md5.update(chunk),
+            || rayon::join(|| sha1.update(chunk), || sha256.update(chunk)),
+        );
+        done += chunk.len() as u64;
+        progress(done);
+    }
+
+    Some(FileDigests {
+        md5: format!("{:x}", md5.finalize()),
+        sha1: format!("{:x}", sha1.finalize()),
+        sha256: format!("{:x}", sha256.finalize()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digests_of_known_input() {
+        let cancel = AtomicBool::new(false);
+        let mut reports = Vec::new();
+        let digests = digest_data(b"abc", 2, &cancel, |done| reports.push(done)).unwrap();
+        assert_eq!(digests.md5, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(digests.sha1, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(digests.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(reports, vec![2, 3]);
+
+        // Chunking does not change the result
+        assert_eq!(digest_data(b"abc", DIGEST_CHUNK, &cancel, |_| {}), Some(digests));
+    }
+
+    #[test]
+    fn empty_data_and_cancel() {
+        let cancel = AtomicBool::new(false);
+        let empty = digest_data(&[], DIGEST_CHUNK, &cancel, |_| {}).unwrap();
+        assert_eq!(empty.sha256, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(digest_data(b"abc", 1, &cancel, |_| {}), None);
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::{BTreeMap, HashSet};
+
 use anyhow::Result;
 use capstone::prelude::*;
 
+use crate::cfg::CfgInstruction;
+
 /// Supported CPU architectures for disassembly.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Architecture {
@@ -29,6 +33,11 @@ impl Architecture {
         }
     }
 
+    /// Parse an architecture from its `Debug` name (as stored in sessions).
+    pub fn from_name(name: &str) -> Option<Architecture> {
+        Self::all().iter().copied().find(|arch| format!("{:?}", arch) == name)
+    }
+
     pub fn all() -> &'static [Architecture] {
         &[
             Architecture::X86_64,
@@ -123,6 +132,327 @@ pub fn disassemble(
     })
 }
 
+/// Address ranges that hold Thumb code in an otherwise ARM (32-bit) image.
+///
+/// Ranges are half-open `[start, end)`, kept sorted and merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThumbRanges {
+    ranges: Vec<(u64, u64)>,
+}
+
+impl ThumbRanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The marked ranges, sorted by start address.
+    pub fn ranges(&self) -> &[(u64, u64)] {
+        &self.ranges
+    }
+
+    /// Mark `[start, end)` as Thumb, merging with adjacent or overlapping ranges.
+    pub fn add(&mut self, start: u64, end: u64) {
+        if start >= end {
+            return;
+        }
+        let mut start = start;
+        let mut end = end;
+        self.ranges.retain(|&(s, e)| {
+            if e < start || s > end {
+                true
+            } else {
+                start = start.min(s);
+                end = end.max(e);
+                false
+            }
+        });
+        let idx = self.ranges.partition_point(|&(s, _)| s < start);
+        self.ranges.insert(idx, (start, end));
+    }
+
+    /// Unmark `[start, end)`, splitting ranges that straddle it.
+    pub fn remove(&mut self, start: u64, end: u64) {
+        if start >= end {
+            return;
+        }
+        let mut kept = Vec::with_capacity(self.ranges.len() + 1);
+        for &(s, e) in &self.ranges {
+            if e <= start || s >= end {
+                kept.push((s, e));
+                continue;
+            }
+            if s < start {
+                kept.push((s, start));
+            }
+            if e > end {
+                kept.push((end, e));
+            }
+        }
+        self.ranges = kept;
+    }
+
+    /// Whether `addr` falls in a Thumb range.
+    pub fn contains(&self, addr: u64) -> bool {
+        let idx = self.ranges.partition_point(|&(s, _)| s <= addr);
+        idx > 0 && addr < self.ranges[idx - 1].1
+    }
+
+    /// The first address after `addr` where the decoding mode changes, if any.
+    pub fn next_boundary(&self, addr: u64) -> Option<u64> {
+        let idx = self.ranges.partition_point(|&(s, _)| s <= addr);
+        if idx > 0 && addr < self.ranges[idx - 1].1 {
+            return Some(self.ranges[idx - 1].1);
+        }
+        self.ranges.get(idx).map(|&(s, _)| s)
+    }
+}
+
+/// Disassemble ARM code that interleaves ARM and Thumb regions.
+///
+/// For `Architecture::Arm32`, bytes inside `thumb` are decoded in Thumb mode and
+/// everything else in ARM mode. Other architectures ignore `thumb` and behave
+/// like [`disassemble`].
+pub fn disassemble_mixed(
+    data: &[u8],
+    base_address: u64,
+    arch: Architecture,
+    max_instructions: usize,
+    thumb: &ThumbRanges,
+) -> Result<DisassemblyResult> {
+    if arch != Architecture::Arm32 || thumb.is_empty() {
+        return disassemble(data, base_address, arch, max_instructions);
+    }
+
+    let arm_cs = create_capstone(arch)?;
+    let thumb_cs = create_thumb_capstone()?;
+
+    let mut instructions = Vec::new();
+    let mut pos = 0usize;
+
+    while pos < data.len() && instructions.len() < max_instructions {
+        let addr = base_address + pos as u64;
+        let run_end = thumb
+            .next_boundary(addr)
+            .map_or(data.len(), |b| (b - base_address).min(data.len() as u64) as usize);
+        let cs = if thumb.contains(addr) { &thumb_cs } else { &arm_cs };
+
+        let insns = cs.disasm_count(&data[pos..run_end], addr, max_instructions - instructions.len())
+            .map_err(|e| anyhow::anyhow!("Disassembly failed: {}", e))?;
+
+        let mut consumed = 0;
+        for insn in insns.iter() {
+            let bytes = insn.bytes().to_vec();
+            consumed += bytes.len();
+
+            instructions.push(Instruction {
+                address: insn.address(),
+                bytes,
+                mnemonic: insn.mnemonic().unwrap_or("???").to_string(),
+                operands: insn.op_str().unwrap_or("").to_string(),
+            });
+        }
+
+        pos += consumed;
+        // Capstone stops at the first undecodable bytes; do the same across runs.
+        if pos < run_end {
+            break;
+        }
+    }
+
+    Ok(DisassemblyResult {
+        arch,
+        base_address,
+        instructions,
+        bytes_consumed: pos,
+        error: None,
+    })
+}
+
+/// Disassemble only the code reachable from `base_address` (recursive descent).
+///
+/// Each path is decoded until a return, an unconditional jump or undecodable
+/// bytes; direct jump and call targets inside `data` start new paths. Control
+/// flow is recognized as for the CFG (`CfgInstruction`), so this finds more
+/// than a linear sweep on x86 only. Instructions are returned in address
+/// order; gaps between them are bytes no path reached (usually data). A jump
+/// into the middle of a decoded instruction lists both decodings, and
+/// `bytes_consumed` counts the bytes they share once.
+pub fn disassemble_recursive(
+    data: &[u8],
+    base_address: u64,
+    arch: Architecture,
+    max_instructions: usize,
+    thumb: &ThumbRanges,
+) -> Result<DisassemblyResult> {
+    let cs = create_capstone(arch)?;
+    let thumb_cs = if arch == Architecture::Arm32 && !thumb.is_empty() {
+        Some(create_thumb_capstone()?)
+    } else {
+        None
+    };
+    let end = base_address + data.len() as u64;
+
+    let mut decoded: BTreeMap<u64, Instruction> = BTreeMap::new();
+    let mut queued: HashSet<u64> = HashSet::from([base_address]);
+    let mut pending = vec![base_address];
+
+    'paths: while let Some(mut addr) = pending.pop() {
+        while addr < end && !decoded.contains_key(&addr) {
+            if decoded.len() >= max_instructions {
+                break 'paths;
+            }
+            let cs = match &thumb_cs {
+                Some(thumb_cs) if thumb.contains(addr) => thumb_cs,
+                _ => &cs,
+            };
+            let insns = cs.disasm_count(&data[(addr - base_address) as usize..], addr, 1)
+                .map_err(|e| anyhow::anyhow!("Disassembly failed: {}", e))?;
+            let Some(insn) = insns.iter().next() else { break };
+
+            let flow = CfgInstruction {
+                address: addr,
+                size: insn.bytes().len() as u8,
+                mnemonic: insn.mnemonic().unwrap_or("???").to_string(),
+                operands: insn.op_str().unwrap_or("").to_string(),
+                bytes: insn.bytes().to_vec(),
+            };
+            if flow.is_jump() || flow.is_call() {
+                if let Some(target) = flow.target_address().filter(|t| (base_address..end).contains(t)) {
+                    if queued.insert(target) {
+                        pending.push(target);
+                    }
+                }
+            }
+            let next = addr + flow.bytes.len() as u64;
+            let stop = flow.is_return() || flow.is_unconditional_jump();
+            decoded.insert(addr, Instruction {
+                address: addr,
+                bytes: flow.bytes,
+                mnemonic: flow.mnemonic,
+                operands: flow.operands,
+            });
+            if stop {
+                break;
+            }
+            addr = next;
+        }
+    }
+
+    let instructions: Vec<Instruction> = decoded.into_values().collect();
+    let mut bytes_consumed = 0;
+    let mut covered_end = base_address;
+    for insn in &instructions {
+        let insn_end = insn.address + insn.bytes.len() as u64;
+        bytes_consumed += insn_end.saturating_sub(insn.address.max(covered_end)) as usize;
+        covered_end = covered_end.max(insn_end);
+    }
+    Ok(DisassemblyResult {
+        arch,
+        base_address,
+        bytes_consumed,
+        instructions,
+        error: None,
+    })
+}
+
+/// Replace hex addresses in an operand string with symbol names.
+///
+/// Every `0x...` token (and a leading `#` immediate marker, as printed for ARM)
+/// whose value `lookup` resolves is swapped for the symbol, so
+/// `call 0x401200` reads `call decrypt_routine`. Returns `None` if nothing
+/// was resolved.
+pub fn symbolize_operands<'a>(operands: &str, lookup: impl Fn(u64) -> Option<&'a str>) -> Option<String> {
+    let bytes = operands.as_bytes();
+    let mut out = String::with_capacity(operands.len());
+    let mut replaced = false;
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i + 1 < bytes.len() {
+        let at_token_start = i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_');
+        if !(at_token_start && bytes[i] == b'0' && (bytes[i + 1] == b'x' || bytes[i + 1] == b'X')) {
+            i += 1;
+            continue;
+        }
+
+        let digits_start = i + 2;
+        let mut end = digits_start;
+        while end < bytes.len() && bytes[end].is_ascii_hexdigit() {
+            end += 1;
+        }
+        let bounded = end == bytes.len() || !(bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_');
+
+        let symbol = if end > digits_start && bounded {
+            u64::from_str_radix(&operands[digits_start..end], 16).ok().and_then(&lookup)
+        } else {
+            None
+        };
+
+        if let Some(name) = symbol {
+            let start = if i > 0 && bytes[i - 1] == b'#' { i - 1 } else { i };
+            out.push_str(&operands[copied..start]);
+            out.push_str(name);
+            copied = end;
+            replaced = true;
+        }
+        i = end.max(i + 1);
+    }
+
+    if !replaced {
+        return None;
+    }
+    out.push_str(&operands[copied..]);
+    Some(out)
+}
+
+/// An ARM/Thumb interworking branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeSwitch {
+    /// `blx #imm`: the target is known and always runs in the other mode.
+    Direct { target: u64, thumb: bool },
+    /// `bx`/`blx` through a register: bit 0 of the register picks the mode at runtime.
+    Register,
+}
+
+/// Detect a BX/BLX mode-switch hint in ARM or Thumb code.
+/// `in_thumb` is the mode `insn` was decoded in. `bx lr` is a plain return and
+/// is not reported.
+pub fn mode_switch_hint(insn: &Instruction, in_thumb: bool) -> Option<ModeSwitch> {
+    let mnemonic = insn.mnemonic.to_lowercase();
+    if mnemonic != "bx" && mnemonic != "blx" {
+        return None;
+    }
+
+    let operand = insn.operands.trim();
+    if let Some(imm) = operand.strip_prefix('#') {
+        let target = match imm.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+            None => imm.parse().ok()?,
+        };
+        return Some(ModeSwitch::Direct { target, thumb: !in_thumb });
+    }
+
+    if operand == "lr" {
+        None
+    } else {
+        Some(ModeSwitch::Register)
+    }
+}
+
+/// Create a Capstone instance decoding 32-bit ARM in Thumb mode.
+fn create_thumb_capstone() -> Result<Capstone> {
+    Capstone::new()
+        .arm()
+        .mode(arch::arm::ArchMode::Thumb)
+        .detail(true)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create Capstone: {}", e))
+}
+
 /// Create a Capstone instance for the given architecture.
 fn create_capstone(arch: Architecture) -> Result<Capstone> {
     let cs = match arch {
@@ -276,6 +606,14 @@ pub fn detect_architecture(data: &[u8]) -> Option<Architecture> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_architecture_from_name() {
+        for arch in Architecture::all() {
+            assert_eq!(Architecture::from_name(&format!("{:?}", arch)), Some(*arch));
+        }
+        assert_eq!(Architecture::from_name("x86-64"), None);
+    }
+
     #[test]
     fn test_disasm_x86_64() {
         // mov rax, rbx; ret
@@ -299,6 +637,128 @@ mod tests {
         assert_eq!(result.instructions[2].mnemonic, "ret");
     }
 
+    // push {r4, lr}; movs r0, #1; pop {r4, pc}
+    const THUMB_CODE: [u8; 6] = [0x10, 0xB5, 0x01, 0x20, 0x10, 0xBD];
+
+    #[test]
+    fn test_disasm_thumb_setting() {
+        let mut thumb = ThumbRanges::new();
+        thumb.add(0x1000, 0x1000 + THUMB_CODE.len() as u64);
+
+        let result = disassemble_mixed(&THUMB_CODE, 0x1000, Architecture::Arm32, 10, &thumb).unwrap();
+        let mnemonics: Vec<&str> = result.instructions.iter().map(|i| i.mnemonic.as_str()).collect();
+        assert_eq!(mnemonics, ["push", "movs", "pop"]);
+        assert_eq!(result.bytes_consumed, THUMB_CODE.len());
+
+        // The same bytes decoded as ARM are not the Thumb sequence.
+        let arm = disassemble_mixed(&THUMB_CODE, 0x1000, Architecture::Arm32, 10, &ThumbRanges::new()).unwrap();
+        let arm_mnemonics: Vec<&str> = arm.instructions.iter().map(|i| i.mnemonic.as_str()).collect();
+        assert_ne!(arm_mnemonics, mnemonics);
+        assert!(arm.instructions.first().is_none_or(|i| i.mnemonic != "push"));
+    }
+
+    #[test]
+    fn test_disasm_mixed_arm_then_thumb() {
+        // ARM: mov r0, r1 (E1A00001), then the Thumb sequence.
+        let mut code = vec![0x01, 0x00, 0xA0, 0xE1];
+        code.extend_from_slice(&THUMB_CODE);
+        let mut thumb = ThumbRanges::new();
+        thumb.add(4, code.len() as u64);
+
+        let result = disassemble_mixed(&code, 0, Architecture::Arm32, 10, &thumb).unwrap();
+        let mnemonics: Vec<&str> = result.instructions.iter().map(|i| i.mnemonic.as_str()).collect();
+        assert_eq!(mnemonics, ["mov", "push", "movs", "pop"]);
+        assert_eq!(result.instructions[1].address, 4);
+    }
+
+    #[test]
+    fn test_disasm_recursive_skips_data() {
+        let code = [
+            0xEB, 0x02,                   // 0x1000: jmp 0x1004
+            0xFF, 0xFF,                   // data
+            0xE8, 0x04, 0x00, 0x00, 0x00, // 0x1004: call 0x100D
+            0xC3,                         // 0x1009: ret
+            0x00, 0x00, 0x00,             // data
+            0x48, 0x89, 0xD8,             // 0x100D: mov rax, rbx
+            0xC3,                         // 0x1010: ret
+        ];
+        let result = disassemble_recursive(&code, 0x1000, Architecture::X86_64, 100, &ThumbRanges::new()).unwrap();
+        let listing: Vec<(u64, &str)> = result.instructions.iter().map(|i| (i.address, i.mnemonic.as_str())).collect();
+        assert_eq!(listing, [(0x1000, "jmp"), (0x1004, "call"), (0x1009, "ret"), (0x100D, "mov"), (0x1010, "ret")]);
+        assert_eq!(result.bytes_consumed, 12);
+
+        let capped = disassemble_recursive(&code, 0x1000, Architecture::X86_64, 2, &ThumbRanges::new()).unwrap();
+        assert_eq!(capped.instructions.len(), 2);
+
+        // jmp 0x1001 lands inside itself: both decodings are listed, the
+        // shared byte is counted once
+        let overlapping = [0xEB, 0xFF, 0xC0, 0xC3];
+        let result = disassemble_recursive(&overlapping, 0x1000, Architecture::X86_64, 100, &ThumbRanges::new()).unwrap();
+        let addresses: Vec<u64> = result.instructions.iter().map(|i| i.address).collect();
+        assert_eq!(addresses, [0x1000, 0x1001, 0x1003]);
+        assert_eq!(result.bytes_consumed, 4);
+    }
+
+    #[test]
+    fn test_mode_switch_hints() {
+        // ARM blx #imm (FA000000) at 0x1000 targets 0x1008 in Thumb mode.
+        let code = [0x00, 0x00, 0x00, 0xFA];
+        let result = disassemble(&code, 0x1000, Architecture::Arm32, 1).unwrap();
+        assert_eq!(
+            mode_switch_hint(&result.instructions[0], false),
+            Some(ModeSwitch::Direct { target: 0x1008, thumb: true })
+        );
+
+        let insn = |m: &str, ops: &str| Instruction {
+            address: 0,
+            bytes: vec![],
+            mnemonic: m.to_string(),
+            operands: ops.to_string(),
+        };
+        assert_eq!(mode_switch_hint(&insn("bx", "r3"), true), Some(ModeSwitch::Register));
+        assert_eq!(mode_switch_hint(&insn("bx", "lr"), false), None);
+        assert_eq!(mode_switch_hint(&insn("mov", "r0, r1"), false), None);
+    }
+
+    #[test]
+    fn test_symbolize_operands() {
+        let lookup = |addr: u64| match addr {
+            0x401200 => Some("decrypt_routine"),
+            0x1008 => Some("thumb_entry"),
+            _ => None,
+        };
+
+        assert_eq!(symbolize_operands("0x401200", lookup).as_deref(), Some("decrypt_routine"));
+        assert_eq!(symbolize_operands("#0x1008", lookup).as_deref(), Some("thumb_entry"));
+        assert_eq!(
+            symbolize_operands("qword ptr [rip + 0x10], 0x401200", lookup).as_deref(),
+            Some("qword ptr [rip + 0x10], decrypt_routine")
+        );
+        // Unknown addresses and non-address tokens are left alone.
+        assert_eq!(symbolize_operands("rax, 0x10", lookup), None);
+        assert_eq!(symbolize_operands("r0x1008", lookup), None);
+        assert_eq!(symbolize_operands("0x401200a", lookup), None);
+        assert_eq!(symbolize_operands("", lookup), None);
+    }
+
+    #[test]
+    fn test_thumb_ranges_merge_and_split() {
+        let mut r = ThumbRanges::new();
+        r.add(0x100, 0x200);
+        r.add(0x300, 0x400);
+        r.add(0x1F0, 0x300);
+        assert_eq!(r.ranges(), &[(0x100, 0x400)]);
+
+        r.remove(0x200, 0x280);
+        assert_eq!(r.ranges(), &[(0x100, 0x200), (0x280, 0x400)]);
+        assert!(r.contains(0x1FF));
+        assert!(!r.contains(0x200));
+        assert_eq!(r.next_boundary(0x0), Some(0x100));
+        assert_eq!(r.next_boundary(0x150), Some(0x200));
+        assert_eq!(r.next_boundary(0x220), Some(0x280));
+        assert_eq!(r.next_boundary(0x400), None);
+    }
+
     #[test]
     fn test_detect_elf_x86_64() {
         let mut elf = vec![0u8; 64];
@@ -0,0 +1,194 @@
+//! Text encoding detection.
+//!
+//! A byte order mark settles the encoding. Without one, the start of the
+//! file is checked for UTF-16 (ASCII text with every other byte zero), then
+//! for plain ASCII or valid UTF-8 with few control characters. Anything else
+//! is treated as binary.
+
+use crate::text::TextEncoding;
+
+/// Bytes examined by [`detect`]; longer input is cut to its start.
+pub const DETECT_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Largest fraction of control characters (other than whitespace and
+/// escape) still accepted as text.
+const MAX_CONTROL_FRACTION: f64 = 0.01;
+
+/// Smallest fraction of UTF-16 code units that must be ASCII text for
+/// BOM-less UTF-16.
+const MIN_UTF16_ASCII_FRACTION: f64 = 0.6;
+
+/// Character set of a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Charset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Charset::Ascii => "ASCII",
+            Charset::Utf8 => "UTF-8",
+            Charset::Utf16Le => "UTF-16LE",
+            Charset::Utf16Be => "UTF-16BE",
+            Charset::Utf32Le => "UTF-32LE",
+            Charset::Utf32Be => "UTF-32BE",
+        }
+    }
+
+    /// Byte order mark of this charset (empty for ASCII).
+    pub fn bom(&self) -> &'static [u8] {
+        match self {
+            Charset::Ascii => &[],
+            Charset::Utf8 => &[0xEF, 0xBB, 0xBF],
+            Charset::Utf16Le => &[0xFF, 0xFE],
+            Charset::Utf16Be => &[0xFE, 0xFF],
+            Charset::Utf32Le => &[0xFF, 0xFE, 0x00, 0x00],
+            Charset::Utf32Be => &[0x00, 0x00, 0xFE, 0xFF],
+        }
+    }
+
+    /// Encoding of the text view that reads this charset (UTF-32 has none).
+    pub fn text_encoding(&self) -> Option<TextEncoding> {
+        match self {
+            Charset::Ascii => Some(TextEncoding::Ascii),
+            Charset::Utf8 => Some(TextEncoding::Utf8),
+            Charset::Utf16Le => Some(TextEncoding::Utf16Le),
+            Charset::Utf16Be => Some(TextEncoding::Utf16Be),
+            Charset::Utf32Le | Charset::Utf32Be => None,
+        }
+    }
+}
+
+/// Likely encoding of a file that holds text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoding {
+    pub charset: Charset,
+    /// Found from a byte order mark at the start (otherwise from the content).
+    pub bom: bool,
+}
+
+impl Encoding {
+    /// Label such as "UTF-16LE (BOM)".
+    pub fn label(&self) -> String {
+        if self.bom {
+            format!("{} (BOM)", self.charset.label())
+        } else {
+            self.charset.label().to_string()
+        }
+    }
+}
+
+/// Charset named by a byte order mark at the start of `data`.
+fn bom_charset(data: &[u8]) -> Option<Charset> {
+    // UTF-32LE before UTF-16LE: its mark starts with the UTF-16LE one
+    [Charset::Utf32Le, Charset::Utf32Be, Charset::Utf8, Charset::Utf16Le, Charset::Utf16Be]
+        .into_iter()
+        .find(|charset| data.starts_with(charset.bom()))
+}
+
+/// True for bytes that rarely occur in text.
+fn is_control(b: u8) -> bool {
+    (b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0C | 0x1B)) || b == 0x7F
+}
+
+/// UTF-16 byte order of mostly-ASCII text without a BOM.
+fn utf16_order(data: &[u8]) -> Option<Charset> {
+    let units = data.len() / 2;
+    if units < 4 {
+        return None;
+    }
+    let ascii_units = |high: usize, low: usize| {
+        data.chunks_exact(2)
+            .filter(|pair| pair[high] == 0 && pair[low] != 0 && !is_control(pair[low]) && pair[low].is_ascii())
+            .count()
+    };
+    let min_units = (units as f64 * MIN_UTF16_ASCII_FRACTION) as usize;
+    if ascii_units(1, 0) >= min_units {
+        Some(Charset::Utf16Le)
+    } else if ascii_units(0, 1) >= min_units {
+        Some(Charset::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// Likely text encoding of `data` (the start of a file), or `None` if it
+/// looks binary.
+pub fn detect(data: &[u8]) -> Option<Encoding> {
+    if let Some(charset) = bom_charset(data) {
+        return Some(Encoding { charset, bom: true });
+    }
+    let data = &data[..data.len().min(DETECT_SAMPLE_BYTES)];
+    if data.is_empty() {
+        return None;
+    }
+    if let Some(charset) = utf16_order(data) {
+        return Some(Encoding { charset, bom: false });
+    }
+
+    let controls = data.iter().filter(|&&b| is_control(b)).count();
+    if data.contains(&0) || controls as f64 > data.len() as f64 * MAX_CONTROL_FRACTION {
+        return None;
+    }
+    let charset = if data.is_ascii() {
+        Charset::Ascii
+    } else {
+        // The sample may end inside a multi-byte character
+        match std::str::from_utf8(data) {
+            Ok(_) => Charset::Utf8,
+            Err(e) if e.error_len().is_none() => Charset::Utf8,
+            Err(_) => return None,
+        }
+    };
+    Some(Encoding { charset, bom: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|u| if big_endian { u.to_be_bytes() } else { u.to_le_bytes() })
+            .collect()
+    }
+
+    #[test]
+    fn byte_order_marks() {
+        let detected = |data: &[u8]| detect(data).map(|e| (e.charset, e.bom));
+        assert_eq!(detected(b"\xEF\xBB\xBFhello"), Some((Charset::Utf8, true)));
+        assert_eq!(detected(b"\xFF\xFEh\x00i\x00"), Some((Charset::Utf16Le, true)));
+        assert_eq!(detected(b"\xFE\xFF\x00h"), Some((Charset::Utf16Be, true)));
+        assert_eq!(detected(b"\xFF\xFE\x00\x00h\x00\x00\x00"), Some((Charset::Utf32Le, true)));
+        assert_eq!(detected(b"\x00\x00\xFE\xFF"), Some((Charset::Utf32Be, true)));
+        assert_eq!(detect(b"\xFF\xFEh\x00").unwrap().label(), "UTF-16LE (BOM)");
+    }
+
+    #[test]
+    fn text_without_bom() {
+        let charset = |data: &[u8]| detect(data).map(|e| e.charset);
+        assert_eq!(charset(b"line one\r\nline two\tend\n"), Some(Charset::Ascii));
+        assert_eq!(charset("caf\u{e9} na\u{ef}ve r\u{e9}sum\u{e9}".as_bytes()), Some(Charset::Utf8));
+        // Cut inside the last character
+        assert_eq!(charset(&"d\u{e9}j\u{e0} vu \u{e9}".as_bytes()[..11]), Some(Charset::Utf8));
+        assert_eq!(charset(&utf16("Hello, UTF-16 world", false)), Some(Charset::Utf16Le));
+        assert_eq!(charset(&utf16("Hello, UTF-16 world", true)), Some(Charset::Utf16Be));
+    }
+
+    #[test]
+    fn binary_is_not_text() {
+        assert_eq!(detect(b""), None);
+        assert_eq!(detect(b"MZ\x90\x00\x03\x00\x00\x00\x04\x00\x00\x00\xFF\xFF\x00\x00"), None);
+        assert_eq!(detect(b"caf\xE9 latin-1"), None);
+        assert_eq!(detect(&[0u8; 64]), None);
+        let mut controls = vec![b'a'; 50];
+        controls[10] = 0x01;
+        assert_eq!(detect(&controls), None);
+    }
+}
@@ -1,32 +1,67 @@
+use rayon::prelude::*;
+
 /// Compute per-block Shannon entropy on the CPU.
 /// Returns one f32 per block. Entropy ranges from 0.0 (uniform) to 8.0 (max).
+///
+/// Blocks are independent and processed in parallel with rayon.
 pub fn compute_entropy_cpu(data: &[u8], block_size: usize) -> Vec<f32> {
     if data.is_empty() || block_size == 0 {
         return vec![];
     }
 
-    let mut results = Vec::new();
-
-    for chunk in data.chunks(block_size) {
-        let mut freq = [0u32; 256];
-        for &b in chunk {
-            freq[b as usize] += 1;
-        }
+    data.par_chunks(block_size).map(block_entropy).collect()
+}
 
-        let total = chunk.len() as f64;
-        let entropy: f64 = freq
-            .iter()
-            .filter(|&&f| f > 0)
-            .map(|&f| {
-                let p = f as f64 / total;
-                -p * p.log2()
-            })
-            .sum();
-
-        results.push(entropy as f32);
+/// Shannon entropy of a single block.
+fn block_entropy(block: &[u8]) -> f32 {
+    let mut freq = [0u32; 256];
+    for &b in block {
+        freq[b as usize] += 1;
     }
 
-    results
+    let total = block.len() as f64;
+    let entropy: f64 = freq
+        .iter()
+        .filter(|&&f| f > 0)
+        .map(|&f| {
+            let p = f as f64 / total;
+            -p * p.log2()
+        })
+        .sum();
+
+    entropy as f32
+}
+
+/// Entropy change (bits/byte) between neighboring blocks that marks the
+/// boundary of a region (e.g. plain data followed by encrypted data).
+pub const BOUNDARY_THRESHOLD: f32 = 2.0;
+
+/// Whether block `i` starts a region: its entropy differs from the
+/// previous block's by at least `threshold`.
+fn is_boundary(entropy: &[f32], i: usize, threshold: f32) -> bool {
+    i > 0 && (entropy[i] - entropy[i - 1]).abs() >= threshold
+}
+
+/// First boundary block after `block`.
+pub fn next_entropy_boundary(entropy: &[f32], block: usize, threshold: f32) -> Option<usize> {
+    (block + 1..entropy.len()).find(|&i| is_boundary(entropy, i, threshold))
+}
+
+/// Last boundary block before `block`.
+pub fn prev_entropy_boundary(entropy: &[f32], block: usize, threshold: f32) -> Option<usize> {
+    (1..block.min(entropy.len())).rev().find(|&i| is_boundary(entropy, i, threshold))
+}
+
+/// Block with the highest entropy (the first one on ties).
+pub fn max_entropy_block(entropy: &[f32]) -> Option<usize> {
+    let mut best: Option<(usize, f32)> = None;
+    for (i, &e) in entropy.iter().enumerate() {
+        match best {
+            Some((_, max)) if e <= max => {}
+            _ => best = Some((i, e)),
+        }
+    }
+    best.map(|(i, _)| i)
 }
 
 #[cfg(test)]
@@ -63,6 +98,27 @@ mod tests {
         assert!(compute_entropy_cpu(&[], 256).is_empty());
     }
 
+    #[test]
+    fn cpu_entropy_parallel_matches_serial() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x7157);
+        // Mix random and low-variety regions, with a partial last block
+        let mut data = vec![0u8; 1 << 20];
+        rng.fill(&mut data[..1 << 19]);
+        for b in &mut data[1 << 19..] {
+            *b = rng.gen_range(0..4);
+        }
+        data.truncate(data.len() - 100);
+
+        for block_size in [256, 4096, 1000] {
+            let serial: Vec<f32> = data.chunks(block_size).map(block_entropy).collect();
+            let parallel = compute_entropy_cpu(&data, block_size);
+            assert_eq!(parallel.len(), serial.len());
+            assert!(parallel.iter().zip(&serial).all(|(p, s)| p.to_bits() == s.to_bits()));
+        }
+    }
+
     #[test]
     fn cpu_entropy_multiple_blocks() {
         let mut data = vec![0u8; 256]; // block 0: all zeros
@@ -72,4 +128,21 @@ mod tests {
         assert!(result[0].abs() < 0.001);
         assert!((result[1] - 8.0).abs() < 0.001);
     }
+
+    #[test]
+    fn entropy_boundaries_and_maximum() {
+        // Text-like, then an encrypted region, then padding
+        let entropy = [4.5, 4.8, 4.6, 7.9, 7.95, 7.9, 0.0, 0.0];
+        assert_eq!(next_entropy_boundary(&entropy, 0, BOUNDARY_THRESHOLD), Some(3));
+        assert_eq!(next_entropy_boundary(&entropy, 3, BOUNDARY_THRESHOLD), Some(6));
+        assert_eq!(next_entropy_boundary(&entropy, 6, BOUNDARY_THRESHOLD), None);
+        assert_eq!(prev_entropy_boundary(&entropy, 6, BOUNDARY_THRESHOLD), Some(3));
+        assert_eq!(prev_entropy_boundary(&entropy, 3, BOUNDARY_THRESHOLD), None);
+        assert_eq!(prev_entropy_boundary(&entropy, 100, BOUNDARY_THRESHOLD), Some(6));
+        assert_eq!(max_entropy_block(&entropy), Some(4));
+
+        assert_eq!(next_entropy_boundary(&[], 0, BOUNDARY_THRESHOLD), None);
+        assert_eq!(max_entropy_block(&[]), None);
+        assert_eq!(max_entropy_block(&[1.0, 1.0]), Some(0));
+    }
 }
@@ -0,0 +1,103 @@
+//! Filesystem metadata of an opened file (timestamps, permissions).
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+/// OS-level metadata of a file, read once when it is opened.
+///
+/// Timestamps are seconds since the Unix epoch; a timestamp the platform or
+/// filesystem does not record (e.g. creation time on many Linux filesystems)
+/// is `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub created: Option<u64>,
+    pub modified: Option<u64>,
+    pub accessed: Option<u64>,
+    pub readonly: bool,
+    /// Unix permission bits (`st_mode & 0o7777`); `None` on other platforms.
+    pub mode: Option<u32>,
+}
+
+impl FileMetadata {
+    /// Read the metadata of the file at `path`.
+    pub fn read(path: &Path) -> Result<Self> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("failed to read metadata for {}", path.display()))?;
+
+        Ok(Self {
+            created: metadata.created().ok().and_then(unix_secs),
+            modified: metadata.modified().ok().and_then(unix_secs),
+            accessed: metadata.accessed().ok().and_then(unix_secs),
+            readonly: metadata.permissions().readonly(),
+            mode: unix_mode(&metadata),
+        })
+    }
+
+    /// Permissions as text: `rwxr-xr-x (0755)` with Unix mode bits,
+    /// otherwise `read-only` or `read-write`.
+    pub fn permissions_label(&self) -> String {
+        match self.mode {
+            Some(mode) => {
+                let rwx: String = (0..9)
+                    .map(|i| {
+                        let bit = 0o400 >> i;
+                        if mode & bit == 0 { '-' } else { ['r', 'w', 'x'][i % 3] }
+                    })
+                    .collect();
+                format!("{} ({:04o})", rwx, mode)
+            }
+            None if self.readonly => "read-only".to_string(),
+            None => "read-write".to_string(),
+        }
+    }
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn read_metadata_of_temp_file() {
+        let mut f = NamedTempFile::new().expect("failed to create temp file");
+        f.write_all(b"metadata").expect("failed to write fixture");
+
+        let meta = FileMetadata::read(f.path()).unwrap();
+        assert!(meta.modified.is_some());
+        assert!(!meta.readonly);
+        #[cfg(unix)]
+        assert!(meta.mode.is_some_and(|m| m & 0o200 != 0));
+
+        assert!(FileMetadata::read(Path::new("/nonexistent/titanview")).is_err());
+    }
+
+    #[test]
+    fn permissions_label_formats() {
+        let mut meta = FileMetadata { created: None, modified: None, accessed: None, readonly: false, mode: Some(0o755) };
+        assert_eq!(meta.permissions_label(), "rwxr-xr-x (0755)");
+        meta.mode = Some(0o640);
+        assert_eq!(meta.permissions_label(), "rw-r----- (0640)");
+        meta.mode = None;
+        assert_eq!(meta.permissions_label(), "read-write");
+        meta.readonly = true;
+        assert_eq!(meta.permissions_label(), "read-only");
+    }
+}
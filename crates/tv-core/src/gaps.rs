@@ -0,0 +1,155 @@
+//! Unaccounted regions between carved files.
+//!
+//! In a disk image the bytes outside every recognized file are slack space,
+//! unallocated clusters or remnants of deleted files. Given the carved
+//! regions, this finds the gaps between them and classifies their content.
+
+use std::ops::Range;
+
+use crate::histogram::ByteHistogram;
+
+/// Bytes read per sample when classifying a large gap.
+const GAP_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Evenly spaced samples read from a gap larger than `GAP_SAMPLES * GAP_SAMPLE_BYTES`.
+const GAP_SAMPLES: usize = 16;
+
+/// Content of a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapKind {
+    /// Only zero bytes.
+    Zeros,
+    /// Entropy above 7 bits/byte (compressed, encrypted or random).
+    HighEntropy,
+    /// Mostly printable ASCII.
+    Text,
+    /// Anything else.
+    Binary,
+}
+
+impl GapKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GapKind::Zeros => "Zeros",
+            GapKind::HighEntropy => "High entropy",
+            GapKind::Text => "Text",
+            GapKind::Binary => "Binary",
+        }
+    }
+}
+
+/// A region not covered by any carved file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    pub offset: u64,
+    pub size: u64,
+    pub kind: GapKind,
+}
+
+/// Ranges of `0..file_len` outside every `(offset, size)` region, at least
+/// `min_size` bytes long. Regions may overlap or nest.
+pub fn uncovered_ranges(regions: &[(u64, u64)], file_len: u64, min_size: u64) -> Vec<Range<u64>> {
+    let mut sorted: Vec<(u64, u64)> = regions.iter()
+        .filter(|&&(_, size)| size > 0)
+        .map(|&(offset, size)| (offset, offset.saturating_add(size)))
+        .collect();
+    sorted.sort_unstable();
+
+    let mut gaps = Vec::new();
+    let mut covered_to = 0u64;
+    for (start, end) in sorted {
+        if start > covered_to && start - covered_to >= min_size {
+            gaps.push(covered_to..start.min(file_len));
+        }
+        covered_to = covered_to.max(end);
+        if covered_to >= file_len {
+            break;
+        }
+    }
+    if file_len > covered_to && file_len - covered_to >= min_size {
+        gaps.push(covered_to..file_len);
+    }
+    gaps.retain(|gap| !gap.is_empty());
+    gaps
+}
+
+/// Classify the content of a gap. Gaps larger than the sample budget are
+/// classified from evenly spaced samples.
+pub fn classify_gap(data: &[u8]) -> GapKind {
+    let hist = if data.len() <= GAP_SAMPLES * GAP_SAMPLE_BYTES {
+        ByteHistogram::from_data(data)
+    } else {
+        let step = (data.len() - GAP_SAMPLE_BYTES) / (GAP_SAMPLES - 1);
+        let mut hist = ByteHistogram::new();
+        for i in 0..GAP_SAMPLES {
+            let start = i * step;
+            hist.merge(&ByteHistogram::from_data(&data[start..start + GAP_SAMPLE_BYTES]));
+        }
+        hist
+    };
+
+    if hist.counts[0] == hist.total {
+        GapKind::Zeros
+    } else if hist.entropy() > 7.0 {
+        GapKind::HighEntropy
+    } else if hist.looks_ascii() {
+        GapKind::Text
+    } else {
+        GapKind::Binary
+    }
+}
+
+/// Gaps of at least `min_size` bytes between the carved `(offset, size)`
+/// regions of `data`, classified.
+pub fn find_gaps(data: &[u8], regions: &[(u64, u64)], min_size: u64) -> Vec<Gap> {
+    uncovered_ranges(regions, data.len() as u64, min_size)
+        .into_iter()
+        .map(|range| Gap {
+            offset: range.start,
+            size: range.end - range.start,
+            kind: classify_gap(&data[range.start as usize..range.end as usize]),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uncovered_ranges_merge_overlaps() {
+        // Nested and overlapping regions, a tiny gap and a trailing gap
+        let regions = [(100, 50), (110, 10), (140, 30), (172, 20), (300, 0)];
+        assert_eq!(uncovered_ranges(&regions, 400, 1), vec![0..100, 170..172, 192..400]);
+        assert_eq!(uncovered_ranges(&regions, 400, 16), vec![0..100, 192..400]);
+        assert_eq!(uncovered_ranges(&[], 64, 1), vec![0..64]);
+        // Regions running past the end of the file
+        assert_eq!(uncovered_ranges(&[(10, 100)], 50, 1), vec![0..10]);
+        assert!(uncovered_ranges(&[(0, 50)], 50, 1).is_empty());
+    }
+
+    #[test]
+    fn classify_gap_contents() {
+        assert_eq!(classify_gap(&[0u8; 4096]), GapKind::Zeros);
+        assert_eq!(classify_gap(b"deleted file remnant: meeting notes, 10am\n"), GapKind::Text);
+        let random: Vec<u8> = (0..65536u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        assert_eq!(classify_gap(&random), GapKind::HighEntropy);
+        assert_eq!(classify_gap(&[0, 0, 1, 0, 2, 0, 0, 3]), GapKind::Binary);
+
+        // Large gaps are sampled: zeros with a single stray byte between samples
+        let mut large = vec![0u8; GAP_SAMPLES * GAP_SAMPLE_BYTES * 2];
+        large[GAP_SAMPLE_BYTES + 1] = 0xFF;
+        assert_eq!(classify_gap(&large), GapKind::Zeros);
+    }
+
+    #[test]
+    fn find_gaps_between_regions() {
+        let mut data = vec![0u8; 1024];
+        data[256..600].fill(b'A');
+        let gaps = find_gaps(&data, &[(0, 256), (600, 300)], 64);
+        assert_eq!(gaps, vec![
+            Gap { offset: 256, size: 344, kind: GapKind::Text },
+            Gap { offset: 900, size: 124, kind: GapKind::Zeros },
+        ]);
+    }
+}
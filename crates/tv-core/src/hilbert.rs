@@ -0,0 +1,105 @@
+//! Hilbert curve mapping.
+//!
+//! The Hilbert view lays the file out along a Hilbert curve filling an
+//! `n` x `n` grid (`n` a power of two), so bytes that are close in the file
+//! stay close on screen. Cell `d` along the curve holds the `d`-th slice of
+//! the file. These functions match `xy2d` / `d2xy` in `shaders/hilbert.wgsl`.
+
+/// Index along the curve of cell (`x`, `y`) in an `n` x `n` grid.
+pub fn xy2d(n: u32, x: u32, y: u32) -> u64 {
+    let mut d: u64 = 0;
+    let mut s: u32 = n / 2;
+    let mut px = x;
+    let mut py = y;
+
+    while s > 0 {
+        let rx = u32::from(px & s > 0);
+        let ry = u32::from(py & s > 0);
+        d += (s as u64) * (s as u64) * (((3 * rx) ^ ry) as u64);
+
+        // Rotate quadrant
+        if ry == 0 {
+            if rx == 1 {
+                // Only the bits below `s` matter from here on
+                px = s - 1 - (px & (s - 1));
+                py = s - 1 - (py & (s - 1));
+            }
+            std::mem::swap(&mut px, &mut py);
+        }
+
+        s /= 2;
+    }
+
+    d
+}
+
+/// Cell (x, y) at index `d` along the curve in an `n` x `n` grid; the
+/// inverse of [`xy2d`].
+pub fn d2xy(n: u32, d: u64) -> (u32, u32) {
+    let (mut x, mut y, mut t, mut s) = (0u32, 0u32, d, 1u32);
+    while s < n {
+        let rx = (1 & (t / 2)) as u32;
+        let ry = (1 & (t ^ rx as u64)) as u32;
+
+        // Rotate quadrant
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - x;
+                y = s - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn d2xy_inverts_xy2d() {
+        for n in [4, 8, 16] {
+            for y in 0..n {
+                for x in 0..n {
+                    assert_eq!(d2xy(n, xy2d(n, x, y)), (x, y), "n={} x={} y={}", n, x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn indices_cover_the_grid_once() {
+        for n in [2, 4, 8, 16, 64] {
+            let cells = n as u64 * n as u64;
+            let mut seen = vec![false; cells as usize];
+            for y in 0..n {
+                for x in 0..n {
+                    let d = xy2d(n, x, y);
+                    assert!(d < cells, "n={} d={}", n, d);
+                    assert!(!std::mem::replace(&mut seen[d as usize], true), "n={} d={} twice", n, d);
+                }
+            }
+            for d in 0..cells {
+                assert_eq!(xy2d(n, d2xy(n, d).0, d2xy(n, d).1), d);
+            }
+        }
+    }
+
+    #[test]
+    fn curve_moves_one_cell_at_a_time() {
+        let n = 16;
+        assert_eq!(d2xy(n, 0), (0, 0));
+        assert_eq!(d2xy(n, 255), (15, 0));
+        for d in 1..256 {
+            let (ax, ay) = d2xy(n, d - 1);
+            let (bx, by) = d2xy(n, d);
+            assert_eq!(ax.abs_diff(bx) + ay.abs_diff(by), 1, "d={}", d);
+        }
+    }
+}
@@ -3,6 +3,11 @@
 //! Computes the distribution of byte values (0-255) in a data block,
 //! useful for identifying encrypted/compressed data vs. structured data.
 
+use crate::FileRegion;
+
+/// Size of each block read by a sampled histogram.
+pub const SAMPLE_BLOCK_BYTES: u64 = 64 * 1024;
+
 /// Histogram of byte values (0-255).
 #[derive(Debug, Clone)]
 pub struct ByteHistogram {
@@ -152,6 +157,121 @@ impl ByteHistogram {
 
         printable as f64 / self.total as f64 > 0.85
     }
+
+    /// Byte values occurring more than `factor` times as often as in a
+    /// uniform distribution, most frequent first.
+    pub fn spikes(&self, factor: f64) -> Vec<u8> {
+        let threshold = self.total as f64 / 256.0 * factor;
+        let mut spikes: Vec<u8> = (0..=255u8)
+            .filter(|&b| self.counts[b as usize] > 0 && self.counts[b as usize] as f64 > threshold)
+            .collect();
+        spikes.sort_by_key(|&b| std::cmp::Reverse(self.counts[b as usize]));
+        spikes
+    }
+}
+
+/// Byte values must occur this many times more (or less) often than a
+/// typical value to be flagged by [`anomalies`].
+const ANOMALY_FACTOR: f64 = 8.0;
+
+/// Minimum count (or expected count, for under-represented values) for a
+/// byte value to be flagged; smaller samples are noise.
+const ANOMALY_MIN_COUNT: u64 = 16;
+
+/// Under-represented values are only flagged in broad distributions (where
+/// most byte values occur), not in e.g. text where most values are absent.
+const ANOMALY_MIN_UNIQUE: u32 = 192;
+
+/// Direction of a byte frequency anomaly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Occurs far more often than a typical byte value (padding, XOR key).
+    Over,
+    /// Occurs far less often than a typical byte value, or not at all.
+    Under,
+}
+
+/// A byte value whose frequency stands out from the rest of the distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteAnomaly {
+    pub byte: u8,
+    pub count: u64,
+    /// Count of a typical byte value (median of the values that occur).
+    pub expected: f64,
+    pub kind: AnomalyKind,
+}
+
+impl ByteAnomaly {
+    /// How many times more often (or less often, for `Under`) the value
+    /// occurs than expected.
+    pub fn ratio(&self) -> f64 {
+        match self.kind {
+            AnomalyKind::Over => self.count as f64 / self.expected,
+            AnomalyKind::Under => self.expected / self.count.max(1) as f64,
+        }
+    }
+}
+
+/// Flag byte values occurring far more or less than expected for the
+/// distribution: over-represented values first (most frequent first),
+/// then under-represented ones in byte order.
+///
+/// "Expected" is the median count of the values that occur, so a few
+/// outliers do not skew it. A single dominant value in otherwise random
+/// data is typical of padding, or of a single-byte XOR key leaking
+/// through zero bytes.
+pub fn anomalies(hist: &ByteHistogram) -> Vec<ByteAnomaly> {
+    let mut present: Vec<u64> = hist.counts.iter().copied().filter(|&c| c > 0).collect();
+    if present.is_empty() {
+        return Vec::new();
+    }
+    present.sort_unstable();
+    let expected = present[present.len() / 2] as f64;
+    let broad = present.len() as u32 >= ANOMALY_MIN_UNIQUE && expected >= ANOMALY_MIN_COUNT as f64;
+
+    let anomaly = |byte: u8, kind| ByteAnomaly { byte, count: hist.counts[byte as usize], expected, kind };
+    let mut over: Vec<ByteAnomaly> = (0..=255u8)
+        .filter(|&b| {
+            let count = hist.counts[b as usize];
+            count >= ANOMALY_MIN_COUNT && count as f64 >= expected * ANOMALY_FACTOR
+        })
+        .map(|b| anomaly(b, AnomalyKind::Over))
+        .collect();
+    over.sort_by_key(|a| std::cmp::Reverse(a.count));
+
+    let under = (0..=255u8)
+        .filter(|&b| broad && hist.counts[b as usize] as f64 * ANOMALY_FACTOR <= expected)
+        .map(|b| anomaly(b, AnomalyKind::Under));
+
+    over.into_iter().chain(under).collect()
+}
+
+/// Regions to read for a histogram of `region` limited to `max_bytes`.
+///
+/// The whole region if it fits. Otherwise `max_bytes` worth of
+/// `SAMPLE_BLOCK_BYTES` blocks spread evenly from its start to its end, so
+/// the distribution represents all of it rather than a prefix.
+pub fn sample_regions(region: FileRegion, max_bytes: u64) -> Vec<FileRegion> {
+    if region.length <= max_bytes {
+        return vec![region];
+    }
+    let block = SAMPLE_BLOCK_BYTES.min(max_bytes).max(1);
+    let blocks = max_bytes / block;
+    if blocks <= 1 {
+        return vec![FileRegion::new(region.offset, block)];
+    }
+    // First block at the start, last one ending at the end of the region
+    let span = region.length - block;
+    (0..blocks)
+        .map(|i| FileRegion::new(region.offset + (span as u128 * i as u128 / (blocks - 1) as u128) as u64, block))
+        .collect()
+}
+
+/// Offsets of the first and last occurrence of `byte` in `data`.
+pub fn occurrence_range(data: &[u8], byte: u8) -> Option<(u64, u64)> {
+    let first = memchr::memchr(byte, data)?;
+    let last = memchr::memrchr(byte, data)?;
+    Some((first as u64, last as u64))
 }
 
 /// Statistics derived from a histogram.
@@ -177,6 +297,23 @@ pub struct HistogramStats {
 mod tests {
     use super::*;
 
+    #[test]
+    fn sampled_regions_cover_the_whole_range() {
+        let region = FileRegion::new(1000, 100 * SAMPLE_BLOCK_BYTES);
+        assert_eq!(sample_regions(region, u64::MAX), vec![region]);
+        assert_eq!(sample_regions(region, region.length), vec![region]);
+
+        let samples = sample_regions(region, 10 * SAMPLE_BLOCK_BYTES);
+        assert_eq!(samples.len(), 10);
+        assert_eq!(samples[0].offset, 1000);
+        assert_eq!(samples[9].end(), region.end());
+        assert!(samples.iter().all(|s| s.length == SAMPLE_BLOCK_BYTES && region.contains(s)));
+        assert!(samples.windows(2).all(|w| w[0].end() <= w[1].offset));
+
+        // Budget smaller than one block: a single block at the start
+        assert_eq!(sample_regions(region, 100), vec![FileRegion::new(1000, 100)]);
+    }
+
     #[test]
     fn test_empty_histogram() {
         let hist = ByteHistogram::new();
@@ -243,4 +380,43 @@ mod tests {
         assert_eq!(hist.counts[0x00], 50);
         assert_eq!(hist.counts[0x01], 50);
     }
+
+    #[test]
+    fn test_anomalies_skewed_distribution() {
+        // Uniform background without any 0x00, plus a dominant 0x5A
+        let mut data: Vec<u8> = (1..=255u8).cycle().take(255 * 64).collect();
+        data.extend(std::iter::repeat_n(0x5A, 4000));
+        let hist = ByteHistogram::from_data(&data);
+
+        let found = anomalies(&hist);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].byte, found[0].kind), (0x5A, AnomalyKind::Over));
+        assert_eq!(found[0].expected, 64.0);
+        assert!(found[0].ratio() > 60.0);
+        assert_eq!((found[1].byte, found[1].kind, found[1].count), (0x00, AnomalyKind::Under, 0));
+
+        // A uniform distribution and text (mostly absent values) have none
+        let uniform: Vec<u8> = (0..=255u8).cycle().take(256 * 64).collect();
+        assert!(anomalies(&ByteHistogram::from_data(&uniform)).is_empty());
+        assert!(anomalies(&ByteHistogram::from_data(b"short text")).is_empty());
+        assert!(anomalies(&ByteHistogram::new()).is_empty());
+    }
+
+    #[test]
+    fn test_occurrence_range() {
+        assert_eq!(occurrence_range(b"xaxxax", b'a'), Some((1, 4)));
+        assert_eq!(occurrence_range(b"xaxxax", b'b'), None);
+    }
+
+    #[test]
+    fn test_spikes() {
+        let mut data: Vec<u8> = (0..=255u8).cycle().take(256 * 4).collect();
+        data.extend(std::iter::repeat_n(0x00, 200));
+        data.extend(std::iter::repeat_n(0xFF, 100));
+        let hist = ByteHistogram::from_data(&data);
+
+        assert_eq!(hist.spikes(4.0), vec![0x00, 0xFF]);
+        assert_eq!(hist.spikes(100.0), Vec::<u8>::new());
+        assert!(ByteHistogram::new().spikes(4.0).is_empty());
+    }
 }
@@ -0,0 +1,157 @@
+//! Known-hash lists for triage.
+//!
+//! Lists are newline-delimited MD5, SHA-1 or SHA-256 hashes (as written by
+//! `sha256sum` or exported from NSRL-style sets); the first field of each
+//! line is used and lines that are not a hash are skipped. A file is flagged
+//! known bad if any of its digests is on a blocklist, known good if one is on
+//! an allowlist.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::digest::FileDigests;
+
+/// Hex lengths of the accepted digests (MD5, SHA-1, SHA-256).
+const HASH_HEX_LENGTHS: [usize; 3] = [32, 40, 64];
+
+/// Result of checking a hash against the loaded lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashVerdict {
+    KnownGood,
+    KnownBad,
+    Unknown,
+}
+
+impl HashVerdict {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HashVerdict::KnownGood => "Known good",
+            HashVerdict::KnownBad => "Known bad",
+            HashVerdict::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Allowlist and blocklist of MD5, SHA-1 and SHA-256 hashes (lowercase hex).
+#[derive(Debug, Clone, Default)]
+pub struct HashDatabase {
+    pub known_good: HashSet<String>,
+    pub known_bad: HashSet<String>,
+}
+
+impl HashDatabase {
+    /// True if no list is loaded.
+    pub fn is_empty(&self) -> bool {
+        self.known_good.is_empty() && self.known_bad.is_empty()
+    }
+
+    /// Look up a hash (any case). Known bad wins over known good.
+    pub fn check(&self, hash: &str) -> HashVerdict {
+        let hash = hash.to_ascii_lowercase();
+        if self.known_bad.contains(&hash) {
+            HashVerdict::KnownBad
+        } else if self.known_good.contains(&hash) {
+            HashVerdict::KnownGood
+        } else {
+            HashVerdict::Unknown
+        }
+    }
+
+    /// Look up all digests of a file. Known bad on any digest wins, then
+    /// known good on any digest.
+    pub fn check_digests(&self, digests: &FileDigests) -> HashVerdict {
+        let verdicts = digests.named().map(|(_, hash)| self.check(hash));
+        if verdicts.contains(&HashVerdict::KnownBad) {
+            HashVerdict::KnownBad
+        } else if verdicts.contains(&HashVerdict::KnownGood) {
+            HashVerdict::KnownGood
+        } else {
+            HashVerdict::Unknown
+        }
+    }
+}
+
+/// Parse a hash list. Returns the hashes and the number of non-empty,
+/// non-comment lines skipped because they hold no MD5/SHA-1/SHA-256 hash.
+pub fn parse_hash_list(text: &str) -> (HashSet<String>, usize) {
+    let mut hashes = HashSet::new();
+    let mut skipped = 0;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .next()
+            .unwrap_or("")
+            .trim_matches('"');
+        if HASH_HEX_LENGTHS.contains(&field.len()) && field.bytes().all(|b| b.is_ascii_hexdigit()) {
+            hashes.insert(field.to_ascii_lowercase());
+        } else {
+            skipped += 1;
+        }
+    }
+    (hashes, skipped)
+}
+
+/// Load a hash list from a file (see `parse_hash_list`).
+pub fn load_hash_list(path: &Path) -> Result<(HashSet<String>, usize)> {
+    let bytes = std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(parse_hash_list(&String::from_utf8_lossy(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const EMPTY_SHA1: &str = "da39a3ee5e6b4b0d3255bfef95601890afd80709";
+
+    #[test]
+    fn test_parse_hash_list_formats() {
+        let text = format!(
+            "# allowlist\n\n{}  empty.bin\n\"{}\",\"abc.txt\"\n{}\nda39a3ee5e6b4b0d3255bfef9560189\nnot a hash\n",
+            EMPTY_SHA256.to_uppercase(),
+            ABC_SHA256,
+            EMPTY_SHA1
+        );
+        let (hashes, skipped) = parse_hash_list(&text);
+        assert_eq!(
+            hashes,
+            HashSet::from([EMPTY_SHA256.to_string(), ABC_SHA256.to_string(), EMPTY_SHA1.to_string()])
+        );
+        assert_eq!(skipped, 2, "truncated hash and text line");
+    }
+
+    #[test]
+    fn test_hash_database_check() {
+        let mut db = HashDatabase::default();
+        assert!(db.is_empty());
+        assert_eq!(db.check(EMPTY_SHA256), HashVerdict::Unknown);
+
+        db.known_good.insert(EMPTY_SHA256.to_string());
+        db.known_good.insert(ABC_SHA256.to_string());
+        db.known_bad.insert(ABC_SHA256.to_string());
+        assert_eq!(db.check(&EMPTY_SHA256.to_uppercase()), HashVerdict::KnownGood);
+        assert_eq!(db.check(ABC_SHA256), HashVerdict::KnownBad);
+    }
+
+    #[test]
+    fn test_hash_database_check_digests() {
+        let digests = FileDigests {
+            md5: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+            sha1: EMPTY_SHA1.to_string(),
+            sha256: EMPTY_SHA256.to_string(),
+        };
+        let mut db = HashDatabase::default();
+        assert_eq!(db.check_digests(&digests), HashVerdict::Unknown);
+
+        db.known_good.insert(EMPTY_SHA1.to_string());
+        assert_eq!(db.check_digests(&digests), HashVerdict::KnownGood);
+        db.known_bad.insert(digests.md5.clone());
+        assert_eq!(db.check_digests(&digests), HashVerdict::KnownBad);
+    }
+}
@@ -6,25 +6,66 @@ pub mod classify;
 pub mod signatures;
 pub mod disasm;
 pub mod cfg;
+pub mod callgraph;
 pub mod templates;
+pub mod checksum;
 pub mod builtin_templates;
 pub mod histogram;
 pub mod xrefs;
+pub mod strings;
 pub mod project;
+pub mod carve;
+pub mod known_hashes;
+pub mod file_metadata;
+pub mod analysis;
+pub mod xor;
+pub mod digest;
+pub mod text;
+pub mod encoding;
+pub mod sections;
+pub mod gaps;
+pub mod png;
+pub mod period;
+pub mod paste;
+pub mod hilbert;
+pub mod structure;
 
 pub use types::*;
-pub use mapped_file::MappedFile;
+pub use mapped_file::{FileSource, MappedFile};
 pub use pattern::{scan_pattern_cpu, scan_pattern_parallel};
-pub use disasm::{Architecture, Instruction, DisassemblyResult, disassemble, detect_architecture};
+pub use disasm::{
+    Architecture, Instruction, DisassemblyResult, disassemble, detect_architecture,
+    ThumbRanges, ModeSwitch, disassemble_mixed, disassemble_recursive, mode_switch_hint, symbolize_operands,
+};
 pub use signatures::{CarveInfo, analyze_carve_size, get_extension};
-pub use cfg::{ControlFlowGraph, BasicBlock, CfgInstruction, CfgEdge, EdgeType};
+pub use cfg::{
+    ControlFlowGraph, BasicBlock, CfgInstruction, CfgEdge, EdgeType,
+    LayeredLayout, LayoutNode, layered_layout,
+};
+pub use callgraph::{CallGraph, CallEdge, FunctionNode, CALL_NODE_WIDTH, CALL_NODE_HEIGHT};
 pub use templates::{
-    StructTemplate, TemplateField, FieldType, FieldValue, TemplateResult, PrimitiveType,
+    StructTemplate, TemplateField, FieldType, FieldValue, FieldStatus, FieldRange, TemplateResult, PrimitiveType,
     apply_template, load_template_from_file, load_template_from_json,
     save_template_to_file, save_template_to_json, example_template_json,
     TemplateCollection, load_template_collection,
 };
-pub use builtin_templates::{builtin_templates, get_template};
-pub use histogram::{ByteHistogram, HistogramStats};
+pub use builtin_templates::{builtin_templates, get_template, template_for_signature, SIGNATURE_TEMPLATES};
+pub use checksum::{ChecksumAlgo, crc32, adler32, compute_block_checksums};
+pub use histogram::{AnomalyKind, ByteAnomaly, ByteHistogram, HistogramStats};
 pub use xrefs::{XRefTable, XRef, XRefType};
+pub use strings::{printable_string_at, string_label_name, label_referenced_strings, find_strings, FoundString};
 pub use project::{Project, Bookmark, Label, LabelType, Comment, ProjectError};
+pub use carve::{CarveEntry, carve_and_hash, manifest_csv, sha256_hex};
+pub use known_hashes::{HashDatabase, HashVerdict, load_hash_list, parse_hash_list};
+pub use file_metadata::FileMetadata;
+pub use analysis::{Analysis, DEFAULT_BLOCK_SIZE};
+pub use xor::{brute_single_byte, xor_decode};
+pub use digest::{digest_data, FileDigests, DIGEST_CHUNK};
+pub use text::{decode_text, decode_text_offsets, TextEncoding};
+pub use sections::{parse_sections, ExecutableFormat, Section, SectionFlags, SectionLayout};
+pub use gaps::{find_gaps, Gap, GapKind};
+pub use png::encode_png;
+pub use period::autocorrelate;
+pub use paste::{decode_pasted, PasteEncoding};
+pub use hilbert::{d2xy, xy2d};
+pub use structure::{parse_structure, ContainerFormat, FileStructure, StructureEntry};
@@ -1,19 +1,51 @@
+use std::collections::HashMap;
 use std::fs::File;
-use std::path::Path;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
 
-use anyhow::{Context, Result};
-use memmap2::Mmap;
+use anyhow::{bail, Context, Result};
+use memmap2::{Mmap, MmapOptions};
 
 use crate::types::FileRegion;
 
+/// Largest device read into memory when it cannot be memory-mapped.
+pub const DEVICE_READ_LIMIT: u64 = 1024 * 1024 * 1024;
+
+/// Bytes behind a [`MappedFile`].
+enum Backing {
+    /// Memory-mapped file or device.
+    Mapped(Mmap),
+    /// Device contents read into memory (device without mmap support),
+    /// shared by every open of the device.
+    Buffered(Arc<Vec<u8>>),
+    /// Bytes that were never on disk (see [`MappedFile::from_memory`]).
+    Memory(Arc<[u8]>),
+}
+
+impl std::ops::Deref for Backing {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Backing::Mapped(mmap) => mmap,
+            Backing::Buffered(data) => data,
+            Backing::Memory(data) => data,
+        }
+    }
+}
+
 /// A memory-mapped file providing zero-copy byte slices.
+///
+/// Block devices (e.g. `/dev/sdb`) are supported: their size is queried from
+/// the device, and they are read into memory when they cannot be mapped.
 pub struct MappedFile {
-    mmap: Mmap,
+    data: Backing,
     len: u64,
 }
 
 impl MappedFile {
-    /// Open and memory-map a file.
+    /// Open and memory-map a file or block device.
     pub fn open(path: &Path) -> Result<Self> {
         let file = File::open(path)
             .with_context(|| format!("failed to open {}", path.display()))?;
@@ -21,6 +53,10 @@ impl MappedFile {
         let metadata = file.metadata()
             .with_context(|| format!("failed to read metadata for {}", path.display()))?;
 
+        if !metadata.is_file() {
+            return Self::open_device(file, path);
+        }
+
         let len = metadata.len();
 
         // SAFETY: We keep the file handle alive via the Mmap.
@@ -29,7 +65,36 @@ impl MappedFile {
         let mmap = unsafe { Mmap::map(&file) }
             .with_context(|| format!("failed to mmap {}", path.display()))?;
 
-        Ok(Self { mmap, len })
+        Ok(Self { data: Backing::Mapped(mmap), len })
+    }
+
+    /// Open a device, which has no regular-file size: map it with the size
+    /// reported by the device, or read it into memory if mapping fails.
+    fn open_device(mut file: File, path: &Path) -> Result<Self> {
+        let len = device_size(&mut file)
+            .with_context(|| format!("failed to query the size of {}", path.display()))?;
+        let map_len = usize::try_from(len)
+            .with_context(|| format!("{} is too large to open on this platform", path.display()))?;
+
+        // SAFETY: The device must not be written while mapped (open it read-only
+        // or unmounted for forensic work).
+        match unsafe { MmapOptions::new().len(map_len).map(&file) } {
+            Ok(mmap) => Ok(Self { data: Backing::Mapped(mmap), len }),
+            Err(e) => {
+                let data = shared_device_buffer(path, || {
+                    log::warn!("cannot mmap {} ({}), reading it into memory", path.display(), e);
+                    read_device(&mut file, len)
+                        .with_context(|| format!("failed to read {}", path.display()))
+                })?;
+                Ok(Self { len: data.len() as u64, data: Backing::Buffered(data) })
+            }
+        }
+    }
+
+    /// Bytes held in memory (e.g. decoded from the clipboard), viewed like a
+    /// file. Clones of `data` can back other opens on other threads.
+    pub fn from_memory(data: Arc<[u8]>) -> Self {
+        Self { len: data.len() as u64, data: Backing::Memory(data) }
     }
 
     /// Total file size in bytes.
@@ -43,24 +108,118 @@ impl MappedFile {
     }
 
     /// Get a zero-copy byte slice for the given region.
-    /// Returns an empty slice if the region is out of bounds.
+    ///
+    /// The region is clamped to the file: a region running past EOF yields
+    /// the bytes up to EOF, and one starting at or past EOF (or of zero
+    /// length) yields an empty slice. This never panics, so callers may pass
+    /// lengths without checking the remaining bytes; check the returned
+    /// length if fewer bytes matter.
     pub fn slice(&self, region: FileRegion) -> &[u8] {
-        let start = region.offset as usize;
-        let end = region.end().min(self.len) as usize;
-
-        if start >= self.mmap.len() || start >= end {
-            return &[];
-        }
+        // Clamp in u64 before narrowing, so huge offsets can't wrap on 32-bit targets
+        let file_end = self.len.min(self.data.len() as u64);
+        let end = region.end().min(file_end);
+        let start = region.offset.min(end);
 
-        &self.mmap[start..end]
+        &self.data[start as usize..end as usize]
     }
 
-    /// Get a byte slice starting at `offset` with at most `len` bytes.
+    /// Get a byte slice starting at `offset` with at most `len` bytes
+    /// (clamped to the file like [`Self::slice`]).
     pub fn slice_at(&self, offset: u64, len: u64) -> &[u8] {
         self.slice(FileRegion::new(offset, len))
     }
 }
 
+/// Where the bytes of an open file come from. Background jobs reopen the
+/// file from its source on their own thread, as [`MappedFile`] is not `Send`.
+#[derive(Clone)]
+pub enum FileSource {
+    /// File or block device on disk.
+    Path(PathBuf),
+    /// Bytes held in memory, shared with the [`MappedFile`]s opened from them.
+    Memory(Arc<[u8]>),
+}
+
+impl FileSource {
+    /// Open the file (or a new view of the in-memory bytes).
+    pub fn open(&self) -> Result<MappedFile> {
+        match self {
+            FileSource::Path(path) => MappedFile::open(path),
+            FileSource::Memory(data) => Ok(MappedFile::from_memory(Arc::clone(data))),
+        }
+    }
+}
+
+/// Buffers of the unmappable devices currently open, by path.
+fn device_buffers() -> &'static Mutex<HashMap<PathBuf, Weak<Vec<u8>>>> {
+    static BUFFERS: OnceLock<Mutex<HashMap<PathBuf, Weak<Vec<u8>>>>> = OnceLock::new();
+    BUFFERS.get_or_init(Mutex::default)
+}
+
+/// The buffer of device `path` if another open still holds it, else the one
+/// `read` returns. Background jobs reopen the file by path, so this keeps
+/// them from each reading the whole device again. The lock is held while
+/// reading, so concurrent opens wait for the first read instead of repeating it.
+fn shared_device_buffer(path: &Path, read: impl FnOnce() -> Result<Vec<u8>>) -> Result<Arc<Vec<u8>>> {
+    let mut buffers = device_buffers().lock().unwrap_or_else(PoisonError::into_inner);
+    buffers.retain(|_, data| data.strong_count() > 0);
+    if let Some(data) = buffers.get(path).and_then(Weak::upgrade) {
+        return Ok(data);
+    }
+    let data = Arc::new(read()?);
+    buffers.insert(path.to_path_buf(), Arc::downgrade(&data));
+    Ok(data)
+}
+
+/// Size of a device in bytes (`BLKGETSIZE64` for block devices on Linux,
+/// otherwise the offset of its end).
+#[cfg(target_os = "linux")]
+fn device_size(file: &mut File) -> io::Result<u64> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::io::AsRawFd;
+
+    // _IOR(0x12, 114, size_t)
+    const BLKGETSIZE64: u64 = (2 << 30) | ((std::mem::size_of::<usize>() as u64) << 16) | (0x12 << 8) | 114;
+
+    if file.metadata()?.file_type().is_block_device() {
+        let mut size: u64 = 0;
+        // SAFETY: BLKGETSIZE64 writes one u64 through the pointer, and the
+        // descriptor stays open for the duration of the call.
+        let ret = unsafe { libc::ioctl(file.as_raw_fd(), BLKGETSIZE64 as _, &mut size as *mut u64) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        return Ok(size);
+    }
+    seek_size(file)
+}
+
+/// Size of a device in bytes (the offset of its end).
+#[cfg(not(target_os = "linux"))]
+fn device_size(file: &mut File) -> io::Result<u64> {
+    seek_size(file)
+}
+
+/// Seek to the end to find the size, then rewind.
+fn seek_size(file: &mut impl Seek) -> io::Result<u64> {
+    let len = file.seek(SeekFrom::End(0))?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(len)
+}
+
+/// Read up to `len` bytes sequentially (devices that cannot be mapped).
+fn read_device(reader: &mut impl Read, len: u64) -> Result<Vec<u8>> {
+    if len > DEVICE_READ_LIMIT {
+        bail!(
+            "device cannot be memory-mapped and is larger than {} MB",
+            DEVICE_READ_LIMIT / (1024 * 1024)
+        );
+    }
+    let mut data = Vec::with_capacity(len as usize);
+    reader.take(len).read_to_end(&mut data)?;
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +275,40 @@ mod tests {
         assert!(slice.is_empty());
     }
 
+    #[test]
+    fn slice_at_and_around_eof() {
+        let data = b"0123456789";
+        let f = create_fixture(data);
+        let mf = MappedFile::open(f.path()).unwrap();
+
+        // Last byte, then exactly at EOF
+        assert_eq!(mf.slice(FileRegion::new(9, 1)), b"9");
+        assert!(mf.slice(FileRegion::new(10, 1)).is_empty());
+        // Past EOF, including lengths that would overflow the end offset
+        assert!(mf.slice(FileRegion::new(11, 5)).is_empty());
+        assert_eq!(mf.slice(FileRegion::new(8, u64::MAX)), b"89");
+        assert!(mf.slice(FileRegion::new(u64::MAX, u64::MAX)).is_empty());
+    }
+
+    #[test]
+    fn slice_zero_length_region() {
+        let f = create_fixture(b"data");
+        let mf = MappedFile::open(f.path()).unwrap();
+
+        assert!(mf.slice(FileRegion::new(0, 0)).is_empty());
+        assert!(mf.slice(FileRegion::new(2, 0)).is_empty());
+        assert!(mf.slice(FileRegion::new(4, 0)).is_empty());
+    }
+
+    #[test]
+    fn slice_of_empty_file() {
+        let f = create_fixture(b"");
+        let mf = MappedFile::open(f.path()).unwrap();
+
+        assert!(mf.is_empty());
+        assert!(mf.slice(FileRegion::new(0, 16)).is_empty());
+    }
+
     #[test]
     fn slice_at_helper() {
         let data = b"0123456789";
@@ -144,4 +337,56 @@ mod tests {
         // Check last 16 bytes
         assert_eq!(mf.slice_at(4080, 16), &data[4080..4096]);
     }
+
+    #[test]
+    fn device_helpers_on_regular_data() {
+        let f = create_fixture(b"0123456789");
+        let mut file = File::open(f.path()).unwrap();
+        assert_eq!(device_size(&mut file).unwrap(), 10);
+
+        // Streaming fallback reads at most the reported size
+        let mut reader = io::Cursor::new(b"0123456789".to_vec());
+        assert_eq!(read_device(&mut reader, 4).unwrap(), b"0123");
+        let mut reader = io::Cursor::new(b"0123".to_vec());
+        assert_eq!(read_device(&mut reader, 10).unwrap(), b"0123");
+        assert!(read_device(&mut reader, DEVICE_READ_LIMIT + 1).is_err());
+    }
+
+    #[test]
+    fn device_buffer_is_shared_while_open() {
+        let path = Path::new("/dev/test-shared-buffer");
+        let first = shared_device_buffer(path, || Ok(b"disk".to_vec())).unwrap();
+        let second = shared_device_buffer(path, || panic!("read twice")).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // Read again once every open is dropped
+        drop((first, second));
+        let reread = shared_device_buffer(path, || Ok(b"new".to_vec())).unwrap();
+        assert_eq!(reread.as_slice(), b"new");
+        assert!(shared_device_buffer(Path::new("/dev/test-unreadable"), || bail!("no")).is_err());
+    }
+
+    #[test]
+    fn memory_source_shares_its_bytes() {
+        let data: Arc<[u8]> = b"MZ\x90\x00".to_vec().into();
+        let source = FileSource::Memory(Arc::clone(&data));
+        let mf = source.open().unwrap();
+        assert_eq!(mf.len(), 4);
+        assert_eq!(mf.slice(FileRegion::new(1, 10)), b"Z\x90\x00");
+        assert_eq!(Arc::strong_count(&data), 3);
+
+        // The bytes are freed with the last view of them
+        drop((source, mf));
+        assert_eq!(Arc::strong_count(&data), 1);
+        assert!(MappedFile::from_memory(Vec::new().into()).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn open_character_device() {
+        // Not a regular file: opened through the device path (size 0)
+        let mf = MappedFile::open(Path::new("/dev/null")).unwrap();
+        assert!(mf.is_empty());
+        assert!(mf.slice(FileRegion::new(0, 16)).is_empty());
+    }
 }
@@ -0,0 +1,122 @@
+//! Decoding of pasted binary data.
+//!
+//! Snippets copied from reports, packet captures or logs arrive as text:
+//! either a hex dump (`4D 5A 90 00`, `0x4d,0x5a`, `\x4d\x5a`, `4d5a9000`)
+//! or Base64. Hex is tried first, since short hex strings are often valid
+//! Base64 too.
+
+/// Text encoding a pasted snippet was decoded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteEncoding {
+    Hex,
+    Base64,
+}
+
+impl PasteEncoding {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PasteEncoding::Hex => "hex",
+            PasteEncoding::Base64 => "Base64",
+        }
+    }
+}
+
+/// Decode a hex dump or Base64 string into bytes.
+pub fn decode_pasted(text: &str) -> Result<(Vec<u8>, PasteEncoding), String> {
+    if text.trim().is_empty() {
+        return Err("Clipboard is empty".to_string());
+    }
+    if let Some(bytes) = decode_hex(text) {
+        return Ok((bytes, PasteEncoding::Hex));
+    }
+    if let Some(bytes) = decode_base64(text) {
+        return Ok((bytes, PasteEncoding::Base64));
+    }
+    Err("Clipboard text is neither hex nor Base64".to_string())
+}
+
+/// Hex digits with whitespace, commas, colons and `0x` / `\x` prefixes
+/// ignored. Returns `None` for other characters or an odd digit count.
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    // `x` is not a hex digit, so dropping the prefixes cannot merge digits wrongly
+    let cleaned = text.replace("0x", "").replace("0X", "").replace("\\x", "");
+    let digits: Vec<u8> = cleaned.bytes()
+        .filter(|b| !b.is_ascii_whitespace() && !matches!(b, b',' | b':'))
+        .map(|b| (b as char).to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()?;
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    Some(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+/// Standard or URL-safe Base64, with or without padding; whitespace is
+/// ignored.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    let mut symbols = 0usize;
+    let mut padding = false;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => {
+                padding = true;
+                continue;
+            }
+            _ => return None,
+        };
+        // Nothing may follow the padding
+        if padding {
+            return None;
+        }
+        symbols += 1;
+        bits = (bits << 6 | value as u32) & 0xFFF;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+    // A lone trailing symbol holds less than a byte
+    if symbols == 0 || symbols % 4 == 1 {
+        return None;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_hex_in_common_notations() {
+        let mz = vec![0x4D, 0x5A, 0x90, 0x00];
+        for text in ["4D 5A 90 00", "4d5a9000", "0x4d, 0x5a, 0x90, 0x00", "\\x4d\\x5a\\x90\\x00", "4d:5a:90:00\n"] {
+            assert_eq!(decode_pasted(text), Ok((mz.clone(), PasteEncoding::Hex)), "{}", text);
+        }
+    }
+
+    #[test]
+    fn decodes_base64_when_not_hex() {
+        assert_eq!(decode_pasted("TVqQAA=="), Ok((vec![0x4D, 0x5A, 0x90, 0x00], PasteEncoding::Base64)));
+        assert_eq!(decode_pasted("TVqQAA"), Ok((vec![0x4D, 0x5A, 0x90, 0x00], PasteEncoding::Base64)));
+        assert_eq!(decode_pasted("SGVs\nbG8="), Ok((b"Hello".to_vec(), PasteEncoding::Base64)));
+        assert_eq!(decode_pasted("-_8="), Ok((vec![0xFB, 0xFF], PasteEncoding::Base64)));
+        // Even-length hex wins over Base64
+        assert_eq!(decode_pasted("cafe").map(|(_, enc)| enc), Ok(PasteEncoding::Hex));
+    }
+
+    #[test]
+    fn rejects_other_text() {
+        assert!(decode_pasted("   ").is_err());
+        assert!(decode_pasted("hello world!").is_err());
+        assert!(decode_pasted("TVqQA").is_err());
+        assert!(decode_pasted("TQ==TQ==").is_err());
+    }
+}
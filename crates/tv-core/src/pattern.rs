@@ -20,6 +20,8 @@ pub fn scan_pattern_cpu(data: &[u8], pattern: &[u8]) -> Vec<u64> {
 /// High-performance parallel pattern scan using SIMD (memchr) + multi-threading (rayon).
 /// Splits data across CPU cores, each using SIMD-accelerated search.
 /// Returns sorted offsets where `pattern` occurs in `data`.
+/// Patterns of any length are supported; chunks overlap by `pattern.len() - 1`
+/// bytes so matches spanning a chunk boundary are found.
 ///
 /// Performance: ~3-10 GB/s on modern CPUs (vs ~500 MB/s for naive or GPU with PCIe overhead).
 pub fn scan_pattern_parallel(data: &[u8], pattern: &[u8]) -> Vec<u64> {
@@ -184,6 +186,27 @@ mod tests {
         assert_eq!(hits, vec![boundary_pos as u64]);
     }
 
+    #[test]
+    fn parallel_scan_long_pattern_across_chunks() {
+        // A 300-byte pattern straddling every chunk boundary
+        let mut data = vec![0u8; 4 * 1024 * 1024];
+        let pattern: Vec<u8> = (0..300).map(|i| (i % 251 + 1) as u8).collect();
+        let chunk_size = (data.len() / rayon::current_num_threads().max(1)).max(1024 * 1024);
+        let mut expected = Vec::new();
+        for boundary in (chunk_size..data.len()).step_by(chunk_size) {
+            let pos = boundary - 150;
+            data[pos..pos + pattern.len()].copy_from_slice(&pattern);
+            expected.push(pos as u64);
+        }
+        let end = data.len() - pattern.len();
+        data[end..].copy_from_slice(&pattern);
+        expected.push(end as u64);
+
+        let hits = scan_pattern_parallel(&data, &pattern);
+        assert_eq!(hits, expected);
+        assert_eq!(hits, scan_pattern_cpu(&data, &pattern));
+    }
+
     #[test]
     fn parallel_matches_naive() {
         // Verify parallel results match naive implementation
@@ -0,0 +1,97 @@
+//! Repetition period detection.
+//!
+//! Record tables, tiled images and block-cipher output in ECB mode repeat
+//! with a fixed stride. Comparing the data with shifted copies of itself
+//! (byte-equality autocorrelation) reveals that stride.
+
+use crate::histogram::ByteHistogram;
+
+/// Longest period tried.
+pub const MAX_PERIOD: usize = 1024;
+
+/// Bytes examined; longer input is cut to its start.
+pub const PERIOD_SAMPLE_BYTES: usize = 16 * 1024;
+
+/// How far the match rate must exceed chance (the rate two random bytes of
+/// the same distribution are equal) to count as a repetition.
+const MIN_EXCESS: f64 = 0.2;
+
+/// Lags scoring within this of the best one are treated as equally good, so
+/// the fundamental period wins over its multiples.
+const MULTIPLE_TOLERANCE: f64 = 0.05;
+
+/// Fraction of positions where `data[i] == data[i + lag]`.
+fn match_rate(data: &[u8], lag: usize) -> f64 {
+    let pairs = data.len() - lag;
+    let matches = data.iter().zip(&data[lag..]).filter(|(a, b)| a == b).count();
+    matches as f64 / pairs as f64
+}
+
+/// Dominant repetition period of `data` in bytes, if it has one.
+///
+/// Tries every lag up to `MAX_PERIOD` that fits at least twice in the data
+/// and returns the smallest lag whose match rate is (close to) the best,
+/// provided that rate is well above chance. Constant data has no period.
+pub fn autocorrelate(data: &[u8]) -> Option<usize> {
+    let data = &data[..data.len().min(PERIOD_SAMPLE_BYTES)];
+    let max_lag = (data.len() / 2).min(MAX_PERIOD);
+    if max_lag < 1 {
+        return None;
+    }
+
+    let hist = ByteHistogram::from_data(data);
+    let total = hist.total as f64;
+    let chance: f64 = hist.counts.iter().map(|&c| (c as f64 / total).powi(2)).sum();
+
+    let rates: Vec<f64> = (1..=max_lag).map(|lag| match_rate(data, lag)).collect();
+    let best = rates.iter().copied().fold(0.0, f64::max);
+    if best - chance < MIN_EXCESS {
+        return None;
+    }
+    rates.iter()
+        .position(|&rate| rate >= best - MULTIPLE_TOLERANCE)
+        .map(|idx| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo-random bytes (xorshift32).
+    fn noise(len: usize, seed: u32) -> Vec<u8> {
+        let mut x = seed.max(1);
+        (0..len).map(|_| {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            (x >> 24) as u8
+        }).collect()
+    }
+
+    #[test]
+    fn finds_record_stride() {
+        // 24-byte records: magic, incrementing index, constant flags, random payload
+        let payload = noise(4096, 7);
+        let mut table = Vec::new();
+        for i in 0..200u32 {
+            table.extend_from_slice(b"REC\0");
+            table.extend_from_slice(&i.to_le_bytes());
+            table.extend_from_slice(&[1, 0, 0, 0, 0xFF, 0xFF, 0, 0]);
+            table.extend_from_slice(&payload[i as usize * 8..i as usize * 8 + 8]);
+        }
+        assert_eq!(autocorrelate(&table), Some(24));
+
+        // A random 37-byte tile repeated: the fundamental period, not a multiple
+        let tile = noise(37, 3);
+        let tiled: Vec<u8> = tile.iter().copied().cycle().take(37 * 50).collect();
+        assert_eq!(autocorrelate(&tiled), Some(37));
+    }
+
+    #[test]
+    fn aperiodic_data_has_no_period() {
+        assert_eq!(autocorrelate(&noise(8192, 1)), None);
+        assert_eq!(autocorrelate(&[0u8; 4096]), None);
+        assert_eq!(autocorrelate(&[0x42]), None);
+        assert_eq!(autocorrelate(&[]), None);
+    }
+}
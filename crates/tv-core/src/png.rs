@@ -0,0 +1,124 @@
+//! Minimal PNG encoder for exporting rendered views (Hilbert curve, minimap).
+//!
+//! Writes 8-bit RGBA images with stored (uncompressed) deflate blocks, which
+//! every PNG reader accepts without pulling in a compression library.
+
+use crate::checksum::{adler32, crc32};
+
+/// PNG file signature.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Largest payload of a stored deflate block.
+const STORED_BLOCK_MAX: usize = 65535;
+
+/// Encode `rgba` (4 bytes per pixel, rows top to bottom) as a PNG file.
+///
+/// Panics if `rgba` is not `width * height * 4` bytes long.
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 4;
+    assert_eq!(rgba.len(), stride * height as usize, "RGBA buffer does not match the image size");
+
+    // Every scanline starts with its filter type (0 = none)
+    let mut raw = Vec::with_capacity(rgba.len() + height as usize);
+    for row in rgba.chunks(stride.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, deflate, no filter method extensions, no interlace
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// A zlib stream holding `data` in stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(STORED_BLOCK_MAX).max(1);
+    let mut out = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    // Deflate, 32 KB window, no preset dictionary (header is a multiple of 31)
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut chunks = data.chunks(STORED_BLOCK_MAX).peekable();
+    if chunks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Append a PNG chunk: length, type, data and the CRC of type and data.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Chunks of a PNG file as (type, data), checking each CRC.
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        let mut chunks = Vec::new();
+        let mut pos = 8;
+        while pos < png.len() {
+            let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+            let body = &png[pos + 4..pos + 8 + len];
+            let crc = u32::from_be_bytes(png[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(body));
+            chunks.push((body[..4].try_into().unwrap(), body[4..].to_vec()));
+            pos += 12 + len;
+        }
+        chunks
+    }
+
+    #[test]
+    fn encodes_valid_rgba_png() {
+        // Large enough to need several stored blocks
+        let (width, height) = (200u32, 100u32);
+        let rgba: Vec<u8> = (0..width * height * 4).map(|i| (i % 251) as u8).collect();
+        let png = encode_png(width, height, &rgba);
+        assert_eq!(png[..8], PNG_SIGNATURE);
+
+        let chunks = chunks(&png);
+        let kinds: Vec<&[u8; 4]> = chunks.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 200, 0, 0, 0, 100, 8, 6, 0, 0, 0]);
+
+        let mut raw = Vec::new();
+        flate2::read::ZlibDecoder::new(&chunks[1].1[..]).read_to_end(&mut raw).unwrap();
+        assert_eq!(raw.len(), rgba.len() + height as usize);
+        for (row, line) in raw.chunks(width as usize * 4 + 1).enumerate() {
+            assert_eq!(line[0], 0);
+            assert_eq!(line[1..], rgba[row * width as usize * 4..(row + 1) * width as usize * 4]);
+        }
+    }
+
+    #[test]
+    fn encodes_empty_image() {
+        let png = encode_png(0, 0, &[]);
+        let chunks = chunks(&png);
+        let mut raw = Vec::new();
+        flate2::read::ZlibDecoder::new(&chunks[1].1[..]).read_to_end(&mut raw).unwrap();
+        assert!(raw.is_empty());
+    }
+}
@@ -0,0 +1,486 @@
+//! Section and segment layout of ELF, PE and Mach-O executables.
+//!
+//! A focused header parser: it reads the section table (or, for ELF files
+//! without one, the loadable segments) and reports where each part lives in
+//! the file. Headers are bounds-checked; a truncated table yields the
+//! entries read so far.
+
+/// Upper bound on parsed entries, against corrupt counts.
+const MAX_SECTIONS: usize = 4096;
+
+/// Longest section name read from a string table.
+const MAX_NAME_LEN: usize = 256;
+
+/// Executable container format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutableFormat {
+    Elf,
+    Pe,
+    MachO,
+}
+
+impl ExecutableFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExecutableFormat::Elf => "ELF",
+            ExecutableFormat::Pe => "PE",
+            ExecutableFormat::MachO => "Mach-O",
+        }
+    }
+}
+
+/// Memory permissions of a section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SectionFlags {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl SectionFlags {
+    /// `rwx`-style label, e.g. `r-x`.
+    pub fn label(&self) -> String {
+        [(self.read, 'r'), (self.write, 'w'), (self.execute, 'x')]
+            .iter()
+            .map(|&(set, c)| if set { c } else { '-' })
+            .collect()
+    }
+}
+
+/// A section (or segment) of an executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    /// File offset of the section data.
+    pub offset: u64,
+    /// Bytes stored in the file (0 for memory-only sections such as `.bss`).
+    pub size: u64,
+    /// Virtual address (RVA for PE).
+    pub address: u64,
+    pub flags: SectionFlags,
+}
+
+impl Section {
+    /// File offset just past the section data.
+    pub fn end(&self) -> u64 {
+        self.offset.saturating_add(self.size)
+    }
+}
+
+/// Sections of an executable, in header order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionLayout {
+    pub format: ExecutableFormat,
+    pub sections: Vec<Section>,
+}
+
+impl SectionLayout {
+    /// First section whose file data contains `offset`.
+    pub fn section_at(&self, offset: u64) -> Option<&Section> {
+        self.sections.iter().find(|s| s.size > 0 && (s.offset..s.end()).contains(&offset))
+    }
+}
+
+/// Parse the section layout of an ELF, PE or Mach-O file starting at the
+/// beginning of `data`. `None` for other data or when no section is found.
+pub fn parse_sections(data: &[u8]) -> Option<SectionLayout> {
+    let (format, sections) = if data.starts_with(b"\x7FELF") {
+        (ExecutableFormat::Elf, parse_elf(data)?)
+    } else if data.starts_with(b"MZ") {
+        (ExecutableFormat::Pe, parse_pe(data)?)
+    } else {
+        (ExecutableFormat::MachO, parse_macho(data)?)
+    };
+    (!sections.is_empty()).then_some(SectionLayout { format, sections })
+}
+
+/// Bounds-checked integer reads in the file's byte order.
+struct Reader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, offset: u64) -> Option<[u8; N]> {
+        let start = usize::try_from(offset).ok()?;
+        self.data.get(start..start.checked_add(N)?)?.try_into().ok()
+    }
+
+    fn u16(&self, offset: u64) -> Option<u16> {
+        let b = self.bytes(offset)?;
+        Some(if self.big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+    }
+
+    fn u32(&self, offset: u64) -> Option<u32> {
+        let b = self.bytes(offset)?;
+        Some(if self.big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+    }
+
+    fn u64(&self, offset: u64) -> Option<u64> {
+        let b = self.bytes(offset)?;
+        Some(if self.big_endian { u64::from_be_bytes(b) } else { u64::from_le_bytes(b) })
+    }
+
+    /// A 32- or 64-bit word.
+    fn word(&self, offset: u64, is_64: bool) -> Option<u64> {
+        if is_64 { self.u64(offset) } else { self.u32(offset).map(u64::from) }
+    }
+
+    /// NUL-terminated string at `offset`, at most `max` bytes.
+    fn c_str(&self, offset: u64, max: usize) -> String {
+        let start = usize::try_from(offset).unwrap_or(usize::MAX).min(self.data.len());
+        let bytes = &self.data[start..start.saturating_add(max).min(self.data.len())];
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).into_owned()
+    }
+}
+
+/// ELF section headers, or the `PT_LOAD` segments when there are none.
+fn parse_elf(data: &[u8]) -> Option<Vec<Section>> {
+    const SHT_NOBITS: u32 = 8;
+    const SHF_WRITE: u64 = 0x1;
+    const SHF_ALLOC: u64 = 0x2;
+    const SHF_EXECINSTR: u64 = 0x4;
+
+    let is_64 = *data.get(4)? == 2;
+    let r = Reader { data, big_endian: *data.get(5)? == 2 };
+
+    let (sh_off, sh_entsize, sh_num, sh_strndx) = if is_64 {
+        (r.u64(0x28)?, r.u16(0x3A)?, r.u16(0x3C)?, r.u16(0x3E)?)
+    } else {
+        (r.u32(0x20)? as u64, r.u16(0x2E)?, r.u16(0x30)?, r.u16(0x32)?)
+    };
+    // Headers past the end of the data are treated as missing
+    let header = |i: u16| sh_off.checked_add(i as u64 * sh_entsize as u64).filter(|&h| h < data.len() as u64);
+    // sh_offset of the section name string table
+    let names = header(sh_strndx).and_then(|h| r.word(h + if is_64 { 0x18 } else { 0x10 }, is_64));
+
+    let mut sections = Vec::new();
+    // Entry 0 is the reserved null section
+    for i in 1..sh_num.min(MAX_SECTIONS as u16) {
+        let Some(h) = header(i) else { break };
+        let (Some(name), Some(kind)) = (r.u32(h), r.u32(h + 4)) else { break };
+        let fields = if is_64 {
+            (r.u64(h + 0x08), r.u64(h + 0x10), r.u64(h + 0x18), r.u64(h + 0x20))
+        } else {
+            (r.word(h + 0x08, false), r.word(h + 0x0C, false), r.word(h + 0x10, false), r.word(h + 0x14, false))
+        };
+        let (Some(flags), Some(address), Some(offset), Some(size)) = fields else { break };
+        sections.push(Section {
+            name: names.map_or_else(String::new, |n| r.c_str(n.saturating_add(name as u64), MAX_NAME_LEN)),
+            offset,
+            size: if kind == SHT_NOBITS { 0 } else { size },
+            address,
+            flags: SectionFlags {
+                read: flags & SHF_ALLOC != 0,
+                write: flags & SHF_WRITE != 0,
+                execute: flags & SHF_EXECINSTR != 0,
+            },
+        });
+    }
+    if sections.is_empty() {
+        sections = parse_elf_segments(&r, is_64)?;
+    }
+    Some(sections)
+}
+
+/// Loadable segments from the ELF program headers.
+fn parse_elf_segments(r: &Reader, is_64: bool) -> Option<Vec<Section>> {
+    const PT_LOAD: u32 = 1;
+    const PF_X: u32 = 0x1;
+    const PF_W: u32 = 0x2;
+    const PF_R: u32 = 0x4;
+
+    let (ph_off, ph_entsize, ph_num) = if is_64 {
+        (r.u64(0x20)?, r.u16(0x36)?, r.u16(0x38)?)
+    } else {
+        (r.u32(0x1C)? as u64, r.u16(0x2A)?, r.u16(0x2C)?)
+    };
+
+    let mut segments = Vec::new();
+    for i in 0..ph_num.min(MAX_SECTIONS as u16) {
+        let Some(h) = ph_off.checked_add(i as u64 * ph_entsize as u64).filter(|&h| h < r.data.len() as u64) else {
+            break;
+        };
+        let fields = if is_64 {
+            (r.u32(h), r.u32(h + 4), r.u64(h + 0x08), r.u64(h + 0x10), r.u64(h + 0x20))
+        } else {
+            (r.u32(h), r.u32(h + 0x18), r.word(h + 4, false), r.word(h + 8, false), r.word(h + 0x10, false))
+        };
+        let (Some(kind), Some(flags), Some(offset), Some(address), Some(size)) = fields else { break };
+        if kind != PT_LOAD {
+            continue;
+        }
+        segments.push(Section {
+            name: format!("LOAD[{}]", i),
+            offset,
+            size,
+            address,
+            flags: SectionFlags { read: flags & PF_R != 0, write: flags & PF_W != 0, execute: flags & PF_X != 0 },
+        });
+    }
+    Some(segments)
+}
+
+/// PE section table.
+fn parse_pe(data: &[u8]) -> Option<Vec<Section>> {
+    const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+    const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+    const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
+
+    let r = Reader { data, big_endian: false };
+    let pe = r.u32(0x3C)? as u64;
+    if r.bytes::<4>(pe)? != *b"PE\0\0" {
+        return None;
+    }
+    let num_sections = r.u16(pe + 6)? as usize;
+    let table = pe + 24 + r.u16(pe + 20)? as u64;
+
+    let mut sections = Vec::new();
+    for i in 0..num_sections.min(MAX_SECTIONS) {
+        let h = table + i as u64 * 40;
+        let name = r.bytes::<8>(h);
+        let fields = (r.u32(h + 12), r.u32(h + 16), r.u32(h + 20), r.u32(h + 36));
+        let (Some(name), (Some(address), Some(size), Some(offset), Some(flags))) = (name, fields) else { break };
+        let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        sections.push(Section {
+            name: String::from_utf8_lossy(&name[..end]).into_owned(),
+            offset: offset as u64,
+            size: size as u64,
+            address: address as u64,
+            flags: SectionFlags {
+                read: flags & IMAGE_SCN_MEM_READ != 0,
+                write: flags & IMAGE_SCN_MEM_WRITE != 0,
+                execute: flags & IMAGE_SCN_MEM_EXECUTE != 0,
+            },
+        });
+    }
+    Some(sections)
+}
+
+/// Mach-O sections, listed as `segment,section`. Segments without sections
+/// (e.g. `__LINKEDIT`) are listed themselves.
+fn parse_macho(data: &[u8]) -> Option<Vec<Section>> {
+    const LC_SEGMENT: u32 = 0x1;
+    const LC_SEGMENT_64: u32 = 0x19;
+    const VM_PROT_READ: u32 = 0x1;
+    const VM_PROT_WRITE: u32 = 0x2;
+    const VM_PROT_EXECUTE: u32 = 0x4;
+    // S_ZEROFILL, S_GB_ZEROFILL, S_THREAD_LOCAL_ZEROFILL
+    const ZEROFILL_TYPES: [u32; 3] = [0x1, 0xC, 0x12];
+
+    let magic = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+    let (is_64, big_endian) = match magic {
+        0xFEED_FACE => (false, false),
+        0xFEED_FACF => (true, false),
+        0xCEFA_EDFE => (false, true),
+        0xCFFA_EDFE => (true, true),
+        _ => return None,
+    };
+    let r = Reader { data, big_endian };
+    let ncmds = r.u32(16)? as usize;
+
+    let mut sections = Vec::new();
+    let mut cmd = if is_64 { 32u64 } else { 28u64 };
+    for _ in 0..ncmds.min(MAX_SECTIONS) {
+        let (Some(kind), Some(cmd_size)) = (r.u32(cmd), r.u32(cmd + 4)) else { break };
+        if kind == LC_SEGMENT || kind == LC_SEGMENT_64 {
+            let seg_64 = kind == LC_SEGMENT_64;
+            let w = if seg_64 { 8 } else { 4 };
+            let segname = r.c_str(cmd + 8, 16);
+            // vmaddr, vmsize, fileoff, filesize, then maxprot, initprot, nsects
+            let fields = (
+                r.word(cmd + 24, seg_64),
+                r.word(cmd + 24 + 2 * w, seg_64),
+                r.word(cmd + 24 + 3 * w, seg_64),
+                r.u32(cmd + 24 + 4 * w + 4),
+                r.u32(cmd + 24 + 4 * w + 8),
+            );
+            let (Some(address), Some(offset), Some(size), Some(prot), Some(nsects)) = fields else { break };
+            let flags = SectionFlags {
+                read: prot & VM_PROT_READ != 0,
+                write: prot & VM_PROT_WRITE != 0,
+                execute: prot & VM_PROT_EXECUTE != 0,
+            };
+
+            if nsects == 0 {
+                sections.push(Section { name: segname.clone(), offset, size, address, flags });
+            }
+            let first = cmd + 24 + 4 * w + 16;
+            let sect_size = if seg_64 { 80 } else { 68 };
+            for s in 0..(nsects as u64).min(MAX_SECTIONS as u64) {
+                let h = first + s * sect_size;
+                // addr, size, offset and flags (after align, reloff, nreloc)
+                let fields = (
+                    r.word(h + 32, seg_64),
+                    r.word(h + 32 + w, seg_64),
+                    r.u32(h + 32 + 2 * w),
+                    r.u32(h + 32 + 2 * w + 16),
+                );
+                let (Some(address), Some(size), Some(offset), Some(sect_flags)) = fields else { break };
+                sections.push(Section {
+                    name: format!("{},{}", segname, r.c_str(h, 16)),
+                    offset: offset as u64,
+                    size: if ZEROFILL_TYPES.contains(&(sect_flags & 0xFF)) { 0 } else { size },
+                    address,
+                    flags,
+                });
+                if sections.len() >= MAX_SECTIONS {
+                    return Some(sections);
+                }
+            }
+        }
+        if cmd_size == 0 {
+            break;
+        }
+        cmd += cmd_size as u64;
+    }
+    Some(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    #[test]
+    fn parses_elf64_sections() {
+        let mut data = vec![0u8; 0x200];
+        put(&mut data, 0, b"\x7FELF\x02\x01");
+        put(&mut data, 0x28, &0x100u64.to_le_bytes()); // e_shoff
+        put(&mut data, 0x3A, &64u16.to_le_bytes()); // e_shentsize
+        put(&mut data, 0x3C, &4u16.to_le_bytes()); // e_shnum
+        put(&mut data, 0x3E, &3u16.to_le_bytes()); // e_shstrndx
+        put(&mut data, 0x80, b"\0.text\0.bss\0.shstrtab\0");
+
+        let section = |data: &mut Vec<u8>, i: usize, name: u32, kind: u32, flags: u64, addr: u64, offset: u64, size: u64| {
+            let h = 0x100 + i * 64;
+            put(data, h, &name.to_le_bytes());
+            put(data, h + 4, &kind.to_le_bytes());
+            put(data, h + 0x08, &flags.to_le_bytes());
+            put(data, h + 0x10, &addr.to_le_bytes());
+            put(data, h + 0x18, &offset.to_le_bytes());
+            put(data, h + 0x20, &size.to_le_bytes());
+        };
+        section(&mut data, 1, 1, 1, 0x6, 0x401000, 0x40, 0x20);
+        section(&mut data, 2, 7, 8, 0x3, 0x402000, 0x60, 0x1000);
+        section(&mut data, 3, 12, 3, 0, 0, 0x80, 0x16);
+
+        let layout = parse_sections(&data).unwrap();
+        assert_eq!(layout.format, ExecutableFormat::Elf);
+        let names: Vec<&str> = layout.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, [".text", ".bss", ".shstrtab"]);
+        let text = &layout.sections[0];
+        assert_eq!((text.offset, text.size, text.address), (0x40, 0x20, 0x401000));
+        assert_eq!(text.flags.label(), "r-x");
+        // NOBITS sections take no file space
+        assert_eq!(layout.sections[1].size, 0);
+        assert_eq!(layout.sections[1].flags.label(), "rw-");
+        assert_eq!(layout.section_at(0x50).map(|s| s.name.as_str()), Some(".text"));
+        assert_eq!(layout.section_at(0x60), None);
+    }
+
+    #[test]
+    fn elf_without_sections_lists_load_segments() {
+        let mut data = vec![0u8; 0x100];
+        put(&mut data, 0, b"\x7FELF\x01\x01");
+        put(&mut data, 0x1C, &0x34u32.to_le_bytes()); // e_phoff
+        put(&mut data, 0x2A, &32u16.to_le_bytes()); // e_phentsize
+        put(&mut data, 0x2C, &2u16.to_le_bytes()); // e_phnum
+        // PT_PHDR, then PT_LOAD r-x
+        put(&mut data, 0x34, &6u32.to_le_bytes());
+        let h = 0x34 + 32;
+        put(&mut data, h, &1u32.to_le_bytes());
+        put(&mut data, h + 4, &0u32.to_le_bytes());
+        put(&mut data, h + 8, &0x8048000u32.to_le_bytes());
+        put(&mut data, h + 0x10, &0x100u32.to_le_bytes());
+        put(&mut data, h + 0x18, &5u32.to_le_bytes());
+
+        let layout = parse_sections(&data).unwrap();
+        assert_eq!(layout.sections.len(), 1);
+        let load = &layout.sections[0];
+        assert_eq!(load.name, "LOAD[1]");
+        assert_eq!((load.offset, load.size, load.address), (0, 0x100, 0x8048000));
+        assert_eq!(load.flags.label(), "r-x");
+    }
+
+    #[test]
+    fn parses_pe_sections() {
+        let mut data = vec![0u8; 0x400];
+        put(&mut data, 0, b"MZ");
+        put(&mut data, 0x3C, &0x80u32.to_le_bytes());
+        put(&mut data, 0x80, b"PE\0\0");
+        put(&mut data, 0x86, &2u16.to_le_bytes()); // NumberOfSections
+        put(&mut data, 0x94, &0xF0u16.to_le_bytes()); // SizeOfOptionalHeader
+        let table = 0x80 + 24 + 0xF0;
+        for (i, (name, rva, size, raw, flags)) in [
+            (&b".text\0\0\0"[..], 0x1000u32, 0x200u32, 0x400u32, 0x6000_0020u32),
+            (&b".data\0\0\0"[..], 0x2000, 0x100, 0x600, 0xC000_0040),
+        ].into_iter().enumerate() {
+            let h = table + i * 40;
+            put(&mut data, h, name);
+            put(&mut data, h + 12, &rva.to_le_bytes());
+            put(&mut data, h + 16, &size.to_le_bytes());
+            put(&mut data, h + 20, &raw.to_le_bytes());
+            put(&mut data, h + 36, &flags.to_le_bytes());
+        }
+
+        let layout = parse_sections(&data).unwrap();
+        assert_eq!(layout.format, ExecutableFormat::Pe);
+        assert_eq!(layout.sections.len(), 2);
+        assert_eq!(layout.sections[0].name, ".text");
+        assert_eq!((layout.sections[0].offset, layout.sections[0].size, layout.sections[0].address), (0x400, 0x200, 0x1000));
+        assert_eq!(layout.sections[0].flags.label(), "r-x");
+        assert_eq!(layout.sections[1].flags.label(), "rw-");
+    }
+
+    #[test]
+    fn parses_macho64_sections() {
+        let mut data = vec![0u8; 0x200];
+        put(&mut data, 0, &0xFEED_FACFu32.to_le_bytes());
+        put(&mut data, 16, &2u32.to_le_bytes()); // ncmds
+        // LC_SEGMENT_64 __TEXT with one section
+        let cmd = 32;
+        put(&mut data, cmd, &0x19u32.to_le_bytes());
+        put(&mut data, cmd + 4, &(72u32 + 80).to_le_bytes());
+        put(&mut data, cmd + 8, b"__TEXT");
+        put(&mut data, cmd + 24, &0x1_0000_0000u64.to_le_bytes());
+        put(&mut data, cmd + 48, &0x1000u64.to_le_bytes()); // filesize
+        put(&mut data, cmd + 60, &5u32.to_le_bytes()); // initprot r-x
+        put(&mut data, cmd + 64, &1u32.to_le_bytes()); // nsects
+        let sect = cmd + 72;
+        put(&mut data, sect, b"__text");
+        put(&mut data, sect + 16, b"__TEXT");
+        put(&mut data, sect + 32, &0x1_0000_0F00u64.to_le_bytes());
+        put(&mut data, sect + 40, &0x80u64.to_le_bytes());
+        put(&mut data, sect + 48, &0xF00u32.to_le_bytes());
+        // LC_SEGMENT_64 __LINKEDIT without sections
+        let cmd = sect + 80;
+        put(&mut data, cmd, &0x19u32.to_le_bytes());
+        put(&mut data, cmd + 4, &72u32.to_le_bytes());
+        put(&mut data, cmd + 8, b"__LINKEDIT");
+        put(&mut data, cmd + 40, &0x1000u64.to_le_bytes()); // fileoff
+        put(&mut data, cmd + 48, &0x40u64.to_le_bytes());
+        put(&mut data, cmd + 60, &1u32.to_le_bytes());
+
+        let layout = parse_sections(&data).unwrap();
+        assert_eq!(layout.format, ExecutableFormat::MachO);
+        let text = &layout.sections[0];
+        assert_eq!(text.name, "__TEXT,__text");
+        assert_eq!((text.offset, text.size, text.address), (0xF00, 0x80, 0x1_0000_0F00));
+        assert_eq!(text.flags.label(), "r-x");
+        let linkedit = &layout.sections[1];
+        assert_eq!((linkedit.name.as_str(), linkedit.offset, linkedit.size), ("__LINKEDIT", 0x1000, 0x40));
+        assert_eq!(linkedit.flags.label(), "r--");
+    }
+
+    #[test]
+    fn other_data_has_no_layout() {
+        assert_eq!(parse_sections(b"plain text, not an executable"), None);
+        assert_eq!(parse_sections(b"MZ"), None);
+        assert_eq!(parse_sections(&[]), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Printable string extraction and string-label analysis.
+//!
+//! Finds ASCII strings at given offsets and turns data cross-references that
+//! point at strings into labels named after their contents (`str_Error_open`).
+
+use std::collections::HashSet;
+
+use crate::project::{Label, LabelType, Project};
+use crate::xrefs::{XRefTable, XRefType};
+
+/// Minimum number of printable characters for a run to count as a string.
+pub const MIN_STRING_LEN: usize = 4;
+/// Maximum number of bytes read for a single string.
+pub const MAX_STRING_LEN: usize = 256;
+/// Maximum length of generated label names (including the `str_` prefix).
+pub const MAX_STRING_LABEL_LEN: usize = 32;
+
+/// Check if a byte is part of a printable ASCII string.
+fn is_printable(b: u8) -> bool {
+    (0x20..=0x7E).contains(&b) || b == b'\t'
+}
+
+/// Get the printable ASCII string starting at `offset`.
+///
+/// The string must begin at `offset` (the preceding byte is not printable),
+/// be at least `min_len` characters long, and is cut at [`MAX_STRING_LEN`].
+pub fn printable_string_at(data: &[u8], offset: usize, min_len: usize) -> Option<&str> {
+    if offset >= data.len() || (offset > 0 && is_printable(data[offset - 1])) {
+        return None;
+    }
+
+    let window = &data[offset..data.len().min(offset + MAX_STRING_LEN)];
+    let len = window.iter().take_while(|&&b| is_printable(b)).count();
+    if len < min_len.max(1) {
+        return None;
+    }
+
+    // Only printable ASCII, so always valid UTF-8
+    std::str::from_utf8(&window[..len]).ok()
+}
+
+/// A printable ASCII string found in the data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundString {
+    pub offset: u64,
+    pub text: String,
+}
+
+/// Find every run of at least `min_len` printable ASCII characters.
+pub fn find_strings(data: &[u8], min_len: usize) -> Vec<FoundString> {
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let len = data[pos..].iter().take_while(|&&b| is_printable(b)).count();
+        if len >= min_len.max(1) {
+            found.push(FoundString {
+                offset: pos as u64,
+                // Only printable ASCII, so always valid UTF-8
+                text: String::from_utf8_lossy(&data[pos..pos + len]).into_owned(),
+            });
+        }
+        pos += len.max(1);
+    }
+    found
+}
+
+/// Build a label name from string contents: `str_` followed by its
+/// alphanumeric words joined with `_`, capped at `max_len` characters.
+///
+/// Returns `None` if the string has no alphanumeric characters.
+pub fn string_label_name(text: &str, max_len: usize) -> Option<String> {
+    let mut name = String::from("str");
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        if name.len() + 1 >= max_len {
+            break;
+        }
+        name.push('_');
+        name.extend(word.chars().take(max_len - name.len()));
+    }
+
+    (name.len() > 3).then_some(name)
+}
+
+/// Create labels for strings referenced by data xrefs (lea, mov, ...).
+///
+/// Targets that already carry a label are skipped, each target is labelled
+/// once, and names clashing with existing labels get a numeric suffix.
+/// `data` is the whole file; xref targets are file offsets.
+pub fn label_referenced_strings(table: &XRefTable, data: &[u8], project: &Project) -> Vec<Label> {
+    let mut names: HashSet<String> = project.labels.iter().map(|l| l.name.clone()).collect();
+    let mut labels = Vec::new();
+
+    for target in table.referenced_addresses() {
+        let is_data_ref = table.get_refs_to(target).is_some_and(|refs| {
+            refs.iter().any(|r| matches!(r.xref_type, XRefType::Data | XRefType::Read | XRefType::Write))
+        });
+        if !is_data_ref || project.get_label(target).is_some() {
+            continue;
+        }
+
+        let Ok(offset) = usize::try_from(target) else { continue };
+        let Some(text) = printable_string_at(data, offset, MIN_STRING_LEN) else { continue };
+        let Some(base) = string_label_name(text, MAX_STRING_LABEL_LEN) else { continue };
+
+        let mut name = base.clone();
+        let mut n = 2;
+        while names.contains(&name) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        names.insert(name.clone());
+
+        let mut label = Label::new(target, name);
+        label.label_type = LabelType::String;
+        label.size = Some(text.len() as u64);
+        label.notes = text.to_string();
+        labels.push(label);
+    }
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xrefs::XRef;
+    use std::path::Path;
+
+    #[test]
+    fn test_find_strings() {
+        let data = b"GET /\x00ab\x00\x01Error: open\tfailed";
+        let found = find_strings(data, MIN_STRING_LEN);
+        assert_eq!(found, vec![
+            FoundString { offset: 0, text: "GET /".to_string() },
+            FoundString { offset: 10, text: "Error: open\tfailed".to_string() },
+        ]);
+        assert_eq!(find_strings(data, 2).len(), 3);
+        assert!(find_strings(b"", 4).is_empty());
+    }
+
+    #[test]
+    fn test_printable_string_at() {
+        let data = b"\x00\x00Error: open\x00ab\x00";
+        assert_eq!(printable_string_at(data, 2, MIN_STRING_LEN), Some("Error: open"));
+        // Not at the start of the string
+        assert_eq!(printable_string_at(data, 3, MIN_STRING_LEN), None);
+        // Too short
+        assert_eq!(printable_string_at(data, 14, MIN_STRING_LEN), None);
+        assert_eq!(printable_string_at(data, 100, MIN_STRING_LEN), None);
+    }
+
+    #[test]
+    fn test_string_label_name() {
+        assert_eq!(string_label_name("Error: open", 32).as_deref(), Some("str_Error_open"));
+        assert_eq!(string_label_name("%s -> %d\n", 32).as_deref(), Some("str_s_d"));
+        assert_eq!(string_label_name("!!! ...", 32), None);
+
+        let long = string_label_name("The quick brown fox jumps over the lazy dog", 20).unwrap();
+        assert!(long.len() <= 20);
+        assert!(long.starts_with("str_The_quick"));
+    }
+
+    #[test]
+    fn test_label_referenced_strings() {
+        let mut data = vec![0u8; 0x100];
+        data[0x40..0x4B].copy_from_slice(b"Error: open");
+        data[0x60..0x6B].copy_from_slice(b"Error: open");
+        data[0x80..0x85].copy_from_slice(b"hello");
+
+        let mut table = XRefTable::new();
+        for (from, to, xref_type) in [
+            (0x0, 0x40, XRefType::Data),
+            (0x4, 0x40, XRefType::Read),
+            (0x8, 0x60, XRefType::Data),
+            (0xC, 0x80, XRefType::Call),
+            (0x10, 0x90, XRefType::Data),
+        ] {
+            table.add(XRef { from, to, xref_type, mnemonic: "lea".to_string() });
+        }
+
+        let project = Project::new(Path::new("test.bin"), data.len() as u64);
+        let labels = label_referenced_strings(&table, &data, &project);
+
+        let names: Vec<(u64, &str)> = labels.iter().map(|l| (l.address, l.name.as_str())).collect();
+        assert_eq!(names, vec![(0x40, "str_Error_open"), (0x60, "str_Error_open_2")]);
+        assert_eq!(labels[0].label_type, LabelType::String);
+        assert_eq!(labels[0].size, Some(11));
+
+        // Already labelled targets are left alone
+        let mut project = project;
+        project.add_label(Label::new(0x40, "msg".to_string()));
+        let labels = label_referenced_strings(&table, &data, &project);
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].address, 0x60);
+        assert_eq!(labels[0].name, "str_Error_open");
+    }
+}
@@ -0,0 +1,319 @@
+//! Quick structure of well-known containers: PNG chunks and ZIP entries.
+//!
+//! Focused parsers for files whose signature was found at offset 0, giving
+//! a navigable list without writing a template. PNG chunks are walked from
+//! the signature to `IEND`; ZIP entries are read from the central directory
+//! (ZIP64 included). Reads are bounds-checked and a truncated file yields
+//! the entries read so far.
+
+/// Upper bound on parsed entries, against corrupt counts.
+const MAX_ENTRIES: usize = 65536;
+
+/// PNG file signature.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// ZIP end of central directory record, and its size without the comment.
+const ZIP_EOCD: &[u8] = b"PK\x05\x06";
+const ZIP_EOCD_LEN: usize = 22;
+/// ZIP64 end of central directory locator and record.
+const ZIP64_LOCATOR: &[u8] = b"PK\x06\x07";
+const ZIP64_EOCD: &[u8] = b"PK\x06\x06";
+/// Central directory file header and its size without the variable fields.
+const ZIP_CENTRAL_HEADER: &[u8] = b"PK\x01\x02";
+const ZIP_CENTRAL_HEADER_LEN: usize = 46;
+
+/// Container format with a quick structure parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerFormat {
+    Png,
+    Zip,
+}
+
+impl ContainerFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContainerFormat::Png => "PNG",
+            ContainerFormat::Zip => "ZIP",
+        }
+    }
+
+    /// What one entry is called ("chunk", "entry").
+    pub fn entry_label(&self) -> &'static str {
+        match self {
+            ContainerFormat::Png => "chunk",
+            ContainerFormat::Zip => "entry",
+        }
+    }
+
+    /// Format of a signature name from the built-in database.
+    pub fn from_signature(name: &str) -> Option<Self> {
+        if name == "PNG" {
+            Some(ContainerFormat::Png)
+        } else if name.starts_with("ZIP") {
+            Some(ContainerFormat::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// A chunk or archive member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructureEntry {
+    /// File offset of the chunk, or of the member's local header.
+    pub offset: u64,
+    /// Chunk type or member path.
+    pub name: String,
+    /// Whole chunk (length, type, data and CRC) or compressed member data.
+    pub size: u64,
+}
+
+/// Entries of a container, in file (PNG) or directory (ZIP) order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStructure {
+    pub format: ContainerFormat,
+    pub entries: Vec<StructureEntry>,
+}
+
+/// Parse `data` (a whole file) as `format`. `None` when no entry is found.
+pub fn parse_structure(format: ContainerFormat, data: &[u8]) -> Option<FileStructure> {
+    let entries = match format {
+        ContainerFormat::Png => parse_png_chunks(data),
+        ContainerFormat::Zip => parse_zip_entries(data),
+    };
+    (!entries.is_empty()).then_some(FileStructure { format, entries })
+}
+
+fn bytes_at<const N: usize>(data: &[u8], offset: u64) -> Option<[u8; N]> {
+    let start = usize::try_from(offset).ok()?;
+    data.get(start..start.checked_add(N)?)?.try_into().ok()
+}
+
+/// True if `magic` is at `offset`.
+fn has_magic(data: &[u8], offset: u64, magic: &[u8]) -> bool {
+    usize::try_from(offset).ok().and_then(|start| data.get(start..)).is_some_and(|rest| rest.starts_with(magic))
+}
+
+fn u16_le(data: &[u8], offset: u64) -> Option<u16> {
+    bytes_at(data, offset).map(u16::from_le_bytes)
+}
+
+fn u32_le(data: &[u8], offset: u64) -> Option<u32> {
+    bytes_at(data, offset).map(u32::from_le_bytes)
+}
+
+fn u64_le(data: &[u8], offset: u64) -> Option<u64> {
+    bytes_at(data, offset).map(u64::from_le_bytes)
+}
+
+/// Chunks of a PNG file, up to and including `IEND`.
+pub fn parse_png_chunks(data: &[u8]) -> Vec<StructureEntry> {
+    let mut entries = Vec::new();
+    if !data.starts_with(PNG_SIGNATURE) {
+        return entries;
+    }
+    let mut pos = PNG_SIGNATURE.len() as u64;
+    while entries.len() < MAX_ENTRIES {
+        let (Some(length), Some(kind)) = (bytes_at::<4>(data, pos), bytes_at::<4>(data, pos + 4)) else { break };
+        // Chunk types are four ASCII letters; anything else is not a chunk
+        if !kind.iter().all(u8::is_ascii_alphabetic) {
+            break;
+        }
+        let size = 12 + u32::from_be_bytes(length) as u64;
+        if pos + size > data.len() as u64 {
+            break;
+        }
+        entries.push(StructureEntry { offset: pos, name: String::from_utf8_lossy(&kind).into_owned(), size });
+        if &kind == b"IEND" {
+            break;
+        }
+        pos += size;
+    }
+    entries
+}
+
+/// Offset of the end of central directory record, searched backwards over
+/// the largest possible archive comment.
+fn find_zip_eocd(data: &[u8]) -> Option<u64> {
+    let last = data.len().checked_sub(ZIP_EOCD_LEN)?;
+    let first = last.saturating_sub(u16::MAX as usize);
+    (first..=last).rev().find(|&pos| data[pos..].starts_with(ZIP_EOCD)).map(|pos| pos as u64)
+}
+
+/// Central directory offset and entry count from the ZIP64 records located
+/// just before the end of central directory record at `eocd`.
+fn zip64_directory(data: &[u8], eocd: u64) -> Option<(u64, u64)> {
+    let locator = eocd.checked_sub(20)?;
+    if !has_magic(data, locator, ZIP64_LOCATOR) {
+        return None;
+    }
+    let record = u64_le(data, locator + 8)?;
+    if !has_magic(data, record, ZIP64_EOCD) {
+        return None;
+    }
+    Some((u64_le(data, record + 48)?, u64_le(data, record + 32)?))
+}
+
+/// Members of a ZIP archive, from its central directory.
+pub fn parse_zip_entries(data: &[u8]) -> Vec<StructureEntry> {
+    let mut entries = Vec::new();
+    let Some(eocd) = find_zip_eocd(data) else { return entries };
+    let (Some(count), Some(directory)) = (u16_le(data, eocd + 10), u32_le(data, eocd + 16)) else { return entries };
+    let (mut pos, count) = if count == u16::MAX || directory == u32::MAX {
+        match zip64_directory(data, eocd) {
+            Some(found) => found,
+            None => return entries,
+        }
+    } else {
+        (directory as u64, count as u64)
+    };
+
+    while (entries.len() as u64) < count && entries.len() < MAX_ENTRIES {
+        if !has_magic(data, pos, ZIP_CENTRAL_HEADER) {
+            break;
+        }
+        let (Some(compressed), Some(name_len), Some(extra_len), Some(comment_len), Some(local)) = (
+            u32_le(data, pos + 20),
+            u16_le(data, pos + 28),
+            u16_le(data, pos + 30),
+            u16_le(data, pos + 32),
+            u32_le(data, pos + 42),
+        ) else {
+            break;
+        };
+        let name_start = pos + ZIP_CENTRAL_HEADER_LEN as u64;
+        let Some(name) = data.get(name_start as usize..(name_start + name_len as u64) as usize) else { break };
+        let extra_start = name_start + name_len as u64;
+        let extra = data.get(extra_start as usize..(extra_start + extra_len as u64) as usize).unwrap_or(&[]);
+        let (size, offset) = zip64_sizes(extra, pos, data, compressed, local);
+        entries.push(StructureEntry { offset, name: String::from_utf8_lossy(name).into_owned(), size });
+        pos = extra_start + extra_len as u64 + comment_len as u64;
+    }
+    entries
+}
+
+/// Compressed size and local header offset of a central directory entry,
+/// taking the 64-bit values from the ZIP64 extra field where the 32-bit
+/// ones are saturated.
+fn zip64_sizes(extra: &[u8], header: u64, data: &[u8], compressed: u32, local: u32) -> (u64, u64) {
+    let (mut size, mut offset) = (compressed as u64, local as u64);
+    let mut field = 0;
+    while let (Some(id), Some(len)) = (u16_le(extra, field), u16_le(extra, field + 2)) {
+        let body = field + 4;
+        if id == 0x0001 {
+            // Only the saturated values are present, in this order
+            let uncompressed_saturated = u32_le(data, header + 24) == Some(u32::MAX);
+            let mut next = body + if uncompressed_saturated { 8 } else { 0 };
+            if compressed == u32::MAX {
+                size = u64_le(extra, next).unwrap_or(size);
+                next += 8;
+            }
+            if local == u32::MAX {
+                offset = u64_le(extra, next).unwrap_or(offset);
+            }
+            break;
+        }
+        field = body + len as u64;
+    }
+    (size, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_chunk(kind: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut chunk = (payload.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(payload);
+        chunk.extend_from_slice(&[0; 4]); // CRC, not checked
+        chunk
+    }
+
+    /// Archive of stored members, with an optional ZIP64 extra field on each.
+    fn zip(members: &[(&str, &[u8])], zip64: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for (name, content) in members {
+            let local = data.len() as u32;
+            data.extend_from_slice(b"PK\x03\x04");
+            data.extend_from_slice(&[0; 14]);
+            data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            data.extend_from_slice(&[0; 2]);
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(content);
+
+            let (size32, local32) = if zip64 { (u32::MAX, u32::MAX) } else { (content.len() as u32, local) };
+            directory.extend_from_slice(b"PK\x01\x02");
+            directory.extend_from_slice(&[0; 16]);
+            directory.extend_from_slice(&size32.to_le_bytes());
+            directory.extend_from_slice(&size32.to_le_bytes());
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&(if zip64 { 28u16 } else { 0 }).to_le_bytes());
+            directory.extend_from_slice(&[0; 10]);
+            directory.extend_from_slice(&local32.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+            if zip64 {
+                directory.extend_from_slice(&1u16.to_le_bytes());
+                directory.extend_from_slice(&24u16.to_le_bytes());
+                directory.extend_from_slice(&(content.len() as u64).to_le_bytes());
+                directory.extend_from_slice(&(content.len() as u64).to_le_bytes());
+                directory.extend_from_slice(&(local as u64).to_le_bytes());
+            }
+        }
+        let directory_offset = data.len() as u32;
+        data.extend_from_slice(&directory);
+        data.extend_from_slice(b"PK\x05\x06");
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(members.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        data.extend_from_slice(&directory_offset.to_le_bytes());
+        data.extend_from_slice(&[0; 2]);
+        data
+    }
+
+    #[test]
+    fn lists_png_chunks_up_to_iend() {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(png_chunk(b"IHDR", &[0; 13]));
+        data.extend(png_chunk(b"IDAT", &[1; 100]));
+        data.extend(png_chunk(b"IEND", &[]));
+        data.extend_from_slice(b"trailing data");
+
+        let structure = parse_structure(ContainerFormat::Png, &data).unwrap();
+        let chunks: Vec<_> = structure.entries.iter().map(|e| (e.offset, e.name.as_str(), e.size)).collect();
+        assert_eq!(chunks, [(8, "IHDR", 25), (33, "IDAT", 112), (145, "IEND", 12)]);
+
+        // A truncated chunk ends the list
+        assert_eq!(parse_png_chunks(&data[..100]).len(), 1);
+        assert!(parse_structure(ContainerFormat::Png, b"not a png").is_none());
+    }
+
+    #[test]
+    fn lists_zip_members_from_central_directory() {
+        let data = zip(&[("a.txt", b"hello"), ("dir/b.bin", &[7; 40])], false);
+        let structure = parse_structure(ContainerFormat::Zip, &data).unwrap();
+        let members: Vec<_> = structure.entries.iter().map(|e| (e.offset, e.name.as_str(), e.size)).collect();
+        assert_eq!(members, [(0, "a.txt", 5), (40, "dir/b.bin", 40)]);
+
+        // ZIP64 extra fields carry the saturated sizes and offsets
+        assert_eq!(parse_zip_entries(&zip(&[("a.txt", b"hello"), ("dir/b.bin", &[7; 40])], true)), structure.entries);
+    }
+
+    #[test]
+    fn damaged_zip_yields_entries_read_so_far() {
+        let data = zip(&[("a.txt", b"hello"), ("b.txt", b"world")], false);
+        let directory = u32_le(&data, data.len() as u64 - 6).unwrap() as usize;
+        let mut damaged = data.clone();
+        // Signature of the second central directory header
+        damaged[directory + ZIP_CENTRAL_HEADER_LEN + "a.txt".len()] = b'X';
+        assert_eq!(parse_zip_entries(&damaged).len(), 1);
+        // Without the end record there is no directory to read
+        assert!(parse_zip_entries(&data[..data.len() - 1]).is_empty());
+        assert_eq!(ContainerFormat::from_signature("ZIP/JAR/APK/DOCX"), Some(ContainerFormat::Zip));
+        assert_eq!(ContainerFormat::from_signature("JPEG"), None);
+    }
+}
@@ -6,6 +6,8 @@
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
+use crate::checksum::ChecksumAlgo;
+
 /// Primitive data types supported in templates.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -72,6 +74,32 @@ pub enum FieldType {
         base: PrimitiveType,
         bits: HashMap<u64, String>,
     },
+    /// Stored checksum, verified against a checksum computed over `over`.
+    Computed {
+        algo: ChecksumAlgo,
+        over: FieldRange,
+    },
+}
+
+/// Byte range within a structure, relative to its start (end exclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl FieldRange {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl FieldType {
@@ -84,6 +112,7 @@ impl FieldType {
             FieldType::Magic(bytes) => bytes.len(),
             FieldType::Enum { base, .. } => base.size(),
             FieldType::Flags { base, .. } => base.size(),
+            FieldType::Computed { algo, .. } => algo.size(),
         }
     }
 }
@@ -99,6 +128,9 @@ pub struct TemplateField {
     pub offset: usize,
     /// Optional description/comment.
     pub description: Option<String>,
+    /// Expected value; a decoded value that differs is flagged as an error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<FieldValue>,
 }
 
 /// A structure template definition.
@@ -166,6 +198,7 @@ impl TemplateBuilder {
             field_type,
             offset,
             description: None,
+            expect: None,
         });
         self.offset += size;
         self
@@ -179,11 +212,20 @@ impl TemplateBuilder {
             field_type,
             offset,
             description: Some(desc.to_string()),
+            expect: None,
         });
         self.offset += size;
         self
     }
 
+    /// Set the expected value of the last added field.
+    pub fn expect(mut self, value: FieldValue) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.expect = Some(value);
+        }
+        self
+    }
+
     /// Add padding bytes.
     pub fn padding(mut self, size: usize) -> Self {
         self.offset += size;
@@ -210,7 +252,8 @@ impl TemplateBuilder {
 }
 
 /// Interpreted value of a field.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FieldValue {
     /// Unsigned integer.
     Unsigned(u64),
@@ -228,6 +271,9 @@ pub enum FieldValue {
     Enum { value: u64, name: Option<String> },
     /// Flags with active flag names.
     Flags { value: u64, active: Vec<String> },
+    /// Stored checksum and the one computed over the covered range
+    /// (`None` if the range lies outside the data).
+    Checksum { stored: u64, computed: Option<u64> },
     /// Error reading value.
     Error(String),
 }
@@ -283,6 +329,11 @@ impl FieldValue {
                     format!("0x{:X} [{}]", value, active.join(", "))
                 }
             }
+            FieldValue::Checksum { stored, computed } => match computed {
+                Some(c) if c == stored => format!("0x{:08X} ✓", stored),
+                Some(c) => format!("0x{:08X} ✗ (computed 0x{:08X})", stored, c),
+                None => format!("0x{:08X} (range out of bounds)", stored),
+            },
             FieldValue::Error(e) => format!("Error: {}", e),
         }
     }
@@ -294,9 +345,49 @@ impl FieldValue {
             FieldValue::Signed(v) => Some(*v as u64),
             FieldValue::Enum { value, .. } => Some(*value),
             FieldValue::Flags { value, .. } => Some(*value),
+            FieldValue::Checksum { stored, .. } => Some(*stored),
             _ => None,
         }
     }
+
+    /// Check a decoded value against an expected one. Numeric values compare
+    /// by number (so an enum can be expected as a plain integer) and magic
+    /// bytes compare against expected bytes.
+    pub fn matches_expected(&self, expected: &FieldValue) -> bool {
+        if let (Some(a), Some(b)) = (self.as_u64(), expected.as_u64()) {
+            return a == b;
+        }
+        match (self, expected) {
+            (FieldValue::Magic { bytes, .. } | FieldValue::Bytes(bytes), FieldValue::Bytes(e)) => bytes == e,
+            _ => self == expected,
+        }
+    }
+}
+
+/// Validation status of a decoded field.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum FieldStatus {
+    /// Value decoded and passed all checks.
+    #[default]
+    Ok,
+    /// Value decoded but looks suspicious (e.g. unknown enum value).
+    Warning(String),
+    /// Value could not be read or failed a check (EOF, magic, expected value).
+    Error(String),
+}
+
+impl FieldStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, FieldStatus::Ok)
+    }
+
+    /// Explanation for warnings and errors.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            FieldStatus::Ok => None,
+            FieldStatus::Warning(m) | FieldStatus::Error(m) => Some(m),
+        }
+    }
 }
 
 /// Result of applying a template to data.
@@ -310,22 +401,43 @@ pub struct TemplateResult {
     pub fields: Vec<(TemplateField, FieldValue)>,
     /// Whether all magic bytes matched.
     pub magic_ok: bool,
+    /// Validation status of each field (same order as `fields`).
+    pub status: Vec<FieldStatus>,
+}
+
+impl TemplateResult {
+    /// Number of fields with an error status.
+    pub fn error_count(&self) -> usize {
+        self.status.iter().filter(|s| matches!(s, FieldStatus::Error(_))).count()
+    }
+
+    /// Number of fields with a warning status.
+    pub fn warning_count(&self) -> usize {
+        self.status.iter().filter(|s| matches!(s, FieldStatus::Warning(_))).count()
+    }
 }
 
 /// Apply a template to data at a given offset.
 pub fn apply_template(template: &StructTemplate, data: &[u8], base_offset: u64) -> TemplateResult {
     let mut fields = Vec::new();
+    let mut status = Vec::new();
     let mut magic_ok = true;
     let le = template.little_endian;
 
     for field in &template.fields {
         let start = field.offset;
         let size = field.field_type.size();
-        let end = start + size;
 
-        let value = if end <= data.len() {
-            let bytes = &data[start..end];
-            interpret_field(&field.field_type, bytes, le)
+        // Fields running off the end of the data decode as errors, not panics
+        let value = if let Some(bytes) = start.checked_add(size).and_then(|end| data.get(start..end)) {
+            match &field.field_type {
+                FieldType::Computed { algo, over } => {
+                    let stored = read_unsigned(PrimitiveType::U32, bytes, le);
+                    let computed = data.get(over.start..over.end).map(|covered| algo.compute(covered));
+                    FieldValue::Checksum { stored, computed }
+                }
+                field_type => interpret_field(field_type, bytes, le),
+            }
         } else {
             FieldValue::Error("Out of bounds".to_string())
         };
@@ -337,6 +449,7 @@ pub fn apply_template(template: &StructTemplate, data: &[u8], base_offset: u64)
             }
         }
 
+        status.push(validate_field(field, &value, data.len()));
         fields.push((field.clone(), value));
     }
 
@@ -345,7 +458,50 @@ pub fn apply_template(template: &StructTemplate, data: &[u8], base_offset: u64)
         base_offset,
         fields,
         magic_ok,
+        status,
+    }
+}
+
+/// Determine the validation status of a decoded field.
+fn validate_field(field: &TemplateField, value: &FieldValue, data_len: usize) -> FieldStatus {
+    let size = field.field_type.size();
+    match value {
+        FieldValue::Error(_) => {
+            let available = data_len.saturating_sub(field.offset);
+            return FieldStatus::Error(format!(
+                "Reads past end of data ({} of {} bytes available)",
+                available.min(size),
+                size
+            ));
+        }
+        FieldValue::Magic { matches: false, .. } => {
+            if let FieldType::Magic(expected) = &field.field_type {
+                let hex: Vec<String> = expected.iter().map(|b| format!("{:02X}", b)).collect();
+                return FieldStatus::Error(format!("Magic mismatch, expected {}", hex.join(" ")));
+            }
+        }
+        _ => {}
+    }
+
+    if let FieldValue::Checksum { stored, computed } = value {
+        return match computed {
+            Some(c) if c == stored => FieldStatus::Ok,
+            Some(c) => FieldStatus::Error(format!("Checksum mismatch, computed 0x{:08X}", c)),
+            None => FieldStatus::Error("Checksum range reads past end of data".to_string()),
+        };
     }
+
+    if let Some(expected) = &field.expect {
+        if !value.matches_expected(expected) {
+            return FieldStatus::Error(format!("Expected {}", expected.display()));
+        }
+    }
+
+    if let FieldValue::Enum { name: None, .. } = value {
+        return FieldStatus::Warning("Value not in enum".to_string());
+    }
+
+    FieldStatus::Ok
 }
 
 /// Interpret bytes according to field type.
@@ -390,6 +546,10 @@ fn interpret_field(field_type: &FieldType, bytes: &[u8], little_endian: bool) ->
                 .collect();
             FieldValue::Flags { value, active }
         }
+        FieldType::Computed { .. } => {
+            // Needs the whole structure; computed in `apply_template`
+            FieldValue::Checksum { stored: read_unsigned(PrimitiveType::U32, bytes, little_endian), computed: None }
+        }
     }
 }
 
@@ -563,12 +723,14 @@ pub fn example_template_json() -> String {
                 field_type: FieldType::Magic(vec![0x7F, 0x45, 0x4C, 0x46]),
                 offset: 0,
                 description: Some("Magic bytes identifying the format".to_string()),
+                expect: None,
             },
             TemplateField {
                 name: "version".to_string(),
                 field_type: FieldType::Primitive(PrimitiveType::U16),
                 offset: 4,
                 description: Some("Version number".to_string()),
+                expect: Some(FieldValue::Unsigned(1)),
             },
             TemplateField {
                 name: "flags".to_string(),
@@ -584,12 +746,14 @@ pub fn example_template_json() -> String {
                 },
                 offset: 6,
                 description: Some("Option flags".to_string()),
+                expect: None,
             },
             TemplateField {
                 name: "name".to_string(),
                 field_type: FieldType::String(16),
                 offset: 10,
                 description: Some("Name string (16 chars)".to_string()),
+                expect: None,
             },
         ],
         size: 26,
@@ -749,4 +913,116 @@ mod tests {
             panic!("Expected Unsigned");
         }
     }
+
+    #[test]
+    fn test_field_status_eof_magic_and_expect() {
+        let template = StructTemplate::builder("Test")
+            .field("magic", FieldType::Magic(vec![0x89, b'P']))
+            .field("version", FieldType::Primitive(PrimitiveType::U16))
+            .expect(FieldValue::Unsigned(1))
+            .field("kind", FieldType::Enum { base: PrimitiveType::U8, values: HashMap::new() })
+            .field("tail", FieldType::Primitive(PrimitiveType::U32))
+            .build();
+
+        let data = [0x89, b'X', 0x02, 0x00, 0x07, 0xAA];
+        let result = apply_template(&template, &data, 0);
+
+        assert!(matches!(result.status[0], FieldStatus::Error(_)), "magic mismatch");
+        assert_eq!(result.status[1], FieldStatus::Error("Expected 1".to_string()));
+        assert!(matches!(result.status[2], FieldStatus::Warning(_)), "unknown enum value");
+        assert_eq!(
+            result.status[3].message(),
+            Some("Reads past end of data (1 of 4 bytes available)")
+        );
+        assert_eq!(result.error_count(), 3);
+        assert_eq!(result.warning_count(), 1);
+
+        let good = [0x89, b'P', 0x01, 0x00];
+        let result = apply_template(&template, &good, 0);
+        assert!(result.status[0].is_ok());
+        assert!(result.status[1].is_ok());
+    }
+
+    #[test]
+    fn test_apply_template_near_eof() {
+        let template = StructTemplate::builder("Test")
+            .field("magic", FieldType::Magic(vec![0x7F, b'E']))
+            .field("size", FieldType::Primitive(PrimitiveType::U32))
+            .field("flags", FieldType::Primitive(PrimitiveType::U16))
+            .build();
+
+        // Only the magic and half of `size` are left before EOF
+        let file = [0u8, 0, 0, 0, 0x7F, b'E', 0x10, 0x00];
+        let result = apply_template(&template, &file[4..], 4);
+
+        assert_eq!(result.fields.len(), 3);
+        assert!(result.status[0].is_ok());
+        assert!(matches!(result.fields[1].1, FieldValue::Error(_)));
+        assert_eq!(
+            result.status[1].message(),
+            Some("Reads past end of data (2 of 4 bytes available)")
+        );
+        assert_eq!(
+            result.status[2].message(),
+            Some("Reads past end of data (0 of 2 bytes available)")
+        );
+        assert_eq!(result.error_count(), 2);
+
+        // Nothing left at all
+        let result = apply_template(&template, &[], 8);
+        assert_eq!(result.error_count(), 3);
+    }
+
+    #[test]
+    fn test_expect_json_roundtrip() {
+        let template = StructTemplate::builder("Expect")
+            .field("sig", FieldType::Primitive(PrimitiveType::U32))
+            .expect(FieldValue::Unsigned(0x89504E47))
+            .field("raw", FieldType::ByteArray(2))
+            .expect(FieldValue::Bytes(vec![0x0D, 0x0A]))
+            .build();
+
+        let json = save_template_to_json(&template).expect("serialize");
+        let parsed = load_template_from_json(&json).expect("parse");
+        assert_eq!(parsed.fields[0].expect, Some(FieldValue::Unsigned(0x89504E47)));
+        assert_eq!(parsed.fields[1].expect, Some(FieldValue::Bytes(vec![0x0D, 0x0A])));
+
+        let data = [0x47, 0x4E, 0x50, 0x89, 0x0D, 0x0A];
+        let result = apply_template(&parsed, &data, 0);
+        assert!(result.status.iter().all(FieldStatus::is_ok));
+    }
+
+    #[test]
+    fn test_computed_checksum_field() {
+        // PNG IHDR chunk of a 1x1 RGBA image: length, type, data, crc
+        let template = StructTemplate::builder("IHDR")
+            .little_endian(false)
+            .field("length", FieldType::Primitive(PrimitiveType::U32))
+            .field("type", FieldType::Magic(b"IHDR".to_vec()))
+            .field("data", FieldType::ByteArray(13))
+            .field("crc", FieldType::Computed { algo: ChecksumAlgo::Crc32, over: FieldRange::new(4, 21) })
+            .build();
+        assert_eq!(template.size, 25);
+
+        let mut data = b"\x00\x00\x00\x0DIHDR\x00\x00\x00\x01\x00\x00\x00\x01\x08\x06\x00\x00\x00\x1F\x15\xC4\x89".to_vec();
+        let result = apply_template(&template, &data, 0);
+        assert_eq!(
+            result.fields[3].1,
+            FieldValue::Checksum { stored: 0x1F15_C489, computed: Some(0x1F15_C489) }
+        );
+        assert!(result.status[3].is_ok());
+
+        // Corrupt one covered byte
+        data[10] ^= 0xFF;
+        let result = apply_template(&template, &data, 0);
+        assert!(matches!(result.status[3], FieldStatus::Error(_)));
+
+        // JSON round trip keeps the computed field
+        let json = save_template_to_json(&template).expect("serialize");
+        let parsed = load_template_from_json(&json).expect("parse");
+        assert!(matches!(
+            parsed.fields[3].field_type,
+            FieldType::Computed { algo: ChecksumAlgo::Crc32, over } if over == FieldRange::new(4, 21)
+        ));
+    }
 }
@@ -0,0 +1,162 @@
+//! Decoding raw bytes as text in common encodings.
+//!
+//! Used by the text view to render a region as readable text. Bytes that
+//! are not valid in the chosen encoding become U+FFFD; control characters
+//! other than newlines and tabs are shown as '.'.
+
+/// Text encoding of a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    /// 7-bit ASCII.
+    #[default]
+    Ascii,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1.
+    Latin1,
+    /// IBM code page 037 (US/Canada mainframes).
+    Ebcdic,
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 6] = [
+        TextEncoding::Ascii,
+        TextEncoding::Utf8,
+        TextEncoding::Utf16Le,
+        TextEncoding::Utf16Be,
+        TextEncoding::Latin1,
+        TextEncoding::Ebcdic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextEncoding::Ascii => "ASCII",
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Latin1 => "Latin-1",
+            TextEncoding::Ebcdic => "EBCDIC (CP037)",
+        }
+    }
+}
+
+/// EBCDIC code page 037 to Latin-1 (CP037 is a permutation of Latin-1).
+const EBCDIC_037_TO_LATIN1: [u8; 256] = [
+    0x00, 0x01, 0x02, 0x03, 0x9C, 0x09, 0x86, 0x7F, 0x97, 0x8D, 0x8E, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x9D, 0x85, 0x08, 0x87, 0x18, 0x19, 0x92, 0x8F, 0x1C, 0x1D, 0x1E, 0x1F,
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x0A, 0x17, 0x1B, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x05, 0x06, 0x07,
+    0x90, 0x91, 0x16, 0x93, 0x94, 0x95, 0x96, 0x04, 0x98, 0x99, 0x9A, 0x9B, 0x14, 0x15, 0x9E, 0x1A,
+    0x20, 0xA0, 0xE2, 0xE4, 0xE0, 0xE1, 0xE3, 0xE5, 0xE7, 0xF1, 0xA2, 0x2E, 0x3C, 0x28, 0x2B, 0x7C,
+    0x26, 0xE9, 0xEA, 0xEB, 0xE8, 0xED, 0xEE, 0xEF, 0xEC, 0xDF, 0x21, 0x24, 0x2A, 0x29, 0x3B, 0xAC,
+    0x2D, 0x2F, 0xC2, 0xC4, 0xC0, 0xC1, 0xC3, 0xC5, 0xC7, 0xD1, 0xA6, 0x2C, 0x25, 0x5F, 0x3E, 0x3F,
+    0xF8, 0xC9, 0xCA, 0xCB, 0xC8, 0xCD, 0xCE, 0xCF, 0xCC, 0x60, 0x3A, 0x23, 0x40, 0x27, 0x3D, 0x22,
+    0xD8, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0xAB, 0xBB, 0xF0, 0xFD, 0xFE, 0xB1,
+    0xB0, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0xAA, 0xBA, 0xE6, 0xB8, 0xC6, 0xA4,
+    0xB5, 0x7E, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xA1, 0xBF, 0xD0, 0xDD, 0xDE, 0xAE,
+    0x5E, 0xA3, 0xA5, 0xB7, 0xA9, 0xA7, 0xB6, 0xBC, 0xBD, 0xBE, 0x5B, 0x5D, 0xAF, 0xA8, 0xB4, 0xD7,
+    0x7B, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0xAD, 0xF4, 0xF6, 0xF2, 0xF3, 0xF5,
+    0x7D, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0xB9, 0xFB, 0xFC, 0xF9, 0xFA, 0xFF,
+    0x5C, 0xF7, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xB2, 0xD4, 0xD6, 0xD2, 0xD3, 0xD5,
+    0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xB3, 0xDB, 0xDC, 0xD9, 0xDA, 0x9F,
+];
+
+/// Character shown for a decoded code point: newlines and tabs are kept,
+/// carriage returns dropped, other control characters shown as '.'.
+fn display_char(c: char) -> Option<char> {
+    match c {
+        '\n' | '\t' => Some(c),
+        '\r' => None,
+        c if c.is_control() => Some('.'),
+        c => Some(c),
+    }
+}
+
+/// Decode `data` as `encoding` for display.
+pub fn decode_text(data: &[u8], encoding: TextEncoding) -> String {
+    decode_text_offsets(data, encoding).into_iter().map(|(_, c)| c).collect()
+}
+
+/// Like [`decode_text`], with the offset in `data` of the first byte of
+/// each displayed character.
+pub fn decode_text_offsets(data: &[u8], encoding: TextEncoding) -> Vec<(usize, char)> {
+    let single = |map: fn(u8) -> char| -> Vec<(usize, char)> {
+        data.iter()
+            .enumerate()
+            .filter_map(|(i, &b)| display_char(map(b)).map(|c| (i, c)))
+            .collect()
+    };
+    match encoding {
+        TextEncoding::Ascii => single(|b| if b.is_ascii() { b as char } else { char::REPLACEMENT_CHARACTER }),
+        TextEncoding::Utf8 => {
+            let mut chars = Vec::with_capacity(data.len());
+            let mut offset = 0;
+            // Same replacement rules as `String::from_utf8_lossy`
+            for chunk in data.utf8_chunks() {
+                for (i, c) in chunk.valid().char_indices() {
+                    chars.extend(display_char(c).map(|c| (offset + i, c)));
+                }
+                offset += chunk.valid().len();
+                if !chunk.invalid().is_empty() {
+                    chars.push((offset, char::REPLACEMENT_CHARACTER));
+                    offset += chunk.invalid().len();
+                }
+            }
+            chars
+        }
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let big_endian = encoding == TextEncoding::Utf16Be;
+            let units = data.chunks_exact(2).map(|pair| {
+                let pair = [pair[0], pair[1]];
+                if big_endian { u16::from_be_bytes(pair) } else { u16::from_le_bytes(pair) }
+            });
+            let mut chars = Vec::with_capacity(data.len() / 2);
+            let mut offset = 0;
+            for c in char::decode_utf16(units) {
+                let units = c.as_ref().map_or(1, |c| c.len_utf16());
+                chars.extend(display_char(c.unwrap_or(char::REPLACEMENT_CHARACTER)).map(|c| (offset, c)));
+                offset += units * 2;
+            }
+            // A trailing odd byte is half a code unit
+            if data.len() % 2 == 1 {
+                chars.push((data.len() - 1, char::REPLACEMENT_CHARACTER));
+            }
+            chars
+        }
+        TextEncoding::Latin1 => single(|b| b as char),
+        TextEncoding::Ebcdic => single(|b| EBCDIC_037_TO_LATIN1[b as usize] as char),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_each_encoding() {
+        assert_eq!(decode_text(b"Hi\tthere\r\n\x01", TextEncoding::Ascii), "Hi\tthere\n.");
+        assert_eq!(decode_text(b"caf\xC3\xA9 \xFF", TextEncoding::Utf8), "café \u{FFFD}");
+        assert_eq!(decode_text(b"A\x00\xE9\x00\x00\xD8B", TextEncoding::Utf16Le), "Aé\u{FFFD}\u{FFFD}");
+        assert_eq!(decode_text(b"\x00A\x00\xE9\xD8\x00", TextEncoding::Utf16Be), "Aé\u{FFFD}");
+        assert_eq!(decode_text(b"caf\xE9 \x85", TextEncoding::Latin1), "café .");
+        // "HELLO, world" in EBCDIC
+        let ebcdic = [0xC8, 0xC5, 0xD3, 0xD3, 0xD6, 0x6B, 0x40, 0xA6, 0x96, 0x99, 0x93, 0x84, 0x25];
+        assert_eq!(decode_text(&ebcdic, TextEncoding::Ebcdic), "HELLO, world\n");
+    }
+
+    #[test]
+    fn offsets_point_at_the_first_byte_of_each_char() {
+        let chars = decode_text_offsets(b"a\r\nc\xC3\xA9\xFFd", TextEncoding::Utf8);
+        assert_eq!(chars, [(0, 'a'), (2, '\n'), (3, 'c'), (4, 'é'), (6, '\u{FFFD}'), (7, 'd')]);
+        // Surrogate pair (U+1F600) takes two code units
+        let chars = decode_text_offsets(b"A\x00\x3D\xD8\x00\xDEB\x00", TextEncoding::Utf16Le);
+        assert_eq!(chars, [(0, 'A'), (2, '\u{1F600}'), (6, 'B')]);
+        let chars = decode_text_offsets(&[0xC8, 0x25, 0x40], TextEncoding::Ebcdic);
+        assert_eq!(chars, [(0, 'H'), (1, '\n'), (2, ' ')]);
+    }
+
+    #[test]
+    fn non_ascii_bytes_are_replaced_in_ascii() {
+        assert_eq!(decode_text(&[b'a', 0x80, 0xFF], TextEncoding::Ascii), "a\u{FFFD}\u{FFFD}");
+    }
+}
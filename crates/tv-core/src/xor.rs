@@ -0,0 +1,74 @@
+//! Single-byte XOR brute force.
+//!
+//! Obfuscated strings are often XORed with one repeated key byte. Trying all
+//! 256 keys and scoring how text-like the result is usually surfaces the key.
+
+use crate::histogram::ByteHistogram;
+
+/// Whether a byte is printable ASCII or common whitespace.
+fn is_printable(byte: u8) -> bool {
+    matches!(byte, 0x20..=0x7E | b'\t' | b'\n' | b'\r')
+}
+
+/// Score every single-byte XOR key by how much the decoded data looks like
+/// text, best first.
+///
+/// The score (0.0 - 1.0) is the printable-ASCII ratio of the decoded bytes,
+/// weighted with the ratio of letters and spaces so real text ranks above
+/// punctuation soup. It only depends on byte counts, so the cost is fixed
+/// once the histogram of `data` is built.
+pub fn brute_single_byte(data: &[u8]) -> Vec<(u8, f32)> {
+    let hist = ByteHistogram::from_data(data);
+    let total = hist.total.max(1) as f32;
+
+    let mut scores: Vec<(u8, f32)> = (0..=255u8)
+        .map(|key| {
+            let (mut printable, mut letters) = (0u64, 0u64);
+            for (byte, &count) in hist.counts.iter().enumerate() {
+                let decoded = byte as u8 ^ key;
+                if is_printable(decoded) {
+                    printable += count;
+                }
+                if decoded.is_ascii_alphabetic() || decoded == b' ' {
+                    letters += count;
+                }
+            }
+            let score = 0.7 * printable as f32 / total + 0.3 * letters as f32 / total;
+            (key, score)
+        })
+        .collect();
+
+    // Stable sort: equal scores keep key order
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scores
+}
+
+/// XOR every byte of `data` with `key`.
+pub fn xor_decode(data: &[u8], key: u8) -> Vec<u8> {
+    data.iter().map(|&b| b ^ key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_key_of_xored_text() {
+        let plain = b"Hello, World! This is a secret message, hidden with XOR.";
+        let encoded = xor_decode(plain, 0x5A);
+
+        let candidates = brute_single_byte(&encoded);
+        assert_eq!(candidates.len(), 256);
+        assert_eq!(candidates[0].0, 0x5A);
+        assert!(candidates[0].1 > 0.95);
+        assert!(candidates[1].1 < candidates[0].1);
+        assert_eq!(xor_decode(&encoded, candidates[0].0), plain);
+    }
+
+    #[test]
+    fn empty_data_keeps_key_order() {
+        let candidates = brute_single_byte(&[]);
+        assert_eq!(candidates[0], (0, 0.0));
+        assert_eq!(candidates[255], (255, 0.0));
+    }
+}
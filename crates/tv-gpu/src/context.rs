@@ -1,10 +1,22 @@
 use anyhow::{Context, Result};
 use wgpu::util::DeviceExt;
 
+use crate::memory::record_allocation;
+
+/// Longest pattern the GPU scan shader can match (it packs patterns into four u32s).
+pub const MAX_GPU_PATTERN_LEN: usize = 16;
+
+/// Match offsets stored per single-pattern dispatch chunk.
+pub const MAX_SCAN_RESULTS: u32 = 65536;
+
 /// Holds the GPU device and queue. Entry point for all GPU compute operations.
 pub struct GpuContext {
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
+    /// Name of the adapter the device was created on.
+    pub adapter_name: String,
+    /// Limits of the created device (buffer sizes are raised to the adapter's).
+    pub limits: wgpu::Limits,
 }
 
 impl GpuContext {
@@ -21,19 +33,58 @@ impl GpuContext {
             .await
             .context("no suitable GPU adapter found")?;
 
-        log::info!("GPU adapter: {}", adapter.get_info().name);
+        let adapter_name = adapter.get_info().name;
+        log::info!("GPU adapter: {}", adapter_name);
+
+        // The defaults cap buffers at 256 MB / 128 MB bindings; ask for what
+        // the adapter actually supports so fewer jobs need chunking.
+        let adapter_limits = adapter.limits();
+        let required_limits = wgpu::Limits {
+            max_buffer_size: adapter_limits.max_buffer_size,
+            max_storage_buffer_binding_size: adapter_limits.max_storage_buffer_binding_size,
+            ..wgpu::Limits::default()
+        };
 
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: Some("tv-gpu"),
                 required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
+                required_limits,
                 memory_hints: wgpu::MemoryHints::Performance,
             }, None)
             .await
             .context("failed to create GPU device")?;
 
-        Ok(Self { device, queue })
+        let limits = device.limits();
+        log::info!(
+            "GPU limits: max buffer {} MB, max storage binding {} MB",
+            limits.max_buffer_size >> 20,
+            limits.max_storage_buffer_binding_size >> 20
+        );
+
+        Ok(Self { device, queue, adapter_name, limits })
+    }
+
+    /// Largest buffer usable as a single storage binding on this device.
+    pub fn max_storage_bytes(&self) -> u64 {
+        self.limits
+            .max_buffer_size
+            .min(self.limits.max_storage_buffer_binding_size as u64)
+    }
+
+    /// Fail with a readable error if a storage buffer of `size` bytes would
+    /// exceed the device limits, instead of letting wgpu abort the dispatch.
+    pub fn ensure_buffer_fits(&self, size: u64, what: &str) -> Result<()> {
+        let max = self.max_storage_bytes();
+        if size > max {
+            anyhow::bail!(
+                "{} needs a {:.1} MB GPU buffer, but this device allows at most {:.1} MB",
+                what,
+                size as f64 / (1024.0 * 1024.0),
+                max as f64 / (1024.0 * 1024.0)
+            );
+        }
+        Ok(())
     }
 
     /// Run the passthrough compute shader: output[i] = input[i] + 1.
@@ -143,6 +194,10 @@ impl GpuContext {
     /// Compute per-block Shannon entropy on the GPU.
     /// `data` is raw bytes, `block_size` must be a multiple of 256 (workgroup size).
     /// Returns one f32 entropy value per block (0.0 = uniform, 8.0 = max entropy).
+    ///
+    /// Large inputs are split into dispatch-sized chunks that are double-buffered:
+    /// chunk N+1 is uploaded and submitted before chunk N is read back, so
+    /// compute overlaps with transfer.
     pub fn compute_entropy(&self, data: &[u8], block_size: u32) -> Result<Vec<f32>> {
         assert!(
             block_size >= 256 && block_size % 256 == 0,
@@ -158,20 +213,54 @@ impl GpuContext {
         const MAX_WORKGROUPS: u32 = 65535;
         let chunk_bytes = MAX_WORKGROUPS as usize * block_size as usize;
 
-        let mut all_results = Vec::new();
+        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("entropy_shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../../shaders/entropy.wgsl").into(),
+            ),
+        });
+
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("entropy_pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let mut all_results = Vec::with_capacity(data.len().div_ceil(block_size as usize));
+        let mut in_flight: Option<PendingReadback> = None;
 
         for chunk_start in (0..data.len()).step_by(chunk_bytes) {
             let chunk_end = (chunk_start + chunk_bytes).min(data.len());
             let chunk = &data[chunk_start..chunk_end];
-            let chunk_results = self.compute_entropy_chunk(chunk, block_size)?;
-            all_results.extend(chunk_results);
+            let next = self.submit_entropy_chunk(&pipeline, chunk, block_size);
+
+            if let Some(pending) = in_flight.replace(next) {
+                self.finish_readback(pending, "entropy", |bytes| {
+                    all_results.extend_from_slice(bytemuck::cast_slice::<u8, f32>(bytes));
+                })?;
+            }
+        }
+
+        if let Some(pending) = in_flight {
+            self.finish_readback(pending, "entropy", |bytes| {
+                all_results.extend_from_slice(bytemuck::cast_slice::<u8, f32>(bytes));
+            })?;
         }
 
         Ok(all_results)
     }
 
-    /// Internal: compute entropy for a single chunk that fits within dispatch limits.
-    fn compute_entropy_chunk(&self, data: &[u8], block_size: u32) -> Result<Vec<f32>> {
+    /// Internal: upload and dispatch entropy for a single chunk that fits within
+    /// dispatch limits. Returns immediately with the readback still in flight.
+    fn submit_entropy_chunk(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        data: &[u8],
+        block_size: u32,
+    ) -> PendingReadback {
         let num_blocks = (data.len() as u32).div_ceil(block_size);
 
         // Pad data to 4-byte alignment for u32 storage buffer
@@ -218,22 +307,6 @@ impl GpuContext {
             mapped_at_creation: false,
         });
 
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("entropy_shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                include_str!("../../../shaders/entropy.wgsl").into(),
-            ),
-        });
-
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("entropy_pipeline"),
-            layout: None,
-            module: &shader,
-            entry_point: Some("main"),
-            compilation_options: Default::default(),
-            cache: None,
-        });
-
         let bind_group_layout = pipeline.get_bind_group_layout(0);
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("entropy_bind_group"),
@@ -263,7 +336,7 @@ impl GpuContext {
                 label: Some("entropy_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&pipeline);
+            pass.set_pipeline(pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
             pass.dispatch_workgroups(num_blocks, 1, 1);
         }
@@ -271,22 +344,43 @@ impl GpuContext {
         encoder.copy_buffer_to_buffer(&output_buf, 0, &staging_buf, 0, output_size);
         self.queue.submit(Some(encoder.finish()));
 
-        let staging_slice = staging_buf.slice(..);
-        let (tx, rx) = std::sync::mpsc::channel();
-        staging_slice.map_async(wgpu::MapMode::Read, move |result| {
-            tx.send(result).unwrap();
-        });
-        self.device.poll(wgpu::Maintain::Wait);
-        rx.recv()
-            .context("entropy readback channel closed")?
-            .context("entropy readback failed")?;
+        PendingReadback::new(staging_buf, vec![input_buf, output_buf, params_buf])
+    }
 
+    /// Wait for an in-flight readback without blocking the queue, then hand the
+    /// mapped bytes to `read`.
+    ///
+    /// Uses `Maintain::Poll` in a loop rather than `Maintain::Wait` so work
+    /// submitted after `pending` keeps running while this chunk is copied out.
+    fn finish_readback(
+        &self,
+        pending: PendingReadback,
+        what: &str,
+        read: impl FnOnce(&[u8]),
+    ) -> Result<()> {
+        loop {
+            match pending.rx.try_recv() {
+                Ok(result) => {
+                    result.with_context(|| format!("{} readback failed", what))?;
+                    break;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    self.device.poll(wgpu::Maintain::Poll);
+                    std::thread::yield_now();
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    anyhow::bail!("{} readback channel closed", what);
+                }
+            }
+        }
+
+        let staging_slice = pending.staging_buf.slice(..);
         let mapped = staging_slice.get_mapped_range();
-        let result: Vec<f32> = bytemuck::cast_slice(&mapped).to_vec();
+        read(&mapped);
         drop(mapped);
-        staging_buf.unmap();
+        pending.staging_buf.unmap();
 
-        Ok(result)
+        Ok(())
     }
 
     /// Classify each block of `block_size` bytes by content type on the GPU.
@@ -438,19 +532,22 @@ impl GpuContext {
         Ok(result)
     }
 
-    /// Scan data for occurrences of a byte pattern (up to 16 bytes).
-    /// Returns a sorted list of byte offsets where the pattern was found.
-    /// Scan `data` for all occurrences of `pattern` (1–16 bytes).
-    /// Processes data in GPU-friendly chunks. Returns sorted match offsets.
-    pub fn scan_pattern(&self, data: &[u8], pattern: &[u8]) -> Result<Vec<u64>> {
-        anyhow::ensure!(
-            !pattern.is_empty() && pattern.len() <= 16,
-            "pattern must be 1-16 bytes, got {}",
-            pattern.len()
-        );
+    /// Scan `data` for all occurrences of `pattern`.
+    /// Processes data in GPU-friendly chunks. Returns sorted match offsets and
+    /// the true match count, which exceeds the stored offsets when a chunk has
+    /// more than `MAX_SCAN_RESULTS` matches.
+    ///
+    /// Patterns longer than `MAX_GPU_PATTERN_LEN` are matched on the CPU
+    /// (`tv_core::scan_pattern_parallel`).
+    pub fn scan_pattern(&self, data: &[u8], pattern: &[u8]) -> Result<PatternScan> {
+        anyhow::ensure!(!pattern.is_empty(), "pattern must not be empty");
+        if pattern.len() > MAX_GPU_PATTERN_LEN {
+            let offsets = tv_core::scan_pattern_parallel(data, pattern);
+            return Ok(PatternScan { total: offsets.len() as u64, offsets });
+        }
 
         if data.len() < pattern.len() {
-            return Ok(vec![]);
+            return Ok(PatternScan { offsets: vec![], total: 0 });
         }
 
         // --- Create shader & pipeline ONCE ---
@@ -482,7 +579,7 @@ impl GpuContext {
         });
 
         // --- Persistent results + staging buffers (reused across chunks) ---
-        let max_results: u32 = 65536;
+        let max_results = MAX_SCAN_RESULTS;
         let results_size = ((1 + max_results) as u64) * 4;
 
         let staging_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
@@ -496,8 +593,13 @@ impl GpuContext {
         const MAX_WORKGROUPS: u32 = 65534;
         const WORKGROUP_SIZE: u32 = 256;
         let chunk_size = (MAX_WORKGROUPS * WORKGROUP_SIZE) as usize;
+        self.ensure_buffer_fits(results_size, "Pattern scan results")?;
+        self.ensure_buffer_fits(chunk_size.min(data.len()) as u64 + 4, "Pattern scan chunk")?;
         let mut all_offsets = Vec::new();
+        let mut total = 0u64;
 
+        // A match starting in the overlap doesn't fit in the previous chunk, so
+        // each match is counted by exactly one chunk
         for chunk_start in (0..data.len()).step_by(chunk_size) {
             let actual_start = if chunk_start > 0 {
                 chunk_start.saturating_sub(pattern.len() - 1)
@@ -507,10 +609,11 @@ impl GpuContext {
             let chunk_end = (chunk_start + chunk_size).min(data.len());
             let chunk = &data[actual_start..chunk_end];
 
-            let offsets = self.scan_pattern_chunk_with(
+            let (offsets, chunk_total) = self.scan_pattern_chunk_with(
                 chunk, pattern, &pipeline, &pattern_buf, &staging_buf,
                 max_results, results_size,
             )?;
+            total += chunk_total;
 
             for offset in offsets {
                 let global_offset = actual_start as u64 + offset;
@@ -522,10 +625,11 @@ impl GpuContext {
 
         all_offsets.sort();
         all_offsets.dedup();
-        Ok(all_offsets)
+        Ok(PatternScan { offsets: all_offsets, total })
     }
 
     /// Run a single scan chunk, reusing the pre-created pipeline and buffers.
+    /// Returns the stored offsets and the chunk's full match count.
     fn scan_pattern_chunk_with(
         &self,
         data: &[u8],
@@ -535,7 +639,7 @@ impl GpuContext {
         staging_buf: &wgpu::Buffer,
         max_results: u32,
         results_size: u64,
-    ) -> Result<Vec<u64>> {
+    ) -> Result<(Vec<u64>, u64)> {
         // Pad data to 4-byte alignment
         let padded_len = (data.len() + 3) & !3;
         let mut padded_data = data.to_vec();
@@ -633,6 +737,7 @@ impl GpuContext {
         let mapped = staging_slice.get_mapped_range();
         let raw: &[u32] = bytemuck::cast_slice(&mapped);
 
+        let total = raw[0] as u64;
         let hit_count = raw[0].min(max_results) as usize;
         let mut offsets: Vec<u64> = raw[1..=hit_count]
             .iter()
@@ -649,7 +754,24 @@ impl GpuContext {
         self.device.poll(wgpu::Maintain::Wait);
 
         offsets.sort();
-        Ok(offsets)
+        Ok((offsets, total))
+    }
+}
+
+/// Result of a single-pattern scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternScan {
+    /// Sorted match offsets. At most `MAX_SCAN_RESULTS` are stored per
+    /// dispatch chunk; the rest are only counted.
+    pub offsets: Vec<u64>,
+    /// Number of matches, including those that were not stored.
+    pub total: u64,
+}
+
+impl PatternScan {
+    /// Whether some matches were counted but not stored.
+    pub fn is_truncated(&self) -> bool {
+        self.total > self.offsets.len() as u64
     }
 }
 
@@ -662,30 +784,130 @@ pub struct MultiPatternMatch {
     pub offset: u64,
 }
 
+/// A submitted dispatch whose staging buffer is being mapped for readback.
+struct PendingReadback {
+    staging_buf: wgpu::Buffer,
+    rx: std::sync::mpsc::Receiver<Result<(), wgpu::BufferAsyncError>>,
+    /// Per-dispatch buffers that must outlive the GPU work reading them.
+    _buffers: Vec<wgpu::Buffer>,
+}
+
+impl PendingReadback {
+    /// Request a read mapping of `staging_buf`. Must be called after the
+    /// commands writing it have been submitted.
+    fn new(staging_buf: wgpu::Buffer, buffers: Vec<wgpu::Buffer>) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        staging_buf.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        Self {
+            staging_buf,
+            rx,
+            _buffers: buffers,
+        }
+    }
+}
+
+/// GPU resources shared by every chunk of one `scan_multi_pattern` call.
+struct MultiPatternPass<'a> {
+    pipeline: &'a wgpu::ComputePipeline,
+    patterns_buf: &'a wgpu::Buffer,
+    meta_buf: &'a wgpu::Buffer,
+    num_patterns: u32,
+    max_pattern_len: u32,
+}
+
+/// Maximum hits recorded per multi-pattern chunk.
+const MULTI_SCAN_MAX_RESULTS: u32 = 65536;
+
 impl GpuContext {
     /// Scan data for multiple patterns simultaneously (e.g., file signatures).
     /// All patterns are checked at every position in a single GPU pass.
     /// Returns matches sorted by offset.
     ///
     /// This amortizes the PCIe transfer cost across all patterns, making it
-    /// much faster than running N separate scans for N patterns.
+    /// much faster than running N separate scans for N patterns. Chunks are
+    /// double-buffered like [`GpuContext::compute_entropy`].
     pub fn scan_multi_pattern(&self, data: &[u8], patterns: &[&[u8]]) -> Result<Vec<MultiPatternMatch>> {
         if patterns.is_empty() || data.is_empty() {
             return Ok(vec![]);
         }
 
-        // Find max pattern length and validate
+        // Find max pattern length and validate (the shader skips patterns
+        // running past the end of the data, so short data still matches short patterns)
         let max_pattern_len = patterns.iter().map(|p| p.len()).max().unwrap_or(0);
-        if max_pattern_len == 0 || data.len() < max_pattern_len {
+        let min_pattern_len = patterns.iter().map(|p| p.len()).min().unwrap_or(0);
+        if min_pattern_len == 0 || data.len() < min_pattern_len {
             return Ok(vec![]);
         }
 
+        // Build concatenated pattern buffer with metadata
+        let mut pattern_bytes = Vec::new();
+        let mut pattern_meta: Vec<u32> = Vec::new(); // [offset, len] pairs
+
+        for pattern in patterns {
+            let offset = pattern_bytes.len() as u32;
+            let len = pattern.len() as u32;
+            pattern_meta.push(offset);
+            pattern_meta.push(len);
+            pattern_bytes.extend_from_slice(pattern);
+            // Pad each pattern to 4-byte alignment for cleaner reads
+            while pattern_bytes.len() % 4 != 0 {
+                pattern_bytes.push(0);
+            }
+        }
+
+        let patterns_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("multi_scan_patterns"),
+            contents: &pattern_bytes,
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let meta_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("multi_scan_meta"),
+            contents: bytemuck::cast_slice(&pattern_meta),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        // Shader and pipeline
+        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("multi_scan_shader"),
+            source: wgpu::ShaderSource::Wgsl(
+                include_str!("../../../shaders/multi_pattern.wgsl").into(),
+            ),
+        });
+
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("multi_scan_pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let pass = MultiPatternPass {
+            pipeline: &pipeline,
+            patterns_buf: &patterns_buf,
+            meta_buf: &meta_buf,
+            num_patterns: patterns.len() as u32,
+            max_pattern_len: max_pattern_len as u32,
+        };
+
         // Process in chunks to stay within GPU dispatch limits
         const MAX_WORKGROUPS: u32 = 65534;
         const WORKGROUP_SIZE: u32 = 256;
         let chunk_size = (MAX_WORKGROUPS * WORKGROUP_SIZE) as usize;
+        self.ensure_buffer_fits(chunk_size.min(data.len()) as u64 + 4, "Multi-pattern scan chunk")?;
+
+        // Pattern tables plus two chunks in flight (input, results, staging, params)
+        let results_size = (1 + MULTI_SCAN_MAX_RESULTS as u64 * 2) * 4;
+        let input_size = ((chunk_size + max_pattern_len).min(data.len()) as u64 + 3) & !3;
+        let per_chunk = input_size + 2 * results_size + 16;
+        record_allocation("Multi-pattern scan", (pattern_bytes.len() + pattern_meta.len() * 4) as u64 + 2 * per_chunk);
 
         let mut all_matches = Vec::new();
+        let mut in_flight: Option<(usize, PendingReadback)> = None;
 
         for chunk_start in (0..data.len()).step_by(chunk_size) {
             // Overlap with previous chunk to catch patterns at boundaries
@@ -697,14 +919,14 @@ impl GpuContext {
             let chunk_end = (chunk_start + chunk_size).min(data.len());
             let chunk = &data[actual_start..chunk_end];
 
-            let mut chunk_matches = self.scan_multi_pattern_chunk(chunk, patterns)?;
-
-            // Adjust offsets to global positions and deduplicate
-            for m in &mut chunk_matches {
-                m.offset += actual_start as u64;
+            let next = self.submit_multi_pattern_chunk(&pass, chunk);
+            if let Some((start, pending)) = in_flight.replace((actual_start, next)) {
+                self.read_multi_pattern_chunk(pending, start, &mut all_matches)?;
             }
+        }
 
-            all_matches.extend(chunk_matches);
+        if let Some((start, pending)) = in_flight {
+            self.read_multi_pattern_chunk(pending, start, &mut all_matches)?;
         }
 
         // Sort by offset and deduplicate
@@ -714,56 +936,47 @@ impl GpuContext {
         Ok(all_matches)
     }
 
-    /// Internal: scan a single chunk for multiple patterns.
-    fn scan_multi_pattern_chunk(&self, data: &[u8], patterns: &[&[u8]]) -> Result<Vec<MultiPatternMatch>> {
-        // Build concatenated pattern buffer with metadata
-        let mut pattern_bytes = Vec::new();
-        let mut pattern_meta: Vec<u32> = Vec::new(); // [offset, len] pairs
-
-        for pattern in patterns {
-            let offset = pattern_bytes.len() as u32;
-            let len = pattern.len() as u32;
-            pattern_meta.push(offset);
-            pattern_meta.push(len);
-            pattern_bytes.extend_from_slice(pattern);
-            // Pad each pattern to 4-byte alignment for cleaner reads
-            while pattern_bytes.len() % 4 != 0 {
-                pattern_bytes.push(0);
-            }
-        }
-
-        // Pad pattern buffer total to 4-byte alignment
-        while pattern_bytes.len() % 4 != 0 {
-            pattern_bytes.push(0);
+    /// Scan the `region` of `data` for matches *starting* inside it.
+    ///
+    /// The scanned window runs past the end of the region by the longest
+    /// pattern, so a match straddling the boundary with the next region is
+    /// reported by exactly one of the two. Offsets are relative to `data`.
+    pub fn scan_multi_pattern_region(
+        &self,
+        data: &[u8],
+        region: tv_core::FileRegion,
+        patterns: &[&[u8]],
+    ) -> Result<Vec<MultiPatternMatch>> {
+        let max_pattern_len = patterns.iter().map(|p| p.len()).max().unwrap_or(0) as u64;
+        let data_len = data.len() as u64;
+        let start = region.offset.min(data_len);
+        let end = region.end().min(data_len);
+        let window_end = end.saturating_add(max_pattern_len.saturating_sub(1)).min(data_len);
+
+        let mut matches = self.scan_multi_pattern(&data[start as usize..window_end as usize], patterns)?;
+        matches.retain(|m| start + m.offset < end);
+        for m in &mut matches {
+            m.offset += start;
         }
+        Ok(matches)
+    }
 
+    /// Internal: upload and dispatch a single chunk for multiple patterns.
+    /// Returns immediately with the readback still in flight.
+    fn submit_multi_pattern_chunk(&self, pass: &MultiPatternPass, data: &[u8]) -> PendingReadback {
         // Pad data to 4-byte alignment
         let padded_len = (data.len() + 3) & !3;
         let mut padded_data = data.to_vec();
         padded_data.resize(padded_len, 0);
 
-        // Create buffers
         let input_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("multi_scan_input"),
             contents: &padded_data,
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let patterns_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("multi_scan_patterns"),
-            contents: &pattern_bytes,
-            usage: wgpu::BufferUsages::STORAGE,
-        });
-
-        let meta_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("multi_scan_meta"),
-            contents: bytemuck::cast_slice(&pattern_meta),
-            usage: wgpu::BufferUsages::STORAGE,
-        });
-
         // Results: [count, (pattern_idx, offset), (pattern_idx, offset), ...]
-        let max_results: u32 = 65536;
-        let results_size = ((1 + max_results * 2) as u64) * 4;
+        let results_size = ((1 + MULTI_SCAN_MAX_RESULTS * 2) as u64) * 4;
 
         let results_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("multi_scan_results"),
@@ -788,14 +1001,11 @@ impl GpuContext {
             max_pattern_len: u32,
         }
 
-        // Calculate max pattern length for shared memory sizing in shader
-        let max_pattern_len = patterns.iter().map(|p| p.len()).max().unwrap_or(0) as u32;
-
         let params = Params {
             total_bytes: data.len() as u32,
-            num_patterns: patterns.len() as u32,
-            max_results,
-            max_pattern_len,
+            num_patterns: pass.num_patterns,
+            max_results: MULTI_SCAN_MAX_RESULTS,
+            max_pattern_len: pass.max_pattern_len,
         };
 
         let params_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -804,24 +1014,7 @@ impl GpuContext {
             usage: wgpu::BufferUsages::UNIFORM,
         });
 
-        // Shader and pipeline
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("multi_scan_shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                include_str!("../../../shaders/multi_pattern.wgsl").into(),
-            ),
-        });
-
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("multi_scan_pipeline"),
-            layout: None,
-            module: &shader,
-            entry_point: Some("main"),
-            compilation_options: Default::default(),
-            cache: None,
-        });
-
-        let bind_group_layout = pipeline.get_bind_group_layout(0);
+        let bind_group_layout = pass.pipeline.get_bind_group_layout(0);
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("multi_scan_bind_group"),
             layout: &bind_group_layout,
@@ -840,11 +1033,11 @@ impl GpuContext {
                 },
                 wgpu::BindGroupEntry {
                     binding: 3,
-                    resource: patterns_buf.as_entire_binding(),
+                    resource: pass.patterns_buf.as_entire_binding(),
                 },
                 wgpu::BindGroupEntry {
                     binding: 4,
-                    resource: meta_buf.as_entire_binding(),
+                    resource: pass.meta_buf.as_entire_binding(),
                 },
             ],
         });
@@ -854,57 +1047,43 @@ impl GpuContext {
         });
 
         {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("multi_scan_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&pipeline);
-            pass.set_bind_group(0, &bind_group, &[]);
+            cpass.set_pipeline(pass.pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
             let workgroups = (data.len() as u32).div_ceil(256).min(65535);
-            pass.dispatch_workgroups(workgroups, 1, 1);
+            cpass.dispatch_workgroups(workgroups, 1, 1);
         }
 
         encoder.copy_buffer_to_buffer(&results_buf, 0, &staging_buf, 0, results_size);
         self.queue.submit(Some(encoder.finish()));
 
-        // Read back results
-        let staging_slice = staging_buf.slice(..);
-        let (tx, rx) = std::sync::mpsc::channel();
-        staging_slice.map_async(wgpu::MapMode::Read, move |result| {
-            let _ = tx.send(result);
-        });
-        self.device.poll(wgpu::Maintain::Wait);
-        rx.recv()
-            .context("multi_scan readback channel closed")?
-            .context("multi_scan readback failed")?;
-
-        let mapped = staging_slice.get_mapped_range();
-        let raw: &[u32] = bytemuck::cast_slice(&mapped);
-
-        let hit_count = raw[0].min(max_results) as usize;
-        let mut matches = Vec::with_capacity(hit_count);
-
-        for i in 0..hit_count {
-            let base = 1 + i * 2;
-            matches.push(MultiPatternMatch {
-                pattern_idx: raw[base],
-                offset: raw[base + 1] as u64,
-            });
-        }
-
-        drop(mapped);
-        staging_buf.unmap();
-
-        // Clean up
-        drop(input_buf);
-        drop(patterns_buf);
-        drop(meta_buf);
-        drop(results_buf);
-        drop(params_buf);
-        self.device.poll(wgpu::Maintain::Wait);
+        PendingReadback::new(staging_buf, vec![input_buf, results_buf, params_buf])
+    }
 
-        matches.sort_by_key(|m| (m.offset, m.pattern_idx));
-        Ok(matches)
+    /// Internal: wait for a chunk submitted by `submit_multi_pattern_chunk` and
+    /// append its hits, rebased by `chunk_start`, to `out`.
+    fn read_multi_pattern_chunk(
+        &self,
+        pending: PendingReadback,
+        chunk_start: usize,
+        out: &mut Vec<MultiPatternMatch>,
+    ) -> Result<()> {
+        self.finish_readback(pending, "multi_scan", |bytes| {
+            let raw: &[u32] = bytemuck::cast_slice(bytes);
+            let hit_count = raw[0].min(MULTI_SCAN_MAX_RESULTS) as usize;
+            out.reserve(hit_count);
+
+            for i in 0..hit_count {
+                let base = 1 + i * 2;
+                out.push(MultiPatternMatch {
+                    pattern_idx: raw[base],
+                    offset: raw[base + 1] as u64 + chunk_start as u64,
+                });
+            }
+        })
     }
 
     /// Generate a Hilbert curve visualization of file data.
@@ -914,7 +1093,7 @@ impl GpuContext {
     ///
     /// # Arguments
     /// * `file_size` - Total file size in bytes
-    /// * `entropy` - Optional per-block entropy data (block_size=256)
+    /// * `entropy` - Optional entropy per Hilbert cell, in curve order
     /// * `classification` - Optional per-block classification data
     /// * `raw_data` - Optional raw file data (for byte value mode)
     /// * `texture_size` - Output texture size (must be power of 2: 256, 512, 1024)
@@ -950,10 +1129,21 @@ impl GpuContext {
         let class_slice = classification.unwrap_or(&empty_class);
 
         // Pad data to 4-byte alignment (sampled data is already small - one byte per pixel)
+        // Per-block classes grow with the file (4 bytes per block)
+        self.ensure_buffer_fits(entropy_slice.len() as u64 * 4, "Hilbert entropy input")?;
+        self.ensure_buffer_fits(class_slice.len() as u64 * 4, "Hilbert classification input")?;
+
         let padded_data_len = ((data_slice.len() + 3) / 4) * 4;
         let mut padded_data = data_slice.to_vec();
         padded_data.resize(padded_data_len.max(4), 0);
 
+        // Inputs (empty ones get a 16-byte placeholder), params, output and staging
+        let output_size = (texture_size * texture_size * 4) as u64; // RGBA8
+        let input_bytes = padded_data.len() as u64
+            + (entropy_slice.len().max(4) * 4) as u64
+            + (class_slice.len().max(4) * 4) as u64;
+        record_allocation("Hilbert curve", input_bytes + 16 + 2 * output_size);
+
         // Create buffers
         let input_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("hilbert_input"),
@@ -1009,7 +1199,6 @@ impl GpuContext {
         });
 
         // Output texture buffer
-        let output_size = (texture_size * texture_size * 4) as u64; // RGBA8
         let output_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("hilbert_output"),
             size: output_size,
@@ -1136,19 +1325,142 @@ impl GpuContext {
         data_b: &[u8],
         max_diffs: usize,
     ) -> Result<Vec<u64>> {
+        Ok(self.compute_diff_counted(data_a, data_b, max_diffs)?.0)
+    }
+
+    /// Like [`GpuContext::compute_diff`], but also returns the exact number of
+    /// differing bytes, even when more than `max_diffs` offsets exist.
+    pub fn compute_diff_counted(
+        &self,
+        data_a: &[u8],
+        data_b: &[u8],
+        max_diffs: usize,
+    ) -> Result<(Vec<u64>, u64)> {
+        let mut diff_offsets = Vec::new();
+        let mut total = 0u64;
+        self.diff_flags_with(data_a, data_b, |diff_flags, chunk_start, chunk_len| {
+            // Extract diff offsets from flags
+            for (word_idx, &flags) in diff_flags.iter().enumerate() {
+                if flags == 0 {
+                    continue;
+                }
+                total += (flags & 0xF).count_ones() as u64;
+                if diff_offsets.len() >= max_diffs {
+                    continue;
+                }
+                let word_offset = (word_idx * 4) as u64;
+                for bit in 0..4 {
+                    if (flags & (1 << bit)) != 0 {
+                        let offset = word_offset + bit as u64;
+                        if offset < chunk_len as u64 && diff_offsets.len() < max_diffs {
+                            diff_offsets.push(chunk_start as u64 + offset);
+                        }
+                    }
+                }
+            }
+        })?;
+        Ok((diff_offsets, total))
+    }
+
+    /// Count differing bytes between two buffers without collecting offsets.
+    ///
+    /// Cheap compared to [`GpuContext::compute_diff`]: the shader already emits
+    /// per-word flags, so this is a popcount over the readback.
+    pub fn count_diff(&self, data_a: &[u8], data_b: &[u8]) -> Result<u64> {
+        let mut total = 0u64;
+        self.diff_flags_with(data_a, data_b, |diff_flags, _, _| {
+            total += diff_flags
+                .iter()
+                .map(|&flags| (flags & 0xF).count_ones() as u64)
+                .sum::<u64>();
+        })?;
+        Ok(total)
+    }
+
+    /// Bytes compared per diff dispatch: bounded by the storage binding size
+    /// and by the 65535-workgroup dispatch limit (one u32 per invocation).
+    fn diff_chunk_bytes(&self) -> usize {
+        const MAX_WORKGROUPS: u64 = 65535;
+        const WORKGROUP_SIZE: u64 = 256;
+        let dispatch_max = MAX_WORKGROUPS * WORKGROUP_SIZE * 4;
+        (self.max_storage_bytes().min(dispatch_max) & !3) as usize
+    }
+
+    /// Internal: run the diff shader over the common prefix of both buffers,
+    /// chunk by chunk, and hand each chunk's per-word flags (bit N set = byte N
+    /// of the word differs) to `visit` along with the chunk's start and length.
+    fn diff_flags_with(
+        &self,
+        data_a: &[u8],
+        data_b: &[u8],
+        visit: impl FnMut(&[u32], usize, usize),
+    ) -> Result<()> {
+        self.diff_flags_chunked(data_a, data_b, self.diff_chunk_bytes(), visit)
+    }
+
+    fn diff_flags_chunked(
+        &self,
+        data_a: &[u8],
+        data_b: &[u8],
+        chunk_bytes: usize,
+        mut visit: impl FnMut(&[u32], usize, usize),
+    ) -> Result<()> {
         let compare_len = data_a.len().min(data_b.len());
         if compare_len == 0 {
-            return Ok(vec![]);
+            return Ok(());
+        }
+        anyhow::ensure!(chunk_bytes >= 4, "diff chunk size must be at least one word");
+
+        // Load shader
+        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("diff_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../../shaders/diff.wgsl").into()),
+        });
+
+        // Create pipeline
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("diff_pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        for chunk_start in (0..compare_len).step_by(chunk_bytes) {
+            let chunk_end = (chunk_start + chunk_bytes).min(compare_len);
+            let flags = self.diff_chunk(
+                &pipeline,
+                &data_a[chunk_start..chunk_end],
+                &data_b[chunk_start..chunk_end],
+            )?;
+            visit(&flags, chunk_start, chunk_end - chunk_start);
         }
+        Ok(())
+    }
+
+    /// Run the diff shader over one chunk of equal-length buffers and read
+    /// back the per-word flags.
+    fn diff_chunk(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        chunk_a: &[u8],
+        chunk_b: &[u8],
+    ) -> Result<Vec<u32>> {
+        let compare_len = chunk_a.len();
 
         // Pad to 4-byte alignment
         let padded_len = ((compare_len + 3) / 4) * 4;
         let word_count = padded_len / 4;
 
-        let mut padded_a = data_a[..compare_len].to_vec();
+        self.ensure_buffer_fits(padded_len as u64, "Diff chunk")?;
+        // Both inputs, the flags output and its staging copy, and the params
+        record_allocation("Diff", padded_len as u64 * 4 + 16);
+
+        let mut padded_a = chunk_a.to_vec();
         padded_a.resize(padded_len, 0);
 
-        let mut padded_b = data_b[..compare_len].to_vec();
+        let mut padded_b = chunk_b.to_vec();
         padded_b.resize(padded_len, 0);
 
         // Create buffers
@@ -1203,22 +1515,6 @@ impl GpuContext {
             mapped_at_creation: false,
         });
 
-        // Load shader
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("diff_shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../../../shaders/diff.wgsl").into()),
-        });
-
-        // Create pipeline
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("diff_pipeline"),
-            layout: None,
-            module: &shader,
-            entry_point: Some("main"),
-            compilation_options: Default::default(),
-            cache: None,
-        });
-
         // Create bind group
         let bind_group_layout = pipeline.get_bind_group_layout(0);
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -1254,7 +1550,7 @@ impl GpuContext {
                 label: Some("diff_pass"),
                 timestamp_writes: None,
             });
-            pass.set_pipeline(&pipeline);
+            pass.set_pipeline(pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
 
             // 256 threads per workgroup, each handles one u32 (4 bytes)
@@ -1277,29 +1573,7 @@ impl GpuContext {
             .context("diff readback failed")?;
 
         let mapped = staging_slice.get_mapped_range();
-        let diff_flags: &[u32] = bytemuck::cast_slice(&mapped);
-
-        // Extract diff offsets from flags
-        let mut diff_offsets = Vec::new();
-        for (word_idx, &flags) in diff_flags.iter().enumerate() {
-            if flags != 0 {
-                let base_offset = (word_idx * 4) as u64;
-                for bit in 0..4 {
-                    if (flags & (1 << bit)) != 0 {
-                        let offset = base_offset + bit as u64;
-                        if offset < compare_len as u64 {
-                            diff_offsets.push(offset);
-                            if diff_offsets.len() >= max_diffs {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-            if diff_offsets.len() >= max_diffs {
-                break;
-            }
-        }
+        let result = bytemuck::cast_slice(&mapped).to_vec();
 
         drop(mapped);
         staging_buf.unmap();
@@ -1311,7 +1585,7 @@ impl GpuContext {
         drop(output_buf);
         self.device.poll(wgpu::Maintain::Wait);
 
-        Ok(diff_offsets)
+        Ok(result)
     }
 }
 
@@ -1432,6 +1706,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_entropy_spans_multiple_chunks() {
+        let ctx = create_context();
+        // Just over two dispatch chunks at block_size=256, with a distinct
+        // entropy level in each chunk so misordered readbacks would show up.
+        let chunk_bytes = 65535 * 256;
+        let mut data = vec![0u8; chunk_bytes * 2 + 512];
+        for (i, b) in data[chunk_bytes..chunk_bytes * 2].iter_mut().enumerate() {
+            *b = (i % 2) as u8;
+        }
+        for (i, b) in data[chunk_bytes * 2..].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+
+        let result = ctx.compute_entropy(&data, 256).unwrap();
+        assert_eq!(result.len(), 65535 * 2 + 2);
+        assert!(result[0].abs() < 0.01);
+        assert!(result[65534].abs() < 0.01);
+        assert!((result[65535] - 1.0).abs() < 0.01);
+        assert!((result[65535 * 2 - 1] - 1.0).abs() < 0.01);
+        assert!((result[65535 * 2] - 8.0).abs() < 0.01);
+        assert!((result[65535 * 2 + 1] - 8.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_entropy_larger_block_size() {
         let ctx = create_context();
@@ -1461,7 +1759,7 @@ mod tests {
         data[500] = 0xDE;
         data[501] = 0xAD;
 
-        let offsets = ctx.scan_pattern(&data, &[0xDE, 0xAD]).unwrap();
+        let offsets = ctx.scan_pattern(&data, &[0xDE, 0xAD]).unwrap().offsets;
         assert_eq!(offsets, vec![100, 500]);
     }
 
@@ -1469,7 +1767,7 @@ mod tests {
     fn test_scan_pattern_not_found() {
         let ctx = create_context();
         let data = vec![0u8; 512];
-        let offsets = ctx.scan_pattern(&data, &[0xFF, 0xFE]).unwrap();
+        let offsets = ctx.scan_pattern(&data, &[0xFF, 0xFE]).unwrap().offsets;
         assert!(offsets.is_empty());
     }
 
@@ -1484,7 +1782,7 @@ mod tests {
         data[254] = 0xAB;
         data[255] = 0xCD;
 
-        let offsets = ctx.scan_pattern(&data, &[0xAB, 0xCD]).unwrap();
+        let offsets = ctx.scan_pattern(&data, &[0xAB, 0xCD]).unwrap().offsets;
         assert_eq!(offsets, vec![0, 254]);
     }
 
@@ -1496,10 +1794,37 @@ mod tests {
         data[20] = 0xFF;
         data[30] = 0xFF;
 
-        let offsets = ctx.scan_pattern(&data, &[0xFF]).unwrap();
+        let offsets = ctx.scan_pattern(&data, &[0xFF]).unwrap().offsets;
         assert_eq!(offsets, vec![10, 20, 30]);
     }
 
+    #[test]
+    fn test_scan_pattern_counts_past_result_cap() {
+        let ctx = create_context();
+        // 100k matches of a single byte, more than the results buffer holds
+        let data: Vec<u8> = (0..200_000).map(|i| if i % 2 == 0 { 0xAA } else { 0 }).collect();
+
+        let scan = ctx.scan_pattern(&data, &[0xAA]).unwrap();
+        assert_eq!(scan.total, 100_000);
+        assert_eq!(scan.offsets.len(), MAX_SCAN_RESULTS as usize);
+        assert!(scan.is_truncated());
+        assert!(scan.offsets.iter().all(|&o| o % 2 == 0 && o < 200_000));
+    }
+
+    #[test]
+    fn test_scan_long_pattern_uses_cpu() {
+        let ctx = create_context();
+        let pattern = b"This program cannot be run in DOS mode";
+        let mut data = vec![0u8; 4096];
+        data[100..100 + pattern.len()].copy_from_slice(pattern);
+        data[4096 - pattern.len()..].copy_from_slice(pattern);
+
+        let scan = ctx.scan_pattern(&data, pattern).unwrap();
+        assert_eq!(scan.offsets, vec![100, (4096 - pattern.len()) as u64]);
+        assert_eq!(scan.total, 2);
+        assert!(ctx.scan_pattern(&data, &[]).is_err());
+    }
+
     // --- Classification tests ---
 
     #[test]
@@ -1587,21 +1912,83 @@ mod tests {
             let data = file.slice(tv_core::FileRegion::new(0, file.len()));
 
             // ELF magic at offset 0
-            let offsets = ctx.scan_pattern(data, &[0x7F, 0x45, 0x4C, 0x46]).unwrap();
+            let offsets = ctx.scan_pattern(data, &[0x7F, 0x45, 0x4C, 0x46]).unwrap().offsets;
             assert_eq!(offsets, vec![0]);
 
             // JPEG magic at offset 0x100
-            let offsets = ctx.scan_pattern(data, &[0xFF, 0xD8, 0xFF, 0xE0]).unwrap();
+            let offsets = ctx.scan_pattern(data, &[0xFF, 0xD8, 0xFF, 0xE0]).unwrap().offsets;
             assert_eq!(offsets, vec![0x100]);
 
             // PNG magic at offset 0x200
-            let offsets = ctx.scan_pattern(data, &[0x89, 0x50, 0x4E, 0x47]).unwrap();
+            let offsets = ctx.scan_pattern(data, &[0x89, 0x50, 0x4E, 0x47]).unwrap().offsets;
             assert_eq!(offsets, vec![0x200]);
         }
     }
 
     // --- Multi-pattern scan tests ---
 
+    #[test]
+    fn test_multi_pattern_spans_multiple_chunks() {
+        let ctx = create_context();
+        // Three dispatch chunks; one hit per chunk plus one straddling a boundary.
+        let chunk_size = 65534 * 256;
+        let mut data = vec![0u8; chunk_size * 2 + 4096];
+        data[10..14].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        data[chunk_size - 2..chunk_size + 2].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        data[chunk_size + 100..chunk_size + 104].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        data[chunk_size * 2 + 7..chunk_size * 2 + 11].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let patterns: Vec<&[u8]> = vec![&[0xDE, 0xAD, 0xBE, 0xEF]];
+        let matches = ctx.scan_multi_pattern(&data, &patterns).unwrap();
+        let offsets: Vec<u64> = matches.iter().map(|m| m.offset).collect();
+
+        assert_eq!(
+            offsets,
+            vec![
+                10,
+                (chunk_size - 2) as u64,
+                (chunk_size + 100) as u64,
+                (chunk_size * 2 + 7) as u64,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_pattern_regions_report_boundary_hits_once() {
+        let ctx = create_context();
+        // A 16-byte signature straddling the 64 MB scan boundary, one ending
+        // exactly at it and one starting exactly at it
+        let region_len: usize = 64 * 1024 * 1024;
+        let sig: &[u8] = b"SIGNATURE-16-BYT";
+        let mut data = vec![0u8; region_len + 4096];
+        data[region_len - 8..region_len + 8].copy_from_slice(sig);
+        data[region_len - 40..region_len - 24].copy_from_slice(sig);
+        data[region_len + 64..region_len + 80].copy_from_slice(sig);
+        data[region_len - 24..region_len - 16].copy_from_slice(b"END-SIGN");
+        data[region_len + 4096 - 2..].copy_from_slice(b"EN");
+
+        let patterns: Vec<&[u8]> = vec![sig, b"END-SIGN", b"EN"];
+        let mut offsets = Vec::new();
+        for start in (0..data.len()).step_by(region_len) {
+            let region = tv_core::FileRegion::new(start as u64, region_len as u64);
+            for m in ctx.scan_multi_pattern_region(&data, region, &patterns).unwrap() {
+                assert!(m.offset >= region.offset && m.offset < region.end());
+                offsets.push((m.offset, m.pattern_idx));
+            }
+        }
+        offsets.sort();
+
+        let expected = vec![
+            ((region_len - 40) as u64, 0),
+            ((region_len - 24) as u64, 1),
+            ((region_len - 24) as u64, 2),
+            ((region_len - 8) as u64, 0),
+            ((region_len + 64) as u64, 0),
+            ((region_len + 4096 - 2) as u64, 2),
+        ];
+        assert_eq!(offsets, expected);
+    }
+
     #[test]
     fn test_multi_pattern_empty_patterns() {
         let ctx = create_context();
@@ -1715,4 +2102,62 @@ mod tests {
         let matches = ctx.scan_multi_pattern(&data, &patterns).unwrap();
         assert!(matches.is_empty());
     }
+    #[test]
+    fn test_diff_counted_exact_total_when_capped() {
+        let ctx = create_context();
+        let a = vec![0u8; 1024];
+        let mut b = a.clone();
+        for i in (0..1024).step_by(3) {
+            b[i] = 0xFF;
+        }
+        let expected = (0..1024).step_by(3).count() as u64;
+
+        let (offsets, total) = ctx.compute_diff_counted(&a, &b, 10).unwrap();
+        assert_eq!(offsets, vec![0, 3, 6, 9, 12, 15, 18, 21, 24, 27]);
+        assert_eq!(total, expected);
+        assert_eq!(ctx.count_diff(&a, &b).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_diff_chunks_match_single_pass() {
+        let ctx = create_context();
+        assert!(ctx.max_storage_bytes() >= wgpu::Limits::default().max_storage_buffer_binding_size as u64);
+        assert!(ctx.ensure_buffer_fits(ctx.max_storage_bytes() + 1, "Test").unwrap_err().to_string().contains("at most"));
+
+        let a: Vec<u8> = (0..10_003u32).map(|i| (i % 251) as u8).collect();
+        let mut b = a.clone();
+        for i in (5..10_003).step_by(997) {
+            b[i] ^= 0x5A;
+        }
+        let expected = ctx.compute_diff(&a, &b, usize::MAX).unwrap();
+        assert_eq!(expected.len(), 11);
+
+        // Chunks of 1 KB, with a short last chunk that ends mid-word
+        let mut offsets = Vec::new();
+        let mut chunks = 0;
+        ctx.diff_flags_chunked(&a, &b, 1024, |flags, start, len| {
+            chunks += 1;
+            for (word, &f) in flags.iter().enumerate() {
+                for bit in 0..4 {
+                    if f & (1 << bit) != 0 && word * 4 + bit < len {
+                        offsets.push((start + word * 4 + bit) as u64);
+                    }
+                }
+            }
+        })
+        .unwrap();
+        assert_eq!(chunks, 10);
+        assert_eq!(offsets, expected);
+    }
+
+    #[test]
+    fn test_diff_count_ignores_padding() {
+        let ctx = create_context();
+        // 7 bytes: last word is half padding.
+        let a = [1u8, 2, 3, 4, 5, 6, 7];
+        let b = [1u8, 0, 3, 4, 5, 6, 0];
+        assert_eq!(ctx.count_diff(&a, &b).unwrap(), 2);
+        assert_eq!(ctx.compute_diff(&a, &b, 100).unwrap(), vec![1, 6]);
+        assert_eq!(ctx.count_diff(&a, &[]).unwrap(), 0);
+    }
 }
@@ -1,3 +1,5 @@
 mod context;
+mod memory;
 
-pub use context::GpuContext;
+pub use context::{GpuContext, PatternScan, MAX_GPU_PATTERN_LEN, MAX_SCAN_RESULTS};
+pub use memory::{memory_usage, reset_memory_usage, set_memory_budget, take_budget_warning, OperationMemory};
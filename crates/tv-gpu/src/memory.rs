@@ -0,0 +1,117 @@
+//! Approximate GPU memory accounting per operation.
+//!
+//! Operations report the combined size of the buffers they are about to
+//! create for one dispatch. Every background job builds its own
+//! `GpuContext`, so the figures are kept process-wide. An optional budget
+//! turns oversized dispatches into a warning before the allocation happens,
+//! which on low-VRAM devices is otherwise a silent failure.
+
+use std::sync::{Mutex, OnceLock};
+
+/// Buffer usage of one kind of GPU operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationMemory {
+    pub operation: &'static str,
+    /// Buffers of the latest dispatch (bytes).
+    pub last_bytes: u64,
+    /// Largest dispatch so far (bytes).
+    pub peak_bytes: u64,
+    /// Dispatches that went over the budget.
+    pub over_budget: u32,
+}
+
+#[derive(Debug, Default)]
+struct Tracker {
+    budget: Option<u64>,
+    operations: Vec<OperationMemory>,
+    /// Latest budget warning not yet shown to the user.
+    warning: Option<String>,
+}
+
+impl Tracker {
+    fn record(&mut self, operation: &'static str, bytes: u64) {
+        let index = match self.operations.iter().position(|op| op.operation == operation) {
+            Some(index) => index,
+            None => {
+                self.operations.push(OperationMemory { operation, last_bytes: 0, peak_bytes: 0, over_budget: 0 });
+                self.operations.len() - 1
+            }
+        };
+        let op = &mut self.operations[index];
+        op.last_bytes = bytes;
+        op.peak_bytes = op.peak_bytes.max(bytes);
+
+        if let Some(budget) = self.budget.filter(|&budget| bytes > budget) {
+            op.over_budget += 1;
+            let warning = format!(
+                "{} needs about {:.1} MB of GPU memory, over the {:.1} MB budget",
+                operation,
+                bytes as f64 / (1024.0 * 1024.0),
+                budget as f64 / (1024.0 * 1024.0)
+            );
+            log::warn!("{}", warning);
+            self.warning = Some(warning);
+        }
+    }
+}
+
+fn tracker() -> &'static Mutex<Tracker> {
+    static TRACKER: OnceLock<Mutex<Tracker>> = OnceLock::new();
+    TRACKER.get_or_init(Mutex::default)
+}
+
+/// Record that `operation` is about to create `bytes` of buffers for one
+/// dispatch, warning if that exceeds the budget. The dispatch still runs.
+pub(crate) fn record_allocation(operation: &'static str, bytes: u64) {
+    if let Ok(mut tracker) = tracker().lock() {
+        tracker.record(operation, bytes);
+    }
+}
+
+/// Set the per-dispatch GPU memory budget (`None` = no budget).
+pub fn set_memory_budget(budget: Option<u64>) {
+    if let Ok(mut tracker) = tracker().lock() {
+        tracker.budget = budget;
+    }
+}
+
+/// Usage recorded so far, in order of first use.
+pub fn memory_usage() -> Vec<OperationMemory> {
+    tracker().lock().map(|t| t.operations.clone()).unwrap_or_default()
+}
+
+/// Forget the recorded usage (the budget is kept).
+pub fn reset_memory_usage() {
+    if let Ok(mut tracker) = tracker().lock() {
+        tracker.operations.clear();
+        tracker.warning = None;
+    }
+}
+
+/// The latest budget warning, if one was raised since the last call.
+pub fn take_budget_warning() -> Option<String> {
+    tracker().lock().ok().and_then(|mut t| t.warning.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_peak_and_budget_per_operation() {
+        let mut tracker = Tracker::default();
+        tracker.record("Diff", 400);
+        tracker.record("Hilbert curve", 100);
+        tracker.record("Diff", 200);
+        assert_eq!(tracker.operations.len(), 2);
+        assert_eq!(tracker.operations[0], OperationMemory { operation: "Diff", last_bytes: 200, peak_bytes: 400, over_budget: 0 });
+        assert!(tracker.warning.is_none());
+
+        tracker.budget = Some(300);
+        tracker.record("Diff", 300);
+        assert!(tracker.warning.is_none());
+        tracker.record("Diff", 301);
+        assert_eq!(tracker.operations[0].over_budget, 1);
+        assert!(tracker.warning.take().is_some_and(|w| w.starts_with("Diff needs")));
+    }
+}
@@ -4,7 +4,7 @@
 
 use egui::{Context, Color32, RichText, ScrollArea};
 use tv_core::{Project, Bookmark, Label, LabelType};
-use crate::state::AppState;
+use crate::state::{AppState, LoadedFile};
 use std::path::PathBuf;
 
 /// Current tab in the bookmarks window.
@@ -15,6 +15,48 @@ pub enum BookmarksTab {
     Labels,
 }
 
+/// Undoable annotation changes kept per project.
+const UNDO_LIMIT: usize = 50;
+
+/// A reversible annotation change: what was at the key before it.
+#[derive(Debug, Clone)]
+pub enum AnnotationChange {
+    /// Bookmark at `offset` before the change (`None`: there was none).
+    Bookmark { offset: u64, previous: Option<Bookmark> },
+    /// Label at `address` before the change (`None`: there was none).
+    Label { address: u64, previous: Option<Label> },
+}
+
+impl AnnotationChange {
+    /// Restore the previous annotation through the project API.
+    fn revert(self, project: &mut Project) -> String {
+        match self {
+            AnnotationChange::Bookmark { offset, previous } => {
+                project.remove_bookmark(offset);
+                match previous {
+                    Some(bookmark) => {
+                        let message = format!("Restored bookmark \"{}\"", bookmark.name);
+                        project.add_bookmark(bookmark);
+                        message
+                    }
+                    None => format!("Removed bookmark at 0x{:X}", offset),
+                }
+            }
+            AnnotationChange::Label { address, previous } => {
+                project.remove_label(address);
+                match previous {
+                    Some(label) => {
+                        let message = format!("Restored label \"{}\"", label.name);
+                        project.add_label(label);
+                        message
+                    }
+                    None => format!("Removed label at 0x{:X}", address),
+                }
+            }
+        }
+    }
+}
+
 /// State for the bookmarks/labels window.
 pub struct BookmarksState {
     /// Current project data.
@@ -41,6 +83,8 @@ pub struct BookmarksState {
     pub modified: bool,
     /// Status message.
     pub status_message: Option<(String, bool)>, // (message, is_error)
+    /// Changes to revert with Ctrl+Z, oldest first (at most `UNDO_LIMIT`).
+    pub undo_stack: Vec<AnnotationChange>,
 }
 
 impl Default for BookmarksState {
@@ -58,13 +102,22 @@ impl Default for BookmarksState {
             selected_label: None,
             modified: false,
             status_message: None,
+            undo_stack: Vec::new(),
         }
     }
 }
 
 impl BookmarksState {
-    /// Initialize or get the project for the current file.
-    pub fn ensure_project(&mut self, file_path: &std::path::Path, file_size: u64) {
+    /// Initialize or get the project for the current file. In-memory files
+    /// get a project that lives with the tab and is never saved.
+    pub fn ensure_project(&mut self, file: &LoadedFile) {
+        let file_size = file.mapped.len();
+        let Some(file_path) = file.disk_path() else {
+            if self.project.is_none() {
+                self.project = Some(Project::new(&file.path, file_size));
+            }
+            return;
+        };
         if self.project.is_none() {
             // Try to load existing project
             let proj_path = Project::project_path_for(file_path);
@@ -105,26 +158,74 @@ impl BookmarksState {
         self.modified = false;
         self.selected_bookmark = None;
         self.selected_label = None;
+        self.undo_stack.clear();
+    }
+
+    /// Remember a change for undo, dropping the oldest past `UNDO_LIMIT`.
+    fn record(&mut self, change: AnnotationChange) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(change);
+        self.modified = true;
     }
 
     /// Add a bookmark at the current viewport offset.
     pub fn add_bookmark_at(&mut self, offset: u64, name: String) {
         if let Some(ref mut project) = self.project {
+            let previous = project.get_bookmark(offset).cloned();
             project.add_bookmark(Bookmark::new(offset, name));
-            self.modified = true;
+            self.record(AnnotationChange::Bookmark { offset, previous });
+        }
+    }
+
+    /// Remove the bookmark at `offset` (undoable).
+    pub fn remove_bookmark(&mut self, offset: u64) {
+        if let Some(ref mut project) = self.project {
+            let previous = project.get_bookmark(offset).cloned();
+            if previous.is_some() {
+                project.remove_bookmark(offset);
+                self.selected_bookmark = None;
+                self.record(AnnotationChange::Bookmark { offset, previous });
+            }
         }
     }
 
     /// Add a label at the given address.
     pub fn add_label_at(&mut self, address: u64, name: String, label_type: LabelType) {
         if let Some(ref mut project) = self.project {
+            let previous = project.get_label(address).cloned();
             let mut label = Label::new(address, name);
             label.label_type = label_type;
             project.add_label(label);
-            self.modified = true;
+            self.record(AnnotationChange::Label { address, previous });
         }
     }
 
+    /// Remove the label at `address` (undoable).
+    pub fn remove_label(&mut self, address: u64) {
+        if let Some(ref mut project) = self.project {
+            let previous = project.get_label(address).cloned();
+            if previous.is_some() {
+                project.remove_label(address);
+                self.selected_label = None;
+                self.record(AnnotationChange::Label { address, previous });
+            }
+        }
+    }
+
+    /// Revert the last annotation change. Returns false if there is none.
+    pub fn undo(&mut self) -> bool {
+        let Some(project) = self.project.as_mut() else { return false };
+        let Some(change) = self.undo_stack.pop() else { return false };
+        let message = change.revert(project);
+        self.selected_bookmark = None;
+        self.selected_label = None;
+        self.modified = true;
+        self.status_message = Some((format!("Undo: {}", message), false));
+        true
+    }
+
     /// Get label at address (if any).
     pub fn get_label(&self, address: u64) -> Option<&Label> {
         self.project.as_ref()?.get_label(address)
@@ -145,7 +246,7 @@ impl BookmarksWindow {
             return;
         }
 
-        egui::Window::new("Bookmarks & Labels")
+        let response = egui::Window::new("Bookmarks & Labels")
             .open(visible)
             .default_size([500.0, 450.0])
             .min_size([400.0, 300.0])
@@ -153,6 +254,14 @@ impl BookmarksWindow {
             .show(ctx, |ui| {
                 Self::show_contents(ui, state, bookmarks);
             });
+
+        // Ctrl+Z while this is the front window (text fields keep their own undo)
+        let Some(response) = response else { return };
+        let focused = ctx.top_layer_id() == Some(response.response.layer_id)
+            && ctx.memory(|m| m.focused().is_none());
+        if focused && ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, egui::Key::Z)) {
+            bookmarks.undo();
+        }
     }
 
     fn show_contents(ui: &mut egui::Ui, state: &mut AppState, bookmarks: &mut BookmarksState) {
@@ -165,7 +274,7 @@ impl BookmarksWindow {
 
         // Ensure project exists
         if let Some(ref file) = state.file {
-            bookmarks.ensure_project(&file.path, file.mapped.len());
+            bookmarks.ensure_project(file);
         }
 
         // Toolbar
@@ -176,9 +285,20 @@ impl BookmarksWindow {
 
             ui.separator();
 
+            // Undo button
+            if ui.add_enabled(!bookmarks.undo_stack.is_empty(), egui::Button::new("Undo"))
+                .on_hover_text("Revert the last add/delete  (Ctrl+Z)")
+                .clicked()
+            {
+                bookmarks.undo();
+            }
+
             // Save button
             let save_text = if bookmarks.modified { "Save *" } else { "Save" };
-            if ui.button(save_text).clicked() {
+            if ui.add_enabled(bookmarks.project_path.is_some(), egui::Button::new(save_text))
+                .on_disabled_hover_text("In-memory files have no project file")
+                .clicked()
+            {
                 if let Err(e) = bookmarks.save() {
                     bookmarks.status_message = Some((format!("Save failed: {}", e), true));
                 }
@@ -301,11 +421,7 @@ impl BookmarksWindow {
 
                 // Apply deletions
                 if let Some(offset) = to_delete {
-                    if let Some(ref mut project) = bookmarks.project {
-                        project.remove_bookmark(offset);
-                        bookmarks.modified = true;
-                        bookmarks.selected_bookmark = None;
-                    }
+                    bookmarks.remove_bookmark(offset);
                 }
 
                 // Apply navigation
@@ -424,11 +540,7 @@ impl BookmarksWindow {
 
                 // Apply deletions
                 if let Some(addr) = to_delete {
-                    if let Some(ref mut project) = bookmarks.project {
-                        project.remove_label(addr);
-                        bookmarks.modified = true;
-                        bookmarks.selected_label = None;
-                    }
+                    bookmarks.remove_label(addr);
                 }
 
                 // Apply navigation
@@ -454,3 +566,57 @@ fn parse_offset(input: &str) -> Option<u64> {
         s.parse().ok()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_project() -> BookmarksState {
+        BookmarksState {
+            project: Some(Project::new(std::path::Path::new("sample.bin"), 0x1000)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn undo_reverts_adds_and_deletes() {
+        let mut state = state_with_project();
+        state.add_bookmark_at(0x10, "header".to_string());
+        state.add_label_at(0x20, "main".to_string(), LabelType::Function);
+        state.remove_bookmark(0x10);
+        state.remove_bookmark(0x99); // nothing there: not recorded
+        assert_eq!(state.undo_stack.len(), 3);
+
+        // Deleted bookmark comes back with its name
+        assert!(state.undo());
+        assert_eq!(state.get_bookmark(0x10).map(|b| b.name.as_str()), Some("header"));
+        // Added label goes away
+        assert!(state.undo());
+        assert!(state.get_label(0x20).is_none());
+        assert!(state.undo());
+        assert!(state.get_bookmark(0x10).is_none());
+        assert!(!state.undo());
+    }
+
+    #[test]
+    fn undo_restores_replaced_label() {
+        let mut state = state_with_project();
+        state.add_label_at(0x40, "old".to_string(), LabelType::Data);
+        state.add_label_at(0x40, "new".to_string(), LabelType::Code);
+        assert!(state.undo());
+        let label = state.get_label(0x40).unwrap();
+        assert_eq!(label.name, "old");
+        assert_eq!(label.label_type, LabelType::Data);
+    }
+
+    #[test]
+    fn undo_stack_is_bounded_and_cleared_with_project() {
+        let mut state = state_with_project();
+        for i in 0..UNDO_LIMIT as u64 + 5 {
+            state.add_bookmark_at(i, format!("b{}", i));
+        }
+        assert_eq!(state.undo_stack.len(), UNDO_LIMIT);
+        state.clear();
+        assert!(state.undo_stack.is_empty());
+    }
+}
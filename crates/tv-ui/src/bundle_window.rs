@@ -0,0 +1,137 @@
+//! Analysis bundle export window.
+//!
+//! Picks which artifacts (report, CSVs, images, disassembly, session) go into
+//! one export folder; the app writes them.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use egui::Context;
+
+/// A file written to the analysis bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BundleArtifact {
+    Report,
+    SearchResults,
+    Signatures,
+    Histogram,
+    Hilbert,
+    Minimap,
+    Disassembly,
+    Session,
+}
+
+impl BundleArtifact {
+    pub const ALL: [BundleArtifact; 8] = [
+        BundleArtifact::Report,
+        BundleArtifact::SearchResults,
+        BundleArtifact::Signatures,
+        BundleArtifact::Histogram,
+        BundleArtifact::Hilbert,
+        BundleArtifact::Minimap,
+        BundleArtifact::Disassembly,
+        BundleArtifact::Session,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BundleArtifact::Report => "Analysis report (JSON)",
+            BundleArtifact::SearchResults => "Search results (CSV)",
+            BundleArtifact::Signatures => "Signatures (CSV)",
+            BundleArtifact::Histogram => "Byte histogram (CSV)",
+            BundleArtifact::Hilbert => "Hilbert curve (PNG)",
+            BundleArtifact::Minimap => "Minimap (PNG)",
+            BundleArtifact::Disassembly => "Disassembly (text)",
+            BundleArtifact::Session => "Session",
+        }
+    }
+
+    /// File name inside the bundle folder.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            BundleArtifact::Report => "report.json",
+            BundleArtifact::SearchResults => "search_results.csv",
+            BundleArtifact::Signatures => "signatures.csv",
+            BundleArtifact::Histogram => "histogram.csv",
+            BundleArtifact::Hilbert => "hilbert.png",
+            BundleArtifact::Minimap => "minimap.png",
+            BundleArtifact::Disassembly => "disassembly.asm",
+            BundleArtifact::Session => "session.titan",
+        }
+    }
+}
+
+/// State for the bundle export window.
+#[derive(Default)]
+pub struct BundleState {
+    pub open: bool,
+    /// Artifacts the user unchecked.
+    pub excluded: HashSet<BundleArtifact>,
+}
+
+/// Folder the bundle of `file_name` is written to inside `parent`.
+pub fn bundle_dir(parent: &Path, file_name: &str) -> PathBuf {
+    parent.join(format!("{}_analysis", file_name))
+}
+
+/// Analysis bundle export window.
+pub struct BundleWindow;
+
+impl BundleWindow {
+    /// Show the artifact picker. `available` lists the artifacts that have
+    /// data; returns the checked ones when the user exports.
+    pub fn show(ctx: &Context, bundle: &mut BundleState, available: &[BundleArtifact]) -> Option<Vec<BundleArtifact>> {
+        if !bundle.open {
+            return None;
+        }
+
+        let mut chosen = None;
+        let mut open = true;
+        egui::Window::new("Export Bundle")
+            .open(&mut open)
+            .default_width(300.0)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Write these files to one folder:");
+                for artifact in BundleArtifact::ALL {
+                    let has_data = available.contains(&artifact);
+                    let mut included = has_data && !bundle.excluded.contains(&artifact);
+                    let response = ui.add_enabled(has_data, egui::Checkbox::new(&mut included, artifact.label()))
+                        .on_hover_text(artifact.file_name())
+                        .on_disabled_hover_text("Nothing to export yet");
+                    if response.changed() {
+                        if included {
+                            bundle.excluded.remove(&artifact);
+                        } else {
+                            bundle.excluded.insert(artifact);
+                        }
+                    }
+                }
+
+                ui.separator();
+                let selected: Vec<BundleArtifact> = BundleArtifact::ALL
+                    .into_iter()
+                    .filter(|a| available.contains(a) && !bundle.excluded.contains(a))
+                    .collect();
+                if ui.add_enabled(!selected.is_empty(), egui::Button::new("Export to folder...")).clicked() {
+                    chosen = Some(selected);
+                }
+            });
+
+        bundle.open = open && chosen.is_none();
+        chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_file_names_are_unique() {
+        let names: HashSet<&str> = BundleArtifact::ALL.iter().map(|a| a.file_name()).collect();
+        assert_eq!(names.len(), BundleArtifact::ALL.len());
+        assert!(BundleArtifact::Session.file_name().ends_with(crate::session::SESSION_EXTENSION));
+        assert_eq!(bundle_dir(Path::new("out"), "disk.img"), Path::new("out").join("disk.img_analysis"));
+    }
+}
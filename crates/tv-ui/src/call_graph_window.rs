@@ -0,0 +1,410 @@
+//! Call graph visualization window.
+//!
+//! Shows functions detected in the disassembly as nodes connected by call
+//! edges, next to a list of functions with their in/out call counts.
+//! Clicking a function opens its control flow graph.
+
+use std::collections::HashMap;
+
+use egui::{Context, Color32, Pos2, Rect, Stroke, Vec2, FontId, Sense, ScrollArea};
+use tv_core::{CallGraph, CfgInstruction, FunctionNode, CALL_NODE_HEIGHT, CALL_NODE_WIDTH};
+
+/// State for the call graph window.
+pub struct CallGraphState {
+    /// The computed call graph (if any).
+    pub graph: Option<CallGraph>,
+    /// Instructions the graph was built from (used to build per-function CFGs).
+    pub instructions: Vec<CfgInstruction>,
+    /// Function names resolved from project labels.
+    pub names: HashMap<u64, String>,
+    /// Pan offset for the view.
+    pub pan: Vec2,
+    /// Zoom level (1.0 = 100%).
+    pub zoom: f32,
+    /// Currently hovered function address.
+    pub hovered: Option<u64>,
+    /// Currently selected function address.
+    pub selected: Option<u64>,
+}
+
+impl Default for CallGraphState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CallGraphState {
+    pub fn new() -> Self {
+        Self {
+            graph: None,
+            instructions: Vec::new(),
+            names: HashMap::new(),
+            pan: Vec2::ZERO,
+            zoom: 1.0,
+            hovered: None,
+            selected: None,
+        }
+    }
+
+    /// Build the graph from instructions. `names` maps addresses to labels;
+    /// labelled addresses inside the disassembly are treated as function starts.
+    pub fn build(&mut self, instructions: Vec<CfgInstruction>, names: HashMap<u64, String>) {
+        let entries: Vec<u64> = names.keys().copied().collect();
+        self.graph = Some(CallGraph::build(&instructions, &entries));
+        self.instructions = instructions;
+        self.names = names;
+        self.hovered = None;
+        self.selected = None;
+        self.reset_view();
+    }
+
+    /// Reset the view to center on the graph.
+    pub fn reset_view(&mut self) {
+        self.pan = Vec2::ZERO;
+        self.zoom = 1.0;
+    }
+
+    /// Display name of a function: its label, or `sub_XXXXXXXX`.
+    pub fn function_name(&self, address: u64) -> String {
+        self.names
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| format!("sub_{:08X}", address))
+    }
+
+    /// Instructions of a function's body (empty for external functions).
+    pub fn function_instructions(&self, address: u64) -> &[CfgInstruction] {
+        match &self.graph {
+            Some(graph) => graph.function_instructions(address, &self.instructions),
+            None => &[],
+        }
+    }
+}
+
+/// Call graph visualization window.
+pub struct CallGraphWindow;
+
+impl CallGraphWindow {
+    /// Returns the address of a function whose CFG should be opened.
+    pub fn show(ctx: &Context, state: &mut CallGraphState, visible: &mut bool) -> Option<u64> {
+        if !*visible {
+            return None;
+        }
+
+        let mut open_cfg = None;
+        egui::Window::new("Call Graph")
+            .open(visible)
+            .default_size([900.0, 600.0])
+            .min_size([500.0, 300.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                open_cfg = Self::show_contents(ui, state);
+            });
+        open_cfg
+    }
+
+    fn show_contents(ui: &mut egui::Ui, state: &mut CallGraphState) -> Option<u64> {
+        // Toolbar
+        ui.horizontal(|ui| {
+            if ui.button("Reset View").clicked() {
+                state.reset_view();
+            }
+
+            ui.separator();
+
+            ui.label("Zoom:");
+            if ui.button("-").clicked() {
+                state.zoom = (state.zoom - 0.1).max(0.2);
+            }
+            ui.label(format!("{:.0}%", state.zoom * 100.0));
+            if ui.button("+").clicked() {
+                state.zoom = (state.zoom + 0.1).min(3.0);
+            }
+
+            ui.separator();
+
+            if let Some(graph) = &state.graph {
+                ui.label(format!("{} functions, {} call edges", graph.functions.len(), graph.edges.len()));
+            }
+        });
+
+        ui.separator();
+
+        if state.graph.is_none() {
+            ui.centered_and_justified(|ui| {
+                ui.label("No call graph to display.\nClick 'Call Graph' in the disassembler.");
+            });
+            return None;
+        }
+
+        let mut open_cfg = None;
+
+        egui::SidePanel::left("call_graph_functions")
+            .resizable(true)
+            .default_width(260.0)
+            .show_inside(ui, |ui| {
+                if let Some(addr) = Self::show_function_list(ui, state) {
+                    open_cfg = Some(addr);
+                }
+            });
+
+        if let Some(addr) = Self::show_canvas(ui, state) {
+            open_cfg = Some(addr);
+        }
+
+        if let Some(addr) = open_cfg {
+            state.selected = Some(addr);
+        }
+        open_cfg
+    }
+
+    fn show_function_list(ui: &mut egui::Ui, state: &CallGraphState) -> Option<u64> {
+        let graph = state.graph.as_ref()?;
+        let mut clicked = None;
+
+        ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("call_graph_function_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Function");
+                    ui.strong("In");
+                    ui.strong("Out");
+                    ui.end_row();
+
+                    for func in graph.functions.values() {
+                        let name = state.function_name(func.address);
+                        let text = if func.external {
+                            egui::RichText::new(name).monospace().weak()
+                        } else {
+                            egui::RichText::new(name).monospace()
+                        };
+                        let response = ui.selectable_label(state.selected == Some(func.address), text)
+                            .on_hover_text(format!(
+                                "0x{:X}, {} instructions{}",
+                                func.address,
+                                func.instruction_count,
+                                if func.external { " (outside disassembly)" } else { "" },
+                            ));
+                        if response.clicked() && !func.external {
+                            clicked = Some(func.address);
+                        }
+                        ui.label(func.calls_in.to_string());
+                        ui.label(func.calls_out.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+
+        clicked
+    }
+
+    fn show_canvas(ui: &mut egui::Ui, state: &mut CallGraphState) -> Option<u64> {
+        // Taken out for the duration of drawing so hover/selection can be updated
+        let graph = state.graph.take()?;
+        let clicked = Self::draw_graph(ui, state, &graph);
+        state.graph = Some(graph);
+        clicked
+    }
+
+    fn draw_graph(ui: &mut egui::Ui, state: &mut CallGraphState, graph: &CallGraph) -> Option<u64> {
+        let available = ui.available_size();
+        let (response, painter) = ui.allocate_painter(available, Sense::click_and_drag());
+        let canvas_rect = response.rect;
+
+        // Handle pan
+        if response.dragged() {
+            state.pan += response.drag_delta();
+        }
+
+        // Handle zoom with scroll wheel
+        if response.hovered() {
+            let scroll = ui.input(|i| i.raw_scroll_delta.y);
+            if scroll != 0.0 {
+                let zoom_delta = scroll * 0.001;
+                state.zoom = (state.zoom + zoom_delta).clamp(0.2, 3.0);
+            }
+        }
+
+        // Calculate transform
+        let center = canvas_rect.center();
+        let zoom = state.zoom;
+        let pan = state.pan;
+        let transform = |pos: Pos2| -> Pos2 {
+            let scaled = Pos2::new(pos.x * zoom, pos.y * zoom);
+            center + pan + scaled.to_vec2()
+        };
+
+        // Draw edges first (behind nodes)
+        for edge in &graph.edges {
+            if let (Some(from), Some(to)) = (graph.functions.get(&edge.from), graph.functions.get(&edge.to)) {
+                Self::draw_edge(&painter, from, to, edge.call_sites, &transform, zoom);
+            }
+        }
+
+        // Draw nodes
+        let hover_pos = response.hover_pos();
+        let mut new_hovered = None;
+        for func in graph.functions.values() {
+            if let Some(rect) = Self::draw_node(&painter, func, &transform, state, canvas_rect) {
+                if hover_pos.is_some_and(|p| rect.contains(p)) {
+                    new_hovered = Some(func.address);
+                }
+            }
+        }
+        state.hovered = new_hovered;
+
+        if let Some(addr) = state.hovered {
+            let func = &graph.functions[&addr];
+            response.clone().on_hover_text(format!(
+                "{}\nCalled {} time(s), makes {} call(s)",
+                state.function_name(addr),
+                func.calls_in,
+                func.calls_out,
+            ));
+        }
+
+        // Click a node to open its CFG
+        if response.clicked() {
+            let hovered = state.hovered.filter(|a| !graph.functions[a].external);
+            if hovered.is_none() {
+                state.selected = None;
+            }
+            return hovered;
+        }
+        None
+    }
+
+    fn draw_node(
+        painter: &egui::Painter,
+        func: &FunctionNode,
+        transform: &impl Fn(Pos2) -> Pos2,
+        state: &CallGraphState,
+        clip_rect: Rect,
+    ) -> Option<Rect> {
+        let top_left = transform(Pos2::new(func.layout_x, func.layout_y));
+        let size = Vec2::new(CALL_NODE_WIDTH, CALL_NODE_HEIGHT) * state.zoom;
+        let rect = Rect::from_min_size(top_left, size);
+
+        // Skip if outside clip rect
+        if !rect.intersects(clip_rect) {
+            return None;
+        }
+
+        let is_hovered = state.hovered == Some(func.address);
+        let is_selected = state.selected == Some(func.address);
+        let is_root = func.calls_in == 0;
+
+        let bg_color = if is_selected {
+            Color32::from_rgb(60, 60, 100)
+        } else if is_hovered {
+            Color32::from_rgb(50, 50, 70)
+        } else if func.external {
+            Color32::from_rgb(40, 40, 40)
+        } else if is_root {
+            Color32::from_rgb(40, 60, 40)
+        } else {
+            Color32::from_rgb(35, 35, 45)
+        };
+
+        let border_color = if is_selected {
+            Color32::from_rgb(100, 150, 255)
+        } else if is_hovered {
+            Color32::from_rgb(150, 150, 200)
+        } else if func.external {
+            Color32::from_rgb(90, 90, 90)
+        } else if is_root {
+            Color32::from_rgb(100, 200, 100)
+        } else {
+            Color32::from_rgb(80, 80, 100)
+        };
+
+        painter.rect_filled(rect, 4.0, bg_color);
+        painter.rect_stroke(rect, 4.0, Stroke::new(2.0, border_color));
+
+        let name_color = if func.external {
+            Color32::from_rgb(150, 150, 150)
+        } else {
+            Color32::from_rgb(150, 200, 255)
+        };
+        painter.text(
+            Pos2::new(rect.center().x, rect.top() + 11.0 * state.zoom),
+            egui::Align2::CENTER_CENTER,
+            state.function_name(func.address),
+            FontId::monospace(12.0 * state.zoom),
+            name_color,
+        );
+
+        if state.zoom >= 0.5 {
+            painter.text(
+                Pos2::new(rect.center().x, rect.bottom() - 10.0 * state.zoom),
+                egui::Align2::CENTER_CENTER,
+                format!("in {}  out {}", func.calls_in, func.calls_out),
+                FontId::monospace(10.0 * state.zoom),
+                Color32::from_rgb(180, 180, 180),
+            );
+        }
+
+        Some(rect)
+    }
+
+    fn draw_edge(
+        painter: &egui::Painter,
+        from: &FunctionNode,
+        to: &FunctionNode,
+        call_sites: usize,
+        transform: &impl Fn(Pos2) -> Pos2,
+        zoom: f32,
+    ) {
+        let color = Color32::from_rgb(100, 150, 255);
+        let width = (1.0 + call_sites.min(4) as f32 * 0.5) * zoom;
+
+        if from.address == to.address {
+            // Recursive call: small loop on the right side of the node
+            let right = from.layout_x + CALL_NODE_WIDTH;
+            let points = [
+                transform(Pos2::new(right, from.layout_y + CALL_NODE_HEIGHT * 0.3)),
+                transform(Pos2::new(right + 20.0, from.layout_y + CALL_NODE_HEIGHT * 0.3)),
+                transform(Pos2::new(right + 20.0, from.layout_y + CALL_NODE_HEIGHT * 0.7)),
+                transform(Pos2::new(right, from.layout_y + CALL_NODE_HEIGHT * 0.7)),
+            ];
+            painter.add(egui::Shape::line(points.to_vec(), Stroke::new(width, color)));
+            return;
+        }
+
+        let (start, end) = if to.layout_y > from.layout_y {
+            (
+                transform(Pos2::new(from.layout_x + CALL_NODE_WIDTH / 2.0, from.layout_y + CALL_NODE_HEIGHT)),
+                transform(Pos2::new(to.layout_x + CALL_NODE_WIDTH / 2.0, to.layout_y)),
+            )
+        } else {
+            // Call to a function in the same or an upper layer
+            (
+                transform(Pos2::new(from.layout_x + CALL_NODE_WIDTH / 2.0, from.layout_y)),
+                transform(Pos2::new(to.layout_x + CALL_NODE_WIDTH / 2.0, to.layout_y + CALL_NODE_HEIGHT)),
+            )
+        };
+
+        let mid_y = (start.y + end.y) / 2.0;
+        let bezier = egui::epaint::CubicBezierShape::from_points_stroke(
+            [start, Pos2::new(start.x, mid_y), Pos2::new(end.x, mid_y), end],
+            false,
+            Color32::TRANSPARENT,
+            Stroke::new(width, color),
+        );
+        painter.add(bezier);
+
+        // Arrowhead
+        let size = 8.0 * zoom;
+        let dir = if end.y >= start.y { 1.0 } else { -1.0 };
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                end,
+                Pos2::new(end.x - size / 2.0, end.y - size * dir),
+                Pos2::new(end.x + size / 2.0, end.y - size * dir),
+            ],
+            color,
+            Stroke::NONE,
+        ));
+    }
+}
@@ -0,0 +1,12 @@
+/// Read the current text content of the system clipboard.
+/// Returns `None` if the clipboard is unavailable or holds no text.
+pub fn read_text() -> Option<String> {
+    let mut clipboard = match arboard::Clipboard::new() {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Clipboard unavailable: {}", e);
+            return None;
+        }
+    };
+    clipboard.get_text().ok()
+}
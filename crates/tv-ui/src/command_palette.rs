@@ -0,0 +1,586 @@
+//! Command palette (Ctrl+P).
+//!
+//! Every application action is registered once in [`COMMANDS`] with its
+//! name and keyboard shortcut. The app dispatches shortcuts from the
+//! registry and the palette lists it with fuzzy search, so an action added
+//! here is reachable both ways. Users can rebind the shortcuts
+//! ([`KeyBindings`], saved in the config).
+
+use std::collections::{BTreeMap, HashMap};
+
+use egui::{Context, Key, KeyboardShortcut, Modifiers, RichText, ScrollArea};
+
+/// An application action, executed by the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    OpenFile,
+    OpenClipboard,
+    OpenSession,
+    SaveSession,
+    SaveSessionAs,
+    CloseSession,
+    ExportReport,
+    ExportSearchResults,
+    ExportDisassembly,
+    ExportSignatures,
+    ExportBundle,
+    GotoOffset,
+    Search,
+    DeepScan,
+    ToggleDiff,
+    ToggleFileInfo,
+    ToggleSignatures,
+    TogglePerformance,
+    ToggleHilbert,
+    ToggleDisassembly,
+    ToggleInspector,
+    ToggleHistogram,
+    ToggleXrefs,
+    ToggleBookmarks,
+    ToggleScript,
+    ToggleMinimap,
+    ToggleXor,
+    ToggleTextView,
+    ToggleSections,
+    ToggleStructure,
+    ToggleTheme,
+    ToggleNotes,
+    ToggleOverview,
+    ToggleShortcuts,
+    /// Switch to the workspace at this index.
+    Workspace(usize),
+}
+
+impl Command {
+    /// Whether the shortcut fires while a text field has focus. Go to
+    /// Offset was a hex view key and leaves the key to inputs.
+    pub fn works_while_typing(&self) -> bool {
+        !matches!(self, Command::GotoOffset)
+    }
+}
+
+/// A registered command: palette name and optional keyboard shortcut.
+pub struct CommandInfo {
+    pub command: Command,
+    pub name: &'static str,
+    pub shortcut: Option<KeyboardShortcut>,
+}
+
+/// Ctrl, or Cmd on macOS.
+const CTRL: Modifiers = Modifiers { command: true, ..Modifiers::NONE };
+const CTRL_SHIFT: Modifiers = Modifiers { command: true, shift: true, ..Modifiers::NONE };
+const ALT: Modifiers = Modifiers { alt: true, ..Modifiers::NONE };
+
+const fn key(key: Key) -> Option<KeyboardShortcut> {
+    Some(KeyboardShortcut::new(Modifiers::NONE, key))
+}
+
+const fn ctrl(key: Key) -> Option<KeyboardShortcut> {
+    Some(KeyboardShortcut::new(CTRL, key))
+}
+
+const fn ctrl_shift(key: Key) -> Option<KeyboardShortcut> {
+    Some(KeyboardShortcut::new(CTRL_SHIFT, key))
+}
+
+const fn info(command: Command, name: &'static str, shortcut: Option<KeyboardShortcut>) -> CommandInfo {
+    CommandInfo { command, name, shortcut }
+}
+
+/// Shortcut that opens the palette.
+pub const PALETTE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(CTRL, Key::P);
+
+/// Workspaces 1 to 5 (handled by the app).
+pub const WORKSPACE_SHORTCUTS: [KeyboardShortcut; 5] = [
+    KeyboardShortcut::new(CTRL, Key::Num1),
+    KeyboardShortcut::new(CTRL, Key::Num2),
+    KeyboardShortcut::new(CTRL, Key::Num3),
+    KeyboardShortcut::new(CTRL, Key::Num4),
+    KeyboardShortcut::new(CTRL, Key::Num5),
+];
+
+/// Closes the floating windows and the selection.
+pub const ESCAPE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::Escape);
+
+/// Page Up / Page Down in the hex view.
+pub const PAGE_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(Modifiers::NONE, Key::PageUp),
+    KeyboardShortcut::new(Modifiers::NONE, Key::PageDown),
+];
+
+/// Home / End in the hex view.
+pub const FILE_END_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(Modifiers::NONE, Key::Home),
+    KeyboardShortcut::new(Modifiers::NONE, Key::End),
+];
+
+/// Up, Down, Left and Right in the hex view.
+pub const ARROW_SHORTCUTS: [KeyboardShortcut; 4] = [
+    KeyboardShortcut::new(Modifiers::NONE, Key::ArrowUp),
+    KeyboardShortcut::new(Modifiers::NONE, Key::ArrowDown),
+    KeyboardShortcut::new(Modifiers::NONE, Key::ArrowLeft),
+    KeyboardShortcut::new(Modifiers::NONE, Key::ArrowRight),
+];
+
+/// Back to the previous position.
+pub const BACK_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(ALT, Key::ArrowLeft);
+
+/// Copy the current offset.
+pub const COPY_OFFSET_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(CTRL_SHIFT, Key::G);
+
+/// Toggle edit mode.
+pub const EDIT_MODE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(CTRL, Key::E);
+
+/// Previous / next diff region.
+pub const DIFF_REGION_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(CTRL, Key::ArrowUp),
+    KeyboardShortcut::new(CTRL, Key::ArrowDown),
+];
+
+/// All static commands, in palette order (workspaces are added by the app).
+pub const COMMANDS: &[CommandInfo] = &[
+    info(Command::OpenFile, "File: Open...", ctrl(Key::O)),
+    info(Command::OpenClipboard, "File: New from Clipboard", None),
+    info(Command::OpenSession, "File: Open Session...", None),
+    info(Command::SaveSession, "File: Save Session", ctrl(Key::S)),
+    info(Command::SaveSessionAs, "File: Save Session As...", ctrl_shift(Key::S)),
+    info(Command::CloseSession, "File: Close Session", None),
+    info(Command::ExportReport, "Export: Analysis report (JSON)", None),
+    info(Command::ExportSearchResults, "Export: Search results (CSV)", None),
+    info(Command::ExportDisassembly, "Export: Disassembly (text)", None),
+    info(Command::ExportSignatures, "Export: Signatures (CSV)", None),
+    info(Command::ExportBundle, "Export: Analysis bundle...", None),
+    info(Command::GotoOffset, "Navigate: Go to Offset...", ctrl(Key::G)),
+    info(Command::Search, "Analysis: Search", ctrl(Key::F)),
+    info(Command::DeepScan, "Analysis: Run Deep Scan", None),
+    info(Command::ToggleDiff, "Analysis: Binary Diff", key(Key::F6)),
+    info(Command::ToggleFileInfo, "View: File Info", key(Key::F1)),
+    info(Command::ToggleSignatures, "View: Signatures", key(Key::F2)),
+    info(Command::TogglePerformance, "View: Performance", key(Key::F3)),
+    info(Command::ToggleHilbert, "View: Hilbert Curve", key(Key::F4)),
+    info(Command::ToggleDisassembly, "View: Disassembly", key(Key::F5)),
+    info(Command::ToggleInspector, "View: Struct Inspector", key(Key::F7)),
+    info(Command::ToggleHistogram, "View: Byte Histogram", key(Key::F8)),
+    info(Command::ToggleXrefs, "View: Cross-References", key(Key::F9)),
+    info(Command::ToggleBookmarks, "View: Bookmarks/Labels", key(Key::F10)),
+    info(Command::ToggleScript, "View: Script Console", key(Key::F11)),
+    info(Command::ToggleMinimap, "View: Minimap", None),
+    info(Command::ToggleXor, "View: XOR Brute Force", None),
+    info(Command::ToggleTextView, "View: Text View", None),
+    info(Command::ToggleSections, "View: Sections", None),
+    info(Command::ToggleStructure, "View: Structure", None),
+    info(Command::ToggleTheme, "View: Theme...", None),
+    info(Command::ToggleNotes, "View: Notes", None),
+    info(Command::ToggleOverview, "View: File Overview", None),
+    info(Command::ToggleShortcuts, "Help: Keyboard Shortcuts", key(Key::F12)),
+];
+
+/// Keys handled outside the registry (not rebindable).
+pub struct FixedShortcut {
+    pub category: &'static str,
+    pub action: &'static str,
+    /// Shortcuts the handler matches on.
+    pub keys: &'static [KeyboardShortcut],
+}
+
+const fn fixed(category: &'static str, action: &'static str, keys: &'static [KeyboardShortcut]) -> FixedShortcut {
+    FixedShortcut { category, action, keys }
+}
+
+/// Fixed keys, for the shortcut help and rebinding conflicts.
+pub const FIXED_SHORTCUTS: &[FixedShortcut] = &[
+    fixed("General", "Command palette", &[PALETTE_SHORTCUT]),
+    fixed("General", "Switch workspace", &WORKSPACE_SHORTCUTS),
+    fixed("General", "Close windows and the selection", &[ESCAPE_SHORTCUT]),
+    fixed("Navigate", "Scroll one page", &PAGE_SHORTCUTS),
+    fixed("Navigate", "Start / end of the file", &FILE_END_SHORTCUTS),
+    fixed("Navigate", "Scroll, or move the byte being edited", &ARROW_SHORTCUTS),
+    fixed("Navigate", "Back to the previous position", &[BACK_SHORTCUT]),
+    fixed("Navigate", "Copy the current offset", &[COPY_OFFSET_SHORTCUT]),
+    fixed("Edit", "Toggle edit mode", &[EDIT_MODE_SHORTCUT]),
+    fixed("Analysis", "Previous / next diff region", &DIFF_REGION_SHORTCUTS),
+];
+
+/// Mouse gestures for the shortcut help: (category, gesture, action).
+pub const MOUSE_SHORTCUTS: &[(&str, &str, &str)] = &[
+    ("Navigate", "Ctrl+Scroll", "Zoom the hex view"),
+];
+
+/// Whether `shortcut` was pressed this frame (exact modifiers).
+pub fn shortcut_pressed(input: &egui::InputState, shortcut: &KeyboardShortcut) -> bool {
+    input.modifiers.matches_exact(shortcut.modifiers) && input.key_pressed(shortcut.logical_key)
+}
+
+/// A pressed shortcut as stored in the bindings: Ctrl and macOS Cmd both
+/// become the platform command modifier, like the defaults.
+pub fn normalize_shortcut(mut shortcut: KeyboardShortcut) -> KeyboardShortcut {
+    let modifiers = &mut shortcut.modifiers;
+    modifiers.command = modifiers.ctrl || modifiers.command || modifiers.mac_cmd;
+    modifiers.ctrl = false;
+    modifiers.mac_cmd = false;
+    shortcut
+}
+
+/// Category and action of a registered name ("View: Minimap" -> ("View", "Minimap")).
+pub fn split_name(name: &str) -> (&str, &str) {
+    name.split_once(": ").unwrap_or(("General", name))
+}
+
+/// Platform-independent shortcut text for the config ("Ctrl+Shift+F5").
+pub fn shortcut_text(shortcut: &KeyboardShortcut) -> String {
+    let mut text = String::new();
+    for (held, name) in [
+        (shortcut.modifiers.ctrl || shortcut.modifiers.command, "Ctrl+"),
+        (shortcut.modifiers.alt, "Alt+"),
+        (shortcut.modifiers.shift, "Shift+"),
+    ] {
+        if held {
+            text.push_str(name);
+        }
+    }
+    text.push_str(shortcut.logical_key.name());
+    text
+}
+
+/// Parse [`shortcut_text`] output.
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let mut modifiers = Modifiers::NONE;
+    let mut parts: Vec<&str> = text.split('+').collect();
+    let key_name = parts.pop()?;
+    for part in parts {
+        match part {
+            "Ctrl" => modifiers.command = true,
+            "Alt" => modifiers.alt = true,
+            "Shift" => modifiers.shift = true,
+            _ => return None,
+        }
+    }
+    Some(KeyboardShortcut::new(modifiers, Key::from_name(key_name)?))
+}
+
+/// Shortcuts changed by the user; the other commands keep their default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyBindings {
+    /// Command -> shortcut (`None` = unbound).
+    overrides: HashMap<Command, Option<KeyboardShortcut>>,
+}
+
+impl KeyBindings {
+    /// Load from the config map (command name -> shortcut text, empty when
+    /// unbound). Unknown commands and invalid shortcuts are skipped.
+    pub fn from_config(map: &BTreeMap<String, String>) -> Self {
+        let mut bindings = Self::default();
+        for info in COMMANDS {
+            let Some(text) = map.get(info.name) else { continue };
+            let shortcut = if text.is_empty() {
+                None
+            } else if let Some(shortcut) = parse_shortcut(text) {
+                Some(shortcut)
+            } else {
+                log::warn!("Ignoring invalid shortcut {:?} for {}", text, info.name);
+                continue;
+            };
+            bindings.set(info.command, shortcut);
+        }
+        bindings
+    }
+
+    /// Config map of the overrides.
+    pub fn to_config(&self) -> BTreeMap<String, String> {
+        COMMANDS
+            .iter()
+            .filter_map(|info| {
+                let shortcut = self.overrides.get(&info.command)?;
+                Some((info.name.to_string(), shortcut.as_ref().map(shortcut_text).unwrap_or_default()))
+            })
+            .collect()
+    }
+
+    /// Effective shortcut of a registered command.
+    pub fn shortcut(&self, info: &CommandInfo) -> Option<KeyboardShortcut> {
+        self.overrides.get(&info.command).copied().unwrap_or(info.shortcut)
+    }
+
+    /// Bind `command` to `shortcut` (`None` to unbind).
+    pub fn set(&mut self, command: Command, shortcut: Option<KeyboardShortcut>) {
+        let default = COMMANDS.iter().find(|info| info.command == command).and_then(|info| info.shortcut);
+        if shortcut == default {
+            self.overrides.remove(&command);
+        } else {
+            self.overrides.insert(command, shortcut);
+        }
+    }
+
+    /// Whether the user changed the shortcut of `command`.
+    pub fn is_custom(&self, command: Command) -> bool {
+        self.overrides.contains_key(&command)
+    }
+
+    /// Name of another command bound to `shortcut`, or of the fixed key it is.
+    pub fn conflict(&self, command: Command, shortcut: KeyboardShortcut) -> Option<&'static str> {
+        COMMANDS
+            .iter()
+            .find(|info| info.command != command && self.shortcut(info) == Some(shortcut))
+            .map(|info| info.name)
+            .or_else(|| FIXED_SHORTCUTS.iter().find(|fixed| fixed.keys.contains(&shortcut)).map(|fixed| fixed.action))
+    }
+}
+
+/// Commands whose shortcut was pressed this frame (exact modifiers).
+pub fn pressed_commands(input: &egui::InputState, bindings: &KeyBindings) -> Vec<Command> {
+    COMMANDS
+        .iter()
+        .filter(|info| bindings.shortcut(info).is_some_and(|sc| shortcut_pressed(input, &sc)))
+        .map(|info| info.command)
+        .collect()
+}
+
+/// A palette row: command, display name and shortcut text.
+pub struct PaletteEntry {
+    pub command: Command,
+    pub name: String,
+    pub shortcut: String,
+}
+
+impl PaletteEntry {
+    pub fn new(command: Command, name: impl Into<String>, shortcut: impl Into<String>) -> Self {
+        Self { command, name: name.into(), shortcut: shortcut.into() }
+    }
+
+    /// Entries for the registered commands accepted by `available`.
+    pub fn registered(ctx: &Context, bindings: &KeyBindings, available: impl Fn(Command) -> bool) -> Vec<Self> {
+        COMMANDS
+            .iter()
+            .filter(|info| available(info.command))
+            .map(|info| {
+                let shortcut = bindings.shortcut(info).map(|sc| ctx.format_shortcut(&sc)).unwrap_or_default();
+                Self::new(info.command, info.name, shortcut)
+            })
+            .collect()
+    }
+}
+
+/// Fuzzy match of `query` against `text`: every query character must appear
+/// in order (case-insensitive). Higher scores for consecutive characters and
+/// word starts; `None` if it does not match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last_match: Option<usize> = None;
+
+    for qc in query.chars().flat_map(char::to_lowercase).filter(|c| !c.is_whitespace()) {
+        let found = pos + text[pos..].iter().position(|&c| c == qc)?;
+        score += 1;
+        if last_match == Some(found.wrapping_sub(1)) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos).min(5) as i32;
+        last_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Indices of the entries matching `query`, best first (ties keep entry order).
+fn filter_entries(entries: &[PaletteEntry], query: &str) -> Vec<usize> {
+    let mut matches: Vec<(usize, i32)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| fuzzy_score(query, &e.name).map(|s| (i, s)))
+        .collect();
+    matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    matches.into_iter().map(|(i, _)| i).collect()
+}
+
+/// State for the command palette.
+#[derive(Default)]
+pub struct CommandPaletteState {
+    pub open: bool,
+    pub query: String,
+    /// Highlighted row in the filtered list.
+    pub selected: usize,
+}
+
+impl CommandPaletteState {
+    /// Open the palette with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+}
+
+/// Fuzzy-searchable list of commands.
+pub struct CommandPalette;
+
+impl CommandPalette {
+    /// Show the palette; returns the command chosen this frame.
+    pub fn show(ctx: &Context, palette: &mut CommandPaletteState, entries: &[PaletteEntry]) -> Option<Command> {
+        if !palette.open {
+            return None;
+        }
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+            i.consume_key(Modifiers::NONE, Key::Enter),
+            i.consume_key(Modifiers::NONE, Key::Escape),
+        ));
+        if escape {
+            palette.open = false;
+            return None;
+        }
+
+        let matches = filter_entries(entries, &palette.query);
+        if down {
+            palette.selected = (palette.selected + 1).min(matches.len().saturating_sub(1));
+        }
+        if up {
+            palette.selected = palette.selected.saturating_sub(1);
+        }
+        palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = if enter { matches.get(palette.selected).map(|&i| entries[i].command) } else { None };
+
+        egui::Window::new("Command Palette")
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .default_width(440.0)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut palette.query)
+                        .hint_text("Type a command...")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    palette.selected = 0;
+                }
+
+                ui.separator();
+                if matches.is_empty() {
+                    ui.weak("No matching command");
+                    return;
+                }
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (row, &i) in matches.iter().enumerate() {
+                        let entry = &entries[i];
+                        let selected = row == palette.selected;
+                        ui.horizontal(|ui| {
+                            let label = ui.selectable_label(selected, &entry.name);
+                            if selected && (up || down) {
+                                label.scroll_to_me(None);
+                            }
+                            if label.clicked() {
+                                chosen = Some(entry.command);
+                            }
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(RichText::new(&entry.shortcut).weak().monospace());
+                            });
+                        });
+                    }
+                });
+            });
+
+        if chosen.is_some() {
+            palette.open = false;
+        }
+        chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_in_order() {
+        assert!(fuzzy_score("hist", "View: Byte Histogram").is_some());
+        assert!(fuzzy_score("vbh", "View: Byte Histogram").is_some());
+        assert!(fuzzy_score("HISTO", "View: Byte Histogram").is_some());
+        assert_eq!(fuzzy_score("hx", "View: Byte Histogram"), None);
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        // Word starts and consecutive characters rank higher
+        assert!(fuzzy_score("sa", "File: Save Session") > fuzzy_score("sa", "Analysis: Search"));
+    }
+
+    #[test]
+    fn filter_ranks_best_match_first() {
+        let entries = [
+            PaletteEntry::new(Command::ToggleDiff, "Analysis: Binary Diff", "F6"),
+            PaletteEntry::new(Command::ToggleDisassembly, "View: Disassembly", "F5"),
+            PaletteEntry::new(Command::ToggleHilbert, "View: Hilbert Curve", "F4"),
+        ];
+        assert_eq!(filter_entries(&entries, ""), vec![0, 1, 2]);
+        assert_eq!(filter_entries(&entries, "disas"), vec![1]);
+        assert_eq!(filter_entries(&entries, "curve"), vec![2]);
+    }
+
+    #[test]
+    fn shortcut_text_round_trips() {
+        for info in COMMANDS {
+            if let Some(shortcut) = info.shortcut {
+                assert_eq!(parse_shortcut(&shortcut_text(&shortcut)), Some(shortcut), "{}", info.name);
+            }
+        }
+        assert_eq!(shortcut_text(&ctrl_shift(Key::S).unwrap()), "Ctrl+Shift+S");
+        assert_eq!(parse_shortcut("Ctrl+Plus"), ctrl(Key::Plus));
+        assert_eq!(parse_shortcut("Hyper+F5"), None);
+        assert_eq!(parse_shortcut("Ctrl+"), None);
+        assert_eq!(split_name("View: Byte Histogram"), ("View", "Byte Histogram"));
+    }
+
+    #[test]
+    fn key_bindings_override_defaults() {
+        let mut bindings = KeyBindings::default();
+        let histogram = COMMANDS.iter().find(|i| i.command == Command::ToggleHistogram).unwrap();
+        assert_eq!(bindings.shortcut(histogram), key(Key::F8));
+
+        // Rebinding to a taken key reports the other command
+        assert_eq!(bindings.conflict(Command::ToggleHistogram, key(Key::F1).unwrap()), Some("View: File Info"));
+        // ...and the fixed keys too
+        assert_eq!(bindings.conflict(Command::ToggleHistogram, PALETTE_SHORTCUT), Some("Command palette"));
+        assert_eq!(bindings.conflict(Command::ToggleHistogram, EDIT_MODE_SHORTCUT), Some("Toggle edit mode"));
+        assert_eq!(bindings.conflict(Command::ToggleHistogram, WORKSPACE_SHORTCUTS[2]), Some("Switch workspace"));
+        assert_eq!(bindings.conflict(Command::ToggleHistogram, ctrl(Key::H).unwrap()), None);
+        bindings.set(Command::ToggleHistogram, ctrl(Key::H));
+        bindings.set(Command::ToggleMinimap, key(Key::M));
+        bindings.set(Command::ToggleXrefs, None);
+        assert_eq!(bindings.shortcut(histogram), ctrl(Key::H));
+        assert!(bindings.is_custom(Command::ToggleXrefs));
+
+        let config = bindings.to_config();
+        assert_eq!(config.get("View: Byte Histogram").map(String::as_str), Some("Ctrl+H"));
+        assert_eq!(config.get("View: Cross-References").map(String::as_str), Some(""));
+        assert_eq!(KeyBindings::from_config(&config), bindings);
+
+        // Back to the default: no longer an override
+        bindings.set(Command::ToggleHistogram, key(Key::F8));
+        assert!(!bindings.is_custom(Command::ToggleHistogram));
+    }
+
+    #[test]
+    fn registry_shortcuts_are_unique() {
+        let shortcuts: Vec<_> = COMMANDS.iter().filter_map(|info| info.shortcut).collect();
+        for (i, a) in shortcuts.iter().enumerate() {
+            assert!(!shortcuts[i + 1..].contains(a), "duplicate shortcut {:?}", a);
+            assert!(!FIXED_SHORTCUTS.iter().any(|fixed| fixed.keys.contains(a)), "fixed shortcut {:?}", a);
+        }
+    }
+
+    #[test]
+    fn pressed_shortcuts_are_normalized() {
+        let pressed = |modifiers| normalize_shortcut(KeyboardShortcut::new(modifiers, Key::K));
+        let ctrl_k = ctrl(Key::K).unwrap();
+        // Ctrl on Windows/Linux, Cmd on macOS
+        assert_eq!(pressed(Modifiers { ctrl: true, command: true, ..Modifiers::NONE }), ctrl_k);
+        assert_eq!(pressed(Modifiers { mac_cmd: true, command: true, ..Modifiers::NONE }), ctrl_k);
+        assert_eq!(pressed(Modifiers::NONE), key(Key::K).unwrap());
+        assert_eq!(shortcut_text(&pressed(Modifiers::ALT)), "Alt+K");
+    }
+}
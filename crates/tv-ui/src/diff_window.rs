@@ -1,6 +1,7 @@
 use egui::{Context, Color32, RichText, ScrollArea, text::LayoutJob, TextFormat, FontId};
-use crate::state::{AppState, LoadedFile};
-use tv_core::{FileRegion, MappedFile, ViewPort};
+use crate::file_dialog::{self, RememberDirectory};
+use crate::state::{AppState, GpuJob, LoadedFile};
+use tv_core::{FileRegion, ViewPort};
 
 /// Floating window for binary diff comparison.
 pub struct DiffWindow;
@@ -49,9 +50,9 @@ impl DiffWindow {
                 }
             } else {
                 if ui.button("Open file B...").clicked() {
-                    if let Some(path) = rfd::FileDialog::new().pick_file() {
-                        if let Ok(mapped) = MappedFile::open(&path) {
-                            state.diff.file_b = Some(LoadedFile { path, mapped });
+                    if let Some(path) = file_dialog::new().pick_file_remembered() {
+                        if let Ok(file) = LoadedFile::open(path) {
+                            state.diff.file_b = Some(file);
                             state.diff.viewport_b = ViewPort::new(0, 4096);
                             state.diff.sync_scroll = true;
                             state.diff.clear();
@@ -87,7 +88,7 @@ impl DiffWindow {
         ui.horizontal(|ui| {
             if state.diff.computing {
                 ui.spinner();
-                ui.label("Computing diff...");
+                ui.label(state.gpu_job_text(GpuJob::Diff, "Computing diff..."));
             } else {
                 if ui.button("Compute Diff").clicked() {
                     state.diff.computing = true;
@@ -1,7 +1,10 @@
 use egui::{Context, Color32, RichText, ScrollArea, TextStyle};
 use crate::state::AppState;
 use crate::cfg_window::{CfgState, CfgWindow};
-use tv_core::{Architecture, DisassemblyResult, disassemble, detect_architecture, FileRegion};
+use crate::call_graph_window::{CallGraphState, CallGraphWindow};
+use tv_core::{Architecture, DisassemblyResult, disassemble_mixed, disassemble_recursive, detect_architecture, FileRegion};
+use tv_core::{Comment, LabelType, ModeSwitch, Project, ThumbRanges, mode_switch_hint, symbolize_operands};
+use std::collections::HashMap;
 use tv_core::{ControlFlowGraph, CfgInstruction};
 
 /// State for the disassembly window.
@@ -12,6 +15,9 @@ pub struct DisasmState {
     pub auto_detect: bool,
     /// Number of instructions to disassemble.
     pub max_instructions: usize,
+    /// Follow jump and call targets from the start offset (recursive
+    /// descent) instead of decoding every byte in order (linear sweep).
+    pub recursive: bool,
     /// Current disassembly result.
     pub result: Option<DisassemblyResult>,
     /// Whether disassembly is in progress.
@@ -20,6 +26,11 @@ pub struct DisasmState {
     pub selected_idx: Option<usize>,
     /// Follow viewport: auto-disassemble at current offset.
     pub follow_viewport: bool,
+    /// Show the raw instruction bytes column.
+    pub show_bytes: bool,
+    /// Offset to disassemble at on the next pass instead of the viewport
+    /// (set when restoring a session).
+    pub restore_offset: Option<u64>,
     /// Last disassembled offset (to avoid re-computing).
     cached_offset: u64,
     /// Last file size (for cache invalidation).
@@ -28,22 +39,49 @@ pub struct DisasmState {
     pub cfg: CfgState,
     /// Show CFG window.
     pub show_cfg: bool,
+    /// Call graph state.
+    pub call_graph: CallGraphState,
+    /// Show call graph window.
+    pub show_call_graph: bool,
+    /// Regions decoded as Thumb when the architecture is 32-bit ARM.
+    pub thumb_ranges: ThumbRanges,
+    /// User comments keyed by instruction address (file offset).
+    pub instruction_comments: HashMap<u64, String>,
+    /// Set when a comment is added, edited or removed (cleared by the app after syncing).
+    pub comments_modified: bool,
+    /// Comment being edited inline: (address, text).
+    comment_edit: Option<(u64, String)>,
 }
 
+/// Bytes decoded per disassembly pass (and marked per Thumb toggle).
+const DISASM_WINDOW_BYTES: u64 = 4096;
+
+/// Color for inline instruction comments.
+const COMMENT_COLOR: Color32 = Color32::from_rgb(110, 170, 110);
+
 impl Default for DisasmState {
     fn default() -> Self {
         Self {
             arch: Architecture::X86_64,
             auto_detect: true,
             max_instructions: 100,
+            recursive: false,
             result: None,
             computing: false,
             selected_idx: None,
             follow_viewport: false,
+            show_bytes: true,
+            restore_offset: None,
             cached_offset: u64::MAX,
             cached_file_size: 0,
             cfg: CfgState::new(),
             show_cfg: false,
+            call_graph: CallGraphState::new(),
+            show_call_graph: false,
+            thumb_ranges: ThumbRanges::new(),
+            instruction_comments: HashMap::new(),
+            comments_modified: false,
+            comment_edit: None,
         }
     }
 }
@@ -55,18 +93,87 @@ impl DisasmState {
         self.cached_file_size = 0;
     }
 
+    /// Disassemble at `offset` on the next pass, with `arch` or, if `None`,
+    /// the architecture auto-detected from the file header.
+    pub fn open_at(&mut self, offset: u64, arch: Option<Architecture>) {
+        match arch {
+            Some(arch) => {
+                self.arch = arch;
+                self.auto_detect = false;
+            }
+            None => self.auto_detect = true,
+        }
+        self.follow_viewport = false;
+        self.restore_offset = Some(offset);
+        self.selected_idx = None;
+        self.invalidate();
+    }
+
     pub fn needs_recompute(&self, offset: u64, file_size: u64) -> bool {
         self.result.is_none()
             || self.cached_offset != offset
             || self.cached_file_size != file_size
     }
+
+    /// Set the comment for an instruction. An empty (or blank) text removes it.
+    pub fn set_comment(&mut self, address: u64, text: &str) {
+        let text = text.trim();
+        let changed = if text.is_empty() {
+            self.instruction_comments.remove(&address).is_some()
+        } else {
+            self.instruction_comments.insert(address, text.to_string()).as_deref() != Some(text)
+        };
+        self.comments_modified |= changed;
+    }
+
+    /// Replace instruction comments with the ones stored in a project.
+    pub fn load_comments(&mut self, project: &Project) {
+        self.instruction_comments = project
+            .comments
+            .iter()
+            .map(|c| (c.address, c.text.clone()))
+            .collect();
+        self.comment_edit = None;
+    }
+
+    /// Write instruction comments into a project (comments are keyed by file
+    /// offset there too, so the project's comment list is replaced).
+    pub fn store_comments(&self, project: &mut Project) {
+        let mut comments: Vec<Comment> = self
+            .instruction_comments
+            .iter()
+            .map(|(&address, text)| Comment { address, text: text.clone() })
+            .collect();
+        comments.sort_by_key(|c| c.address);
+        project.comments = comments;
+    }
+}
+
+/// Convert disassembled instructions for CFG / call graph analysis.
+fn cfg_instructions(result: &DisassemblyResult) -> Vec<CfgInstruction> {
+    result.instructions.iter()
+        .map(|i| CfgInstruction {
+            address: i.address,
+            size: i.bytes.len() as u8,
+            mnemonic: i.mnemonic.clone(),
+            operands: i.operands.clone(),
+            bytes: i.bytes.clone(),
+        })
+        .collect()
 }
 
 /// Floating window for disassembly view.
 pub struct DisasmWindow;
 
 impl DisasmWindow {
-    pub fn show(ctx: &Context, state: &mut AppState, disasm: &mut DisasmState, visible: &mut bool) {
+    /// `project` supplies labels used to resolve operand addresses to names.
+    pub fn show(
+        ctx: &Context,
+        state: &mut AppState,
+        disasm: &mut DisasmState,
+        project: Option<&Project>,
+        visible: &mut bool,
+    ) {
         if !*visible {
             return;
         }
@@ -77,14 +184,26 @@ impl DisasmWindow {
             .resizable(true)
             .collapsible(true)
             .show(ctx, |ui| {
-                Self::show_contents(ui, state, disasm);
+                Self::show_contents(ui, state, disasm, project);
             });
 
+        // Show call graph window if enabled; clicking a function opens its CFG
+        if let Some(addr) = CallGraphWindow::show(ctx, &mut disasm.call_graph, &mut disasm.show_call_graph) {
+            let body = disasm.call_graph.function_instructions(addr);
+            if !body.is_empty() {
+                let cfg = ControlFlowGraph::build(body, addr);
+                disasm.cfg.clear();
+                disasm.cfg.reset_view();
+                disasm.cfg.cfg = Some(cfg);
+                disasm.show_cfg = true;
+            }
+        }
+
         // Show CFG window if enabled
         CfgWindow::show(ctx, &mut disasm.cfg, &mut disasm.show_cfg);
     }
 
-    fn show_contents(ui: &mut egui::Ui, state: &mut AppState, disasm: &mut DisasmState) {
+    fn show_contents(ui: &mut egui::Ui, state: &mut AppState, disasm: &mut DisasmState, project: Option<&Project>) {
         if !state.has_file() {
             ui.label("Open a file to disassemble.");
             return;
@@ -137,6 +256,40 @@ impl DisasmWindow {
 
             ui.separator();
 
+            egui::ComboBox::from_id_salt("disasm_mode")
+                .selected_text(if disasm.recursive { "Recursive descent" } else { "Linear sweep" })
+                .show_ui(ui, |ui| {
+                    let linear = ui.selectable_value(&mut disasm.recursive, false, "Linear sweep")
+                        .on_hover_text("Decode every byte in order from the offset");
+                    let recursive = ui.selectable_value(&mut disasm.recursive, true, "Recursive descent")
+                        .on_hover_text("Decode only code reached through fall-through, jumps and calls (x86), skipping data in between");
+                    if linear.changed() || recursive.changed() {
+                        disasm.invalidate();
+                    }
+                });
+
+            ui.separator();
+
+            // Thumb toggle for the region starting at the current offset (ARM only)
+            if disasm.result.as_ref().is_some_and(|r| r.arch == Architecture::Arm32) {
+                let mut thumb = disasm.thumb_ranges.contains(current_offset);
+                if ui.checkbox(&mut thumb, "Thumb")
+                    .on_hover_text("Decode the next 4 KB from this offset as Thumb")
+                    .changed()
+                {
+                    let end = current_offset.saturating_add(DISASM_WINDOW_BYTES);
+                    if thumb {
+                        disasm.thumb_ranges.add(current_offset, end);
+                    } else {
+                        disasm.thumb_ranges.remove(current_offset, end);
+                    }
+                    disasm.invalidate();
+                }
+                ui.separator();
+            }
+
+            ui.checkbox(&mut disasm.show_bytes, "Bytes");
+
             // Follow viewport toggle
             ui.checkbox(&mut disasm.follow_viewport, "Follow");
             if ui.button("Refresh").clicked() {
@@ -146,27 +299,33 @@ impl DisasmWindow {
             ui.separator();
 
             // Show CFG button
-            if disasm.result.is_some() {
+            if let Some(ref result) = disasm.result {
                 if ui.button("Show CFG").clicked() {
                     // Build CFG from current disassembly
-                    if let Some(ref result) = disasm.result {
-                        let cfg_instructions: Vec<CfgInstruction> = result.instructions.iter()
-                            .map(|i| CfgInstruction {
-                                address: i.address,
-                                size: i.bytes.len() as u8,
-                                mnemonic: i.mnemonic.clone(),
-                                operands: i.operands.clone(),
-                                bytes: i.bytes.clone(),
-                            })
-                            .collect();
-
-                        if !cfg_instructions.is_empty() {
-                            let entry = cfg_instructions[0].address;
-                            disasm.cfg.cfg = Some(ControlFlowGraph::build(&cfg_instructions, entry));
-                            disasm.show_cfg = true;
-                        }
+                    let cfg_instructions = cfg_instructions(result);
+                    if !cfg_instructions.is_empty() {
+                        let entry = cfg_instructions[0].address;
+                        disasm.cfg.cfg = Some(ControlFlowGraph::build(&cfg_instructions, entry));
+                        disasm.show_cfg = true;
                     }
                 }
+
+                if ui.button("Call Graph")
+                    .on_hover_text("Functions in this disassembly and the calls between them")
+                    .clicked()
+                {
+                    // Function labels name nodes and mark additional function starts
+                    let names: HashMap<u64, String> = project
+                        .map(|p| {
+                            p.labels.iter()
+                                .filter(|l| l.label_type == LabelType::Function)
+                                .map(|l| (l.address, l.name.clone()))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    disasm.call_graph.build(cfg_instructions(result), names);
+                    disasm.show_call_graph = true;
+                }
             }
         });
 
@@ -174,7 +333,8 @@ impl DisasmWindow {
 
         // Offset display
         ui.horizontal(|ui| {
-            ui.label(format!("Offset: 0x{:X}", current_offset));
+            let shown_offset = disasm.result.as_ref().map_or(current_offset, |r| r.base_address);
+            ui.label(format!("Offset: 0x{:X}", shown_offset));
             if let Some(ref result) = disasm.result {
                 ui.weak(format!("({} instructions, {} bytes)",
                     result.instructions.len(),
@@ -187,9 +347,18 @@ impl DisasmWindow {
         let should_compute = disasm.follow_viewport && disasm.needs_recompute(current_offset, file_size);
 
         if should_compute || (disasm.result.is_none() && !disasm.computing) {
+            // A restored session starts where it left off, unless following the viewport
+            let start = match disasm.restore_offset.take() {
+                Some(offset) if !should_compute && offset < file_size => offset,
+                _ => current_offset,
+            };
+
             // Perform disassembly
             if let Some(ref file) = state.file {
-                let data = file.mapped.slice(FileRegion::new(current_offset, 4096.min(file_size - current_offset)));
+                let data = file.mapped.slice(FileRegion::new(
+                    start,
+                    DISASM_WINDOW_BYTES.min(file_size - start),
+                ));
 
                 // Detect or use selected architecture
                 let arch = if disasm.auto_detect {
@@ -200,16 +369,21 @@ impl DisasmWindow {
                     disasm.arch
                 };
 
-                match disassemble(data, current_offset, arch, disasm.max_instructions) {
+                let result = if disasm.recursive {
+                    disassemble_recursive(data, start, arch, disasm.max_instructions, &disasm.thumb_ranges)
+                } else {
+                    disassemble_mixed(data, start, arch, disasm.max_instructions, &disasm.thumb_ranges)
+                };
+                match result {
                     Ok(result) => {
                         disasm.result = Some(result);
-                        disasm.cached_offset = current_offset;
+                        disasm.cached_offset = start;
                         disasm.cached_file_size = file_size;
                     }
                     Err(e) => {
                         disasm.result = Some(DisassemblyResult {
                             arch,
-                            base_address: current_offset,
+                            base_address: start,
                             instructions: vec![],
                             bytes_consumed: 0,
                             error: Some(e.to_string()),
@@ -241,29 +415,56 @@ impl DisasmWindow {
         if let Some(result) = disasm.result.clone() {
             let mut new_selected = disasm.selected_idx;
             let mut new_viewport: Option<u64> = None;
+            let mut follow_switch: Option<(u64, bool)> = None;
+            let labels: HashMap<u64, &str> = project
+                .map(|p| p.labels.iter().map(|l| (l.address, l.name.as_str())).collect())
+                .unwrap_or_default();
 
             ScrollArea::vertical()
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
-                    let (sel, vp) = Self::show_instructions(ui, &result, disasm.selected_idx);
+                    let (sel, vp, switch) = Self::show_instructions(ui, &result, disasm, &labels);
                     new_selected = sel;
                     new_viewport = vp;
+                    follow_switch = switch;
                 });
 
             disasm.selected_idx = new_selected;
             if let Some(offset) = new_viewport {
                 state.viewport.start = offset;
             }
+
+            // Follow a BLX into the other mode: mark the target region and jump there
+            if let Some((target, thumb)) = follow_switch {
+                let end = target.saturating_add(DISASM_WINDOW_BYTES);
+                if thumb {
+                    disasm.thumb_ranges.add(target, end);
+                } else {
+                    disasm.thumb_ranges.remove(target, end);
+                }
+                state.viewport.start = target;
+                disasm.invalidate();
+            }
         }
     }
 
+    /// Returns the clicked instruction, the offset to navigate to, and a
+    /// mode-switch target to follow (`(address, is_thumb)`).
+    ///
+    /// Double-clicking a row edits its comment inline.
     fn show_instructions(
         ui: &mut egui::Ui,
         result: &DisassemblyResult,
-        selected_idx: Option<usize>,
-    ) -> (Option<usize>, Option<u64>) {
+        disasm: &mut DisasmState,
+        labels: &HashMap<u64, &str>,
+    ) -> (Option<usize>, Option<u64>, Option<(u64, bool)>) {
+        let selected_idx = disasm.selected_idx;
         let mut new_selected = selected_idx;
+        let mut end_edit = false;
+        let mut save_comment: Option<(u64, String)> = None;
         let mut new_viewport: Option<u64> = None;
+        let mut follow_switch: Option<(u64, bool)> = None;
+        let is_arm32 = result.arch == Architecture::Arm32;
 
         // Use monospace font
         let mono_style = TextStyle::Monospace;
@@ -271,6 +472,18 @@ impl DisasmWindow {
         for (idx, insn) in result.instructions.iter().enumerate() {
             let is_selected = selected_idx == Some(idx);
 
+            // Bytes skipped by recursive descent
+            if let Some(prev) = idx.checked_sub(1).map(|i| &result.instructions[i]) {
+                let gap = insn.address.saturating_sub(prev.address + prev.bytes.len() as u64);
+                if gap > 0 {
+                    ui.label(
+                        RichText::new(format!("; ... {} byte(s) not reached", gap))
+                            .color(Color32::from_rgb(140, 140, 140))
+                            .text_style(mono_style.clone()),
+                    );
+                }
+            }
+
             let response = ui.horizontal(|ui| {
                 // Address column
                 let addr_text = RichText::new(format!("{:08X}", insn.address))
@@ -281,13 +494,15 @@ impl DisasmWindow {
                 ui.add_space(8.0);
 
                 // Bytes column (fixed width)
-                let bytes_hex = insn.bytes_hex();
-                let bytes_text = RichText::new(format!("{:24}", bytes_hex))
-                    .color(Color32::from_rgb(120, 120, 120))
-                    .text_style(mono_style.clone());
-                ui.label(bytes_text);
-
-                ui.add_space(8.0);
+                if disasm.show_bytes {
+                    let bytes_hex = insn.bytes_hex();
+                    let bytes_text = RichText::new(format!("{:24}", bytes_hex))
+                        .color(Color32::from_rgb(120, 120, 120))
+                        .text_style(mono_style.clone());
+                    ui.label(bytes_text);
+
+                    ui.add_space(8.0);
+                }
 
                 // Mnemonic column
                 let mnemonic_color = Self::mnemonic_color(&insn.mnemonic);
@@ -296,11 +511,78 @@ impl DisasmWindow {
                     .text_style(mono_style.clone());
                 ui.label(mnemonic_text);
 
-                // Operands column
-                let operands_text = RichText::new(&insn.operands)
-                    .color(Color32::from_rgb(200, 200, 200))
-                    .text_style(mono_style.clone());
-                ui.label(operands_text);
+                // Operands column (addresses with a project label show the label name)
+                match symbolize_operands(&insn.operands, |addr| labels.get(&addr).copied()) {
+                    Some(resolved) => {
+                        let operands_text = RichText::new(resolved)
+                            .color(Color32::from_rgb(230, 190, 120))
+                            .text_style(mono_style.clone());
+                        ui.label(operands_text).on_hover_text(&insn.operands);
+                    }
+                    None => {
+                        let operands_text = RichText::new(&insn.operands)
+                            .color(Color32::from_rgb(200, 200, 200))
+                            .text_style(mono_style.clone());
+                        ui.label(operands_text);
+                    }
+                }
+
+                // ARM/Thumb interworking hints
+                if is_arm32 {
+                    match mode_switch_hint(insn, disasm.thumb_ranges.contains(insn.address)) {
+                        Some(ModeSwitch::Direct { target, thumb }) => {
+                            let mode = if thumb { "Thumb" } else { "ARM" };
+                            let hint = RichText::new(format!("; -> {}", mode))
+                                .color(Color32::from_rgb(220, 180, 100))
+                                .text_style(mono_style.clone());
+                            if ui.add(egui::Label::new(hint).sense(egui::Sense::click()))
+                                .on_hover_text(format!("Decode 0x{:X} as {} and jump there", target, mode))
+                                .clicked()
+                            {
+                                follow_switch = Some((target, thumb));
+                            }
+                        }
+                        Some(ModeSwitch::Register) => {
+                            ui.label(
+                                RichText::new("; interworking")
+                                    .color(Color32::from_rgb(140, 140, 140))
+                                    .text_style(mono_style.clone()),
+                            )
+                            .on_hover_text("Target mode is chosen by bit 0 of the register");
+                        }
+                        None => {}
+                    }
+                }
+
+                // Comment column: inline editor or saved comment
+                let editing = disasm.comment_edit.as_ref().is_some_and(|(addr, _)| *addr == insn.address);
+                if editing {
+                    if let Some((addr, text)) = disasm.comment_edit.as_mut() {
+                        ui.label(RichText::new(";").color(COMMENT_COLOR).text_style(mono_style.clone()));
+                        let edit = ui.add(
+                            egui::TextEdit::singleline(text)
+                                .font(mono_style.clone())
+                                .hint_text("comment")
+                                .desired_width(220.0),
+                        );
+                        if !edit.has_focus() && !edit.lost_focus() {
+                            edit.request_focus();
+                        }
+                        if edit.lost_focus() {
+                            end_edit = true;
+                            // Escape cancels, anything else saves
+                            if !ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                save_comment = Some((*addr, text.clone()));
+                            }
+                        }
+                    }
+                } else if let Some(comment) = disasm.instruction_comments.get(&insn.address) {
+                    ui.label(
+                        RichText::new(format!("; {}", comment))
+                            .color(COMMENT_COLOR)
+                            .text_style(mono_style.clone()),
+                    );
+                }
             });
 
             // Make the row clickable
@@ -321,6 +603,10 @@ impl DisasmWindow {
                 new_selected = Some(idx);
                 new_viewport = Some((insn.address / 16) * 16);
             }
+            if row_response.double_clicked() {
+                let text = disasm.instruction_comments.get(&insn.address).cloned().unwrap_or_default();
+                disasm.comment_edit = Some((insn.address, text));
+            }
 
             // Hover effect
             if row_response.hovered() {
@@ -336,7 +622,14 @@ impl DisasmWindow {
             ui.colored_label(Color32::GRAY, "No instructions decoded.");
         }
 
-        (new_selected, new_viewport)
+        if let Some((addr, text)) = save_comment {
+            disasm.set_comment(addr, &text);
+        }
+        if end_edit {
+            disasm.comment_edit = None;
+        }
+
+        (new_selected, new_viewport, follow_switch)
     }
 
     /// Color-code mnemonics by category.
@@ -381,3 +674,53 @@ impl DisasmWindow {
         Color32::from_rgb(220, 220, 220)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_comment_tracks_changes_and_removal() {
+        let mut disasm = DisasmState::default();
+        disasm.set_comment(0x401000, "  decrypt key  ");
+        assert_eq!(disasm.instruction_comments.get(&0x401000).map(String::as_str), Some("decrypt key"));
+        assert!(disasm.comments_modified);
+
+        disasm.comments_modified = false;
+        disasm.set_comment(0x401000, "decrypt key");
+        assert!(!disasm.comments_modified, "unchanged text is not a modification");
+
+        disasm.set_comment(0x401000, "   ");
+        assert!(disasm.instruction_comments.is_empty());
+        assert!(disasm.comments_modified);
+    }
+
+    #[test]
+    fn open_at_sets_offset_and_architecture() {
+        let mut disasm = DisasmState { follow_viewport: true, ..Default::default() };
+        disasm.open_at(0x400, Some(Architecture::Arm64));
+        assert_eq!((disasm.restore_offset, disasm.arch, disasm.auto_detect), (Some(0x400), Architecture::Arm64, false));
+        assert!(!disasm.follow_viewport, "the viewport would override the offset");
+        assert!(disasm.result.is_none());
+
+        disasm.open_at(0x800, None);
+        assert!(disasm.auto_detect);
+        assert_eq!(disasm.restore_offset, Some(0x800));
+    }
+
+    #[test]
+    fn comments_round_trip_through_project() {
+        let mut disasm = DisasmState::default();
+        disasm.set_comment(0x20, "second");
+        disasm.set_comment(0x10, "first");
+
+        let mut project = Project::new(std::path::Path::new("a.bin"), 0x100);
+        disasm.store_comments(&mut project);
+        let addrs: Vec<u64> = project.comments.iter().map(|c| c.address).collect();
+        assert_eq!(addrs, vec![0x10, 0x20]);
+
+        let mut restored = DisasmState::default();
+        restored.load_comments(&project);
+        assert_eq!(restored.instruction_comments, disasm.instruction_comments);
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
+use crate::session::format_timestamp;
 use crate::state::AppState;
-use tv_core::BlockClass;
+use tv_core::{BlockClass, ByteHistogram, DisassemblyResult};
 
 /// Generate a JSON report of the current analysis.
 pub fn export_json(state: &AppState) -> String {
@@ -9,8 +12,17 @@ pub fn export_json(state: &AppState) -> String {
     json.push_str(&format!("  \"file\": {{\n"));
     json.push_str(&format!("    \"name\": {:?},\n", state.file_name()));
     json.push_str(&format!("    \"path\": {:?},\n", state.file_path_display()));
-    json.push_str(&format!("    \"size\": {}\n", state.file_len()));
-    json.push_str("  },\n");
+    json.push_str(&format!("    \"size\": {}", state.file_len()));
+    if let Some(meta) = state.file_metadata {
+        for (key, time) in [("created", meta.created), ("modified", meta.modified), ("accessed", meta.accessed)] {
+            match time {
+                Some(secs) => json.push_str(&format!(",\n    \"{}\": {:?}", key, format_timestamp(secs))),
+                None => json.push_str(&format!(",\n    \"{}\": null", key)),
+            }
+        }
+        json.push_str(&format!(",\n    \"permissions\": {:?}", meta.permissions_label()));
+    }
+    json.push_str("\n  },\n");
 
     // Entropy summary
     if let Some(ref entropy) = state.entropy {
@@ -118,6 +130,33 @@ pub fn export_signatures_csv(state: &AppState) -> String {
     csv
 }
 
+/// Generate a CSV of byte value counts.
+pub fn export_histogram_csv(histogram: &ByteHistogram) -> String {
+    let mut csv = String::from("byte_dec,byte_hex,count,percent\n");
+    for (byte, &count) in histogram.counts.iter().enumerate() {
+        csv.push_str(&format!(
+            "{},0x{:02X},{},{:.4}\n",
+            byte, byte, count, histogram.frequency(byte as u8) * 100.0
+        ));
+    }
+    csv
+}
+
+/// Generate a plain-text listing of a disassembly, with instruction comments.
+pub fn export_disasm_text(result: &DisassemblyResult, comments: &HashMap<u64, String>) -> String {
+    let mut text = format!("; {} @ 0x{:X}\n", result.arch.label(), result.base_address);
+    for insn in &result.instructions {
+        let mut line = format!("{:08X}  {:24}  {}", insn.address, insn.bytes_hex(), insn.text());
+        if let Some(comment) = comments.get(&insn.address) {
+            line.push_str("  ; ");
+            line.push_str(comment);
+        }
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +170,25 @@ mod tests {
         assert!(json.contains("\"size\": 0"));
     }
 
+    #[test]
+    fn json_export_file_metadata() {
+        let mut state = AppState::default();
+        state.file_metadata = Some(tv_core::FileMetadata {
+            created: None,
+            modified: Some(86400),
+            accessed: Some(0),
+            readonly: false,
+            mode: Some(0o644),
+        });
+        let json = export_json(&state);
+        assert!(json.contains("\"size\": 0,\n"));
+        assert!(json.contains("\"created\": null"));
+        assert!(json.contains("\"modified\": \"1970-01-02T00:00:00Z\""));
+        assert!(json.contains("\"accessed\": \"1970-01-01T00:00:00Z\""));
+        // Last section of the report: no trailing comma
+        assert!(json.contains("\"permissions\": \"rw-r--r-- (0644)\"\n  }\n}"));
+    }
+
     #[test]
     fn csv_search_empty() {
         let state = AppState::default();
@@ -144,4 +202,28 @@ mod tests {
         let csv = export_signatures_csv(&state);
         assert_eq!(csv, "offset_dec,offset_hex,name,magic\n");
     }
+
+    #[test]
+    fn csv_histogram_lists_every_byte() {
+        let csv = export_histogram_csv(&ByteHistogram::from_data(b"AAAB"));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 257);
+        assert_eq!(lines[0], "byte_dec,byte_hex,count,percent");
+        assert_eq!(lines[1], "0,0x00,0,0.0000");
+        assert_eq!(lines[66], "65,0x41,3,75.0000");
+        assert_eq!(lines[67], "66,0x42,1,25.0000");
+    }
+
+    #[test]
+    fn disasm_text_includes_comments() {
+        let result = tv_core::disassemble(&[0x55, 0xC3], 0x1000, tv_core::Architecture::X86_64, 10).unwrap();
+        let mut comments = HashMap::new();
+        comments.insert(0x1001, "done".to_string());
+
+        let text = export_disasm_text(&result, &comments);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "; x86-64 @ 0x1000");
+        assert!(lines[1].starts_with("00001000  55") && lines[1].ends_with("push rbp"));
+        assert!(lines[2].ends_with("ret  ; done"), "got {:?}", lines[2]);
+    }
 }
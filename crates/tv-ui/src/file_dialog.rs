@@ -0,0 +1,218 @@
+//! File dialogs that open in the last-used directory.
+//!
+//! The directory of the last file opened or saved (through a dialog, drag and
+//! drop or the command line) is kept in `config.json` under the user config
+//! directory, so it survives restarts. The same file keeps the user's
+//! keyboard shortcut overrides and GPU memory budget.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// Persisted user configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Directory of the last opened or saved file.
+    pub last_directory: Option<PathBuf>,
+    /// Rebound shortcuts: command name -> shortcut text (empty = unbound).
+    pub key_bindings: BTreeMap<String, String>,
+    /// Per-dispatch GPU memory above which a warning is shown (bytes).
+    pub gpu_memory_budget: Option<u64>,
+}
+
+impl Config {
+    /// Load the configuration, falling back to defaults if missing or invalid.
+    pub fn load() -> Self {
+        config_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the configuration, creating the config directory if needed.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = config_path().ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// `config.json` in the platform config directory.
+fn config_path() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|dir| dir.join("titanview").join("config.json"))
+}
+
+fn config() -> &'static Mutex<Config> {
+    static CONFIG: OnceLock<Mutex<Config>> = OnceLock::new();
+    CONFIG.get_or_init(|| Mutex::new(Config::load()))
+}
+
+/// Directory to remember for a picked path: the path itself for folders,
+/// otherwise its parent.
+fn directory_of(path: &Path) -> Option<PathBuf> {
+    if path.is_dir() {
+        Some(path.to_path_buf())
+    } else {
+        path.parent().filter(|p| !p.as_os_str().is_empty()).map(Path::to_path_buf)
+    }
+}
+
+/// Keyboard shortcut overrides saved in the config.
+pub fn key_bindings() -> BTreeMap<String, String> {
+    config().lock().map(|c| c.key_bindings.clone()).unwrap_or_default()
+}
+
+/// Save the keyboard shortcut overrides.
+pub fn save_key_bindings(bindings: BTreeMap<String, String>) {
+    let Ok(mut config) = config().lock() else { return };
+    if config.key_bindings != bindings {
+        config.key_bindings = bindings;
+        if let Err(e) = config.save() {
+            log::warn!("Failed to save config: {}", e);
+        }
+    }
+}
+
+/// GPU memory budget saved in the config.
+pub fn gpu_memory_budget() -> Option<u64> {
+    config().lock().ok().and_then(|c| c.gpu_memory_budget)
+}
+
+/// Save the GPU memory budget.
+pub fn save_gpu_memory_budget(budget: Option<u64>) {
+    let Ok(mut config) = config().lock() else { return };
+    if config.gpu_memory_budget != budget {
+        config.gpu_memory_budget = budget;
+        if let Err(e) = config.save() {
+            log::warn!("Failed to save config: {}", e);
+        }
+    }
+}
+
+/// Remember the directory of an opened or saved file for the next dialog.
+pub fn remember(path: &Path) {
+    let Some(dir) = directory_of(path) else { return };
+    let Ok(mut config) = config().lock() else { return };
+    if config.last_directory.as_ref() != Some(&dir) {
+        config.last_directory = Some(dir);
+        if let Err(e) = config.save() {
+            log::warn!("Failed to save config: {}", e);
+        }
+    }
+}
+
+/// New file dialog starting in the last-used directory.
+pub fn new() -> rfd::FileDialog {
+    let dialog = rfd::FileDialog::new();
+    let last = config().lock().ok().and_then(|c| c.last_directory.clone());
+    match last {
+        Some(dir) if dir.is_dir() => dialog.set_directory(dir),
+        _ => dialog,
+    }
+}
+
+/// Check that `path` is an existing regular file or device (e.g. `/dev/sdb`)
+/// before trying to open it.
+pub fn check_openable(path: &Path) -> Result<(), String> {
+    let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.is_dir() => Err(format!("{} is a folder, not a file", name)),
+        Ok(meta) if !meta.is_file() && !is_device(&meta.file_type()) => {
+            Err(format!("{} is not a regular file or device", name))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Cannot open {}: {}", name, e)),
+    }
+}
+
+/// Block device, or on Linux also a character device (e.g. `/dev/mem`).
+#[cfg(unix)]
+fn is_device(file_type: &std::fs::FileType) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    file_type.is_block_device() || (cfg!(target_os = "linux") && file_type.is_char_device())
+}
+
+/// Devices are opened by path on Unix only.
+#[cfg(not(unix))]
+fn is_device(_file_type: &std::fs::FileType) -> bool {
+    false
+}
+
+/// Dialog actions that remember the chosen location.
+pub trait RememberDirectory {
+    fn pick_file_remembered(self) -> Option<PathBuf>;
+    fn save_file_remembered(self) -> Option<PathBuf>;
+    fn pick_folder_remembered(self) -> Option<PathBuf>;
+}
+
+impl RememberDirectory for rfd::FileDialog {
+    fn pick_file_remembered(self) -> Option<PathBuf> {
+        let path = self.pick_file()?;
+        remember(&path);
+        Some(path)
+    }
+
+    fn save_file_remembered(self) -> Option<PathBuf> {
+        let path = self.save_file()?;
+        remember(&path);
+        Some(path)
+    }
+
+    fn pick_folder_remembered(self) -> Option<PathBuf> {
+        let path = self.pick_folder()?;
+        remember(&path);
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directory_of_files_and_folders() {
+        let tmp = std::env::temp_dir();
+        assert_eq!(directory_of(&tmp), Some(tmp.clone()));
+        assert_eq!(directory_of(&tmp.join("sample.bin")), Some(tmp.clone()));
+        assert_eq!(directory_of(Path::new("sample.bin")), None);
+
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn check_openable_rejects_folders_and_missing_files() {
+        let tmp = std::env::temp_dir();
+        assert!(check_openable(&tmp).unwrap_err().contains("is a folder"));
+        let missing = tmp.join(format!("tv_ui_missing_{}.bin", std::process::id()));
+        assert!(check_openable(&missing).unwrap_err().starts_with("Cannot open tv_ui_missing_"));
+
+        let file = tmp.join(format!("tv_ui_openable_{}.bin", std::process::id()));
+        std::fs::write(&file, b"data").unwrap();
+        assert_eq!(check_openable(&file), Ok(()));
+        std::fs::remove_file(&file).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn check_openable_accepts_devices() {
+        // Devices get past the check and open through `MappedFile::open_device`
+        let device = Path::new("/dev/null");
+        assert_eq!(check_openable(device), Ok(()));
+        let file = crate::state::LoadedFile::open(device.to_path_buf()).unwrap();
+        assert!(file.mapped.is_empty());
+    }
+}
@@ -1,6 +1,15 @@
+use std::sync::atomic::Ordering;
+
 use egui::{Context, Color32};
-use tv_core::BlockClass;
-use crate::state::AppState;
+use tv_core::{BlockClass, ByteHistogram, HashVerdict};
+use crate::file_dialog::{self, RememberDirectory};
+use crate::session::format_timestamp;
+use crate::state::{classification_block_sizes, AnalysisMode, AppState};
+
+/// A byte value is a spike when it occurs this many times more often than uniform.
+const SPIKE_FACTOR: f64 = 8.0;
+/// Maximum number of spikes listed under the sparkline.
+const MAX_SPIKES_SHOWN: usize = 4;
 
 /// Floating window for file metadata and analysis summary.
 pub struct FileInfoWindow;
@@ -21,6 +30,36 @@ impl FileInfoWindow {
             });
     }
 
+    /// Ask how to analyze a file larger than `LARGE_FILE_THRESHOLD`.
+    pub fn show_analysis_prompt(ctx: &Context, state: &mut AppState) {
+        if !state.analysis_prompt || !state.has_file() {
+            return;
+        }
+
+        let file_len = state.file_len();
+        let sampled = AnalysisMode::sampled_for(file_len);
+        egui::Window::new("Large File")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("{} is {}.", state.file_name(), format_size(file_len)));
+                ui.label("Entropy and classification of the whole file may take a long time.");
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Full analysis").clicked() {
+                        state.analysis_request = Some(AnalysisMode::Full);
+                    }
+                    if ui.button(format!("Sampled (1 block in {})", sampled.stride()))
+                        .on_hover_text("Fast overview; refine from File Info (F1)")
+                        .clicked()
+                    {
+                        state.analysis_request = Some(sampled);
+                    }
+                });
+            });
+    }
+
     fn show_contents(ui: &mut egui::Ui, state: &mut AppState) {
         if !state.has_file() {
             ui.label("No file loaded.");
@@ -44,8 +83,124 @@ impl FileInfoWindow {
                 ui.strong("Size:");
                 ui.label(format_size(state.file_len()));
                 ui.end_row();
+
+                ui.strong("Encoding:");
+                match state.text_encoding {
+                    Some(encoding) => {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} text", encoding.label()));
+                            if let Some(text_encoding) = encoding.charset.text_encoding() {
+                                if ui.small_button("Text View")
+                                    .on_hover_text(format!("Read the file as {}", text_encoding.label()))
+                                    .clicked()
+                                {
+                                    state.text_view_request = Some(text_encoding);
+                                }
+                            }
+                        });
+                    }
+                    None => {
+                        ui.weak("Binary");
+                    }
+                }
+                ui.end_row();
+
+                if let Some(structure) = &state.structure {
+                    ui.strong("Structure:");
+                    let summary = format!(
+                        "{}, {} {}(s)",
+                        structure.format.label(),
+                        structure.entries.len(),
+                        structure.format.entry_label()
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(summary);
+                        if ui.small_button("Structure")
+                            .on_hover_text("List them with their offsets and sizes")
+                            .clicked()
+                        {
+                            state.structure_window_request = true;
+                        }
+                    });
+                    ui.end_row();
+                }
+
+                if let Some(meta) = state.file_metadata {
+                    for (label, time) in [("Created:", meta.created), ("Modified:", meta.modified), ("Accessed:", meta.accessed)] {
+                        ui.strong(label);
+                        match time {
+                            Some(secs) => ui.label(format_timestamp(secs)),
+                            None => ui.weak("Not available"),
+                        };
+                        ui.end_row();
+                    }
+
+                    ui.strong("Permissions:");
+                    ui.monospace(meta.permissions_label());
+                    ui.end_row();
+                }
+
+                match state.file_digests {
+                    Some(ref digests) => {
+                        for (name, hash) in digests.named() {
+                            ui.strong(format!("{}:", name));
+                            ui.horizontal(|ui| {
+                                ui.monospace(format!("{}…", &hash[..16])).on_hover_text(hash);
+                                if ui.small_button("Copy").clicked() {
+                                    ui.ctx().copy_text(hash.to_string());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    }
+                    None => {
+                        ui.strong("Hashes:");
+                        ui.horizontal(|ui| match state.file_hash_progress {
+                            Some(fraction) => {
+                                ui.add(egui::ProgressBar::new(fraction).desired_width(120.0).show_percentage());
+                                if ui.small_button("Cancel").clicked() {
+                                    state.file_hash_cancel.store(true, Ordering::Relaxed);
+                                }
+                            }
+                            None => {
+                                ui.weak("Not computed");
+                                if ui.small_button("Hash").on_hover_text("MD5, SHA-1 and SHA-256 in one pass").clicked() {
+                                    state.file_hash_request = true;
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                }
+
+                ui.strong("Known hashes:");
+                match (&state.file_digests, state.hash_db.is_empty()) {
+                    (_, true) => ui.weak("No hash list loaded"),
+                    (None, false) => ui.weak("Waiting for hashes..."),
+                    (Some(digests), false) => {
+                        let verdict = state.hash_db.check_digests(digests);
+                        ui.colored_label(verdict_color(verdict), verdict.label())
+                    }
+                };
+                ui.end_row();
             });
 
+        Self::show_hash_lists(ui, state);
+
+        ui.add_space(12.0);
+
+        // Byte distribution sparkline
+        ui.heading("Byte Distribution");
+        match state.byte_histogram {
+            Some(ref hist) => Self::draw_byte_sparkline(ui, hist),
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.weak("Computing...");
+                });
+            }
+        }
+
         ui.add_space(12.0);
 
         // Entropy section
@@ -57,6 +212,14 @@ impl FileInfoWindow {
                 ui.colored_label(entropy_color, format!("{:.2} bits/byte", stats.avg));
             });
             ui.label(format!("{} blocks analyzed (256 bytes each)", stats.block_count));
+            if let AnalysisMode::Sampled { stride } = state.analysis_mode {
+                ui.horizontal(|ui| {
+                    ui.weak(format!("Sampled: 1 block in {}", stride));
+                    if ui.button("Refine").on_hover_text("Analyze every block").clicked() {
+                        state.analysis_request = Some(AnalysisMode::Full);
+                    }
+                });
+            }
 
             // Entropy bar
             let normalized = stats.avg / 8.0;
@@ -100,6 +263,162 @@ impl FileInfoWindow {
                 ui.add_space(8.0);
                 Self::draw_class_bar(ui, &counts, total);
             }
+            Self::show_class_block_size(ui, state);
+        }
+    }
+
+    /// Block size of the classification, with a menu recomputing it alone
+    /// at another size (entropy is kept).
+    fn show_class_block_size(ui: &mut egui::Ui, state: &mut AppState) {
+        let current = state.class_block_size();
+        ui.horizontal(|ui| {
+            ui.label("Block size:");
+            if let Some(size) = state.classification_request {
+                ui.spinner();
+                ui.weak(format!("Recomputing at {} bytes...", size));
+                return;
+            }
+            ui.menu_button(format!("{} bytes", current), |ui| {
+                for size in classification_block_sizes(state.file_len()) {
+                    if ui.selectable_label(size == current, format!("{} bytes", size)).clicked() {
+                        if size != current {
+                            state.classification_request = Some(size);
+                        }
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Recompute the classification only, at another block size");
+        });
+        ui.weak("Finer blocks separate adjacent regions but are slower to classify.");
+    }
+
+    /// Load/clear buttons for the known-good and known-bad hash lists.
+    fn show_hash_lists(ui: &mut egui::Ui, state: &mut AppState) {
+        ui.horizontal(|ui| {
+            if ui.button("Load known-good list...").on_hover_text("Allowlist of SHA-256 hashes, one per line").clicked() {
+                Self::load_hash_list(state, false);
+            }
+            if ui.button("Load known-bad list...").on_hover_text("Blocklist of SHA-256 hashes, one per line").clicked() {
+                Self::load_hash_list(state, true);
+            }
+            if !state.hash_db.is_empty() && ui.button("Clear").clicked() {
+                state.hash_db = Default::default();
+                state.hash_db_status = None;
+            }
+        });
+        if !state.hash_db.is_empty() {
+            ui.weak(format!(
+                "{} known good, {} known bad",
+                state.hash_db.known_good.len(),
+                state.hash_db.known_bad.len()
+            ));
+        }
+        if let Some((msg, is_error)) = &state.hash_db_status {
+            let color = if *is_error { Color32::from_rgb(255, 100, 100) } else { Color32::from_rgb(100, 200, 100) };
+            ui.colored_label(color, msg);
+        }
+    }
+
+    /// Pick a hash list and add it to the known-good or known-bad set.
+    fn load_hash_list(state: &mut AppState, known_bad: bool) {
+        let Some(path) = file_dialog::new()
+            .add_filter("Hash lists", &["txt", "csv", "md5", "sha1", "sha256"])
+            .add_filter("All files", &["*"])
+            .pick_file_remembered()
+        else {
+            return;
+        };
+
+        let kind = if known_bad { "known-bad" } else { "known-good" };
+        state.hash_db_status = Some(match tv_core::load_hash_list(&path) {
+            Ok((hashes, skipped)) => {
+                let count = hashes.len();
+                let list = if known_bad { &mut state.hash_db.known_bad } else { &mut state.hash_db.known_good };
+                list.extend(hashes);
+                // The file is only hashed up front when a list is loaded
+                if state.has_file() && state.file_digests.is_none() && state.file_hash_progress.is_none() {
+                    state.file_hash_request = true;
+                }
+                let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+                let mut msg = format!("Loaded {} {} hashes from {}", count, kind, name);
+                if skipped > 0 {
+                    msg.push_str(&format!(" ({} lines skipped)", skipped));
+                }
+                (msg, count == 0)
+            }
+            Err(e) => (format!("Load failed: {:#}", e), true),
+        });
+    }
+
+    fn draw_byte_sparkline(ui: &mut egui::Ui, hist: &ByteHistogram) {
+        let desired_size = egui::vec2(ui.available_width().max(256.0), 40.0);
+        let (rect, response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, Color32::from_gray(30));
+
+        let bar_width = rect.width() / 256.0;
+
+        // Printable ASCII band
+        let ascii_band = egui::Rect::from_x_y_ranges(
+            rect.left() + 0x20 as f32 * bar_width..=rect.left() + 0x7F as f32 * bar_width,
+            rect.y_range(),
+        );
+        painter.rect_filled(ascii_band, 0.0, Color32::from_rgba_unmultiplied(100, 180, 100, 25));
+
+        // Square-root scale keeps small counts visible next to a large 0x00 spike
+        let max = (hist.max_count() as f32).sqrt().max(1.0);
+        let spikes = hist.spikes(SPIKE_FACTOR);
+        for (byte, &count) in hist.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let height = (count as f32).sqrt() / max * rect.height();
+            let x = rect.left() + byte as f32 * bar_width;
+            let color = match byte {
+                0x00 => Color32::from_rgb(80, 120, 220),
+                0xFF => Color32::from_rgb(220, 120, 80),
+                _ if spikes.contains(&(byte as u8)) => Color32::from_rgb(230, 200, 80),
+                0x20..=0x7E => Color32::from_rgb(100, 180, 100),
+                _ => Color32::from_gray(150),
+            };
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(x, rect.bottom() - height),
+                    egui::pos2(x + bar_width.max(1.0), rect.bottom()),
+                ),
+                0.0,
+                color,
+            );
+        }
+
+        painter.rect_stroke(rect, 2.0, egui::Stroke::new(1.0, Color32::from_gray(80)));
+
+        if let Some(pos) = response.hover_pos() {
+            let byte = (((pos.x - rect.left()) / bar_width) as usize).min(255) as u8;
+            response.on_hover_text(format!(
+                "0x{:02X}: {} ({:.2}%)",
+                byte,
+                hist.counts[byte as usize],
+                hist.frequency(byte) * 100.0
+            ));
+        }
+
+        // Notable spikes
+        if !spikes.is_empty() {
+            let text = spikes
+                .iter()
+                .take(MAX_SPIKES_SHOWN)
+                .map(|&b| format!("0x{:02X} ({:.1}%)", b, hist.frequency(b) * 100.0))
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui.weak(format!("Spikes: {}", text));
+        }
+        if hist.looks_ascii() {
+            ui.weak("Mostly printable ASCII");
+        } else if hist.looks_encrypted() {
+            ui.weak("Flat distribution (compressed or encrypted)");
         }
     }
 
@@ -167,6 +486,15 @@ fn entropy_to_color(entropy: f32) -> Color32 {
     }
 }
 
+/// Color of a known-hash verdict.
+fn verdict_color(verdict: HashVerdict) -> Color32 {
+    match verdict {
+        HashVerdict::KnownGood => Color32::from_rgb(100, 200, 100),
+        HashVerdict::KnownBad => Color32::from_rgb(255, 100, 100),
+        HashVerdict::Unknown => Color32::GRAY,
+    }
+}
+
 /// Map classification to a color.
 fn class_to_color(class_id: u8) -> Color32 {
     match class_id {
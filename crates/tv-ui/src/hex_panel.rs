@@ -1,7 +1,23 @@
+use std::collections::HashSet;
 use egui::{Ui, ScrollArea, Color32, RichText, FontId, Sense};
 use tv_core::FileRegion;
-use crate::state::AppState;
-use crate::minimap_panel::class_to_subtle_bg;
+use crate::command_palette::{
+    shortcut_pressed, ARROW_SHORTCUTS, BACK_SHORTCUT, COPY_OFFSET_SHORTCUT, EDIT_MODE_SHORTCUT, ESCAPE_SHORTCUT,
+    FILE_END_SHORTCUTS, PAGE_SHORTCUTS,
+};
+use crate::state::{AppState, ClassTint, OffsetRadix, Selection, DEFAULT_HEX_FONT_SIZE, MAX_SEARCH_PATTERN_LEN};
+use crate::theme::Theme;
+use crate::file_dialog::{self, RememberDirectory};
+
+/// Byte offset an arrow key moves the edit selection by (a row for Up/Down).
+fn byte_nav_delta(input: &egui::InputState) -> Option<i64> {
+    // Plain arrows only: Ctrl+arrows step diff regions, Alt+Left goes back
+    let [up, down, left, right] = ARROW_SHORTCUTS;
+    [(left, -1), (right, 1), (up, -(BYTES_PER_ROW as i64)), (down, BYTES_PER_ROW as i64)]
+        .into_iter()
+        .find(|(shortcut, _)| shortcut_pressed(input, shortcut))
+        .map(|(_, delta)| delta)
+}
 
 /// Lookup table for fast byte-to-hex conversion (avoids format! allocations).
 /// Each entry is "XX " (3 bytes) for values 0x00-0xFF.
@@ -76,19 +92,14 @@ const HEX_LUT_NO_SPACE: &[&str; 256] = &[
     "F8", "F9", "FA", "FB", "FC", "FD", "FE", "FF",
 ];
 
-/// Color for modified bytes in edit mode.
-const EDIT_COLOR: Color32 = Color32::from_rgb(255, 100, 100);
-const EDIT_BG: Color32 = Color32::from_rgb(80, 0, 0);
-/// Color for selected byte in edit mode.
-const SELECTED_COLOR: Color32 = Color32::from_rgb(255, 255, 100);
-const SELECTED_BG: Color32 = Color32::from_rgb(80, 80, 0);
-
 /// Bytes per row in the hex view.
 const BYTES_PER_ROW: u64 = 16;
-/// Height of one monospace row in pixels.
+/// Height of one monospace row in pixels at the default font size.
 const ROW_HEIGHT: f32 = 18.0;
 /// Maximum rows that egui f32 scroll can handle reliably (~8M rows = 128 MB).
 const MAX_DIRECT_ROWS: u64 = 8_000_000;
+/// Rows moved by Page Up/Down and the fine navigation page buttons.
+const PAGE_ROWS: u64 = 32;
 
 /// Hex view panel with virtual scrolling.
 pub struct HexPanel;
@@ -102,13 +113,17 @@ impl HexPanel {
 
         let file_len = state.file_len();
         let total_rows = file_len.div_ceil(BYTES_PER_ROW);
+        let offset_fmt = OffsetFormat::for_file(file_len, state.offset_radix);
+        let font_size = state.hex_font_size;
+        let char_width = glyph_width(ui.ctx(), font_size);
 
         // Show edit mode toolbar and dialogs
         Self::show_edit_toolbar(ui, state);
         Self::show_edit_dialogs(ui, state);
 
-        // Keyboard navigation
+        // Keyboard navigation and Ctrl+scroll zoom
         Self::handle_keyboard(ui, state, file_len);
+        Self::handle_zoom(ui, state);
 
         // For large files (>128 MB), show a coarse navigation slider
         let coarse_offset = if total_rows > MAX_DIRECT_ROWS {
@@ -136,6 +151,12 @@ impl HexPanel {
             state.search.rebuild_highlights_for_viewport(vp_start, vp_end);
         }
 
+        // Blinking range from a "locate" action (redraw until it ends)
+        let flash = state.hex_flash_range(ui.input(|i| i.time));
+        if state.hex_flash.is_some() {
+            ui.ctx().request_repaint();
+        }
+
         // Extract all references BEFORE the closure to avoid borrowing state inside.
         // This eliminates the need to clone HashMaps/HashSets every frame.
         let file = match &state.file {
@@ -144,24 +165,113 @@ impl HexPanel {
         };
         let mapped = &file.mapped;
         let classification = state.classification.as_ref();
+        let class_block_size = state.class_block_size();
+        let class_tint = state.class_tint;
+        let byte_tint = class_tint == ClassTint::Bytes && classification.is_some();
         let search_highlights = &state.search.highlight_set;
         let deep_scan_highlights = &state.deep_scan.highlight_set;
         let inspector_highlights = &state.inspector_highlights;
-        let has_highlights = !search_highlights.is_empty() || !deep_scan_highlights.is_empty() || !inspector_highlights.is_empty();
+        let inspector_focus = state.inspector_focus.clone();
+        let selection = state.selection;
+        let highlights = sorted_highlights(&[search_highlights, deep_scan_highlights, inspector_highlights]);
 
         // Capture edit state for the closure (use references, not clones)
         let edit_enabled = state.edit.enabled;
         let selected_offset = state.edit.selected_offset;
         let pending_edits = &state.edit.pending_edits;
         let mut clicked_offset: Option<u64> = None;
+        let theme = &state.theme;
+
+        // Range selection: drag over the hex column, Shift+click to extend
+        let (press_origin, pointer_pos, shift) =
+            ui.input(|i| (i.pointer.press_origin(), i.pointer.latest_pos(), i.modifiers.shift));
+        let selecting = state.selecting;
+        let mut drag_from: Option<u64> = None;
+        let mut drag_to: Option<u64> = None;
+        let mut select_click: Option<u64> = None;
+        let mut search_selection = false;
+        let mut xor_selection = false;
+        let mut compare_selection = false;
+        let mut detect_period = false;
+        // A 4- or 8-byte selection can be followed as a pointer
+        let follow = selection.filter(|s| matches!(s.length(), 4 | 8)).map(|s| (s.start(), s.length()));
+        let follow_value = follow.and_then(|(offset, width)| state.pointer_at(offset, width));
+        let follow_target = follow_value.and_then(|value| state.pointer_target(value));
+        let mut follow_big_endian = state.follow_big_endian;
+        let mut address_base = state.address_base;
+        let mut follow_clicked = false;
+        let mut track_selection = |response: &egui::Response, row_offset: u64, row_len: usize| {
+            if response.drag_started() {
+                drag_from = drag_from.or(hex_byte_at(response, press_origin, row_offset, row_len, char_width));
+            }
+            if response.clicked() {
+                select_click = hex_byte_at(response, response.interact_pointer_pos(), row_offset, row_len, char_width);
+            }
+            if selecting {
+                drag_to = drag_to.or(hex_byte_at(response, pointer_pos, row_offset, row_len, char_width));
+            }
+            if let Some(sel) = selection {
+                response.context_menu(|ui| {
+                    if ui.button("Use as search pattern")
+                        .on_hover_text(format!("Search for {} byte(s)", sel.length().min(MAX_SEARCH_PATTERN_LEN as u64)))
+                        .clicked()
+                    {
+                        search_selection = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("XOR brute force...")
+                        .on_hover_text("Try all single-byte XOR keys on the selection")
+                        .clicked()
+                    {
+                        xor_selection = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Compare with clipboard")
+                        .on_hover_text("Check the selection against hex bytes on the clipboard")
+                        .clicked()
+                    {
+                        compare_selection = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Detect repeat period")
+                        .on_hover_text("Find the record stride of a table or tiled data in the selection")
+                        .clicked()
+                    {
+                        detect_period = true;
+                        ui.close_menu();
+                    }
+                    if let Some(value) = follow_value {
+                        ui.separator();
+                        let text = match follow_target {
+                            Some(target) => format!("Follow as offset -> 0x{:X}", target),
+                            None => "Follow as offset (outside file)".to_string(),
+                        };
+                        if ui.add_enabled(follow_target.is_some(), egui::Button::new(text))
+                            .on_hover_text(format!("Value 0x{:X}", value))
+                            .on_disabled_hover_text(format!("Value 0x{:X} minus base is past the end of the file", value))
+                            .clicked()
+                        {
+                            follow_clicked = true;
+                            ui.close_menu();
+                        }
+                        ui.checkbox(&mut follow_big_endian, "Big-endian");
+                        ui.horizontal(|ui| {
+                            ui.label("Base:");
+                            ui.add(egui::DragValue::new(&mut address_base).hexadecimal(8, false, true).prefix("0x"))
+                                .on_hover_text("Address of file offset 0 (e.g. image base)");
+                        });
+                    }
+                });
+            }
+        };
 
         ScrollArea::vertical()
             .auto_shrink([false, false])
-            .show_rows(ui, ROW_HEIGHT, window_rows, |ui, row_range| {
-                ui.style_mut().override_font_id = Some(FontId::monospace(13.0));
+            .show_rows(ui, row_height(font_size), window_rows, |ui, row_range| {
+                ui.style_mut().override_font_id = Some(FontId::monospace(font_size));
 
                 for row_idx in row_range {
-                    let byte_offset = coarse_offset + (row_idx as u64) * BYTES_PER_ROW;
+                    let byte_offset = row_offset(coarse_offset, row_idx);
                     if byte_offset >= file_len {
                         break;
                     }
@@ -169,45 +279,60 @@ impl HexPanel {
                     let region = FileRegion::new(byte_offset, BYTES_PER_ROW);
                     let data = mapped.slice(region);
 
-                    // Compute classification background for this row's offset column
-                    let class_bg = classification.and_then(|c| {
-                        let block_idx = (byte_offset / 256) as usize;
-                        c.get(block_idx).map(|&v| class_to_subtle_bg(v))
-                    });
+                    // Classification tint for this row's offset column (or whole row)
+                    let (offset_bg, row_bg) = class_backgrounds(theme, classification, class_block_size, byte_offset, class_tint);
 
                     // Helper to get effective byte value (with edits applied)
                     let get_byte = |abs: u64, original: u8| -> u8 {
                         pending_edits.get(&abs).copied().unwrap_or(original)
                     };
 
+                    // Background of a byte outside any highlight
+                    let byte_bg = |abs: u64| -> Color32 {
+                        if byte_tint { class_color(theme, classification, class_block_size, abs) } else { row_bg }
+                    };
+
                     // Helper to check if byte is modified
                     let is_modified = |abs: u64| -> bool {
                         pending_edits.contains_key(&abs)
                     };
 
-                    // Helper to check if byte is selected
+                    // Helper to check if byte is selected (edit cursor or range selection)
                     let is_selected = |abs: u64| -> bool {
-                        selected_offset == Some(abs)
+                        selected_offset == Some(abs) || selection.is_some_and(|s| s.contains(abs))
                     };
 
-                    if !has_highlights && !edit_enabled {
-                        // No highlights and not in edit mode — use fast single-label path
-                        let line = format_hex_line(byte_offset, data);
+                    // Rows far from any highlight skip the per-byte lookups
+                    let row_end = byte_offset + data.len() as u64;
+                    let plain_row = !edit_enabled
+                        && !byte_tint
+                        && !any_in_range(&highlights, byte_offset..row_end)
+                        && !selection.is_some_and(|s| s.start() < row_end && s.end() >= byte_offset)
+                        && !selected_offset.is_some_and(|o| (byte_offset..row_end).contains(&o))
+                        && !inspector_focus.as_ref().is_some_and(|r| r.start < row_end && r.end > byte_offset)
+                        && !flash.as_ref().is_some_and(|r| r.start < row_end && r.end > byte_offset)
+                        && (pending_edits.is_empty() || !(byte_offset..row_end).any(|o| pending_edits.contains_key(&o)));
+
+                    if plain_row {
+                        // No highlights in this row and not in edit mode — use fast single-label path
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
                             let offset_text = RichText::new(&line.offset)
-                                .color(Color32::from_rgb(100, 140, 180))
-                                .background_color(class_bg.unwrap_or(Color32::TRANSPARENT));
+                                .color(theme.offset_text)
+                                .background_color(offset_bg);
                             ui.label(offset_text);
-                            ui.label(RichText::new(&line.hex).color(Color32::from_rgb(220, 220, 220)));
-                            ui.label(RichText::new(&line.ascii).color(Color32::from_rgb(160, 200, 140)));
+                            let hex_text = RichText::new(&line.hex).color(theme.hex_text).background_color(row_bg);
+                            let response = ui.add(egui::Label::new(hex_text).sense(Sense::click_and_drag()));
+                            track_selection(&response, byte_offset, data.len());
+                            ui.label(RichText::new(&line.ascii).color(theme.ascii_text).background_color(row_bg));
                         });
                     } else {
                         // Highlighted path or edit mode: build a rich-text layout per byte
-                        let line = format_hex_line(byte_offset, data);
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
                             let offset_text = RichText::new(&line.offset)
-                                .color(Color32::from_rgb(100, 140, 180))
-                                .background_color(class_bg.unwrap_or(Color32::TRANSPARENT));
+                                .color(theme.offset_text)
+                                .background_color(offset_bg);
                             ui.label(offset_text);
 
                             // Helper to check if byte is highlighted
@@ -219,20 +344,25 @@ impl HexPanel {
                             let get_colors = |abs: u64| -> (Color32, Color32) {
                                 // Edit mode colors take priority
                                 if is_selected(abs) {
-                                    return (SELECTED_COLOR, SELECTED_BG);
+                                    return theme.selected.pair();
                                 }
                                 if is_modified(abs) {
-                                    return (EDIT_COLOR, EDIT_BG);
+                                    return theme.edit.pair();
+                                }
+                                if flash.as_ref().is_some_and(|r| r.contains(&abs)) {
+                                    return theme.inspector_focus.pair();
                                 }
                                 // Then search/highlight colors
                                 if search_highlights.contains(&abs) {
-                                    (Color32::from_rgb(255, 255, 80), Color32::from_rgb(50, 50, 0))
+                                    theme.search.pair()
                                 } else if deep_scan_highlights.contains(&abs) {
-                                    (Color32::from_rgb(80, 255, 255), Color32::from_rgb(0, 50, 50))
+                                    theme.deep_scan.pair()
+                                } else if inspector_focus.as_ref().is_some_and(|r| r.contains(&abs)) {
+                                    theme.inspector_focus.pair()
                                 } else if inspector_highlights.contains(&abs) {
-                                    (Color32::from_rgb(255, 150, 255), Color32::from_rgb(50, 0, 50))
+                                    theme.inspector.pair()
                                 } else {
-                                    (Color32::from_rgb(220, 220, 220), Color32::TRANSPARENT)
+                                    (theme.hex_text, byte_bg(abs))
                                 }
                             };
 
@@ -247,15 +377,15 @@ impl HexPanel {
                                     // Need extra space after byte 7
                                     let h = HEX_LUT_NO_SPACE[byte_val as usize];
                                     job.append(h, 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: bg,
                                         ..Default::default()
                                     });
                                     job.append("  ", 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
-                                        background: Color32::TRANSPARENT,
+                                        background: byte_bg(abs),
                                         ..Default::default()
                                     });
                                     continue;
@@ -263,7 +393,7 @@ impl HexPanel {
                                     HEX_LUT[byte_val as usize]
                                 };
                                 job.append(hex_str, 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
+                                    font_id: FontId::monospace(font_size),
                                     color: fg,
                                     background: bg,
                                     ..Default::default()
@@ -274,45 +404,34 @@ impl HexPanel {
                                 let mut s = "   ".to_string();
                                 if j == 7 { s.push(' '); }
                                 job.append(&s, 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(220, 220, 220),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.hex_text,
                                     ..Default::default()
                                 });
                             }
 
-                            // Make hex clickable in edit mode
+                            // Hex is selectable; clicking a byte edits it in edit mode
+                            let response = ui.add(egui::Label::new(job).sense(Sense::click_and_drag()));
+                            track_selection(&response, byte_offset, data.len());
                             if edit_enabled {
-                                let response = ui.add(egui::Label::new(job).sense(Sense::click()));
-                                if response.clicked() {
+                                if response.clicked() && !shift {
                                     // Calculate which byte was clicked based on cursor position
                                     if let Some(pos) = response.interact_pointer_pos() {
                                         let relative_x = pos.x - response.rect.left();
-                                        // Each byte takes ~24 pixels (2 hex chars + space at 8px each)
-                                        // Plus extra space after byte 7
-                                        let char_width = 8.0;
-                                        let byte_width = char_width * 3.0; // "XX "
-                                        let mut x = 0.0;
-                                        for j in 0..data.len() {
-                                            let next_x = x + byte_width + if j == 7 { char_width } else { 0.0 };
-                                            if relative_x >= x && relative_x < next_x {
-                                                clicked_offset = Some(byte_offset + j as u64);
-                                                break;
-                                            }
-                                            x = next_x;
+                                        if let Some(j) = byte_index_at_x(relative_x, data.len(), char_width) {
+                                            clicked_offset = Some(byte_offset + j as u64);
                                         }
                                     }
                                 }
                                 // Tooltip showing click hint
                                 response.on_hover_text("Click a byte to edit");
-                            } else {
-                                ui.label(job);
                             }
 
                             // ASCII display
                             let mut ascii_job = egui::text::LayoutJob::default();
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             for (j, &original_byte) in data.iter().enumerate() {
@@ -321,13 +440,14 @@ impl HexPanel {
                                 let ch = if byte_val.is_ascii_graphic() || byte_val == b' ' { byte_val as char } else { '.' };
                                 let (fg, bg) = get_colors(abs);
                                 // For ASCII, keep green tint when not highlighted/modified/selected
-                                let fg = if is_highlighted(abs) || is_modified(abs) || is_selected(abs) {
+                                let flashing = flash.as_ref().is_some_and(|r| r.contains(&abs));
+                                let fg = if is_highlighted(abs) || is_modified(abs) || is_selected(abs) || flashing {
                                     fg
                                 } else {
-                                    Color32::from_rgb(160, 200, 140)
+                                    theme.ascii_text
                                 };
                                 ascii_job.append(&ch.to_string(), 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
+                                    font_id: FontId::monospace(font_size),
                                     color: fg,
                                     background: bg,
                                     ..Default::default()
@@ -335,14 +455,14 @@ impl HexPanel {
                             }
                             for _ in data.len()..16 {
                                 ascii_job.append(" ", 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(160, 200, 140),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.ascii_text,
                                     ..Default::default()
                                 });
                             }
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             ui.label(ascii_job);
@@ -351,21 +471,62 @@ impl HexPanel {
                 }
             });
 
+        // Update the range selection
+        if let Some(offset) = drag_from {
+            state.selection = Some(Selection::new(offset, offset));
+            state.selecting = true;
+        }
+        if state.selecting {
+            if let (Some(offset), Some(selection)) = (drag_to, state.selection.as_mut()) {
+                selection.cursor = offset;
+            }
+            if !ui.input(|i| i.pointer.primary_down()) {
+                state.selecting = false;
+            }
+        }
+        if let Some(offset) = select_click {
+            if shift {
+                let anchor = state.selection.map(|s| s.anchor)
+                    .or(state.edit.selected_offset)
+                    .unwrap_or(offset);
+                state.selection = Some(Selection::new(anchor, offset));
+            } else {
+                state.clear_selection();
+            }
+        }
+
+        if search_selection && state.use_selection_as_search().is_some() {
+            ui.ctx().request_repaint();
+        }
+        state.follow_big_endian = follow_big_endian;
+        state.address_base = address_base;
+        if let Some((offset, width)) = follow.filter(|_| follow_clicked) {
+            state.follow_pointer(offset, width);
+        }
+
+        if xor_selection {
+            state.xor_window_request = true;
+            ui.ctx().request_repaint();
+        }
+        if compare_selection {
+            let clipboard = crate::clipboard::read_text().unwrap_or_default();
+            state.compare_selection_to(&clipboard);
+        }
+        if detect_period {
+            state.detect_selection_period();
+        }
+
         // Handle byte click outside the closure
         if let Some(offset) = clicked_offset {
-            state.edit.selected_offset = Some(offset);
-            state.edit.input_buffer.clear();
-            // Pre-fill with current value
-            if let Some(ref file) = state.file {
-                if offset < file.mapped.len() {
-                    let current = state.edit.get_edited_byte(offset)
-                        .unwrap_or_else(|| file.mapped.slice(FileRegion::new(offset, 1))[0]);
-                    state.edit.input_buffer = format!("{:02X}", current);
-                }
-            }
+            state.select_edit_byte(offset);
         }
     }
 
+    /// Number of whole rows fitting in `height` at the current font size.
+    pub fn rows_in(height: f32, font_size: f32) -> u64 {
+        (height / row_height(font_size)).max(0.0) as u64
+    }
+
     /// Show file B in diff mode with synchronized scroll.
     /// Returns the scroll offset for synchronization.
     pub fn show_file_b(ui: &mut Ui, state: &mut AppState, scroll_offset: f32) -> f32 {
@@ -381,6 +542,9 @@ impl HexPanel {
         };
 
         let total_rows = file_len.div_ceil(BYTES_PER_ROW);
+        let offset_fmt = OffsetFormat::for_file(file_len, state.offset_radix);
+        let font_size = state.hex_font_size;
+        let char_width = glyph_width(ui.ctx(), font_size);
 
         // Rebuild diff highlights for the visible viewport (uses caching)
         // Must be done before taking references to avoid borrow conflicts
@@ -394,7 +558,10 @@ impl HexPanel {
         let file_b = state.diff.file_b.as_ref().unwrap(); // Safe: checked above
         let mapped_b = &file_b.mapped;
         let diff_highlights = &state.diff.highlight_set;
-        let has_highlights = !diff_highlights.is_empty();
+        let diff_selected = state.diff.selected_offset;
+        let highlights = sorted_highlights(&[diff_highlights]);
+        let mut clicked_offset: Option<u64> = None;
+        let theme = &state.theme;
 
         let window_rows = if total_rows > MAX_DIRECT_ROWS {
             MAX_DIRECT_ROWS as usize
@@ -408,36 +575,42 @@ impl HexPanel {
             .id_salt("hex_panel_b")
             .auto_shrink([false, false])
             .vertical_scroll_offset(scroll_offset)
-            .show_rows(ui, ROW_HEIGHT, window_rows, |ui, row_range| {
-                ui.style_mut().override_font_id = Some(FontId::monospace(13.0));
+            .show_rows(ui, row_height(font_size), window_rows, |ui, row_range| {
+                ui.style_mut().override_font_id = Some(FontId::monospace(font_size));
 
                 for row_idx in row_range {
-                    let byte_offset = coarse_offset + (row_idx as u64) * BYTES_PER_ROW;
+                    let byte_offset = row_offset(coarse_offset, row_idx);
                     if byte_offset >= file_len {
                         break;
                     }
 
                     let region = FileRegion::new(byte_offset, BYTES_PER_ROW);
                     let data = mapped_b.slice(region);
+                    let row = byte_offset..byte_offset + data.len() as u64;
 
-                    if !has_highlights {
-                        let line = format_hex_line(byte_offset, data);
+                    if !any_in_range(&highlights, row.clone()) && !diff_selected.is_some_and(|o| row.contains(&o)) {
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
-                            ui.label(RichText::new(&line.offset).color(Color32::from_rgb(100, 140, 180)));
-                            ui.label(RichText::new(&line.hex).color(Color32::from_rgb(220, 220, 220)));
-                            ui.label(RichText::new(&line.ascii).color(Color32::from_rgb(160, 200, 140)));
+                            ui.label(RichText::new(&line.offset).color(theme.offset_text));
+                            let hex = RichText::new(&line.hex).color(theme.hex_text);
+                            if let Some(offset) = clickable_hex(ui, hex, byte_offset, data.len(), char_width) {
+                                clicked_offset = Some(offset);
+                            }
+                            ui.label(RichText::new(&line.ascii).color(theme.ascii_text));
                         });
                     } else {
                         // Highlighted path for diff
-                        let line = format_hex_line(byte_offset, data);
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
-                            ui.label(RichText::new(&line.offset).color(Color32::from_rgb(100, 140, 180)));
+                            ui.label(RichText::new(&line.offset).color(theme.offset_text));
 
                             let highlight_colors = |abs: u64| -> (Color32, Color32) {
-                                if diff_highlights.contains(&abs) {
-                                    (Color32::from_rgb(255, 100, 100), Color32::from_rgb(80, 0, 0))
+                                if diff_selected == Some(abs) {
+                                    theme.selected.pair()
+                                } else if diff_highlights.contains(&abs) {
+                                    theme.diff.pair()
                                 } else {
-                                    (Color32::from_rgb(220, 220, 220), Color32::TRANSPARENT)
+                                    (theme.hex_text, Color32::TRANSPARENT)
                                 }
                             };
 
@@ -448,20 +621,20 @@ impl HexPanel {
                                 let (fg, bg) = highlight_colors(abs);
                                 if j == 7 {
                                     job.append(HEX_LUT_NO_SPACE[b as usize], 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: bg,
                                         ..Default::default()
                                     });
                                     job.append("  ", 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: Color32::TRANSPARENT,
                                         ..Default::default()
                                     });
                                 } else {
                                     job.append(HEX_LUT[b as usize], 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: bg,
                                         ..Default::default()
@@ -471,27 +644,33 @@ impl HexPanel {
                             for j in data.len()..16 {
                                 let padding = if j == 7 { "    " } else { "   " };
                                 job.append(padding, 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(220, 220, 220),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.hex_text,
                                     ..Default::default()
                                 });
                             }
-                            ui.label(job);
+                            if let Some(offset) = clickable_hex(ui, job, byte_offset, data.len(), char_width) {
+                                clicked_offset = Some(offset);
+                            }
 
                             // ASCII with highlights
                             let mut ascii_job = egui::text::LayoutJob::default();
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             for (j, &b) in data.iter().enumerate() {
                                 let abs = byte_offset + j as u64;
                                 let ch = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
                                 let (fg, bg) = highlight_colors(abs);
-                                let fg = if diff_highlights.contains(&abs) { fg } else { Color32::from_rgb(160, 200, 140) };
+                                let fg = if diff_highlights.contains(&abs) || diff_selected == Some(abs) {
+                                    fg
+                                } else {
+                                    theme.ascii_text
+                                };
                                 ascii_job.append(&ch.to_string(), 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
+                                    font_id: FontId::monospace(font_size),
                                     color: fg,
                                     background: bg,
                                     ..Default::default()
@@ -499,14 +678,14 @@ impl HexPanel {
                             }
                             for _ in data.len()..16 {
                                 ascii_job.append(" ", 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(160, 200, 140),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.ascii_text,
                                     ..Default::default()
                                 });
                             }
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             ui.label(ascii_job);
@@ -515,6 +694,10 @@ impl HexPanel {
                 }
             });
 
+        if clicked_offset.is_some() {
+            state.diff.selected_offset = clicked_offset;
+        }
+
         scroll_output.state.offset.y
     }
 
@@ -528,6 +711,9 @@ impl HexPanel {
 
         let file_len = state.file_len();
         let total_rows = file_len.div_ceil(BYTES_PER_ROW);
+        let offset_fmt = OffsetFormat::for_file(file_len, state.offset_radix);
+        let font_size = state.hex_font_size;
+        let char_width = glyph_width(ui.ctx(), font_size);
 
         // Rebuild both search and diff highlights (uses caching)
         {
@@ -544,10 +730,16 @@ impl HexPanel {
         };
         let mapped = &file.mapped;
         let classification = state.classification.as_ref();
+        let class_block_size = state.class_block_size();
+        let class_tint = state.class_tint;
+        let byte_tint = class_tint == ClassTint::Bytes && classification.is_some();
         let search_highlights = &state.search.highlight_set;
         let deep_scan_highlights = &state.deep_scan.highlight_set;
         let diff_highlights = &state.diff.highlight_set;
-        let has_highlights = !search_highlights.is_empty() || !deep_scan_highlights.is_empty() || !diff_highlights.is_empty();
+        let diff_selected = state.diff.selected_offset;
+        let highlights = sorted_highlights(&[search_highlights, deep_scan_highlights, diff_highlights]);
+        let mut clicked_offset: Option<u64> = None;
+        let theme = &state.theme;
 
         let window_rows = if total_rows > MAX_DIRECT_ROWS {
             MAX_DIRECT_ROWS as usize
@@ -561,11 +753,11 @@ impl HexPanel {
             .id_salt("hex_panel_a")
             .auto_shrink([false, false])
             .vertical_scroll_offset(scroll_offset)
-            .show_rows(ui, ROW_HEIGHT, window_rows, |ui, row_range| {
-                ui.style_mut().override_font_id = Some(FontId::monospace(13.0));
+            .show_rows(ui, row_height(font_size), window_rows, |ui, row_range| {
+                ui.style_mut().override_font_id = Some(FontId::monospace(font_size));
 
                 for row_idx in row_range {
-                    let byte_offset = coarse_offset + (row_idx as u64) * BYTES_PER_ROW;
+                    let byte_offset = row_offset(coarse_offset, row_idx);
                     if byte_offset >= file_len {
                         break;
                     }
@@ -573,38 +765,44 @@ impl HexPanel {
                     let region = FileRegion::new(byte_offset, BYTES_PER_ROW);
                     let data = mapped.slice(region);
 
-                    let class_bg = classification.and_then(|c| {
-                        let block_idx = (byte_offset / 256) as usize;
-                        c.get(block_idx).map(|&v| class_to_subtle_bg(v))
-                    });
+                    let (offset_bg, row_bg) = class_backgrounds(theme, classification, class_block_size, byte_offset, class_tint);
+                    let row = byte_offset..byte_offset + data.len() as u64;
 
-                    if !has_highlights {
-                        let line = format_hex_line(byte_offset, data);
+                    if !byte_tint && !any_in_range(&highlights, row.clone()) && !diff_selected.is_some_and(|o| row.contains(&o)) {
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
                             let offset_text = RichText::new(&line.offset)
-                                .color(Color32::from_rgb(100, 140, 180))
-                                .background_color(class_bg.unwrap_or(Color32::TRANSPARENT));
+                                .color(theme.offset_text)
+                                .background_color(offset_bg);
                             ui.label(offset_text);
-                            ui.label(RichText::new(&line.hex).color(Color32::from_rgb(220, 220, 220)));
-                            ui.label(RichText::new(&line.ascii).color(Color32::from_rgb(160, 200, 140)));
+                            let hex = RichText::new(&line.hex).color(theme.hex_text).background_color(row_bg);
+                            if let Some(offset) = clickable_hex(ui, hex, byte_offset, data.len(), char_width) {
+                                clicked_offset = Some(offset);
+                            }
+                            ui.label(RichText::new(&line.ascii).color(theme.ascii_text).background_color(row_bg));
                         });
                     } else {
-                        let line = format_hex_line(byte_offset, data);
+                        let line = format_hex_line(byte_offset, data, offset_fmt);
                         ui.horizontal(|ui| {
                             let offset_text = RichText::new(&line.offset)
-                                .color(Color32::from_rgb(100, 140, 180))
-                                .background_color(class_bg.unwrap_or(Color32::TRANSPARENT));
+                                .color(theme.offset_text)
+                                .background_color(offset_bg);
                             ui.label(offset_text);
 
+                            let byte_bg = |abs: u64| -> Color32 {
+                                if byte_tint { class_color(theme, classification, class_block_size, abs) } else { row_bg }
+                            };
                             let highlight_colors = |abs: u64| -> (Color32, Color32) {
-                                if search_highlights.contains(&abs) {
-                                    (Color32::from_rgb(255, 255, 80), Color32::from_rgb(50, 50, 0))
+                                if diff_selected == Some(abs) {
+                                    theme.selected.pair()
+                                } else if search_highlights.contains(&abs) {
+                                    theme.search.pair()
                                 } else if deep_scan_highlights.contains(&abs) {
-                                    (Color32::from_rgb(80, 255, 255), Color32::from_rgb(0, 50, 50))
+                                    theme.deep_scan.pair()
                                 } else if diff_highlights.contains(&abs) {
-                                    (Color32::from_rgb(255, 100, 100), Color32::from_rgb(80, 0, 0))
+                                    theme.diff.pair()
                                 } else {
-                                    (Color32::from_rgb(220, 220, 220), Color32::TRANSPARENT)
+                                    (theme.hex_text, byte_bg(abs))
                                 }
                             };
 
@@ -615,20 +813,20 @@ impl HexPanel {
                                 let (fg, bg) = highlight_colors(abs);
                                 if j == 7 {
                                     job.append(HEX_LUT_NO_SPACE[b as usize], 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: bg,
                                         ..Default::default()
                                     });
                                     job.append("  ", 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
-                                        background: Color32::TRANSPARENT,
+                                        background: byte_bg(abs),
                                         ..Default::default()
                                     });
                                 } else {
                                     job.append(HEX_LUT[b as usize], 0.0, egui::TextFormat {
-                                        font_id: FontId::monospace(13.0),
+                                        font_id: FontId::monospace(font_size),
                                         color: fg,
                                         background: bg,
                                         ..Default::default()
@@ -638,31 +836,37 @@ impl HexPanel {
                             for j in data.len()..16 {
                                 let padding = if j == 7 { "    " } else { "   " };
                                 job.append(padding, 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(220, 220, 220),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.hex_text,
                                     ..Default::default()
                                 });
                             }
-                            ui.label(job);
+                            if let Some(offset) = clickable_hex(ui, job, byte_offset, data.len(), char_width) {
+                                clicked_offset = Some(offset);
+                            }
 
                             // ASCII with highlights
                             let mut ascii_job = egui::text::LayoutJob::default();
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             for (j, &b) in data.iter().enumerate() {
                                 let abs = byte_offset + j as u64;
                                 let ch = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
                                 let (fg, bg) = highlight_colors(abs);
-                                let fg = if search_highlights.contains(&abs) || deep_scan_highlights.contains(&abs) || diff_highlights.contains(&abs) {
+                                let fg = if search_highlights.contains(&abs)
+                                    || deep_scan_highlights.contains(&abs)
+                                    || diff_highlights.contains(&abs)
+                                    || diff_selected == Some(abs)
+                                {
                                     fg
                                 } else {
-                                    Color32::from_rgb(160, 200, 140)
+                                    theme.ascii_text
                                 };
                                 ascii_job.append(&ch.to_string(), 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
+                                    font_id: FontId::monospace(font_size),
                                     color: fg,
                                     background: bg,
                                     ..Default::default()
@@ -670,14 +874,14 @@ impl HexPanel {
                             }
                             for _ in data.len()..16 {
                                 ascii_job.append(" ", 0.0, egui::TextFormat {
-                                    font_id: FontId::monospace(13.0),
-                                    color: Color32::from_rgb(160, 200, 140),
+                                    font_id: FontId::monospace(font_size),
+                                    color: theme.ascii_text,
                                     ..Default::default()
                                 });
                             }
                             ascii_job.append("|", 0.0, egui::TextFormat {
-                                font_id: FontId::monospace(13.0),
-                                color: Color32::from_rgb(160, 200, 140),
+                                font_id: FontId::monospace(font_size),
+                                color: theme.ascii_text,
                                 ..Default::default()
                             });
                             ui.label(ascii_job);
@@ -686,16 +890,20 @@ impl HexPanel {
                 }
             });
 
+        if clicked_offset.is_some() {
+            state.diff.selected_offset = clicked_offset;
+        }
+
         scroll_output.state.offset.y
     }
 
     /// Coarse slider for navigating large files (>128 MB).
     /// Returns the byte offset of the selected window start.
     fn show_coarse_slider(ui: &mut Ui, file_len: u64, state: &mut AppState) -> u64 {
-        let max_offset = file_len.saturating_sub(MAX_DIRECT_ROWS * BYTES_PER_ROW);
+        let max_offset = coarse_max_offset(file_len);
 
         // Store coarse offset in viewport.start (aligned to row boundary)
-        let mut offset = state.viewport.start.min(max_offset);
+        let mut offset = align_to_row(state.viewport.start).min(max_offset);
 
         ui.horizontal(|ui| {
             ui.label("Navigate:");
@@ -706,7 +914,65 @@ impl HexPanel {
                     .custom_formatter(|v, _| format_offset(v as u64))
             );
             if response.changed() {
-                offset = (*slider_val as u64 / BYTES_PER_ROW) * BYTES_PER_ROW;
+                offset = slider_to_offset(*slider_val, max_offset);
+            }
+            response.context_menu(|ui| {
+                ui.label("Jump to:");
+                for pct in [0.0, 10.0, 25.0, 50.0, 75.0, 90.0, 100.0] {
+                    if ui.button(format!("{}%", pct)).clicked() {
+                        offset = percent_to_offset(file_len, pct).min(max_offset);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+
+        // Fine navigation: exact hex offset plus row/page nudges (viewport.start stays the u64 source of truth)
+        ui.horizontal(|ui| {
+            ui.label("Fine:");
+            let page_bytes = (BYTES_PER_ROW * PAGE_ROWS) as i64;
+            let row_bytes = BYTES_PER_ROW as i64;
+            if ui.small_button("<<").on_hover_text("Back one page").clicked() {
+                offset = nudge_offset(offset, -page_bytes, max_offset);
+            }
+            if ui.small_button("<").on_hover_text("Back one row").clicked() {
+                offset = nudge_offset(offset, -row_bytes, max_offset);
+            }
+
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut state.coarse_offset_text)
+                    .desired_width(140.0)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("0x... or 50%")
+            );
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                if let Some(pct) = parse_percent(&state.coarse_offset_text) {
+                    offset = percent_to_offset(file_len, pct).min(max_offset);
+                } else if let Some(target) = parse_offset(&state.coarse_offset_text) {
+                    offset = align_to_row(target).min(max_offset);
+                }
+            }
+            let editing = response.has_focus();
+
+            if ui.small_button(">").on_hover_text("Forward one row").clicked() {
+                offset = nudge_offset(offset, row_bytes, max_offset);
+            }
+            if ui.small_button(">>").on_hover_text("Forward one page").clicked() {
+                offset = nudge_offset(offset, page_bytes, max_offset);
+            }
+            if !state.nav_back.is_empty()
+                && ui.small_button("Back")
+                    .on_hover_text(format!("Return from followed pointer (Alt+Left), {} level(s)", state.nav_back.len()))
+                    .clicked()
+            {
+                if let Some(start) = state.nav_back.pop() {
+                    offset = start.min(max_offset);
+                }
+            }
+
+            // Mirror the authoritative offset while the user isn't typing
+            if !editing {
+                state.coarse_offset_text = format!("0x{:X}", offset);
             }
         });
 
@@ -734,17 +1000,19 @@ impl HexPanel {
             return;
         }
 
-        let page_bytes = BYTES_PER_ROW * 32; // ~32 rows per page
+        let page_bytes = BYTES_PER_ROW * PAGE_ROWS;
+        let mut copy_offset: Option<String> = None;
 
         ui.input(|i| {
-            // Ctrl+G: open "Go to offset" dialog
-            if i.modifiers.ctrl && i.key_pressed(egui::Key::G) {
-                state.goto_open = true;
-                state.goto_text.clear();
+            // Ctrl+G (go to offset) is a registered command, dispatched by the app
+
+            // Ctrl+Shift+G: copy the current offset to the clipboard
+            if shortcut_pressed(i, &COPY_OFFSET_SHORTCUT) {
+                copy_offset = Some(state.offset_clipboard_text());
             }
 
             // Ctrl+E: toggle edit mode
-            if i.modifiers.ctrl && i.key_pressed(egui::Key::E) {
+            if shortcut_pressed(i, &EDIT_MODE_SHORTCUT) {
                 if state.edit.enabled {
                     if state.edit.has_changes() {
                         state.edit.save_dialog_open = true;
@@ -752,52 +1020,111 @@ impl HexPanel {
                         state.edit.clear();
                     }
                 } else {
-                    state.edit.confirm_dialog_open = true;
+                    state.edit.request_enable();
                 }
             }
 
-            // Escape: deselect byte in edit mode
-            if i.key_pressed(egui::Key::Escape) && state.edit.selected_offset.is_some() {
+            // Escape: deselect byte in edit mode and the range selection
+            if shortcut_pressed(i, &ESCAPE_SHORTCUT) {
                 state.edit.selected_offset = None;
                 state.edit.input_buffer.clear();
+                state.clear_selection();
             }
 
+            let [page_up, page_down] = PAGE_SHORTCUTS;
+            let [home, end] = FILE_END_SHORTCUTS;
+            let [arrow_up, arrow_down, ..] = ARROW_SHORTCUTS;
             // Page Down
-            if i.key_pressed(egui::Key::PageDown) {
+            if shortcut_pressed(i, &page_down) {
                 state.viewport.start = state.viewport.start
                     .saturating_add(page_bytes)
                     .min(file_len.saturating_sub(BYTES_PER_ROW));
             }
             // Page Up
-            if i.key_pressed(egui::Key::PageUp) {
+            if shortcut_pressed(i, &page_up) {
                 state.viewport.start = state.viewport.start.saturating_sub(page_bytes);
             }
             // Home
-            if i.key_pressed(egui::Key::Home) {
+            if shortcut_pressed(i, &home) {
                 state.viewport.start = 0;
             }
             // End
-            if i.key_pressed(egui::Key::End) {
+            if shortcut_pressed(i, &end) {
                 state.viewport.start = (file_len.saturating_sub(page_bytes) / BYTES_PER_ROW) * BYTES_PER_ROW;
             }
-            // Arrow Down
-            if i.key_pressed(egui::Key::ArrowDown) {
-                state.viewport.start = state.viewport.start
-                    .saturating_add(BYTES_PER_ROW)
-                    .min(file_len.saturating_sub(BYTES_PER_ROW));
-            }
-            // Arrow Up
-            if i.key_pressed(egui::Key::ArrowUp) {
-                state.viewport.start = state.viewport.start.saturating_sub(BYTES_PER_ROW);
+            // Alt+Left: return from a followed pointer
+            if shortcut_pressed(i, &BACK_SHORTCUT) {
+                state.navigate_back();
+            } else if state.edit.enabled && state.edit.selected_offset.is_some() {
+                // Arrows move the byte selected for editing instead of scrolling
+                if let Some(delta) = byte_nav_delta(i) {
+                    state.move_edit_byte(delta, page_bytes);
+                }
+            } else {
+                // Arrow Down
+                if shortcut_pressed(i, &arrow_down) {
+                    state.viewport.start = state.viewport.start
+                        .saturating_add(BYTES_PER_ROW)
+                        .min(file_len.saturating_sub(BYTES_PER_ROW));
+                }
+                // Arrow Up
+                if shortcut_pressed(i, &arrow_up) {
+                    state.viewport.start = state.viewport.start.saturating_sub(BYTES_PER_ROW);
+                }
             }
         });
 
+        // Copy outside the input closure (copy_text needs the context)
+        if let Some(text) = copy_offset {
+            state.edit.status_message = Some((format!("Copied {}", text), false));
+            ui.ctx().copy_text(text);
+        }
+
         // "Go to offset" modal window
         if state.goto_open {
             Self::show_goto_dialog(ui, state, file_len);
         }
     }
 
+    /// Ask for a destination and write a patched copy of the open file there.
+    fn save_edits_as(state: &mut AppState) {
+        let Some(file) = state.file.as_ref() else { return };
+        let suggested_name = file.path.file_name()
+            .map(|n| format!("{}.patched", n.to_string_lossy()))
+            .unwrap_or_else(|| "patched.bin".to_string());
+        let Some(dest) = file_dialog::new()
+            .set_file_name(&suggested_name)
+            .save_file_remembered()
+        else {
+            return;
+        };
+
+        state.edit.status_message = Some(match state.edit.save_as(&file.path, &dest) {
+            Ok(count) => (format!("Wrote copy with {} edited byte(s) to {}", count, dest.display()), false),
+            Err(e) => (format!("Save As failed: {}", e), true),
+        });
+    }
+
+    /// Ask for a destination and write the pending edits as a text patch.
+    fn export_patch(state: &mut AppState) {
+        let Some(file) = state.file.as_ref() else { return };
+        let file_name = file.path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let Some(dest) = file_dialog::new()
+            .set_file_name(format!("{}.patch", file_name))
+            .add_filter("Patch", &["patch", "txt"])
+            .save_file_remembered()
+        else {
+            return;
+        };
+
+        state.edit.status_message = Some(match std::fs::write(&dest, state.edit.patch_text(&file_name)) {
+            Ok(()) => (format!("Exported {} edit(s) to {}", state.edit.edit_count(), dest.display()), false),
+            Err(e) => (format!("Patch export failed: {}", e), true),
+        });
+    }
+
     /// Show edit mode toolbar.
     fn show_edit_toolbar(ui: &mut Ui, state: &mut AppState) {
         ui.horizontal(|ui| {
@@ -818,6 +1145,20 @@ impl HexPanel {
                         state.edit.save_dialog_open = true;
                     }
 
+                    if ui.button("Save As...")
+                        .on_hover_text("Write a copy with the edits applied, leaving the original untouched")
+                        .clicked()
+                    {
+                        Self::save_edits_as(state);
+                    }
+
+                    if ui.button("Export Patch...")
+                        .on_hover_text("Write the edits as an offset: old -> new text patch")
+                        .clicked()
+                    {
+                        Self::export_patch(state);
+                    }
+
                     if ui.button("Discard").clicked() {
                         state.edit.undo_all();
                         state.edit.status_message = Some(("Changes discarded".to_string(), false));
@@ -835,6 +1176,23 @@ impl HexPanel {
                     }
                 }
 
+                // Range fills (when bytes are selected)
+                if let Some(sel) = state.selection {
+                    ui.separator();
+                    if ui.button("Fill...")
+                        .on_hover_text(format!("Set all {} selected byte(s) to one value", sel.length()))
+                        .clicked()
+                    {
+                        state.edit.fill_input = Some(String::new());
+                    }
+                    if ui.button("Zero Fill")
+                        .on_hover_text(format!("Set all {} selected byte(s) to 00", sel.length()))
+                        .clicked()
+                    {
+                        state.request_fill(0);
+                    }
+                }
+
                 // Byte editor (when a byte is selected)
                 if let Some(offset) = state.edit.selected_offset {
                     ui.separator();
@@ -852,11 +1210,17 @@ impl HexPanel {
                         response.request_focus();
                     }
 
-                    // Apply edit on Enter or when 2 valid hex chars entered
-                    let should_apply = response.lost_focus()
-                        && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                        || state.edit.input_buffer.len() == 2
-                            && state.edit.input_buffer.chars().all(|c| c.is_ascii_hexdigit());
+                    // Arrow keys move to the neighbouring byte (the field keeps the focus)
+                    let moved = response.has_focus()
+                        && ui.input(byte_nav_delta).is_some_and(|delta| state.move_edit_byte(delta, BYTES_PER_ROW * PAGE_ROWS));
+
+                    // Apply edit on Enter or when 2 valid hex chars are typed
+                    // (not when the field was just pre-filled)
+                    let should_apply = !moved
+                        && (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                            || response.changed()
+                                && state.edit.input_buffer.len() == 2
+                                && state.edit.input_buffer.chars().all(|c| c.is_ascii_hexdigit()));
 
                     if should_apply {
                         if let Ok(new_byte) = u8::from_str_radix(&state.edit.input_buffer, 16) {
@@ -880,10 +1244,13 @@ impl HexPanel {
                         state.edit.input_buffer.clear();
                     }
                 }
+            } else if state.edit.read_only {
+                ui.label(RichText::new("READ-ONLY").color(Color32::from_rgb(100, 180, 255)).strong())
+                    .on_hover_text("Opened read-only: editing is disabled");
             } else {
                 // Show button to enable edit mode
                 if ui.button("Enable Edit Mode").clicked() {
-                    state.edit.confirm_dialog_open = true;
+                    state.edit.request_enable();
                 }
                 ui.weak("(Ctrl+E)");
             }
@@ -894,9 +1261,51 @@ impl HexPanel {
                 let color = if *is_error { Color32::RED } else { Color32::from_rgb(100, 200, 100) };
                 ui.label(RichText::new(msg).color(color).small());
             }
+
+            // Offset column radix and classification tint
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.selectable_value(&mut state.offset_radix, OffsetRadix::Decimal, "Dec");
+                ui.selectable_value(&mut state.offset_radix, OffsetRadix::Hex, "Hex");
+                ui.weak("Offsets:");
+                ui.separator();
+                egui::ComboBox::from_id_salt("hex_class_tint")
+                    .selected_text(state.class_tint.label())
+                    .width(60.0)
+                    .show_ui(ui, |ui| {
+                        for tint in ClassTint::ALL {
+                            let response = ui.selectable_value(&mut state.class_tint, tint, tint.label());
+                            if tint == ClassTint::Bytes {
+                                response.on_hover_text("Color every byte by its block's class");
+                            }
+                        }
+                    });
+                ui.weak("Class colors:");
+                ui.separator();
+                if ui.small_button("+").on_hover_text("Larger font (Ctrl+scroll)").clicked() {
+                    state.set_hex_font_size(state.hex_font_size + 1.0);
+                }
+                if ui.small_button(format!("{:.0}px", state.hex_font_size)).on_hover_text("Reset font size").clicked() {
+                    state.set_hex_font_size(DEFAULT_HEX_FONT_SIZE);
+                }
+                if ui.small_button("-").on_hover_text("Smaller font (Ctrl+scroll)").clicked() {
+                    state.set_hex_font_size(state.hex_font_size - 1.0);
+                }
+                ui.weak("Font:");
+            });
         });
     }
 
+    /// Ctrl+scroll over the hex view changes its font size.
+    fn handle_zoom(ui: &Ui, state: &mut AppState) {
+        if !ui.ui_contains_pointer() {
+            return;
+        }
+        let zoom = ui.input(|i| i.zoom_delta());
+        if zoom != 1.0 {
+            state.set_hex_font_size(state.hex_font_size * zoom);
+        }
+    }
+
     /// Show edit mode confirmation and save dialogs.
     fn show_edit_dialogs(ui: &mut Ui, state: &mut AppState) {
         // Enable edit mode confirmation dialog (SAFETY WARNING)
@@ -941,7 +1350,7 @@ impl HexPanel {
                         ui.horizontal(|ui| {
                             if ui.add(
                                 egui::Button::new(RichText::new("I understand, enable editing").color(Color32::from_rgb(255, 100, 100)))
-                            ).clicked() {
+                            ).clicked() && !state.edit.read_only {
                                 state.edit.enabled = true;
                                 state.edit.confirm_dialog_open = false;
                                 state.edit.status_message = Some(("Edit mode enabled - BE CAREFUL!".to_string(), true));
@@ -955,6 +1364,68 @@ impl HexPanel {
                 });
         }
 
+        // Fill value prompt
+        if let Some(mut input) = state.edit.fill_input.take() {
+            let mut open = true;
+            let mut value = None;
+            egui::Window::new("Fill with byte")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ui.ctx(), |ui| {
+                    let len = state.selection.map_or(0, |sel| sel.length());
+                    ui.label(format!("Hex byte to write to the {} selected byte(s):", len));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut input)
+                            .desired_width(30.0)
+                            .font(egui::TextStyle::Monospace)
+                            .char_limit(2)
+                            .hint_text("90"),
+                    );
+                    response.request_focus();
+                    let parsed = u8::from_str_radix(input.trim(), 16).ok();
+                    let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(parsed.is_some(), egui::Button::new("Fill")).clicked()
+                            || enter && parsed.is_some()
+                        {
+                            value = parsed;
+                        }
+                        if ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                            open = false;
+                        }
+                    });
+                });
+            if let Some(value) = value {
+                state.request_fill(value);
+            } else if open {
+                state.edit.fill_input = Some(input);
+            }
+        }
+
+        // Large fill confirmation
+        if let Some(value) = state.edit.fill_confirm {
+            egui::Window::new("Confirm Fill")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ui.ctx(), |ui| {
+                    let len = state.selection.map_or(0, |sel| sel.length());
+                    ui.label(format!("Set {} byte(s) to {:02X}?", len, value));
+                    ui.weak("Every byte becomes a pending edit; large fills use a lot of memory.");
+                    ui.horizontal(|ui| {
+                        if ui.button("Fill").clicked() {
+                            state.edit.fill_confirm = None;
+                            state.fill_selection(value);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            state.edit.fill_confirm = None;
+                        }
+                    });
+                });
+        }
+
         // Save confirmation dialog
         if state.edit.save_dialog_open {
             egui::Window::new("Save Changes")
@@ -1001,6 +1472,8 @@ impl HexPanel {
                                             state.classification = None;
                                             state.cached_entropy_stats = None;
                                             state.cached_class_counts = None;
+                                            state.checksums.a = None;
+                                            state.checksums.pixels.clear();
                                         }
                                         Err(e) => {
                                             state.edit.status_message = Some(
@@ -1034,11 +1507,13 @@ impl HexPanel {
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ui.ctx(), |ui| {
-                ui.label("Enter hex offset (e.g. 0xFF00 or FF00):");
+                ui.label("Enter hex offset (e.g. 0xFF00 or FF00) or a percentage (e.g. 50%):");
                 let response = ui.text_edit_singleline(&mut state.goto_text);
 
-                // Auto-focus the text field
-                if response.gained_focus() || state.goto_text.is_empty() {
+                // Auto-focus the text field (also when pre-filled from the clipboard)
+                if response.gained_focus() || state.goto_text.is_empty()
+                    || ui.memory(|m| m.focused().is_none())
+                {
                     response.request_focus();
                 }
 
@@ -1047,7 +1522,10 @@ impl HexPanel {
                         && ui.input(|i| i.key_pressed(egui::Key::Enter));
 
                     if ui.button("Go").clicked() || enter {
-                        if let Some(offset) = parse_offset(&state.goto_text) {
+                        if let Some(pct) = parse_percent(&state.goto_text) {
+                            state.viewport.start = percent_to_offset(file_len, pct);
+                            state.goto_open = false;
+                        } else if let Some(offset) = parse_offset(&state.goto_text) {
                             let aligned = (offset.min(file_len.saturating_sub(1)) / BYTES_PER_ROW) * BYTES_PER_ROW;
                             state.viewport.start = aligned;
                             state.goto_open = false;
@@ -1061,8 +1539,152 @@ impl HexPanel {
     }
 }
 
+/// Map an x position inside a hex column label to the byte index within its row.
+fn byte_index_at_x(relative_x: f32, row_len: usize, char_width: f32) -> Option<usize> {
+    // Each byte takes 3 glyphs (2 hex chars + space)
+    // Plus extra space after byte 7
+    let byte_width = char_width * 3.0; // "XX "
+    let mut x = 0.0;
+    for j in 0..row_len {
+        let next_x = x + byte_width + if j == 7 { char_width } else { 0.0 };
+        if relative_x >= x && relative_x < next_x {
+            return Some(j);
+        }
+        x = next_x;
+    }
+    None
+}
+
+/// Absolute offset of the byte under `pos` in a row's hex column label.
+fn hex_byte_at(
+    response: &egui::Response,
+    pos: Option<egui::Pos2>,
+    row_offset: u64,
+    row_len: usize,
+    char_width: f32,
+) -> Option<u64> {
+    let pos = pos.filter(|p| response.rect.contains(*p))?;
+    byte_index_at_x(pos.x - response.rect.left(), row_len, char_width).map(|j| row_offset + j as u64)
+}
+
+/// Hex column label for the diff panes. Returns the clicked byte offset, if any.
+fn clickable_hex(ui: &mut Ui, text: impl Into<egui::WidgetText>, byte_offset: u64, row_len: usize, char_width: f32) -> Option<u64> {
+    let response = ui.add(egui::Label::new(text).sense(Sense::click()));
+    if !response.clicked() {
+        return None;
+    }
+    let pos = response.interact_pointer_pos()?;
+    byte_index_at_x(pos.x - response.rect.left(), row_len, char_width).map(|j| byte_offset + j as u64)
+}
+
+/// Row height for a font size, keeping the default line spacing ratio.
+fn row_height(font_size: f32) -> f32 {
+    (ROW_HEIGHT * font_size / DEFAULT_HEX_FONT_SIZE).round()
+}
+
+/// Measured advance of one monospace glyph, in points.
+///
+/// Hit-testing uses this instead of a fixed width so clicks land on the right
+/// byte at any font size or scale factor.
+fn glyph_width(ctx: &egui::Context, font_size: f32) -> f32 {
+    ctx.fonts(|f| f.glyph_width(&FontId::monospace(font_size), '0'))
+}
+
+/// Class color of the block containing `offset` (transparent outside the
+/// classified range).
+fn class_color(theme: &Theme, classification: Option<&Vec<u8>>, block_size: u64, offset: u64) -> Color32 {
+    classification
+        .and_then(|c| c.get((offset / block_size.max(1)) as usize))
+        .map(|&v| theme.class_bg(v))
+        .unwrap_or(Color32::TRANSPARENT)
+}
+
+/// Backgrounds for a row's offset column and its hex/ASCII columns,
+/// from the classification of the block containing `byte_offset`.
+/// With [`ClassTint::Bytes`] the row stays clear and each byte gets its
+/// own [`class_color`].
+fn class_backgrounds(
+    theme: &Theme,
+    classification: Option<&Vec<u8>>,
+    block_size: u64,
+    byte_offset: u64,
+    tint: ClassTint,
+) -> (Color32, Color32) {
+    let class_bg = class_color(theme, classification, block_size, byte_offset);
+    match tint {
+        ClassTint::Off => (Color32::TRANSPARENT, Color32::TRANSPARENT),
+        ClassTint::OffsetColumn | ClassTint::Bytes => (class_bg, Color32::TRANSPARENT),
+        ClassTint::FullRow => (class_bg, class_bg),
+    }
+}
+
+/// Round an offset down to the start of its row.
+fn align_to_row(offset: u64) -> u64 {
+    offset - offset % BYTES_PER_ROW
+}
+
+/// Largest window start for the coarse slider, aligned so every row starts on a row boundary.
+/// The last window ends with the last (possibly partial) row.
+fn coarse_max_offset(file_len: u64) -> u64 {
+    let rows_end = file_len.div_ceil(BYTES_PER_ROW).saturating_mul(BYTES_PER_ROW);
+    align_to_row(rows_end.saturating_sub(MAX_DIRECT_ROWS * BYTES_PER_ROW))
+}
+
+/// Convert a slider value to a row-aligned offset in `[0, max_offset]`.
+/// Past 2^53 the f64 cannot represent every byte, so the cast result is clamped
+/// and re-aligned rather than trusted as-is (`as u64` saturates and maps NaN to 0).
+fn slider_to_offset(value: f64, max_offset: u64) -> u64 {
+    align_to_row((value as u64).min(max_offset))
+}
+
+/// Move a row-aligned offset by `delta` bytes, clamped to `[0, max_offset]`.
+fn nudge_offset(offset: u64, delta: i64, max_offset: u64) -> u64 {
+    let moved = if delta < 0 {
+        offset.saturating_sub(delta.unsigned_abs())
+    } else {
+        offset.saturating_add(delta as u64)
+    };
+    align_to_row(moved.min(max_offset))
+}
+
+/// Highlighted offsets of several sets, sorted, so a row can check for any
+/// highlight with one binary search instead of a lookup per byte.
+fn sorted_highlights(sets: &[&HashSet<u64>]) -> Vec<u64> {
+    let mut offsets: Vec<u64> = sets.iter().flat_map(|set| set.iter().copied()).collect();
+    offsets.sort_unstable();
+    offsets.dedup();
+    offsets
+}
+
+/// Whether any of the sorted `offsets` lies in `range`.
+fn any_in_range(offsets: &[u64], range: std::ops::Range<u64>) -> bool {
+    let i = offsets.partition_point(|&o| o < range.start);
+    offsets.get(i).is_some_and(|&o| o < range.end)
+}
+
+/// Absolute byte offset of row `row_idx` in a window starting at `coarse_offset`.
+fn row_offset(coarse_offset: u64, row_idx: usize) -> u64 {
+    coarse_offset.saturating_add((row_idx as u64).saturating_mul(BYTES_PER_ROW))
+}
+
+/// Row-aligned offset at `pct` percent of the file (clamped to 0..=100),
+/// always inside the file.
+pub(crate) fn percent_to_offset(file_len: u64, pct: f64) -> u64 {
+    // Basis points in integer math, so huge files don't lose precision in f64
+    let basis_points = (pct.clamp(0.0, 100.0) * 100.0).round() as u128;
+    let offset = (file_len as u128 * basis_points / 10_000) as u64;
+    align_to_row(offset.min(file_len.saturating_sub(1)))
+}
+
+/// Parse a percentage string: "50%", "12.5 %". Returns `None` without a `%` suffix
+/// or outside 0..=100.
+fn parse_percent(input: &str) -> Option<f64> {
+    let pct: f64 = input.trim().strip_suffix('%')?.trim().parse().ok()?;
+    (0.0..=100.0).contains(&pct).then_some(pct)
+}
+
 /// Parse an offset string: "0xFF00", "FF00", "1024" (decimal).
-fn parse_offset(input: &str) -> Option<u64> {
+pub(crate) fn parse_offset(input: &str) -> Option<u64> {
     let s = input.trim();
     if s.is_empty() {
         return None;
@@ -1085,10 +1707,43 @@ pub struct HexLine {
     pub ascii: String,
 }
 
+/// How the offset column is rendered: radix plus a fixed digit count so rows stay aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetFormat {
+    pub radix: OffsetRadix,
+    pub digits: usize,
+}
+
+impl Default for OffsetFormat {
+    fn default() -> Self {
+        Self { radix: OffsetRadix::Hex, digits: 8 }
+    }
+}
+
+impl OffsetFormat {
+    /// Size the column so the last offset of the file fits (at least 8 digits).
+    pub fn for_file(file_len: u64, radix: OffsetRadix) -> Self {
+        let last = file_len.saturating_sub(1);
+        let digits = match radix {
+            OffsetRadix::Hex => format!("{:X}", last).len(),
+            OffsetRadix::Decimal => last.to_string().len(),
+        };
+        Self { radix, digits: digits.max(8) }
+    }
+
+    /// Format an offset for the offset column (including the trailing gap).
+    pub fn format(&self, offset: u64) -> String {
+        match self.radix {
+            OffsetRadix::Hex => format!("{:0width$X}  ", offset, width = self.digits),
+            OffsetRadix::Decimal => format!("{:0width$}  ", offset, width = self.digits),
+        }
+    }
+}
+
 /// Format one row of hex output using lookup tables (zero allocations per byte).
-pub fn format_hex_line(byte_offset: u64, data: &[u8]) -> HexLine {
+pub fn format_hex_line(byte_offset: u64, data: &[u8], offset_fmt: OffsetFormat) -> HexLine {
     // Offset column
-    let offset = format!("{:08X}  ", byte_offset);
+    let offset = offset_fmt.format(byte_offset);
 
     // Hex column - use lookup table instead of format!
     let mut hex = String::with_capacity(50);
@@ -1143,7 +1798,7 @@ mod tests {
     #[test]
     fn format_hex_line_full_row() {
         let data: Vec<u8> = (0x00..=0x0F).collect();
-        let line = format_hex_line(0, &data);
+        let line = format_hex_line(0, &data, OffsetFormat::default());
 
         assert_eq!(line.offset, "00000000  ");
         assert_eq!(
@@ -1156,7 +1811,7 @@ mod tests {
     #[test]
     fn format_hex_line_partial_row() {
         let data = b"Hello";
-        let line = format_hex_line(0x100, data);
+        let line = format_hex_line(0x100, data, OffsetFormat::default());
 
         assert_eq!(line.offset, "00000100  ");
         // "Hello" = 48 65 6C 6C 6F then padding
@@ -1169,21 +1824,49 @@ mod tests {
     #[test]
     fn format_hex_line_printable_ascii() {
         let data = b"ABCDEFGHIJKLMNOP";
-        let line = format_hex_line(0, data);
+        let line = format_hex_line(0, data, OffsetFormat::default());
         assert_eq!(line.ascii, "|ABCDEFGHIJKLMNOP|");
     }
 
     #[test]
     fn format_hex_line_at_large_offset() {
         let data = vec![0xFFu8; 16];
-        let line = format_hex_line(0xDEAD_BEEF, &data);
+        let line = format_hex_line(0xDEAD_BEEF, &data, OffsetFormat::default());
         assert_eq!(line.offset, "DEADBEEF  ");
     }
 
+    #[test]
+    fn format_hex_line_beyond_4gb_not_padded_oddly() {
+        let data = vec![0u8; 16];
+        let fmt = OffsetFormat::for_file(0x2_0000_0000, OffsetRadix::Hex);
+        assert_eq!(fmt.digits, 9);
+        assert_eq!(format_hex_line(0x1_0000_0000, &data, fmt).offset, "100000000  ");
+        // Low offsets share the same width so columns stay aligned
+        assert_eq!(format_hex_line(0x10, &data, fmt).offset, "000000010  ");
+    }
+
+    #[test]
+    fn offset_format_decimal() {
+        let fmt = OffsetFormat::for_file(256, OffsetRadix::Decimal);
+        assert_eq!(fmt.digits, 8);
+        assert_eq!(fmt.format(255), "00000255  ");
+
+        let fmt = OffsetFormat::for_file(u64::MAX, OffsetRadix::Decimal);
+        assert_eq!(fmt.digits, 20);
+        assert_eq!(fmt.format(u64::MAX - 1).trim_end(), (u64::MAX - 1).to_string());
+    }
+
+    #[test]
+    fn offset_format_full_64bit_hex() {
+        let fmt = OffsetFormat::for_file(u64::MAX, OffsetRadix::Hex);
+        assert_eq!(fmt.digits, 16);
+        assert_eq!(fmt.format(0xFFFF_FFFF_FFFF_FFF0), "FFFFFFFFFFFFFFF0  ");
+    }
+
     #[test]
     fn format_hex_line_separator_at_byte_8() {
         let data = vec![0xAAu8; 16];
-        let line = format_hex_line(0, &data);
+        let line = format_hex_line(0, &data, OffsetFormat::default());
         // Should have double space between byte 7 and byte 8
         assert!(line.hex.contains("AA  AA"));
     }
@@ -1211,6 +1894,117 @@ mod tests {
         assert!(total_rows > MAX_DIRECT_ROWS);
     }
 
+    #[test]
+    fn coarse_window_math_8tb_file() {
+        let file_len: u64 = 8u64 << 40;
+        let max_offset = coarse_max_offset(file_len);
+        assert_eq!(max_offset % BYTES_PER_ROW, 0);
+        assert_eq!(max_offset, file_len - MAX_DIRECT_ROWS * BYTES_PER_ROW);
+
+        // Last row of the last window is the last row of the file
+        let last_row = row_offset(max_offset, MAX_DIRECT_ROWS as usize - 1);
+        assert_eq!(last_row, file_len - BYTES_PER_ROW);
+
+        // Slider end lands exactly on the last window
+        assert_eq!(slider_to_offset(max_offset as f64, max_offset), max_offset);
+        // A mid-file position lands on the intended row
+        let target = 3u64 << 40 | 0x1230;
+        assert_eq!(slider_to_offset(target as f64, max_offset), target);
+    }
+
+    #[test]
+    fn coarse_max_offset_unaligned_file() {
+        let window = MAX_DIRECT_ROWS * BYTES_PER_ROW;
+        let file_len = window + 1000 + 7;
+        let max_offset = coarse_max_offset(file_len);
+        assert_eq!(max_offset % BYTES_PER_ROW, 0);
+        // The last window reaches the final partial row
+        assert!((max_offset..max_offset + window).contains(&(file_len - 1)));
+        assert_eq!(max_offset + window, file_len.div_ceil(BYTES_PER_ROW) * BYTES_PER_ROW);
+
+        assert_eq!(coarse_max_offset(window + 1024), 1024);
+        assert_eq!(coarse_max_offset(1000), 0);
+    }
+
+    #[test]
+    fn slider_to_offset_beyond_f64_precision() {
+        // Above 2^53 the f64 step exceeds one byte; result must still be aligned and in range
+        let max_offset = align_to_row(u64::MAX - MAX_DIRECT_ROWS * BYTES_PER_ROW);
+        let v = slider_to_offset(((1u64 << 60) + 5) as f64, max_offset);
+        assert_eq!(v % BYTES_PER_ROW, 0);
+        assert_eq!(slider_to_offset(f64::MAX, max_offset), max_offset);
+        assert_eq!(slider_to_offset(-1.0, max_offset), 0);
+        assert_eq!(slider_to_offset(f64::NAN, max_offset), 0);
+    }
+
+    #[test]
+    fn nudge_offset_rows_and_pages() {
+        let max_offset = coarse_max_offset(8u64 << 40);
+        let start = 5u64 << 40;
+        assert_eq!(nudge_offset(start, 16, max_offset), start + 16);
+        assert_eq!(nudge_offset(start, -512, max_offset), start - 512);
+        // Clamped at both ends
+        assert_eq!(nudge_offset(0, -16, max_offset), 0);
+        assert_eq!(nudge_offset(max_offset, 512, max_offset), max_offset);
+        // Exact at TB scale (no f64 round-trip)
+        assert_eq!(nudge_offset(start + 0x10, 16, max_offset) - start, 0x20);
+    }
+
+    #[test]
+    fn highlight_rows_found_by_binary_search() {
+        let search: HashSet<u64> = [0x20, 0x21, 0x105].into_iter().collect();
+        let deep_scan: HashSet<u64> = [0x21, 0x3FF].into_iter().collect();
+        let offsets = sorted_highlights(&[&search, &deep_scan]);
+        assert_eq!(offsets, vec![0x20, 0x21, 0x105, 0x3FF]);
+
+        assert!(any_in_range(&offsets, 0x20..0x30));
+        assert!(!any_in_range(&offsets, 0x30..0x40));
+        assert!(any_in_range(&offsets, 0x100..0x110));
+        assert!(any_in_range(&offsets, 0x3F0..0x400));
+        assert!(!any_in_range(&offsets, 0x400..0x410));
+        assert!(!any_in_range(&[], 0..16));
+    }
+
+    #[test]
+    fn row_offset_never_overflows() {
+        assert_eq!(row_offset(u64::MAX - 8, 1), u64::MAX);
+        assert_eq!(row_offset(0x1_0000_0000, 2), 0x1_0000_0020);
+    }
+
+    #[test]
+    fn byte_index_at_x_accounts_for_gap() {
+        assert_eq!(byte_index_at_x(0.0, 16, 8.0), Some(0));
+        assert_eq!(byte_index_at_x(23.9, 16, 8.0), Some(0));
+        assert_eq!(byte_index_at_x(24.0, 16, 8.0), Some(1));
+        // Byte 7 is wider because of the group separator.
+        assert_eq!(byte_index_at_x(7.0 * 24.0 + 30.0, 16, 8.0), Some(7));
+        assert_eq!(byte_index_at_x(8.0 * 24.0 + 8.0, 16, 8.0), Some(8));
+        // Past the end of a short row.
+        assert_eq!(byte_index_at_x(4.0 * 24.0, 4, 8.0), None);
+        assert_eq!(byte_index_at_x(-1.0, 16, 8.0), None);
+    }
+
+    #[test]
+    fn byte_index_at_x_with_scaled_font() {
+        let ctx = egui::Context::default();
+        ctx.set_pixels_per_point(1.5);
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            let small = glyph_width(ctx, DEFAULT_HEX_FONT_SIZE);
+            let large = glyph_width(ctx, 2.0 * DEFAULT_HEX_FONT_SIZE);
+            assert!(small > 0.0);
+            assert!((large - 2.0 * small).abs() < 1.0);
+
+            // Middle of each byte maps back to that byte at the larger size
+            for j in [0usize, 7, 8, 15] {
+                let gap = if j > 7 { large } else { 0.0 };
+                let x = j as f32 * 3.0 * large + gap + large;
+                assert_eq!(byte_index_at_x(x, 16, large), Some(j));
+            }
+        });
+        assert_eq!(row_height(26.0), 36.0);
+        assert_eq!(row_height(DEFAULT_HEX_FONT_SIZE), ROW_HEIGHT);
+    }
+
     #[test]
     fn format_offset_small() {
         assert_eq!(format_offset(0), "0x0");
@@ -1247,4 +2041,44 @@ mod tests {
         assert_eq!(parse_offset(""), None);
         assert_eq!(parse_offset("  "), None);
     }
+
+    #[test]
+    fn percent_navigation() {
+        assert_eq!(parse_percent("50%"), Some(50.0));
+        assert_eq!(parse_percent(" 12.5 % "), Some(12.5));
+        assert_eq!(parse_percent("50"), None);
+        assert_eq!(parse_percent("150%"), None);
+
+        assert_eq!(percent_to_offset(1000, 0.0), 0);
+        assert_eq!(percent_to_offset(1000, 50.0), 496);
+        // 100% lands on the last row, not past the end
+        assert_eq!(percent_to_offset(1000, 100.0), 992);
+        assert_eq!(percent_to_offset(0, 50.0), 0);
+        // Exact on huge files
+        let len = 8u64 << 40;
+        assert_eq!(percent_to_offset(len, 50.0), 4u64 << 40);
+        assert_eq!(percent_to_offset(len, 12.5), 1u64 << 40);
+    }
+
+    #[test]
+    fn class_backgrounds_per_tint() {
+        let theme = Theme::default();
+        let classes = vec![0u8, 3];
+        let bg = theme.class_bg(3);
+        let t = Color32::TRANSPARENT;
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 256, 256, ClassTint::Off), (t, t));
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 256, 256, ClassTint::OffsetColumn), (bg, t));
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 256, 300, ClassTint::FullRow), (bg, bg));
+        // Finer classification blocks
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 64, 64, ClassTint::FullRow), (bg, bg));
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 64, 128, ClassTint::FullRow), (t, t));
+        // Past the classified range or without classification
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 256, 4096, ClassTint::FullRow), (t, t));
+        assert_eq!(class_backgrounds(&theme, None, 256, 0, ClassTint::FullRow), (t, t));
+        // Per-byte tint leaves the row clear and colors each byte by its block
+        assert_eq!(class_backgrounds(&theme, Some(&classes), 64, 64, ClassTint::Bytes), (bg, t));
+        assert_eq!(class_color(&theme, Some(&classes), 64, 63), theme.class_bg(0));
+        assert_eq!(class_color(&theme, Some(&classes), 64, 64), bg);
+        assert_eq!(class_color(&theme, Some(&classes), 64, 128), t);
+    }
 }
@@ -1,5 +1,7 @@
-use egui::{Context, Color32, ColorImage, TextureHandle, TextureOptions, Vec2};
-use crate::state::AppState;
+use egui::{Context, Color32, ColorImage, Pos2, Rect, Stroke, TextureHandle, TextureOptions, Vec2};
+use tv_core::xy2d;
+use crate::session::HilbertSessionState;
+use crate::state::{AppState, GpuJob};
 
 /// Hilbert curve visualization mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,6 +15,14 @@ pub enum HilbertMode {
 }
 
 impl HilbertMode {
+    pub const ALL: [HilbertMode; 4] =
+        [HilbertMode::Entropy, HilbertMode::Classification, HilbertMode::ByteValue, HilbertMode::BitDensity];
+
+    /// Parse the `Debug` name stored in sessions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| format!("{:?}", m) == name)
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             HilbertMode::Entropy => "Entropy",
@@ -32,6 +42,15 @@ impl HilbertMode {
     }
 }
 
+/// Texture sizes offered in the window (powers of 2).
+pub const TEXTURE_SIZES: [u32; 3] = [256, 512, 1024];
+
+/// Maximum zoom factor relative to fit-to-window.
+const MAX_ZOOM: f32 = 64.0;
+
+/// Screen pixels per texture pixel from which the hover crosshair is drawn.
+const CROSSHAIR_MIN_PIXEL_SIZE: f32 = 6.0;
+
 /// State for the Hilbert visualization window.
 pub struct HilbertState {
     /// Current visualization mode.
@@ -48,8 +67,19 @@ pub struct HilbertState {
     pub computing: bool,
     /// Pending pixel data from background computation.
     pub pending_pixels: Option<Vec<u32>>,
+    /// Side length and pixels of the current texture, kept for PNG export.
+    pixels: Option<(u32, Vec<u32>)>,
     /// Last computation time in ms.
     pub compute_time_ms: Option<f64>,
+    /// Why the entropy behind the texture is partial or sampled (entropy mode).
+    pub entropy_warning: Option<String>,
+    /// Whether the warning is about incomplete entropy, which a later
+    /// regeneration can fix (sampling is not).
+    pub entropy_incomplete: bool,
+    /// Zoom factor (1.0 = whole texture fits the window).
+    pub zoom: f32,
+    /// Texture coordinate (0..1) shown at the center of the view.
+    pub center: Pos2,
 }
 
 impl Default for HilbertState {
@@ -62,7 +92,12 @@ impl Default for HilbertState {
             cached_mode: HilbertMode::Entropy,
             computing: false,
             pending_pixels: None,
+            pixels: None,
             compute_time_ms: None,
+            entropy_warning: None,
+            entropy_incomplete: false,
+            zoom: 1.0,
+            center: Pos2::new(0.5, 0.5),
         }
     }
 }
@@ -78,7 +113,92 @@ impl HilbertState {
     /// Mark cache as invalid (e.g., when file changes).
     pub fn invalidate(&mut self) {
         self.texture = None;
+        self.pixels = None;
         self.cached_file_size = 0;
+        self.entropy_warning = None;
+        self.entropy_incomplete = false;
+    }
+
+    /// The current texture as a PNG file.
+    pub fn png(&self) -> Option<Vec<u8>> {
+        let (size, pixels) = self.pixels.as_ref().filter(|_| self.texture.is_some())?;
+        let rgba: Vec<u8> = pixels.iter().flat_map(|p| p.to_le_bytes()).collect();
+        Some(tv_core::encode_png(*size, *size, &rgba))
+    }
+
+    /// Store the mode and texture size in a session (the offset is set by the app).
+    pub fn save_session(&self, session: &mut HilbertSessionState) {
+        session.mode = format!("{:?}", self.mode);
+        session.order = self.texture_size.trailing_zeros();
+    }
+
+    /// Restore the mode and texture size stored by `save_session`. Unknown
+    /// values keep the current ones.
+    pub fn restore_session(&mut self, session: &HilbertSessionState) {
+        match HilbertMode::from_name(&session.mode) {
+            Some(mode) => self.mode = mode,
+            None => log::warn!("Unknown Hilbert mode in session: {}", session.mode),
+        }
+        let size = 1u32.checked_shl(session.order).unwrap_or(0);
+        if TEXTURE_SIZES.contains(&size) {
+            self.texture_size = size;
+        } else {
+            log::warn!("Unsupported Hilbert order in session: {}", session.order);
+        }
+        self.invalidate();
+    }
+
+    /// Visible part of the texture in texture coordinates (0..1).
+    pub fn visible_uv(&self) -> Rect {
+        Rect::from_center_size(self.center, Vec2::splat(1.0 / self.zoom))
+    }
+
+    /// Texture coordinate under a point given relative to the view (0..1).
+    pub fn uv_at(&self, rel: Vec2) -> Pos2 {
+        let uv = self.visible_uv();
+        uv.min + rel * uv.size()
+    }
+
+    /// Multiply the zoom by `factor`, keeping the texture point under `rel` fixed.
+    pub fn zoom_at(&mut self, factor: f32, rel: Vec2) {
+        let anchor = self.uv_at(rel);
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM);
+        let half = 0.5 / self.zoom;
+        self.center = anchor - rel / self.zoom + Vec2::splat(half);
+        self.clamp_center();
+    }
+
+    /// Move the view by `delta`, a fraction of the view size.
+    pub fn pan_by(&mut self, delta: Vec2) {
+        self.center -= delta / self.zoom;
+        self.clamp_center();
+    }
+
+    /// Back to fit-to-window.
+    pub fn reset_view(&mut self) {
+        self.zoom = 1.0;
+        self.center = Pos2::new(0.5, 0.5);
+    }
+
+    /// Keep the visible area inside the texture.
+    fn clamp_center(&mut self) {
+        let half = 0.5 / self.zoom;
+        self.center.x = self.center.x.clamp(half, 1.0 - half);
+        self.center.y = self.center.y.clamp(half, 1.0 - half);
+    }
+
+    /// Texture pixel and file offset under a point given relative to the view.
+    fn pixel_at(&self, rel: Vec2, file_size: u64) -> (u32, u32, u64, u64) {
+        let uv = self.uv_at(rel);
+        let max = self.texture_size - 1;
+        let x = ((uv.x * self.texture_size as f32) as u32).min(max);
+        let y = ((uv.y * self.texture_size as f32) as u32).min(max);
+
+        // Convert (x, y) to Hilbert index, then to file offset
+        let hilbert_index = xy2d(self.texture_size, x, y);
+        let total_pixels = (self.texture_size * self.texture_size) as u64;
+        let bytes_per_pixel = (file_size / total_pixels).max(1);
+        (x, y, hilbert_index, hilbert_index * bytes_per_pixel)
     }
 
     /// Update cache with new texture.
@@ -109,6 +229,7 @@ impl HilbertState {
         );
 
         self.texture = Some(texture);
+        self.pixels = Some((self.texture_size, pixels));
         self.cached_file_size = file_size;
         self.cached_mode = self.mode;
     }
@@ -167,7 +288,7 @@ impl HilbertWindow {
             egui::ComboBox::from_id_salt("hilbert_size")
                 .selected_text(format!("{}x{}", hilbert.texture_size, hilbert.texture_size))
                 .show_ui(ui, |ui| {
-                    for size in [256, 512, 1024] {
+                    for size in TEXTURE_SIZES {
                         if ui.selectable_value(&mut hilbert.texture_size, size, format!("{}x{}", size, size)).changed() {
                             hilbert.invalidate();
                         }
@@ -190,7 +311,7 @@ impl HilbertWindow {
         if hilbert.computing && hilbert.pending_pixels.is_none() {
             ui.horizontal(|ui| {
                 ui.spinner();
-                ui.label("Computing...");
+                ui.label(state.gpu_job_text(GpuJob::Hilbert, "Computing..."));
             });
         }
 
@@ -204,11 +325,30 @@ impl HilbertWindow {
         if let Some(ms) = hilbert.compute_time_ms {
             ui.weak(format!("Computed in {:.1} ms", ms));
         }
+        if let Some(warning) = hilbert.entropy_warning.clone().filter(|_| hilbert.texture.is_some()) {
+            ui.horizontal(|ui| {
+                ui.weak(warning);
+                if hilbert.entropy_incomplete && !hilbert.computing && ui.button("Regenerate").clicked() {
+                    hilbert.invalidate();
+                    hilbert.computing = true;
+                }
+            });
+        }
 
         ui.separator();
 
         // Display texture
-        if let Some(ref texture) = hilbert.texture {
+        if let Some(texture) = hilbert.texture.clone() {
+            ui.horizontal(|ui| {
+                ui.weak("Scroll to zoom, drag to pan");
+                if hilbert.zoom > 1.0 {
+                    ui.label(format!("{:.1}x", hilbert.zoom));
+                    if ui.small_button("Fit").clicked() {
+                        hilbert.reset_view();
+                    }
+                }
+            });
+
             let available_size = ui.available_size();
             let tex_size = hilbert.texture_size as f32;
 
@@ -216,56 +356,70 @@ impl HilbertWindow {
             let scale = (available_size.x.min(available_size.y) / tex_size).min(1.5);
             let display_size = Vec2::splat(tex_size * scale);
 
-            let response = ui.add(
-                egui::Image::new(texture)
-                    .fit_to_exact_size(display_size)
-                    .sense(egui::Sense::click())
-            );
+            let (rect, response) = ui.allocate_exact_size(display_size, egui::Sense::click_and_drag());
+            ui.painter().image(texture.id(), rect, hilbert.visible_uv(), Color32::WHITE);
+            if let Some(warning) = &hilbert.entropy_warning {
+                Self::paint_warning(ui, rect, warning);
+            }
 
-            // Handle clicks - navigate to the corresponding offset
-            if response.clicked() {
-                if let Some(pos) = response.interact_pointer_pos() {
-                    let rect = response.rect;
-                    let rel_x = (pos.x - rect.left()) / rect.width();
-                    let rel_y = (pos.y - rect.top()) / rect.height();
+            let rel_of = |pos: Pos2| ((pos - rect.min) / rect.size()).clamp(Vec2::ZERO, Vec2::splat(1.0));
 
-                    let x = (rel_x * tex_size) as u32;
-                    let y = (rel_y * tex_size) as u32;
+            // Mouse wheel zooms around the cursor
+            if let Some(pos) = response.hover_pos() {
+                let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                if scroll != 0.0 {
+                    hilbert.zoom_at((scroll / 200.0).exp(), rel_of(pos));
+                }
+            }
 
-                    // Convert (x, y) to Hilbert index, then to file offset
-                    let hilbert_index = xy2d(hilbert.texture_size, x, y);
-                    let total_pixels = (hilbert.texture_size * hilbert.texture_size) as u64;
-                    let bytes_per_pixel = (file_size / total_pixels).max(1);
-                    let offset = (hilbert_index as u64) * bytes_per_pixel;
+            // Drag pans, double-click returns to fit
+            if response.dragged() {
+                hilbert.pan_by(response.drag_delta() / rect.size());
+            }
+            if response.double_clicked() {
+                hilbert.reset_view();
+            }
 
+            // Handle clicks - navigate to the corresponding offset
+            if response.clicked() {
+                if let Some(pos) = response.interact_pointer_pos() {
+                    let (_, _, _, offset) = hilbert.pixel_at(rel_of(pos), file_size);
                     if offset < file_size {
                         state.viewport.start = (offset / 16) * 16;
                     }
                 }
             }
 
-            // Show tooltip with offset info on hover
-            if response.hovered() {
-                if let Some(pos) = ui.input(|i| i.pointer.hover_pos()) {
-                    let rect = response.rect;
-                    if rect.contains(pos) {
-                        let rel_x = (pos.x - rect.left()) / rect.width();
-                        let rel_y = (pos.y - rect.top()) / rect.height();
-
-                        let x = (rel_x * tex_size) as u32;
-                        let y = (rel_y * tex_size) as u32;
-
-                        let hilbert_index = xy2d(hilbert.texture_size, x, y);
-                        let total_pixels = (hilbert.texture_size * hilbert.texture_size) as u64;
-                        let bytes_per_pixel = (file_size / total_pixels).max(1);
-                        let offset = (hilbert_index as u64) * bytes_per_pixel;
-
-                        response.on_hover_text(format!(
-                            "Offset: 0x{:X}\nPixel: ({}, {})\nHilbert index: {}",
-                            offset, x, y, hilbert_index
-                        ));
-                    }
+            // Show offset info on hover; a crosshair once pixels are large enough to aim at
+            if let Some(pos) = response.hover_pos() {
+                let (x, y, hilbert_index, offset) = hilbert.pixel_at(rel_of(pos), file_size);
+                let pixel_size = scale * hilbert.zoom;
+                if pixel_size >= CROSSHAIR_MIN_PIXEL_SIZE {
+                    let uv = hilbert.visible_uv();
+                    let pixel_center = Pos2::new((x as f32 + 0.5) / tex_size, (y as f32 + 0.5) / tex_size);
+                    let screen = rect.min + (pixel_center - uv.min) / uv.size() * rect.size();
+                    let painter = ui.painter_at(rect);
+                    let stroke = Stroke::new(1.0, Color32::from_white_alpha(160));
+                    painter.hline(rect.x_range(), screen.y, stroke);
+                    painter.vline(screen.x, rect.y_range(), stroke);
+                    painter.rect_stroke(
+                        Rect::from_center_size(screen, Vec2::splat(pixel_size)),
+                        0.0,
+                        Stroke::new(1.0, Color32::WHITE),
+                    );
+                    painter.text(
+                        rect.left_top() + Vec2::new(4.0, 4.0),
+                        egui::Align2::LEFT_TOP,
+                        format!("0x{:X}", offset),
+                        egui::FontId::monospace(12.0),
+                        Color32::WHITE,
+                    );
                 }
+
+                response.on_hover_text(format!(
+                    "Offset: 0x{:X}\nPixel: ({}, {})\nHilbert index: {}",
+                    offset, x, y, hilbert_index
+                ));
             }
         } else if !hilbert.computing {
             // Show placeholder
@@ -286,6 +440,20 @@ impl HilbertWindow {
         Self::show_legend(ui, hilbert.mode);
     }
 
+    /// Banner along the bottom of the map saying its entropy is not the full result.
+    fn paint_warning(ui: &egui::Ui, rect: Rect, warning: &str) {
+        let painter = ui.painter_at(rect);
+        let font = egui::FontId::proportional(13.0);
+        let text = format!("⚠ {}", warning);
+        let galley = painter.layout_no_wrap(text, font, Color32::from_rgb(255, 200, 100));
+        let banner = Rect::from_min_max(
+            Pos2::new(rect.left(), rect.bottom() - galley.size().y - 8.0),
+            rect.right_bottom(),
+        );
+        painter.rect_filled(banner, 0.0, Color32::from_black_alpha(180));
+        painter.galley(banner.left_top() + Vec2::new(6.0, 4.0), galley, Color32::WHITE);
+    }
+
     fn show_legend(ui: &mut egui::Ui, mode: HilbertMode) {
         ui.collapsing("Legend", |ui| {
             match mode {
@@ -373,32 +541,68 @@ impl HilbertWindow {
     }
 }
 
-/// Convert (x, y) coordinates to Hilbert curve index.
-/// n = size of grid (power of 2)
-fn xy2d(n: u32, x: u32, y: u32) -> u64 {
-    let mut rx: u32;
-    let mut ry: u32;
-    let mut d: u64 = 0;
-    let mut s: u32 = n / 2;
-    let mut px = x;
-    let mut py = y;
-
-    while s > 0 {
-        rx = if (px & s) > 0 { 1 } else { 0 };
-        ry = if (py & s) > 0 { 1 } else { 0 };
-        d += (s as u64) * (s as u64) * (((3 * rx) ^ ry) as u64);
-
-        // Rotate quadrant
-        if ry == 0 {
-            if rx == 1 {
-                px = s - 1 - px;
-                py = s - 1 - py;
-            }
-            std::mem::swap(&mut px, &mut py);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Session;
+
+    #[test]
+    fn session_preserves_mode_and_texture_size() {
+        let hilbert = HilbertState { mode: HilbertMode::BitDensity, texture_size: 1024, ..Default::default() };
+        let mut session = Session::default();
+        hilbert.save_session(&mut session.hilbert);
+
+        let path = std::env::temp_dir().join(format!("tv_ui_hilbert_session_{}.titan", std::process::id()));
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let mut restored = HilbertState::default();
+        restored.restore_session(&loaded.hilbert);
+        assert_eq!(restored.mode, HilbertMode::BitDensity);
+        assert_eq!(restored.texture_size, 1024);
+
+        // Unknown values keep the current settings
+        restored.restore_session(&HilbertSessionState { mode: "Spiral".to_string(), order: 3, offset: 0 });
+        assert_eq!((restored.mode, restored.texture_size), (HilbertMode::BitDensity, 1024));
+    }
 
-        s /= 2;
+    #[test]
+    fn zoom_keeps_point_under_cursor() {
+        let mut hilbert = HilbertState::default();
+        assert_eq!(hilbert.visible_uv(), Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)));
+
+        let rel = Vec2::new(0.25, 0.75);
+        let before = hilbert.uv_at(rel);
+        hilbert.zoom_at(4.0, rel);
+        assert_eq!(hilbert.zoom, 4.0);
+        assert!((hilbert.uv_at(rel) - before).length() < 1e-5);
+
+        // Zooming out never goes below fit-to-window
+        hilbert.zoom_at(0.01, rel);
+        assert_eq!(hilbert.zoom, 1.0);
+        assert_eq!(hilbert.center, Pos2::new(0.5, 0.5));
     }
 
-    d
+    #[test]
+    fn pan_stays_inside_texture() {
+        let mut hilbert = HilbertState::default();
+        hilbert.zoom_at(2.0, Vec2::splat(0.5));
+        hilbert.pan_by(Vec2::new(-10.0, 0.0));
+        let uv = hilbert.visible_uv();
+        assert!((uv.max.x - 1.0).abs() < 1e-5);
+        assert!((uv.min.y - 0.25).abs() < 1e-5);
+
+        hilbert.reset_view();
+        assert_eq!(hilbert.zoom, 1.0);
+    }
+
+    #[test]
+    fn pixel_offsets_follow_hilbert_order() {
+        let hilbert = HilbertState { texture_size: 4, ..Default::default() };
+        // 4x4 grid over 1600 bytes: 100 bytes per pixel
+        assert_eq!(hilbert.pixel_at(Vec2::new(0.1, 0.1), 1600), (0, 0, 0, 0));
+        assert_eq!(hilbert.pixel_at(Vec2::new(0.3, 0.1), 1600), (1, 0, 1, 100));
+        assert_eq!(hilbert.pixel_at(Vec2::new(0.9, 0.1), 1600), (3, 0, 15, 1500));
+    }
 }
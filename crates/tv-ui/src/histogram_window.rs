@@ -2,9 +2,12 @@
 //!
 //! Shows the distribution of byte values (0-255) in the current file or viewport,
 //! useful for identifying encrypted/compressed data patterns.
+//! Ranges larger than `max_bytes` are sampled in blocks spread evenly over
+//! the whole range by default, so the distribution is not biased toward the
+//! start of huge files.
 
 use egui::{Context, Color32, Pos2, Rect, Stroke, Vec2, FontId, Sense, RichText};
-use tv_core::{ByteHistogram, HistogramStats};
+use tv_core::{AnomalyKind, ByteAnomaly, ByteHistogram, HistogramStats};
 use crate::state::AppState;
 
 /// Scope for histogram computation.
@@ -25,10 +28,18 @@ pub struct HistogramState {
     pub histogram: Option<ByteHistogram>,
     /// Cached stats.
     pub stats: Option<HistogramStats>,
+    /// Byte values with anomalous frequency, with the absolute offsets of
+    /// their first and last occurrence in the analyzed range.
+    pub anomalies: Vec<(ByteAnomaly, Option<(u64, u64)>)>,
     /// Analysis scope.
     pub scope: HistogramScope,
     /// Maximum number of bytes to analyze (for large files).
     pub max_bytes: usize,
+    /// Past `max_bytes`, read blocks spread across the whole range instead
+    /// of only its prefix.
+    pub sampled: bool,
+    /// Length of the range the current histogram was sampled from, if sampled.
+    pub sampled_from: Option<u64>,
     /// Hovered byte value.
     pub hovered_byte: Option<u8>,
     /// Display mode.
@@ -49,8 +60,11 @@ impl Default for HistogramState {
         Self {
             histogram: None,
             stats: None,
+            anomalies: Vec::new(),
             scope: HistogramScope::FullFile,
             max_bytes: 64 * 1024 * 1024, // 64 MB for better coverage
+            sampled: true,
+            sampled_from: None,
             hovered_byte: None,
             log_scale: false,
             show_grid: true,
@@ -67,6 +81,8 @@ impl HistogramState {
     pub fn clear(&mut self) {
         self.histogram = None;
         self.stats = None;
+        self.anomalies.clear();
+        self.sampled_from = None;
         self.computing = false;
         self.progress = 0.0;
         self.cached_file_size = 0;
@@ -153,6 +169,16 @@ impl HistogramWindow {
 
             ui.separator();
 
+            let sample_hint = format!(
+                "Files over {} are sampled: {} KiB blocks spread evenly from start to end. \
+                 Off: only the first {} are counted.",
+                format_bytes(hist_state.max_bytes as u64),
+                tv_core::histogram::SAMPLE_BLOCK_BYTES / 1024,
+                format_bytes(hist_state.max_bytes as u64),
+            );
+            if ui.checkbox(&mut hist_state.sampled, "Sample whole file").on_hover_text(sample_hint).changed() {
+                hist_state.clear();
+            }
             ui.checkbox(&mut hist_state.log_scale, "Log scale");
             ui.checkbox(&mut hist_state.show_grid, "Grid");
 
@@ -187,7 +213,14 @@ impl HistogramWindow {
         // Stats panel
         if let Some(stats) = &hist_state.stats {
             ui.horizontal(|ui| {
-                ui.label(format!("Analyzed: {} bytes", format_bytes(stats.total)));
+                match hist_state.sampled_from {
+                    Some(range) => ui.label(format!("Analyzed: {} sampled from {}", format_bytes(stats.total), format_bytes(range)))
+                        .on_hover_text(format!(
+                            "{} KiB blocks spread evenly across the range",
+                            tv_core::histogram::SAMPLE_BLOCK_BYTES / 1024
+                        )),
+                    None => ui.label(format!("Analyzed: {} bytes", format_bytes(stats.total))),
+                };
                 ui.separator();
                 ui.label(format!("Entropy: {:.2} bits", stats.entropy));
                 ui.separator();
@@ -219,6 +252,10 @@ impl HistogramWindow {
             });
         }
 
+        if !hist_state.anomalies.is_empty() {
+            Self::show_anomalies(ui, state, &hist_state.anomalies);
+        }
+
         ui.separator();
 
         // Histogram chart - clone to avoid borrow conflict
@@ -247,6 +284,56 @@ impl HistogramWindow {
         }
     }
 
+    /// Collapsible table of anomalous byte values; offsets jump the hex view.
+    fn show_anomalies(ui: &mut egui::Ui, state: &mut AppState, anomalies: &[(ByteAnomaly, Option<(u64, u64)>)]) {
+        egui::CollapsingHeader::new(format!("Anomalies ({})", anomalies.len()))
+            .id_salt("hist_anomalies")
+            .default_open(true)
+            .show(ui, |ui| {
+                if anomalies.first().is_some_and(|(a, _)| a.kind == AnomalyKind::Over)
+                    && anomalies.iter().filter(|(a, _)| a.kind == AnomalyKind::Over).count() == 1
+                {
+                    ui.weak("A single dominant value in obfuscated data is often a single-byte XOR key.");
+                }
+                egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                    egui::Grid::new("hist_anomaly_grid").striped(true).show(ui, |ui| {
+                        ui.strong("Byte");
+                        ui.strong("Count");
+                        ui.strong("vs. typical");
+                        ui.strong("First");
+                        ui.strong("Last");
+                        ui.end_row();
+
+                        for (anomaly, range) in anomalies {
+                            ui.label(RichText::new(format!("0x{:02X}", anomaly.byte)).monospace());
+                            ui.label(format_count(anomaly.count));
+                            let (text, color) = match anomaly.kind {
+                                AnomalyKind::Over => (format!("{:.0}x more", anomaly.ratio()), Color32::from_rgb(255, 160, 80)),
+                                AnomalyKind::Under if anomaly.count == 0 => ("absent".to_string(), Color32::from_rgb(120, 170, 255)),
+                                AnomalyKind::Under => (format!("{:.0}x less", anomaly.ratio()), Color32::from_rgb(120, 170, 255)),
+                            };
+                            ui.label(RichText::new(text).color(color))
+                                .on_hover_text(format!("Typical value count: {:.0}", anomaly.expected));
+                            match range {
+                                Some((first, last)) => {
+                                    for offset in [*first, *last] {
+                                        if ui.link(format!("0x{:X}", offset)).clicked() {
+                                            state.viewport.start = offset / 16 * 16;
+                                        }
+                                    }
+                                }
+                                None => {
+                                    ui.weak("-");
+                                    ui.weak("-");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+    }
+
     fn draw_histogram(ui: &mut egui::Ui, histogram: &ByteHistogram, hist_state: &mut HistogramState) {
         let available = ui.available_size();
         let (response, painter) = ui.allocate_painter(available, Sense::hover());
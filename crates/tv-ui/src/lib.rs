@@ -2,6 +2,7 @@ pub mod state;
 mod hex_panel;
 mod minimap_panel;
 pub mod export;
+pub mod clipboard;
 mod perf_window;
 mod file_info_window;
 mod search_window;
@@ -10,6 +11,7 @@ mod hilbert_window;
 mod disasm_window;
 mod diff_window;
 mod cfg_window;
+mod call_graph_window;
 mod struct_inspector;
 mod histogram_window;
 mod xrefs_window;
@@ -19,11 +21,23 @@ pub mod scripting;
 mod script_window;
 mod syntax_highlight;
 pub mod workspace;
+pub mod theme;
+pub mod file_dialog;
+mod theme_window;
+mod xor_window;
+mod text_view_window;
+mod sections_window;
+mod structure_window;
+mod bundle_window;
+mod shortcuts_window;
+mod notes_window;
+mod overview_window;
+pub mod command_palette;
 
 pub use state::AppState;
 pub use hex_panel::HexPanel;
 pub use minimap_panel::MinimapPanel;
-pub use perf_window::{PerfState, PerfWindow};
+pub use perf_window::{GpuLimitsInfo, GpuMemoryInfo, PerfAction, PerfState, PerfWindow};
 pub use file_info_window::FileInfoWindow;
 pub use search_window::SearchWindow;
 pub use signatures_window::SignaturesWindow;
@@ -31,10 +45,22 @@ pub use hilbert_window::{HilbertMode, HilbertState, HilbertWindow};
 pub use disasm_window::{DisasmState, DisasmWindow};
 pub use diff_window::DiffWindow;
 pub use cfg_window::{CfgState, CfgWindow};
+pub use call_graph_window::{CallGraphState, CallGraphWindow};
 pub use struct_inspector::{InspectorState, StructInspector};
 pub use histogram_window::{HistogramScope, HistogramState, HistogramWindow};
 pub use xrefs_window::{XRefsState, XRefsWindow};
-pub use bookmarks_window::{BookmarksState, BookmarksWindow};
+pub use bookmarks_window::{AnnotationChange, BookmarksState, BookmarksWindow};
 pub use scripting::ScriptState;
 pub use script_window::ScriptWindow;
+pub use theme::{Theme, ThemePreset};
+pub use theme_window::ThemeWindow;
+pub use xor_window::{XorState, XorWindow};
+pub use text_view_window::{TextViewState, TextViewWindow};
+pub use sections_window::SectionsWindow;
+pub use structure_window::StructureWindow;
+pub use bundle_window::{bundle_dir, BundleArtifact, BundleState, BundleWindow};
+pub use command_palette::{Command, CommandPalette, CommandPaletteState, KeyBindings, PaletteEntry};
+pub use shortcuts_window::{ShortcutsState, ShortcutsWindow};
+pub use notes_window::NotesWindow;
+pub use overview_window::{OverviewAction, OverviewFile, OverviewWindow};
 pub use workspace::{Workspace, WorkspaceManager, WORKSPACE_EXTENSION};
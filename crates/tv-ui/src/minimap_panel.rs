@@ -1,15 +1,34 @@
 use egui::{Color32, Rect, Sense, Ui, Vec2, Pos2};
 use tv_core::BlockClass;
-use crate::state::{AppState, MinimapCache};
+use crate::hex_panel::percent_to_offset;
+use crate::sections_window::section_color;
+use crate::state::{AppState, EntropyJump, MinimapCache, MinimapRow, CHECKSUM_BLOCK_SIZE, checksum_block_differs};
+use crate::theme::Theme;
 
 /// Right-side minimap showing per-block classification and entropy as a colored vertical bar.
 pub struct MinimapPanel;
 
 /// Width of the minimap bar in pixels.
 const MINIMAP_WIDTH: f32 = 40.0;
+/// Width of the checksum strip along the right edge of the minimap.
+const CHECKSUM_STRIP_WIDTH: f32 = 10.0;
+/// Checksum strip color of blocks that differ from diff file B.
+const CHECKSUM_DIFF_COLOR: Color32 = Color32::from_rgb(255, 70, 70);
+/// Checksum strip color of blocks identical in diff file B.
+const CHECKSUM_SAME_COLOR: Color32 = Color32::from_gray(45);
+/// Width of the section strip along the left edge of the minimap (executables).
+const SECTION_STRIP_WIDTH: f32 = 5.0;
+/// Line marking the start of a section.
+const SECTION_BOUNDARY_COLOR: Color32 = Color32::from_rgba_premultiplied(230, 230, 230, 160);
+/// Rows of the canonical minimap summary; display heights are resampled from it.
+const MINIMAP_BASE_ROWS: usize = 4096;
 
 impl MinimapPanel {
     pub fn show(ui: &mut Ui, state: &mut AppState, computing: bool) {
+        if state.entropy.as_ref().is_some_and(|e| !e.is_empty()) {
+            Self::show_entropy_jumps(ui, state, computing);
+        }
+
         let entropy = match &state.entropy {
             Some(e) if !e.is_empty() => e,
             _ => {
@@ -35,14 +54,12 @@ impl MinimapPanel {
         let available_height = ui.available_height().max(100.0);
         let pixel_rows = (available_height.ceil() as usize).max(1);
 
-        // Check if cache is valid, rebuild if needed
+        // Summarize the blocks when the data changed; a resize only resamples
+        if !state.minimap_cache.base_is_valid(num_blocks, has_classification) {
+            Self::rebuild_base(&mut state.minimap_cache, entropy, classification);
+        }
         if !state.minimap_cache.is_valid(pixel_rows, num_blocks, has_classification) {
-            Self::rebuild_cache(
-                &mut state.minimap_cache,
-                entropy,
-                classification,
-                pixel_rows,
-            );
+            Self::resample_cache(&mut state.minimap_cache, pixel_rows, &state.theme);
         }
 
         let (response, painter) = ui.allocate_painter(
@@ -68,6 +85,59 @@ impl MinimapPanel {
             );
         }
 
+        // Checksum strip along the right edge
+        let path_a = state.file.as_ref().map(|f| f.path.as_path());
+        let path_b = state.diff.file_b.as_ref().map(|f| f.path.as_path());
+        let rebuilt = match state.checksums.checksums_a(path_a).filter(|_| state.checksums.enabled) {
+            Some(sums_a) => {
+                let sums_b = state.checksums.checksums_b(path_b);
+                let key = (pixel_rows, sums_a.len(), sums_b.map_or(0, <[u32]>::len));
+                let stale = state.checksums.pixels.is_empty() || state.checksums.pixels_key != key;
+                stale.then(|| (key, checksum_strip_colors(sums_a, sums_b, pixel_rows)))
+            }
+            None => None,
+        };
+        if let Some((key, pixels)) = rebuilt {
+            state.checksums.pixels = pixels;
+            state.checksums.pixels_key = key;
+        }
+        let checksums_a = state.checksums.checksums_a(path_a).filter(|_| state.checksums.enabled);
+        let checksums_b = state.checksums.checksums_b(path_b);
+        if checksums_a.is_some() {
+            for (row, &color) in state.checksums.pixels.iter().enumerate() {
+                let y_start = rect.min.y + row as f32 * row_height;
+                let y_end = y_start + row_height + 0.5;
+                painter.rect_filled(
+                    Rect::from_min_max(
+                        Pos2::new(rect.max.x - CHECKSUM_STRIP_WIDTH, y_start),
+                        Pos2::new(rect.max.x, y_end.min(rect.max.y)),
+                    ),
+                    0.0,
+                    color,
+                );
+            }
+        }
+
+        // Section strip and boundaries along the left edge
+        if let Some(layout) = state.sections.as_ref().filter(|_| file_len > 0) {
+            let y_at = |offset: u64| rect.min.y + (offset.min(file_len) as f64 / file_len as f64) as f32 * available_height;
+            for section in layout.sections.iter().filter(|s| s.size > 0 && s.offset < file_len) {
+                let (y0, y1) = (y_at(section.offset), y_at(section.end()));
+                painter.rect_filled(
+                    Rect::from_min_max(
+                        Pos2::new(rect.min.x, y0),
+                        Pos2::new(rect.min.x + SECTION_STRIP_WIDTH, y1.max(y0 + 1.0)),
+                    ),
+                    0.0,
+                    section_color(section.flags),
+                );
+                painter.line_segment(
+                    [Pos2::new(rect.min.x, y0), Pos2::new(rect.max.x, y0)],
+                    egui::Stroke::new(1.0, SECTION_BOUNDARY_COLOR),
+                );
+            }
+        }
+
         // Viewport indicator
         if file_len > 0 {
             let vp_start_frac = state.viewport.start as f32 / file_len as f32;
@@ -92,9 +162,7 @@ impl MinimapPanel {
             if let Some(pos) = response.interact_pointer_pos() {
                 let click_frac = (pos.y - rect.min.y) / available_height;
                 let click_frac = click_frac.clamp(0.0, 1.0);
-                let target_offset = (click_frac as f64 * file_len as f64) as u64;
-                let aligned = (target_offset / 16) * 16;
-                state.viewport.start = aligned;
+                state.viewport.start = percent_to_offset(file_len, click_frac as f64 * 100.0);
             }
         }
 
@@ -106,63 +174,183 @@ impl MinimapPanel {
                 if block_idx < num_blocks {
                     let offset = (hover_frac as f64 * file_len as f64) as u64;
                     let class_label = classification
-                        .and_then(|c| c.get(block_idx))
+                        .and_then(|c| c.get((offset / state.class_block_size()) as usize))
                         .map(|&v| BlockClass::from_u8(v).label())
                         .unwrap_or("N/A");
-                    response.on_hover_text(format!(
-                        "Block {}: {} | entropy {:.2}\nOffset: 0x{:X}",
-                        block_idx, class_label, entropy[block_idx], offset
-                    ));
+                    let mut text = format!(
+                        "Block {}: {} | entropy {:.2}\nOffset: 0x{:X} ({:.1}%)",
+                        block_idx, class_label, entropy[block_idx], offset, hover_frac * 100.0
+                    );
+                    if let Some(section) = state.sections.as_ref().and_then(|l| l.section_at(offset)) {
+                        text.push_str(&format!("\nSection: {} ({})", section.name, section.flags.label()));
+                    }
+                    let sum_idx = (offset / CHECKSUM_BLOCK_SIZE as u64) as usize;
+                    if let Some(sums_a) = checksums_a.filter(|sums| sum_idx < sums.len()) {
+                        text.push_str(&format!("\nCRC-32: {:08X}", sums_a[sum_idx]));
+                        if let Some(sums_b) = checksums_b {
+                            let differs = checksum_block_differs(sums_a, sums_b, sum_idx);
+                            text.push_str(if differs { " (differs from B)" } else { " (same in B)" });
+                        }
+                    }
+                    response.on_hover_text(text);
                 }
             }
         }
     }
 
-    /// Rebuild the minimap pixel cache.
-    /// This is called once when entropy/classification data changes or window resizes.
-    /// For a 4GB file, this does 16M+ block iterations ONCE instead of every frame.
-    fn rebuild_cache(
-        cache: &mut MinimapCache,
-        entropy: &[f32],
-        classification: Option<&[u8]>,
-        pixel_rows: usize,
-    ) {
-        let num_blocks = entropy.len();
+    /// Buttons jumping to entropy boundaries and to the highest-entropy block
+    /// (Alt+Left returns). Disabled until the entropy is complete.
+    fn show_entropy_jumps(ui: &mut Ui, state: &mut AppState, computing: bool) {
+        ui.add_enabled_ui(!computing, |ui| {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 2.0;
+                for (label, hover, jump) in [
+                    ("▲", "Previous entropy boundary", EntropyJump::PrevBoundary),
+                    ("▼", "Next entropy boundary (e.g. start of encrypted data)", EntropyJump::NextBoundary),
+                    ("★", "Highest-entropy block", EntropyJump::Maximum),
+                ] {
+                    if ui.small_button(label).on_hover_text(hover).clicked() {
+                        state.jump_to_entropy(jump);
+                    }
+                }
+            });
+        });
+    }
 
-        cache.pixels.clear();
-        cache.pixels.reserve(pixel_rows);
-        cache.cached_height = pixel_rows;
+    /// Summarize the blocks at the canonical resolution.
+    /// This is called once when entropy/classification data changes.
+    /// For a 4GB file, this does 16M+ block iterations ONCE instead of on every resize.
+    /// The classification may use its own block size; rows cover the same
+    /// fraction of both.
+    fn rebuild_base(cache: &mut MinimapCache, entropy: &[f32], classification: Option<&[u8]>) {
+        let num_blocks = entropy.len();
+        let rows = num_blocks.clamp(1, MINIMAP_BASE_ROWS);
+
+        cache.base = (0..rows)
+            .map(|row| {
+                let block_start = row * num_blocks / rows;
+                let block_end = ((row + 1) * num_blocks / rows).max(block_start + 1).min(num_blocks);
+                let max_entropy = entropy.get(block_start..block_end).unwrap_or(&[])
+                    .iter()
+                    .copied()
+                    .fold(0.0f32, f32::max);
+                let class_counts = classification
+                    .and_then(|classes| {
+                        let start = block_start * classes.len() / num_blocks;
+                        let end = (block_end * classes.len() / num_blocks).max(start + 1).min(classes.len());
+                        classes.get(start..end)
+                    })
+                    .map_or([0; 5], class_counts);
+                MinimapRow { max_entropy, class_counts }
+            })
+            .collect();
         cache.cached_block_count = num_blocks;
         cache.cached_has_classification = classification.is_some();
+        cache.pixels.clear();
+    }
 
-        for row in 0..pixel_rows {
-            let frac_start = row as f32 / pixel_rows as f32;
-            let frac_end = (row + 1) as f32 / pixel_rows as f32;
-            let block_start = (frac_start * num_blocks as f32) as usize;
-            let block_end = ((frac_end * num_blocks as f32) as usize).min(num_blocks);
-
-            if block_start >= num_blocks {
-                cache.pixels.push(Color32::BLACK);
-                continue;
-            }
+    /// Resample the block summary to `pixel_rows` pixel colors (cheap, on resize).
+    fn resample_cache(cache: &mut MinimapCache, pixel_rows: usize, theme: &Theme) {
+        cache.pixels = Self::row_colors(cache, pixel_rows, theme);
+        cache.cached_height = pixel_rows;
+    }
 
-            let max_entropy = entropy[block_start..block_end]
-                .iter()
-                .copied()
-                .fold(0.0f32, f32::max);
+    fn row_colors(cache: &MinimapCache, pixel_rows: usize, theme: &Theme) -> Vec<Color32> {
+        let classified = cache.cached_has_classification;
+        resample_rows(&cache.base, pixel_rows)
+            .into_iter()
+            .map(|row| {
+                if classified {
+                    classify_entropy_color(theme.class_color(dominant_of_counts(&row.class_counts)), row.max_entropy)
+                } else {
+                    entropy_to_color(row.max_entropy)
+                }
+            })
+            .collect()
+    }
 
-            let color = if let Some(classes) = classification {
-                let dominant = dominant_block_class(&classes[block_start..block_end.min(classes.len())]);
-                classify_entropy_color(dominant, max_entropy)
-            } else {
-                entropy_to_color(max_entropy)
-            };
+    /// `rows` minimap colors of a file (e.g. a parked tab) from its own
+    /// cache, summarizing the blocks first if needed. `None` before the
+    /// entropy is computed.
+    pub fn strip_colors(
+        cache: &mut MinimapCache,
+        entropy: Option<&[f32]>,
+        classification: Option<&[u8]>,
+        rows: usize,
+        theme: &Theme,
+    ) -> Option<Vec<Color32>> {
+        let entropy = entropy.filter(|e| !e.is_empty())?;
+        if !cache.base_is_valid(entropy.len(), classification.is_some()) {
+            Self::rebuild_base(cache, entropy, classification);
+        }
+        Some(Self::row_colors(cache, rows, theme))
+    }
 
-            cache.pixels.push(color);
+    /// The minimap as a `width` x `height` PNG file, `None` before the
+    /// entropy is computed.
+    pub fn png(state: &mut AppState, width: u32, height: u32) -> Option<Vec<u8>> {
+        let entropy = state.entropy.as_ref().filter(|e| !e.is_empty())?;
+        let classification = state.classification.as_deref();
+        if !state.minimap_cache.base_is_valid(entropy.len(), classification.is_some()) {
+            Self::rebuild_base(&mut state.minimap_cache, entropy, classification);
         }
+        let rgba: Vec<u8> = Self::row_colors(&state.minimap_cache, height as usize, &state.theme)
+            .into_iter()
+            .flat_map(|color| std::iter::repeat_n(color.to_array(), width as usize))
+            .flatten()
+            .collect();
+        Some(tv_core::encode_png(width, height, &rgba))
     }
 }
 
+/// Merge (shrinking) or repeat (growing) summary rows to `pixel_rows` rows:
+/// the highest entropy and the summed class counts of the covered rows.
+pub fn resample_rows(base: &[MinimapRow], pixel_rows: usize) -> Vec<MinimapRow> {
+    let len = base.len();
+    (0..pixel_rows)
+        .map(|row| {
+            let start = row * len / pixel_rows;
+            let end = ((row + 1) * len / pixel_rows).max(start + 1).min(len);
+            let mut merged = MinimapRow { max_entropy: 0.0, class_counts: [0; 5] };
+            for r in base.get(start..end).unwrap_or(&[]) {
+                merged.max_entropy = merged.max_entropy.max(r.max_entropy);
+                for (total, count) in merged.class_counts.iter_mut().zip(r.class_counts) {
+                    *total += count;
+                }
+            }
+            merged
+        })
+        .collect()
+}
+
+/// Checksum strip colors, one per pixel row. Against file B, rows with a
+/// differing block are highlighted; alone, each row is tinted by its first
+/// block's checksum so repeated blocks share a color.
+pub fn checksum_strip_colors(a: &[u32], b: Option<&[u32]>, pixel_rows: usize) -> Vec<Color32> {
+    let num_blocks = a.len();
+    (0..pixel_rows)
+        .map(|row| {
+            let block_start = row * num_blocks / pixel_rows;
+            let block_end = ((row + 1) * num_blocks / pixel_rows).max(block_start + 1).min(num_blocks);
+            if block_start >= num_blocks {
+                return Color32::BLACK;
+            }
+            match b {
+                Some(b) if (block_start..block_end).any(|i| checksum_block_differs(a, b, i)) => CHECKSUM_DIFF_COLOR,
+                Some(_) => CHECKSUM_SAME_COLOR,
+                None => {
+                    let sum = a[block_start];
+                    Color32::from_rgb(
+                        64 + (sum & 0x7F) as u8,
+                        64 + (sum >> 8 & 0x7F) as u8,
+                        64 + (sum >> 16 & 0x7F) as u8,
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
 /// Map an entropy value (0.0 - 8.0) to a color.
 /// Low entropy (0.0) = dark blue, high entropy (8.0) = bright red.
 pub fn entropy_to_color(entropy: f32) -> Color32 {
@@ -194,50 +382,34 @@ pub fn entropy_to_color(entropy: f32) -> Color32 {
     )
 }
 
-/// Map a block class + entropy to a color.
-/// Hue is determined by class, luminosity modulated by entropy.
-pub fn classify_entropy_color(class: BlockClass, entropy: f32) -> Color32 {
+/// Map a block class color + entropy to a color.
+/// Hue comes from the class (see [`Theme::class_color`]), luminosity is modulated by entropy.
+pub fn classify_entropy_color(base: Color32, entropy: f32) -> Color32 {
     // Entropy factor: 0.0 = dim, 8.0 = bright
     let brightness = 0.3 + 0.7 * (entropy / 8.0).clamp(0.0, 1.0);
 
-    let (r, g, b) = match class {
-        BlockClass::Zeros => (0.3, 0.35, 0.5),       // grey-blue
-        BlockClass::Ascii => (0.2, 0.85, 0.3),       // green
-        BlockClass::Utf8 => (0.3, 0.5, 0.95),        // blue
-        BlockClass::Binary => (0.9, 0.65, 0.15),     // amber
-        BlockClass::HighEntropy => (0.95, 0.15, 0.1), // red
-    };
-
     Color32::from_rgb(
-        (r * brightness * 255.0) as u8,
-        (g * brightness * 255.0) as u8,
-        (b * brightness * 255.0) as u8,
+        (base.r() as f32 * brightness) as u8,
+        (base.g() as f32 * brightness) as u8,
+        (base.b() as f32 * brightness) as u8,
     )
 }
 
-/// Return the base color for a BlockClass (used by hex panel offset coloring).
-pub fn class_to_subtle_bg(class_id: u8) -> Color32 {
-    let class = BlockClass::from_u8(class_id);
-    match class {
-        BlockClass::Zeros => Color32::from_rgba_premultiplied(40, 50, 70, 60),
-        BlockClass::Ascii => Color32::from_rgba_premultiplied(30, 80, 40, 60),
-        BlockClass::Utf8 => Color32::from_rgba_premultiplied(40, 60, 100, 60),
-        BlockClass::Binary => Color32::from_rgba_premultiplied(90, 65, 20, 60),
-        BlockClass::HighEntropy => Color32::from_rgba_premultiplied(100, 25, 15, 60),
-    }
-}
-
-/// Find the dominant (most frequent) block class in a slice.
-/// Used for downsampling classification data to pixel rows.
-pub fn dominant_block_class(classes: &[u8]) -> BlockClass {
-    if classes.is_empty() {
-        return BlockClass::Binary;
-    }
+/// Blocks per class (by `BlockClass as u8`).
+fn class_counts(classes: &[u8]) -> [u32; 5] {
     let mut counts = [0u32; 5];
     for &c in classes {
         let idx = (c as usize).min(4);
         counts[idx] += 1;
     }
+    counts
+}
+
+/// Most common class in per-class block counts (Binary if there are none).
+fn dominant_of_counts(counts: &[u32; 5]) -> BlockClass {
+    if counts.iter().all(|&c| c == 0) {
+        return BlockClass::Binary;
+    }
     let max_idx = counts
         .iter()
         .enumerate()
@@ -280,7 +452,7 @@ mod tests {
 
     #[test]
     fn classify_entropy_color_zeros_dim() {
-        let c = classify_entropy_color(BlockClass::Zeros, 0.0);
+        let c = classify_entropy_color(Theme::dark().class_color(BlockClass::Zeros), 0.0);
         // Grey-blue, dim
         assert!(c.r() < 50);
         assert!(c.b() > c.r());
@@ -288,7 +460,7 @@ mod tests {
 
     #[test]
     fn classify_entropy_color_ascii_bright() {
-        let c = classify_entropy_color(BlockClass::Ascii, 4.0);
+        let c = classify_entropy_color(Theme::dark().class_color(BlockClass::Ascii), 4.0);
         // Green, moderate brightness
         assert!(c.g() > c.r());
         assert!(c.g() > c.b());
@@ -296,7 +468,7 @@ mod tests {
 
     #[test]
     fn classify_entropy_color_high_entropy() {
-        let c = classify_entropy_color(BlockClass::HighEntropy, 8.0);
+        let c = classify_entropy_color(Theme::dark().class_color(BlockClass::HighEntropy), 8.0);
         // Red, full brightness
         assert!(c.r() > 200);
         assert!(c.g() < 50);
@@ -306,17 +478,71 @@ mod tests {
     fn dominant_class_majority_vote() {
         // 3 ASCII, 2 Binary, 1 Zeros
         let classes = [1, 1, 1, 3, 3, 0];
-        assert_eq!(dominant_block_class(&classes), BlockClass::Ascii);
+        assert_eq!(dominant_of_counts(&class_counts(&classes)), BlockClass::Ascii);
     }
 
     #[test]
     fn dominant_class_empty() {
-        assert_eq!(dominant_block_class(&[]), BlockClass::Binary);
+        assert_eq!(dominant_of_counts(&class_counts(&[])), BlockClass::Binary);
     }
 
     #[test]
     fn dominant_class_single() {
-        assert_eq!(dominant_block_class(&[4]), BlockClass::HighEntropy);
+        assert_eq!(dominant_of_counts(&class_counts(&[4])), BlockClass::HighEntropy);
+    }
+
+    #[test]
+    fn resize_resamples_without_rebuilding_base() {
+        // 10000 blocks: entropy rises, first half ASCII, second half high entropy
+        let entropy: Vec<f32> = (0..10_000).map(|i| i as f32 * 8.0 / 10_000.0).collect();
+        let classes: Vec<u8> = (0..10_000).map(|i| if i < 5_000 { 1 } else { 4 }).collect();
+        let mut cache = MinimapCache::default();
+        MinimapPanel::rebuild_base(&mut cache, &entropy, Some(&classes));
+        assert_eq!(cache.base.len(), MINIMAP_BASE_ROWS);
+        assert!(cache.base_is_valid(10_000, true));
+        assert!(!cache.base_is_valid(10_000, false));
+
+        let theme = Theme::dark();
+        for height in [200, 333, 8000] {
+            MinimapPanel::resample_cache(&mut cache, height, &theme);
+            assert_eq!(cache.pixels.len(), height);
+            assert!(cache.is_valid(height, 10_000, true));
+        }
+        let rows = resample_rows(&cache.base, 2);
+        assert_eq!(dominant_of_counts(&rows[0].class_counts), BlockClass::Ascii);
+        assert_eq!(dominant_of_counts(&rows[1].class_counts), BlockClass::HighEntropy);
+        assert_eq!(rows.iter().map(|r| r.class_counts.iter().sum::<u32>()).sum::<u32>(), 10_000);
+        assert!((rows[1].max_entropy - entropy[9_999]).abs() < 1e-6);
+
+        cache.invalidate();
+        assert!(!cache.base_is_valid(10_000, true));
+    }
+
+    #[test]
+    fn finer_classification_maps_to_the_same_rows() {
+        // 4 entropy blocks, classification at 4x the resolution: last quarter high entropy
+        let entropy = [1.0, 1.0, 1.0, 7.5];
+        let classes: Vec<u8> = (0..16).map(|i| if i < 12 { 1 } else { 4 }).collect();
+        let mut cache = MinimapCache::default();
+        MinimapPanel::rebuild_base(&mut cache, &entropy, Some(&classes));
+        assert_eq!(cache.base.len(), 4);
+        assert_eq!(cache.base[0].class_counts, [0, 4, 0, 0, 0]);
+        assert_eq!(cache.base[3].class_counts, [0, 0, 0, 0, 4]);
+    }
+
+    #[test]
+    fn checksum_strip_marks_differing_rows() {
+        let a = [1, 2, 3, 4, 5, 6, 7, 8];
+        let b = [1, 2, 3, 4, 5, 0, 7];
+        let colors = checksum_strip_colors(&a, Some(&b), 4);
+        assert_eq!(colors, vec![CHECKSUM_SAME_COLOR, CHECKSUM_SAME_COLOR, CHECKSUM_DIFF_COLOR, CHECKSUM_DIFF_COLOR]);
+
+        // More rows than blocks: every row shows a block
+        let colors = checksum_strip_colors(&a[..2], None, 5);
+        assert_eq!(colors.len(), 5);
+        assert!(colors.iter().all(|&c| c != Color32::BLACK));
+        assert_eq!(colors[0], colors[1]);
+        assert_ne!(colors[0], colors[4]);
     }
 
     #[test]
@@ -0,0 +1,37 @@
+//! Notes window: a free-form scratchpad saved with the session.
+//!
+//! Unlike per-offset comments, the notes are one running log of the
+//! investigation.
+
+use egui::{Context, TextEdit};
+
+/// Notes window.
+pub struct NotesWindow;
+
+impl NotesWindow {
+    /// Show the scratchpad. Returns true if the notes were edited.
+    pub fn show(ctx: &Context, notes: &mut String, visible: &mut bool) -> bool {
+        if !*visible {
+            return false;
+        }
+
+        let mut changed = false;
+        egui::Window::new("Notes")
+            .open(visible)
+            .default_size([420.0, 360.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.weak(format!("{} lines · saved with the session", notes.lines().count()));
+                ui.separator();
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    changed = ui.add(
+                        TextEdit::multiline(notes)
+                            .hint_text("Findings, hypotheses, offsets to revisit...")
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(16),
+                    ).changed();
+                });
+            });
+        changed
+    }
+}
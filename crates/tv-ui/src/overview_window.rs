@@ -0,0 +1,127 @@
+//! Multi-file overview: the minimap of every open file side by side.
+//!
+//! Related samples (a malware family, firmware versions) share their layout;
+//! comparing the entropy/classification strips shows it at a glance without
+//! a byte diff. Clicking a strip opens that file at the clicked position.
+
+use egui::{Color32, Context, Pos2, Rect, RichText, Sense, Stroke, Vec2};
+use crate::hex_panel::percent_to_offset;
+use crate::minimap_panel::MinimapPanel;
+use crate::state::MinimapCache;
+use crate::theme::Theme;
+
+/// Width of one file's strip.
+const STRIP_WIDTH: f32 = 36.0;
+
+/// Strip height when the window leaves little room.
+const MIN_STRIP_HEIGHT: f32 = 120.0;
+
+/// One open file as shown in the overview.
+pub struct OverviewFile<'a> {
+    pub name: &'a str,
+    pub file_len: u64,
+    /// Whether this is the file shown in the hex view.
+    pub active: bool,
+    pub cache: &'a mut MinimapCache,
+    pub entropy: Option<&'a [f32]>,
+    pub classification: Option<&'a [u8]>,
+}
+
+/// What the user asked for in the overview.
+pub enum OverviewAction {
+    /// Make the file at this index active and jump to the offset.
+    Activate { index: usize, offset: u64 },
+    /// Open more files as tabs.
+    AddFiles,
+}
+
+/// Multi-file overview window.
+pub struct OverviewWindow;
+
+impl OverviewWindow {
+    /// Show the strips of `files` (in tab order).
+    pub fn show(ctx: &Context, files: &mut [OverviewFile], theme: &Theme, visible: &mut bool) -> Option<OverviewAction> {
+        if !*visible {
+            return None;
+        }
+
+        let mut action = None;
+        egui::Window::new("File Overview")
+            .open(visible)
+            .default_size([420.0, 480.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} file(s)", files.len()));
+                    if ui.button("Add files...").on_hover_text("Open more files as tabs").clicked() {
+                        action = Some(OverviewAction::AddFiles);
+                    }
+                });
+                ui.separator();
+
+                let strip_height = (ui.available_height() - 2.0 * ui.text_style_height(&egui::TextStyle::Body))
+                    .max(MIN_STRIP_HEIGHT);
+                egui::ScrollArea::horizontal().auto_shrink([false, false]).show(ui, |ui| {
+                    ui.horizontal_top(|ui| {
+                        for (index, file) in files.iter_mut().enumerate() {
+                            if let Some(offset) = Self::show_strip(ui, file, theme, strip_height) {
+                                action = Some(OverviewAction::Activate { index, offset });
+                            }
+                        }
+                    });
+                });
+            });
+        action
+    }
+
+    /// One file's name and strip. Returns the clicked offset.
+    fn show_strip(ui: &mut egui::Ui, file: &mut OverviewFile, theme: &Theme, height: f32) -> Option<u64> {
+        let rows = (height.ceil() as usize).max(1);
+        let colors = MinimapPanel::strip_colors(file.cache, file.entropy, file.classification, rows, theme);
+
+        let mut clicked = None;
+        ui.vertical(|ui| {
+            ui.set_width(STRIP_WIDTH + 8.0);
+            // Names are usually longer than a strip is wide: abbreviate, full name on hover
+            let short: String = file.name.chars().take(6).collect();
+            let text = if file.active { RichText::new(short).strong() } else { RichText::new(short) };
+            ui.label(text).on_hover_text(file.name);
+
+            let (response, painter) = ui.allocate_painter(Vec2::new(STRIP_WIDTH, height), Sense::click());
+            let rect = response.rect;
+            match &colors {
+                Some(colors) => {
+                    let row_height = height / rows as f32;
+                    for (row, &color) in colors.iter().enumerate() {
+                        let y = rect.min.y + row as f32 * row_height;
+                        painter.rect_filled(
+                            Rect::from_min_max(Pos2::new(rect.min.x, y), Pos2::new(rect.max.x, (y + row_height + 0.5).min(rect.max.y))),
+                            0.0,
+                            color,
+                        );
+                    }
+                }
+                None => {
+                    painter.rect_filled(rect, 0.0, Color32::from_gray(30));
+                }
+            }
+            if file.active {
+                painter.rect_stroke(rect, 0.0, Stroke::new(2.0, ui.visuals().selection.stroke.color));
+            }
+
+            let hover_offset = response.hover_pos().map(|pos| {
+                let pct = ((pos.y - rect.min.y) / rect.height()).clamp(0.0, 1.0) as f64 * 100.0;
+                percent_to_offset(file.file_len, pct)
+            });
+            if response.clicked() {
+                clicked = hover_offset;
+            }
+            let status = if colors.is_some() { "" } else { "\nNot analyzed yet (open the tab)" };
+            match hover_offset {
+                Some(offset) => response.on_hover_text(format!("{}\n0x{:X} of {} bytes{}", file.name, offset, file.file_len, status)),
+                None => response,
+            };
+        });
+        clicked
+    }
+}
@@ -5,10 +5,57 @@ use std::time::Instant;
 /// Number of samples to keep in history for graphs.
 const HISTORY_SIZE: usize = 120;
 
+/// GPU device limits, as reported by the compute backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuLimitsInfo {
+    /// Adapter name.
+    pub adapter: String,
+    /// Largest buffer the device can allocate (bytes).
+    pub max_buffer_size: u64,
+    /// Largest storage buffer a shader can bind (bytes).
+    pub max_storage_binding: u64,
+    /// Maximum workgroups per dispatch dimension.
+    pub max_workgroups_per_dimension: u32,
+}
+
+impl GpuLimitsInfo {
+    /// Largest single storage buffer a GPU job can use.
+    pub fn max_storage_bytes(&self) -> u64 {
+        self.max_buffer_size.min(self.max_storage_binding)
+    }
+}
+
+/// Approximate GPU buffer usage of one kind of operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuMemoryInfo {
+    pub operation: String,
+    /// Buffers of the latest dispatch (bytes).
+    pub last_bytes: u64,
+    /// Largest dispatch so far (bytes).
+    pub peak_bytes: u64,
+    /// Dispatches that went over the budget.
+    pub over_budget: u32,
+}
+
+/// Change requested from the performance window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfAction {
+    /// `gpu_memory_budget` was edited (apply and save it).
+    BudgetChanged,
+    /// Forget the recorded GPU memory usage.
+    ResetGpuMemory,
+}
+
 /// Performance metrics state.
 pub struct PerfState {
     /// Whether the performance window is visible.
     pub visible: bool,
+    /// GPU limits (`None` until queried, `Err` if no device could be created).
+    pub gpu_limits: Option<Result<GpuLimitsInfo, String>>,
+    /// GPU buffer usage per operation, refreshed by the app while visible.
+    pub gpu_memory: Vec<GpuMemoryInfo>,
+    /// Per-dispatch GPU memory budget (bytes); larger dispatches warn first.
+    pub gpu_memory_budget: Option<u64>,
     /// Frame time history (in ms).
     frame_times: VecDeque<f32>,
     /// Memory usage history (in MB).
@@ -32,6 +79,9 @@ impl Default for PerfState {
         let now = Instant::now();
         Self {
             visible: false,
+            gpu_limits: None,
+            gpu_memory: Vec::new(),
+            gpu_memory_budget: None,
             frame_times: VecDeque::with_capacity(HISTORY_SIZE),
             memory_usage: VecDeque::with_capacity(HISTORY_SIZE),
             last_frame: now,
@@ -173,25 +223,31 @@ fn get_memory_usage_mb() -> f32 {
     0.0
 }
 
+fn format_mb(bytes: u64) -> String {
+    format!("{:.0} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
 /// Floating performance window.
 pub struct PerfWindow;
 
 impl PerfWindow {
-    pub fn show(ctx: &egui::Context, state: &mut PerfState) {
+    pub fn show(ctx: &egui::Context, state: &mut PerfState) -> Option<PerfAction> {
         if !state.visible {
-            return;
+            return None;
         }
 
+        let mut action = None;
         egui::Window::new("Performance")
             .default_size([320.0, 400.0])
             .resizable(true)
             .collapsible(true)
             .show(ctx, |ui| {
-                Self::show_contents(ui, state);
+                action = Self::show_contents(ui, state);
             });
+        action
     }
 
-    fn show_contents(ui: &mut Ui, state: &mut PerfState) {
+    fn show_contents(ui: &mut Ui, state: &mut PerfState) -> Option<PerfAction> {
         // FPS section
         ui.heading("Frame Rate");
         ui.horizontal(|ui| {
@@ -278,6 +334,97 @@ impl PerfWindow {
             ui.strong("Total Frames:");
             ui.label(format!("{}", state.total_frames));
         });
+
+        ui.add_space(12.0);
+
+        // GPU section
+        ui.heading("GPU");
+        match &state.gpu_limits {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Querying device...");
+                });
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, format!("Unavailable: {}", e));
+            }
+            Some(Ok(limits)) => {
+                egui::Grid::new("perf_gpu_limits").num_columns(2).show(ui, |ui| {
+                    ui.strong("Adapter:");
+                    ui.label(&limits.adapter);
+                    ui.end_row();
+                    ui.strong("Max buffer:");
+                    ui.label(format_mb(limits.max_buffer_size));
+                    ui.end_row();
+                    ui.strong("Max storage binding:");
+                    ui.label(format_mb(limits.max_storage_binding));
+                    ui.end_row();
+                    ui.strong("Max workgroups:");
+                    ui.label(format!("{} per dimension", limits.max_workgroups_per_dimension));
+                    ui.end_row();
+                });
+                ui.weak(format!(
+                    "Data above {} per buffer is processed in chunks.",
+                    format_mb(limits.max_storage_bytes())
+                ));
+            }
+        }
+
+        ui.add_space(12.0);
+        Self::show_gpu_memory(ui, state)
+    }
+
+    /// Per-operation GPU buffer usage and the budget setting.
+    fn show_gpu_memory(ui: &mut Ui, state: &mut PerfState) -> Option<PerfAction> {
+        let mut action = None;
+        ui.heading("GPU Memory");
+        if state.gpu_memory.is_empty() {
+            ui.weak("No GPU job has run yet.");
+        } else {
+            egui::Grid::new("perf_gpu_memory").num_columns(3).striped(true).show(ui, |ui| {
+                ui.strong("Operation");
+                ui.strong("Last");
+                ui.strong("Peak");
+                ui.end_row();
+                for op in &state.gpu_memory {
+                    ui.label(&op.operation);
+                    ui.label(format_mb(op.last_bytes));
+                    if op.over_budget > 0 {
+                        ui.colored_label(Color32::YELLOW, format_mb(op.peak_bytes))
+                            .on_hover_text(format!("Over budget {} time(s)", op.over_budget));
+                    } else {
+                        ui.label(format_mb(op.peak_bytes));
+                    }
+                    ui.end_row();
+                }
+            });
+            if ui.small_button("Reset").clicked() {
+                action = Some(PerfAction::ResetGpuMemory);
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let mut enabled = state.gpu_memory_budget.is_some();
+            if ui.checkbox(&mut enabled, "Warn above").changed() {
+                // Default to the largest single buffer the device allows
+                let default = match &state.gpu_limits {
+                    Some(Ok(limits)) => limits.max_storage_bytes(),
+                    _ => 512 * 1024 * 1024,
+                };
+                state.gpu_memory_budget = enabled.then_some(default);
+                action = Some(PerfAction::BudgetChanged);
+            }
+            if let Some(budget) = state.gpu_memory_budget {
+                let mut mb = budget / (1024 * 1024);
+                if ui.add(egui::DragValue::new(&mut mb).range(16..=65536).suffix(" MB")).changed() {
+                    state.gpu_memory_budget = Some(mb * 1024 * 1024);
+                    action = Some(PerfAction::BudgetChanged);
+                }
+            }
+        });
+        ui.weak("Approximate buffer sizes per dispatch; a dispatch over the budget warns before it runs.");
+        action
     }
 
     /// Draw a line graph with the given data.
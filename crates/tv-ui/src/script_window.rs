@@ -273,7 +273,9 @@ impl ScriptWindow {
         // Apply any pending edits to the edit buffer
         let edits = script.take_edits();
         let edits_count = edits.len();
-        if !edits.is_empty() {
+        if !edits.is_empty() && state.edit.read_only {
+            script.output.push(format!("Error: {} byte edit(s) discarded, file is open read-only.", edits_count));
+        } else if !edits.is_empty() {
             if !state.edit.enabled {
                 // Auto-enable edit mode (with warning in output)
                 script.output.push("Warning: Edit mode auto-enabled for script writes.".to_string());
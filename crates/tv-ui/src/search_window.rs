@@ -1,6 +1,12 @@
 use egui::Context;
-use crate::state::{AppState, parse_hex_pattern};
+use egui::{Color32, FontId, Pos2, Rect, Sense, Vec2};
+use egui::text::{LayoutJob, TextFormat};
+use crate::state::{
+    AppState, GpuJob, SearchBackend, parse_hex_pattern, bucket_start, LIVE_SEARCH_DEBOUNCE_SECS,
+    MAX_SEARCH_PATTERN_LEN, SEARCH_ALIGNMENTS,
+};
 use crate::hex_panel::format_offset;
+use crate::minimap_panel::entropy_to_color;
 
 /// Floating window for pattern search.
 pub struct SearchWindow;
@@ -9,6 +15,12 @@ pub struct SearchWindow;
 const MAX_VISIBLE_RESULTS: usize = 10_000;
 /// Row height for virtual scroll.
 const RESULT_ROW_HEIGHT: f32 = 18.0;
+/// Height of the match density strip.
+const DENSITY_HEIGHT: f32 = 20.0;
+/// Bytes shown before and after each match in the results list.
+const PREVIEW_CONTEXT: usize = 8;
+/// Matched bytes shown in the preview; longer matches are cut.
+const PREVIEW_MATCH_BYTES: usize = 8;
 
 impl SearchWindow {
     pub fn show(ctx: &Context, state: &mut AppState, visible: &mut bool) {
@@ -18,7 +30,7 @@ impl SearchWindow {
 
         egui::Window::new("Search")
             .open(visible)
-            .default_size([320.0, 400.0])
+            .default_size([440.0, 400.0])
             .resizable(true)
             .collapsible(true)
             .show(ctx, |ui| {
@@ -33,8 +45,15 @@ impl SearchWindow {
         }
 
         // Hex input
-        ui.label("Hex pattern (max 16 bytes):");
+        ui.label(format!("Hex pattern (max {} bytes):", MAX_SEARCH_PATTERN_LEN));
         let response = ui.text_edit_singleline(&mut state.search.query_text);
+        let now = ui.input(|i| i.time);
+        if response.changed() {
+            state.search.query_edited(now);
+        }
+        if state.run_live_search(now) {
+            ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(LIVE_SEARCH_DEBOUNCE_SECS));
+        }
 
         // Show parse preview
         match parse_hex_pattern(&state.search.query_text) {
@@ -57,25 +76,67 @@ impl SearchWindow {
             && parse_hex_pattern(&state.search.query_text).is_ok();
 
         ui.horizontal(|ui| {
-            let search_clicked = ui.add_enabled(can_search, egui::Button::new("Search")).clicked();
+            let label = if state.search.live { "Search whole file" } else { "Search" };
+            let search_clicked = ui.add_enabled(can_search, egui::Button::new(label)).clicked();
             let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
 
             if (search_clicked || enter_pressed) && can_search {
                 if let Ok(bytes) = parse_hex_pattern(&state.search.query_text) {
-                    state.search.pattern = Some(bytes);
-                    state.search.searching = true;
-                    state.search.results = None;
-                    state.search.selected_result = None;
-                    state.search.search_duration_ms = None;
+                    state.search.start_search(bytes);
                 }
             }
 
             if state.search.results.is_some() {
                 if ui.button("Clear").clicked() {
-                    state.search.results = None;
-                    state.search.selected_result = None;
-                    state.search.search_duration_ms = None;
-                    state.search.rebuild_highlights();
+                    state.search.live_scope = None;
+                    state.search.clear_results();
+                }
+            }
+
+            if ui.checkbox(&mut state.search.live, "Live")
+                .on_hover_text("Search the visible bytes as you type")
+                .changed()
+            {
+                state.search.query_edited(now);
+            }
+        });
+
+        ui.collapsing("Advanced", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Backend:");
+                for backend in SearchBackend::ALL {
+                    ui.radio_value(&mut state.search.backend, backend, backend.label());
+                }
+            })
+            .response
+            .on_hover_text("CPU search is usually faster; GPU may help on machines with a weak CPU");
+
+            let before = state.search.alignment;
+            ui.horizontal(|ui| {
+                ui.label("Alignment:");
+                let selected = state.search.alignment.map_or_else(
+                    || "Any offset".to_string(),
+                    |a| SEARCH_ALIGNMENTS.iter().find(|(v, _)| *v == a)
+                        .map_or_else(|| format!("{} bytes", a), |(_, label)| label.to_string()),
+                );
+                egui::ComboBox::from_id_salt("search_alignment")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut state.search.alignment, None, "Any offset");
+                        for (value, label) in SEARCH_ALIGNMENTS {
+                            ui.selectable_value(&mut state.search.alignment, Some(value), label);
+                        }
+                    });
+            })
+            .response
+            .on_hover_text("Only keep matches starting at a multiple of this offset");
+
+            // Re-run the search so the filter applies to the full scan
+            if state.search.alignment != before && state.search.results.is_some() && !state.search.searching {
+                if state.search.live_scope.is_some() {
+                    state.search.query_edited(now);
+                } else if let Some(pattern) = state.search.pattern.clone() {
+                    state.search.start_search(pattern);
                 }
             }
         });
@@ -83,7 +144,11 @@ impl SearchWindow {
         if state.search.searching {
             ui.horizontal(|ui| {
                 ui.spinner();
-                ui.label("Searching...");
+                let text = match state.search.backend {
+                    SearchBackend::Gpu => state.gpu_job_text(GpuJob::Search, "Searching..."),
+                    SearchBackend::Cpu => "Searching...".to_string(),
+                };
+                ui.label(text);
             });
         }
 
@@ -109,11 +174,33 @@ impl SearchWindow {
 
             // Stats
             ui.horizontal(|ui| {
-                ui.strong(format!("{} match(es)", count));
+                match state.search.alignment.filter(|&a| a > 1) {
+                    Some(a) => ui.strong(format!("{} match(es) aligned to {}", count, a)),
+                    None => ui.strong(format!("{} match(es)", count)),
+                };
                 if let Some(ms) = state.search.search_duration_ms {
                     ui.weak(format!("in {:.1} ms", ms));
                 }
+                if let Some(backend) = state.search.used_backend {
+                    ui.weak(format!("({})", backend.label()));
+                }
             });
+            if let Some((start, end)) = state.search.live_scope {
+                ui.weak(format!(
+                    "Visible bytes only ({} - {}); search the whole file for all matches.",
+                    format_offset(start),
+                    format_offset(end),
+                ));
+            }
+            if let Some(total) = state.search.total_matches {
+                ui.colored_label(
+                    Color32::from_rgb(230, 180, 80),
+                    match state.search.alignment.filter(|&a| a > 1) {
+                        Some(_) => format!("Filtered from the first matches of {} (GPU result buffer full)", total),
+                        None => format!("{} shown of {} (GPU result buffer full)", count, total),
+                    },
+                );
+            }
 
             if count == 0 {
                 ui.label("No matches found.");
@@ -124,6 +211,10 @@ impl SearchWindow {
             let mem_bytes = count * std::mem::size_of::<u64>();
             ui.weak(format!("Results memory: {}", format_memory(mem_bytes)));
 
+            // Where matches cluster across the file
+            ui.add_space(4.0);
+            Self::show_density(ui, state);
+
             // Navigation buttons
             ui.add_space(4.0);
             ui.horizontal(|ui| {
@@ -166,21 +257,39 @@ impl SearchWindow {
                 .map(|r| r.iter().take(display_count).copied().enumerate().collect())
                 .unwrap_or_default();
 
-            egui::ScrollArea::vertical()
+            let match_len = state.search.pattern.as_ref().map_or(1, |p| p.len());
+            let mut clicked = None;
+            egui::ScrollArea::both()
                 .max_height(250.0)
                 .show_rows(ui, RESULT_ROW_HEIGHT, display_count, |ui, row_range| {
                     for i in row_range {
                         if let Some(&(idx, offset)) = visible_offsets.get(i) {
                             let selected = state.search.selected_result == Some(idx);
                             let text = format!("#{}: {}", idx + 1, format_offset(offset));
-                            if ui.selectable_label(selected, text).clicked() {
-                                state.search.selected_result = Some(idx);
-                                state.viewport.start = (offset / 16) * 16;
-                                state.search.rebuild_highlights();
-                            }
+                            ui.horizontal(|ui| {
+                                if ui.selectable_label(selected, text).clicked() {
+                                    clicked = Some((idx, offset));
+                                }
+                                // Only rows on screen read their context from the file
+                                if let Some(file) = &state.file {
+                                    let before = PREVIEW_CONTEXT.min(offset as usize);
+                                    let shown = match_len.min(PREVIEW_MATCH_BYTES);
+                                    let data = file.mapped.slice_at(
+                                        offset - before as u64,
+                                        (before + shown + PREVIEW_CONTEXT) as u64,
+                                    );
+                                    let (pre, hit, post) = split_preview(data, before, shown);
+                                    ui.label(preview_job(pre, hit, post, match_len > shown, state));
+                                }
+                            });
                         }
                     }
                 });
+            if let Some((idx, offset)) = clicked {
+                state.search.selected_result = Some(idx);
+                state.viewport.start = (offset / 16) * 16;
+                state.search.rebuild_highlights();
+            }
 
             if count > MAX_VISIBLE_RESULTS {
                 ui.weak(format!(
@@ -190,6 +299,101 @@ impl SearchWindow {
             }
         }
     }
+
+    /// Heatmap of match density over file offset. Click a bucket to jump to its first match.
+    fn show_density(ui: &mut egui::Ui, state: &mut AppState) {
+        let file_len = state.file_len();
+        let width = ui.available_width().max(50.0);
+        let buckets = (width as usize).clamp(16, 512);
+        state.search.rebuild_density(file_len, buckets);
+
+        let (response, painter) = ui.allocate_painter(Vec2::new(width, DENSITY_HEIGHT), Sense::click());
+        let rect = response.rect;
+        let density = &state.search.density;
+        let max = density.iter().copied().max().unwrap_or(0).max(1);
+        let bucket_w = width / buckets as f32;
+
+        painter.rect_filled(rect, 0.0, Color32::from_gray(20));
+        for (i, &c) in density.iter().enumerate() {
+            if c == 0 {
+                continue;
+            }
+            // Log scale so a few dense regions don't wash out sparse ones
+            let t = (1.0 + c as f32).ln() / (1.0 + max as f32).ln();
+            let x = rect.min.x + i as f32 * bucket_w;
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(x, rect.min.y), Pos2::new(x + bucket_w + 0.5, rect.max.y)),
+                0.0,
+                entropy_to_color(t * 8.0),
+            );
+        }
+
+        let hovered_bucket = response.hover_pos().map(|pos| {
+            (((pos.x - rect.min.x) / bucket_w) as usize).min(buckets - 1)
+        });
+
+        if response.clicked() {
+            if let Some(bucket) = hovered_bucket {
+                let start = bucket_start(bucket, file_len, buckets);
+                if let Some(results) = &state.search.results {
+                    let idx = results.partition_point(|&o| o < start);
+                    if let Some(&offset) = results.get(idx) {
+                        state.search.selected_result = Some(idx);
+                        state.viewport.start = (offset / 16) * 16;
+                        state.search.rebuild_highlights();
+                    }
+                }
+            }
+        }
+
+        if let Some(bucket) = hovered_bucket {
+            let count = state.search.density.get(bucket).copied().unwrap_or(0);
+            let start = bucket_start(bucket, file_len, buckets);
+            let end = bucket_start(bucket + 1, file_len, buckets);
+            response.on_hover_text(format!(
+                "{} - {}\n{} match(es)",
+                format_offset(start),
+                format_offset(end),
+                count,
+            ));
+        }
+    }
+}
+
+/// Split the bytes read around a match into (before, match, after), where
+/// `data` starts `before` bytes ahead of the match. Clamped at end of file.
+fn split_preview(data: &[u8], before: usize, match_len: usize) -> (&[u8], &[u8], &[u8]) {
+    let match_start = before.min(data.len());
+    let match_end = (before + match_len).min(data.len());
+    (&data[..match_start], &data[match_start..match_end], &data[match_end..])
+}
+
+/// Hex of the context with the match highlighted, then the same bytes as ASCII.
+fn preview_job(before: &[u8], hit: &[u8], after: &[u8], cut: bool, state: &AppState) -> LayoutJob {
+    let font = FontId::monospace(11.0);
+    let (hit_fg, hit_bg) = state.theme.search.pair();
+    let plain = TextFormat { font_id: font.clone(), color: state.theme.hex_text, ..Default::default() };
+    let weak = TextFormat { font_id: font.clone(), color: Color32::GRAY, ..Default::default() };
+    let highlight = TextFormat { font_id: font, color: hit_fg, background: hit_bg, ..Default::default() };
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{:02X}", b)).collect::<String>();
+    let ascii = |bytes: &[u8]| bytes.iter()
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+        .collect::<String>();
+
+    let mut job = LayoutJob::default();
+    job.append(&hex(before), 0.0, plain.clone());
+    job.append(&hex(hit), 4.0, highlight.clone());
+    if cut {
+        job.append("..", 0.0, weak.clone());
+    }
+    job.append(&hex(after), 4.0, plain.clone());
+    job.append(&ascii(before), 12.0, weak.clone());
+    job.append(&ascii(hit), 0.0, highlight);
+    if cut {
+        job.append("..", 0.0, weak.clone());
+    }
+    job.append(&ascii(after), 0.0, weak);
+    job
 }
 
 fn format_memory(bytes: usize) -> String {
@@ -201,3 +405,19 @@ fn format_memory(bytes: usize) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_preview_clamps_at_file_edges() {
+        let data = b"01234567ABCD89abcdef";
+        assert_eq!(split_preview(data, 8, 4), (&b"01234567"[..], &b"ABCD"[..], &b"89abcdef"[..]));
+        // Match near the start of the file: less context before
+        assert_eq!(split_preview(b"ABxyz", 0, 2), (&b""[..], &b"AB"[..], &b"xyz"[..]));
+        // Match running into the end of the file
+        assert_eq!(split_preview(b"0123AB", 4, 4), (&b"0123"[..], &b"AB"[..], &b""[..]));
+        assert_eq!(split_preview(b"", 0, 1), (&b""[..], &b""[..], &b""[..]));
+    }
+}
@@ -0,0 +1,142 @@
+//! Section layout window for ELF, PE and Mach-O executables.
+//!
+//! Shows where each section lives in the file as a proportional map and a
+//! table; clicking a section jumps to it (Alt+Left returns).
+
+use egui::{Color32, Context, Pos2, Rect, RichText, ScrollArea, Sense, Stroke, Ui, Vec2};
+use tv_core::{Section, SectionFlags};
+use crate::state::AppState;
+
+/// Height of the layout map.
+const MAP_HEIGHT: f32 = 22.0;
+
+/// Color of a section by its permissions (code, writable data, read-only data).
+pub(crate) fn section_color(flags: SectionFlags) -> Color32 {
+    if flags.execute {
+        Color32::from_rgb(220, 90, 80)
+    } else if flags.write {
+        Color32::from_rgb(80, 140, 220)
+    } else if flags.read {
+        Color32::from_rgb(100, 190, 110)
+    } else {
+        Color32::from_gray(120)
+    }
+}
+
+/// Section layout window.
+pub struct SectionsWindow;
+
+impl SectionsWindow {
+    pub fn show(ctx: &Context, state: &mut AppState, visible: &mut bool) {
+        if !*visible {
+            return;
+        }
+
+        egui::Window::new("Sections")
+            .open(visible)
+            .default_size([560.0, 380.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                Self::show_contents(ui, state);
+            });
+    }
+
+    fn show_contents(ui: &mut Ui, state: &mut AppState) {
+        if !state.has_file() {
+            ui.label("Open a file to see its sections.");
+            return;
+        }
+        let Some(layout) = &state.sections else {
+            ui.label("Not an ELF, PE or Mach-O executable.");
+            return;
+        };
+
+        let file_len = state.file_len();
+        let current = layout.section_at(state.viewport.start).map(|s| s.offset);
+        let mut jump = None;
+
+        ui.label(format!("{} executable, {} section(s)", layout.format.label(), layout.sections.len()));
+        if let Some(offset) = Self::show_map(ui, &layout.sections, file_len) {
+            jump = Some(offset);
+        }
+        ui.horizontal(|ui| {
+            for (label, flags) in [
+                ("code", SectionFlags { read: true, write: false, execute: true }),
+                ("data", SectionFlags { read: true, write: true, execute: false }),
+                ("read-only", SectionFlags { read: true, write: false, execute: false }),
+            ] {
+                ui.label(RichText::new("■").color(section_color(flags)));
+                ui.weak(label);
+            }
+        });
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("sections_grid").striped(true).num_columns(5).show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Offset");
+                ui.strong("Size");
+                ui.strong("Address");
+                ui.strong("Flags");
+                ui.end_row();
+
+                for section in &layout.sections {
+                    let in_file = section.size > 0 && section.offset < file_len;
+                    let name = RichText::new(&section.name).monospace().color(section_color(section.flags));
+                    let selected = in_file && current == Some(section.offset);
+                    let response = ui.add_enabled(in_file, egui::SelectableLabel::new(selected, name));
+                    if response.on_hover_text("Go to section").clicked() {
+                        jump = Some(section.offset);
+                    }
+                    ui.monospace(format!("0x{:X}", section.offset));
+                    ui.monospace(format!("0x{:X}", section.size));
+                    ui.monospace(format!("0x{:X}", section.address));
+                    ui.monospace(section.flags.label());
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(offset) = jump {
+            state.jump_to_offset(offset);
+        }
+    }
+
+    /// Proportional map of the sections along the file; returns the offset
+    /// of a clicked section.
+    fn show_map(ui: &mut Ui, sections: &[Section], file_len: u64) -> Option<u64> {
+        let width = ui.available_width().max(100.0);
+        let (response, painter) = ui.allocate_painter(Vec2::new(width, MAP_HEIGHT), Sense::click());
+        let rect = response.rect;
+        painter.rect_filled(rect, 2.0, Color32::from_gray(35));
+        if file_len == 0 {
+            return None;
+        }
+
+        let x_at = |offset: u64| rect.min.x + (offset.min(file_len) as f64 / file_len as f64) as f32 * rect.width();
+        for section in sections.iter().filter(|s| s.size > 0) {
+            let (x0, x1) = (x_at(section.offset), x_at(section.end()));
+            let area = Rect::from_min_max(Pos2::new(x0, rect.min.y), Pos2::new(x1.max(x0 + 1.0), rect.max.y));
+            painter.rect_filled(area, 0.0, section_color(section.flags));
+            painter.line_segment([area.left_top(), area.left_bottom()], Stroke::new(1.0, Color32::BLACK));
+        }
+
+        let pos = response.hover_pos()?;
+        let offset = ((pos.x - rect.min.x) / rect.width()).clamp(0.0, 1.0) as f64 * file_len as f64;
+        let hovered = sections.iter().find(|s| s.size > 0 && (s.offset..s.end()).contains(&(offset as u64)));
+        let clicked = response.clicked();
+        match hovered {
+            Some(section) => {
+                response.on_hover_text(format!(
+                    "{} (0x{:X} - 0x{:X}, {})",
+                    section.name, section.offset, section.end(), section.flags.label()
+                ));
+                clicked.then_some(section.offset)
+            }
+            None => {
+                response.on_hover_text(format!("0x{:X}: not in a section", offset as u64));
+                None
+            }
+        }
+    }
+}
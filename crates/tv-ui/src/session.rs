@@ -10,6 +10,8 @@
 use serde::{Serialize, Deserialize};
 use std::path::PathBuf;
 
+use crate::theme::Theme;
+
 /// Version of the session file format.
 pub const SESSION_VERSION: u32 = 1;
 
@@ -46,7 +48,11 @@ pub struct Session {
     pub hilbert: HilbertSessionState,
     /// Histogram state.
     pub histogram: HistogramSessionState,
-    /// Notes/comments about the session.
+    /// Color theme.
+    #[serde(default)]
+    pub theme: Option<Theme>,
+    /// Notes/comments about the session (the notes scratchpad).
+    #[serde(default)]
     pub notes: String,
 }
 
@@ -67,6 +73,7 @@ impl Default for Session {
             disasm: DisasmSessionState::default(),
             hilbert: HilbertSessionState::default(),
             histogram: HistogramSessionState::default(),
+            theme: None,
             notes: String::new(),
         }
     }
@@ -77,6 +84,12 @@ impl Default for Session {
 pub struct ViewportState {
     /// Current offset in the file.
     pub offset: u64,
+    /// Classification tint mode in the hex view.
+    #[serde(default)]
+    pub class_tint: String,
+    /// Hex view font size (0 = default).
+    #[serde(default)]
+    pub font_size: f32,
 }
 
 /// Window visibility states.
@@ -181,17 +194,57 @@ pub struct SearchSessionState {
     pub results: Vec<u64>,
     /// Selected result index.
     pub selected_index: Option<usize>,
+    /// Result alignment constraint.
+    #[serde(default)]
+    pub alignment: Option<u64>,
 }
 
 /// Disassembly session state.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisasmSessionState {
     /// Starting address for disassembly.
     pub address: u64,
     /// Selected architecture.
     pub architecture: String,
+    /// Whether the architecture is auto-detected (`architecture` is then ignored).
+    #[serde(default = "default_true")]
+    pub auto_detect: bool,
     /// Number of instructions to show.
     pub instruction_count: usize,
+    /// Whether the raw bytes column is shown.
+    #[serde(default = "default_true")]
+    pub show_bytes: bool,
+    /// Recursive descent instead of linear sweep.
+    #[serde(default)]
+    pub recursive: bool,
+    /// Per-instruction comments.
+    #[serde(default)]
+    pub comments: Vec<CommentEntry>,
+}
+
+impl Default for DisasmSessionState {
+    fn default() -> Self {
+        Self {
+            address: 0,
+            architecture: String::new(),
+            auto_detect: true,
+            instruction_count: 100,
+            show_bytes: true,
+            recursive: false,
+            comments: Vec::new(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Instruction comment entry for serialization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentEntry {
+    pub address: u64,
+    pub text: String,
 }
 
 /// Hilbert visualization state.
@@ -248,10 +301,15 @@ impl Session {
         path
     }
 
+    /// Session file contents (pretty-printed JSON).
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize session: {}", e))
+    }
+
     /// Save session to a file.
     pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        let json = self.to_json()?;
         std::fs::write(path, json)
             .map_err(|e| format!("Failed to write session file: {}", e))
     }
@@ -281,7 +339,7 @@ impl Session {
 }
 
 /// Format a Unix timestamp as a readable string.
-fn format_timestamp(secs: u64) -> String {
+pub(crate) fn format_timestamp(secs: u64) -> String {
     // Simple formatting without external crate
     let days_since_1970 = secs / 86400;
     let time_of_day = secs % 86400;
@@ -351,6 +409,7 @@ mod tests {
             name: "Start".to_string(),
             color: None,
         });
+        session.notes = "Packed with UPX?\nCheck 0x4000".to_string();
         session.update_timestamp();
 
         // Serialize
@@ -363,6 +422,39 @@ mod tests {
         assert_eq!(loaded.viewport.offset, 0x1000);
         assert!(loaded.windows.search.visible);
         assert_eq!(loaded.bookmarks.len(), 1);
+        assert_eq!(loaded.notes, "Packed with UPX?\nCheck 0x4000");
+    }
+
+    #[test]
+    fn test_disasm_state_roundtrip() {
+        let mut session = Session::with_name("Disasm");
+        session.disasm = DisasmSessionState {
+            address: 0x4010,
+            architecture: "Arm64".to_string(),
+            auto_detect: false,
+            instruction_count: 500,
+            show_bytes: false,
+            recursive: true,
+            comments: vec![CommentEntry { address: 0x4010, text: "entry".to_string() }],
+        };
+
+        let json = serde_json::to_string(&session).unwrap();
+        let loaded: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.disasm.address, 0x4010);
+        assert_eq!(loaded.disasm.architecture, "Arm64");
+        assert!(!loaded.disasm.auto_detect);
+        assert_eq!(loaded.disasm.instruction_count, 500);
+        assert!(!loaded.disasm.show_bytes);
+        assert!(loaded.disasm.recursive);
+        assert_eq!(loaded.disasm.comments[0].text, "entry");
+
+        // Sessions saved before these fields existed keep auto-detect and bytes on
+        let old: DisasmSessionState = serde_json::from_str(
+            r#"{"address": 0, "architecture": "X86_64", "instruction_count": 100}"#,
+        ).unwrap();
+        assert!(old.auto_detect);
+        assert!(old.show_bytes);
+        assert!(!old.recursive);
     }
 
     #[test]
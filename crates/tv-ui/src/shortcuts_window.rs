@@ -0,0 +1,160 @@
+//! Keyboard shortcuts window (F12).
+//!
+//! Lists every binding grouped by category, generated from the command
+//! registry and the fixed keys. Registered shortcuts can be rebound; the
+//! next key press with its modifiers becomes the new shortcut.
+
+use egui::{Context, Event, Key, KeyboardShortcut, RichText, ScrollArea, Ui};
+use crate::command_palette::{normalize_shortcut, split_name, Command, KeyBindings, COMMANDS, FIXED_SHORTCUTS, MOUSE_SHORTCUTS};
+
+/// State for the shortcuts window.
+#[derive(Default)]
+pub struct ShortcutsState {
+    /// Command waiting for its new shortcut.
+    pub capturing: Option<Command>,
+    /// Last rebinding problem (e.g. the key is taken).
+    pub message: Option<String>,
+}
+
+/// Categories in display order (registry and fixed keys).
+fn categories() -> Vec<&'static str> {
+    let mut categories: Vec<&str> = Vec::new();
+    let names = COMMANDS.iter().map(|info| split_name(info.name).0)
+        .chain(FIXED_SHORTCUTS.iter().map(|fixed| fixed.category))
+        .chain(MOUSE_SHORTCUTS.iter().map(|&(category, _, _)| category));
+    for category in names {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    categories
+}
+
+/// Help text of a fixed key group ("Ctrl+1 - Ctrl+5" for long runs).
+fn fixed_keys_text(ctx: &Context, keys: &[KeyboardShortcut]) -> String {
+    match keys {
+        [first, .., last] if keys.len() > 4 => format!("{} - {}", ctx.format_shortcut(first), ctx.format_shortcut(last)),
+        _ => keys.iter().map(|sc| ctx.format_shortcut(sc)).collect::<Vec<_>>().join(" / "),
+    }
+}
+
+/// Keyboard shortcuts window.
+pub struct ShortcutsWindow;
+
+impl ShortcutsWindow {
+    /// Show the shortcut list. Returns true if a binding changed (to save it).
+    pub fn show(ctx: &Context, bindings: &mut KeyBindings, shortcuts: &mut ShortcutsState, visible: &mut bool) -> bool {
+        if !*visible {
+            shortcuts.capturing = None;
+            return false;
+        }
+
+        let mut changed = false;
+        if let Some(command) = shortcuts.capturing {
+            changed = Self::capture(ctx, bindings, shortcuts, command);
+        }
+
+        egui::Window::new("Keyboard Shortcuts")
+            .open(visible)
+            .default_size([460.0, 520.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                changed |= Self::show_contents(ui, bindings, shortcuts);
+            });
+        changed
+    }
+
+    /// Take the first key press as the new shortcut of `command` (Escape cancels).
+    fn capture(ctx: &Context, bindings: &mut KeyBindings, shortcuts: &mut ShortcutsState, command: Command) -> bool {
+        let pressed = ctx.input(|i| i.events.iter().find_map(|event| match event {
+            Event::Key { key, pressed: true, repeat: false, modifiers, .. } => Some(KeyboardShortcut::new(*modifiers, *key)),
+            _ => None,
+        }));
+        let Some(shortcut) = pressed else { return false };
+        shortcuts.capturing = None;
+        if shortcut.logical_key == Key::Escape {
+            return false;
+        }
+        let shortcut = normalize_shortcut(shortcut);
+
+        if let Some(other) = bindings.conflict(command, shortcut) {
+            shortcuts.message = Some(format!("{} is used by {}", ctx.format_shortcut(&shortcut), other));
+            return false;
+        }
+        shortcuts.message = None;
+        bindings.set(command, Some(shortcut));
+        true
+    }
+
+    fn show_contents(ui: &mut Ui, bindings: &mut KeyBindings, shortcuts: &mut ShortcutsState) -> bool {
+        let mut changed = false;
+        ui.weak("Click a shortcut to change it, then press the new keys (Escape cancels).");
+        if let Some(message) = &shortcuts.message {
+            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), message);
+        }
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            for category in categories() {
+                ui.label(RichText::new(category).strong());
+                egui::Grid::new(("shortcuts", category)).num_columns(3).striped(true).show(ui, |ui| {
+                    for info in COMMANDS.iter().filter(|info| split_name(info.name).0 == category) {
+                        ui.label(split_name(info.name).1);
+                        let text = if shortcuts.capturing == Some(info.command) {
+                            "Press keys...".to_string()
+                        } else {
+                            bindings.shortcut(info).map_or_else(|| "-".to_string(), |sc| ui.ctx().format_shortcut(&sc))
+                        };
+                        if ui.button(RichText::new(text).monospace()).on_hover_text("Change shortcut").clicked() {
+                            shortcuts.capturing = Some(info.command);
+                            shortcuts.message = None;
+                        }
+                        ui.horizontal(|ui| {
+                            if bindings.shortcut(info).is_some() && ui.small_button("Clear").clicked() {
+                                bindings.set(info.command, None);
+                                changed = true;
+                            }
+                            if bindings.is_custom(info.command) && ui.small_button("Reset").clicked() {
+                                bindings.set(info.command, info.shortcut);
+                                changed = true;
+                            }
+                        });
+                        ui.end_row();
+                    }
+                    for fixed in FIXED_SHORTCUTS.iter().filter(|fixed| fixed.category == category) {
+                        ui.label(fixed.action);
+                        ui.code(fixed_keys_text(ui.ctx(), fixed.keys));
+                        ui.end_row();
+                    }
+                    for &(_, gesture, action) in MOUSE_SHORTCUTS.iter().filter(|&&(c, _, _)| c == category) {
+                        ui.label(action);
+                        ui.code(gesture);
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(6.0);
+            }
+        });
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_binding_has_a_category() {
+        let categories = categories();
+        assert_eq!(categories.first(), Some(&"File"));
+        for info in COMMANDS {
+            assert!(categories.contains(&split_name(info.name).0));
+        }
+        for fixed in FIXED_SHORTCUTS {
+            assert!(categories.contains(&fixed.category));
+        }
+        for &(category, _, _) in MOUSE_SHORTCUTS {
+            assert!(categories.contains(&category));
+        }
+    }
+}
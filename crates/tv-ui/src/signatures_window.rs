@@ -1,6 +1,7 @@
 use egui::{Context, Color32, RichText};
-use crate::state::{AppState, SignaturesTab, SignatureHit, SignatureSortOrder, SignatureCategory};
-use tv_core::{analyze_carve_size, FileRegion};
+use crate::file_dialog::{self, RememberDirectory};
+use crate::state::{AppState, GpuJob, SignaturesTab, SignatureHit, SignatureSortOrder, SignatureCategory, DEEP_SCAN_CHUNK_OPTIONS_MB, GAP_MIN_BYTES, SIGNATURE_FILTER_DEBOUNCE_SECS};
+use tv_core::{analyze_carve_size, CarveInfo, FileRegion, GapKind};
 use std::path::PathBuf;
 
 /// Floating window for signature detection (quick scan + deep scan).
@@ -9,6 +10,12 @@ pub struct SignaturesWindow;
 /// Row height for virtual scroll.
 const ROW_HEIGHT: f32 = 18.0;
 
+/// Indentation per nesting level in the deep scan list.
+const NEST_INDENT: f32 = 14.0;
+
+/// Magic bytes shown inline before truncating (the full value is on hover).
+const MAGIC_PREVIEW_BYTES: usize = 6;
+
 impl SignaturesWindow {
     pub fn show(ctx: &Context, state: &mut AppState, visible: &mut bool) {
         if !*visible {
@@ -69,11 +76,11 @@ impl SignaturesWindow {
         let suggested_name = format!("carved_0x{:X}.{}", sig.offset, extension);
 
         // Show save dialog
-        let save_path = rfd::FileDialog::new()
+        let save_path = file_dialog::new()
             .set_file_name(&suggested_name)
             .add_filter("Carved file", &[extension])
             .add_filter("All files", &["*"])
-            .save_file()?;
+            .save_file_remembered()?;
 
         // Extract and save
         let export_data = file.mapped.slice(FileRegion::new(sig.offset, export_size));
@@ -98,8 +105,10 @@ impl SignaturesWindow {
             if count == 0 {
                 ui.label("No signatures found in first 1 MB.");
             } else {
-                let mut clicked_offset: Option<u64> = None;
+                let mut clicked_index: Option<usize> = None;
                 let mut export_index: Option<usize> = None;
+                let mut disasm_offset: Option<u64> = None;
+                let mut show_structure = false;
 
                 egui::ScrollArea::vertical()
                     .max_height(300.0)
@@ -115,14 +124,35 @@ impl SignaturesWindow {
                                 ui.horizontal(|ui| {
                                     let text = format!("{} @ 0x{:X}", sig.name, sig.offset);
                                     let color = signature_color(&sig.name);
-                                    if ui.selectable_label(false, RichText::new(&text).color(color)).clicked() {
-                                        clicked_offset = Some(sig.offset);
+                                    let selected = state.edit.selected_offset == Some(sig.offset);
+                                    if ui.selectable_label(selected, RichText::new(&text).color(color)).clicked() {
+                                        clicked_index = Some(i);
+                                    }
+                                    magic_label(ui, &sig.magic);
+                                    if let Some(carve) = state.signature_carves.get(i) {
+                                        ui.weak(carve_label(carve)).on_hover_text(if carve.size_exact {
+                                            "Size from the end marker"
+                                        } else {
+                                            "Extension and estimated size of the embedded file"
+                                        });
                                     }
 
                                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                         if ui.small_button("Export").clicked() {
                                             export_index = Some(i);
                                         }
+                                        if disasm_button(ui, &sig.name) {
+                                            disasm_offset = Some(sig.offset);
+                                        }
+                                        // The structure is parsed for the hit the file starts with
+                                        if sig.offset == 0
+                                            && state.structure.is_some()
+                                            && ui.small_button("Structure")
+                                                .on_hover_text("List the chunks or archive entries")
+                                                .clicked()
+                                        {
+                                            show_structure = true;
+                                        }
                                     });
                                 });
                             }
@@ -130,8 +160,15 @@ impl SignaturesWindow {
                     });
 
                 // Apply deferred actions
-                if let Some(offset) = clicked_offset {
-                    state.viewport.start = (offset / 16) * 16;
+                if let Some(sig) = clicked_index.and_then(|i| state.signatures.as_ref()?.get(i).cloned()) {
+                    state.deep_scan.highlight_hit(&sig);
+                    state.select_offset(sig.offset);
+                }
+                if let Some(offset) = disasm_offset {
+                    state.request_disasm_at(offset);
+                }
+                if show_structure {
+                    state.structure_window_request = true;
                 }
 
                 if let Some(i) = export_index {
@@ -158,7 +195,7 @@ impl SignaturesWindow {
         if state.deep_scan.scanning {
             ui.horizontal(|ui| {
                 ui.spinner();
-                ui.label("Scanning...");
+                ui.label(state.gpu_job_text(GpuJob::DeepScan, "Scanning..."));
             });
 
             // Progress bar
@@ -181,16 +218,50 @@ impl SignaturesWindow {
                 }
             }
         } else if state.deep_scan.results.is_none() {
+            Self::show_deep_scan_options(ui, state);
             if ui.button("Start Deep Scan").clicked() {
                 state.deep_scan.scanning = true;
             }
-            ui.label(RichText::new("Scans entire file for 37 signatures").weak().small());
+            let count = state.deep_scan.scan_category.signatures().len();
+            ui.label(RichText::new(format!("Scans entire file for {} signatures", count)).weak().small());
         }
 
         // Results
         Self::show_deep_scan_results(ui, state);
     }
 
+    /// Signature subset and chunk size of the next deep scan.
+    fn show_deep_scan_options(ui: &mut egui::Ui, state: &mut AppState) {
+        let deep_scan = &mut state.deep_scan;
+        ui.horizontal(|ui| {
+            ui.label("Scan for:");
+            egui::ComboBox::from_id_salt("deep_scan_category")
+                .selected_text(deep_scan.scan_category.label())
+                .width(90.0)
+                .show_ui(ui, |ui| {
+                    for cat in SignatureCategory::ALL {
+                        let label = format!("{} ({})", cat.label(), cat.signatures().len());
+                        ui.selectable_value(&mut deep_scan.scan_category, cat, label);
+                    }
+                });
+
+            ui.label("Chunk:");
+            let chunk_mb = deep_scan.scan_chunk_bytes() / (1024 * 1024);
+            egui::ComboBox::from_id_salt("deep_scan_chunk")
+                .selected_text(format!("{} MB", chunk_mb))
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    for mb in DEEP_SCAN_CHUNK_OPTIONS_MB {
+                        if ui.selectable_label(chunk_mb == mb, format!("{} MB", mb)).clicked() {
+                            deep_scan.scan_chunk_mb = Some(mb);
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("GPU buffer per pass; lower it on GPUs with little memory");
+        });
+    }
+
     fn show_deep_scan_results(ui: &mut egui::Ui, state: &mut AppState) {
         let scanning = state.deep_scan.scanning;
         let duration_ms = state.deep_scan.duration_ms;
@@ -228,15 +299,7 @@ impl SignaturesWindow {
                     .selected_text(state.deep_scan.filter_category.label())
                     .width(90.0)
                     .show_ui(ui, |ui| {
-                        for cat in [
-                            SignatureCategory::All,
-                            SignatureCategory::Executables,
-                            SignatureCategory::Archives,
-                            SignatureCategory::Images,
-                            SignatureCategory::Documents,
-                            SignatureCategory::Databases,
-                            SignatureCategory::Other,
-                        ] {
+                        for cat in SignatureCategory::ALL {
                             if ui.selectable_value(&mut state.deep_scan.filter_category, cat, cat.label()).changed() {
                                 needs_rebuild = true;
                             }
@@ -249,8 +312,12 @@ impl SignaturesWindow {
                         .hint_text("Search...")
                         .desired_width(80.0)
                 );
+                let now = ui.input(|i| i.time);
                 if response.changed() {
-                    needs_rebuild = true;
+                    state.deep_scan.filter_edited(now);
+                }
+                if state.deep_scan.apply_text_filter(now) {
+                    ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(SIGNATURE_FILTER_DEBOUNCE_SECS));
                 }
             });
 
@@ -279,10 +346,19 @@ impl SignaturesWindow {
                             }
                         }
                     });
+
+                let mut grouped = !state.deep_scan.flat_list;
+                if ui.checkbox(&mut grouped, "Group nested")
+                    .on_hover_text("Show hits inside a carved file under that file")
+                    .changed()
+                {
+                    state.deep_scan.flat_list = !grouped;
+                    needs_rebuild = true;
+                }
             });
 
             if needs_rebuild {
-                state.deep_scan.rebuild_filtered_indices();
+                state.deep_scan.refresh_filtered_indices();
             }
 
             // Export buttons
@@ -297,9 +373,30 @@ impl SignaturesWindow {
                     if ui.button("Export All...").on_hover_text("Export filtered signatures").clicked() {
                         export_all = true;
                     }
+                    let carving = state.deep_scan.carve_progress.is_some();
+                    if ui.add_enabled(!carving, egui::Button::new("Carve & Hash..."))
+                        .on_hover_text("Carve every hit with an exact size, hash it (SHA-256) and write manifest.csv")
+                        .clicked()
+                    {
+                        if let Some(folder) = file_dialog::new().pick_folder_remembered() {
+                            state.deep_scan.carve_request = Some(folder);
+                            state.deep_scan.carve_status = None;
+                        }
+                    }
                 });
             }
 
+            // Batch carve progress and outcome
+            if let Some((done, total)) = state.deep_scan.carve_progress {
+                let progress = if total > 0 { done as f32 / total as f32 } else { 0.0 };
+                ui.add(egui::ProgressBar::new(progress)
+                    .text(format!("Carving {} / {}", done, total))
+                    .animate(true));
+            } else if let Some((ref message, is_error)) = state.deep_scan.carve_status {
+                let color = if is_error { Color32::RED } else { Color32::from_rgb(120, 200, 120) };
+                ui.colored_label(color, message);
+            }
+
             // Handle exports (deferred)
             if export_selected {
                 if let Some(sig) = state.deep_scan.selected_result
@@ -325,36 +422,17 @@ impl SignaturesWindow {
             ui.add_space(4.0);
             ui.horizontal(|ui| {
                 if ui.button("|<").on_hover_text("First").clicked() {
-                    state.deep_scan.selected_result = Some(0);
-                    state.deep_scan.update_highlight();
-                    if let Some(sig) = state.deep_scan.get_filtered_signature(0) {
-                        state.viewport.start = (sig.offset / 16) * 16;
-                    }
+                    Self::select_deep_result(state, 0);
                 }
                 if ui.button("<").on_hover_text("Previous").clicked() && sel > 0 {
-                    let new_sel = sel - 1;
-                    state.deep_scan.selected_result = Some(new_sel);
-                    state.deep_scan.update_highlight();
-                    if let Some(sig) = state.deep_scan.get_filtered_signature(new_sel) {
-                        state.viewport.start = (sig.offset / 16) * 16;
-                    }
+                    Self::select_deep_result(state, sel - 1);
                 }
                 ui.label(format!("{} / {}", sel + 1, filtered_count));
                 if ui.button(">").on_hover_text("Next").clicked() && sel + 1 < filtered_count {
-                    let new_sel = sel + 1;
-                    state.deep_scan.selected_result = Some(new_sel);
-                    state.deep_scan.update_highlight();
-                    if let Some(sig) = state.deep_scan.get_filtered_signature(new_sel) {
-                        state.viewport.start = (sig.offset / 16) * 16;
-                    }
+                    Self::select_deep_result(state, sel + 1);
                 }
                 if ui.button(">|").on_hover_text("Last").clicked() {
-                    let last = filtered_count - 1;
-                    state.deep_scan.selected_result = Some(last);
-                    state.deep_scan.update_highlight();
-                    if let Some(sig) = state.deep_scan.get_filtered_signature(last) {
-                        state.viewport.start = (sig.offset / 16) * 16;
-                    }
+                    Self::select_deep_result(state, filtered_count - 1);
                 }
             });
 
@@ -363,6 +441,8 @@ impl SignaturesWindow {
             // Track actions to apply after UI iteration
             let mut clicked_index: Option<usize> = None;
             let mut export_index: Option<usize> = None;
+            let mut toggle_index: Option<usize> = None;
+            let mut disasm_offset: Option<u64> = None;
 
             // Results list - only renders visible rows using filtered_indices
             egui::ScrollArea::vertical()
@@ -372,20 +452,45 @@ impl SignaturesWindow {
                     for i in row_range {
                         if let Some(sig) = state.deep_scan.get_filtered_signature(i) {
                             let selected = state.deep_scan.selected_result == Some(i);
+                            let result_idx = state.deep_scan.filtered_indices[i];
+                            let depth = state.deep_scan.filtered_depths.get(i).copied().unwrap_or(0);
+                            let children = state.deep_scan.child_counts.get(&result_idx).copied().unwrap_or(0);
 
                             ui.horizontal(|ui| {
-                                let text = format!("{} @ 0x{:X}", sig.name, sig.offset);
+                                ui.add_space(depth as f32 * NEST_INDENT);
+                                if children > 0 {
+                                    let expanded = state.deep_scan.expanded.contains(&result_idx);
+                                    let arrow = if expanded { "v" } else { ">" };
+                                    if ui.small_button(arrow)
+                                        .on_hover_text(format!("{} nested signature(s)", children))
+                                        .clicked()
+                                    {
+                                        toggle_index = Some(result_idx);
+                                    }
+                                } else if !state.deep_scan.flat_list {
+                                    ui.add_space(NEST_INDENT);
+                                }
+
+                                let text = if children > 0 {
+                                    format!("{} @ 0x{:X} (+{})", sig.name, sig.offset, children)
+                                } else {
+                                    format!("{} @ 0x{:X}", sig.name, sig.offset)
+                                };
                                 let color = signature_color(&sig.name);
                                 let label = egui::RichText::new(&text).color(color);
 
                                 if ui.selectable_label(selected, label).clicked() {
                                     clicked_index = Some(i);
                                 }
+                                magic_label(ui, &sig.magic);
 
                                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                     if ui.small_button("Export").clicked() {
                                         export_index = Some(i);
                                     }
+                                    if disasm_button(ui, &sig.name) {
+                                        disasm_offset = Some(sig.offset);
+                                    }
                                 });
                             });
                         }
@@ -393,13 +498,15 @@ impl SignaturesWindow {
                 });
 
             // Apply deferred actions
+            if let Some(idx) = toggle_index {
+                state.deep_scan.toggle_expanded(idx);
+            }
+
             if let Some(i) = clicked_index {
-                if let Some(sig) = state.deep_scan.get_filtered_signature(i) {
-                    let offset = sig.offset;
-                    state.deep_scan.selected_result = Some(i);
-                    state.deep_scan.update_highlight();
-                    state.viewport.start = (offset / 16) * 16;
-                }
+                Self::select_deep_result(state, i);
+            }
+            if let Some(offset) = disasm_offset {
+                state.request_disasm_at(offset);
             }
 
             if let Some(i) = export_index {
@@ -414,19 +521,73 @@ impl SignaturesWindow {
             ui.label("No signatures match the current filter.");
         }
 
+        if !scanning && total_count > 0 {
+            Self::show_gaps(ui, state);
+        }
+
         // Clear button
         if !scanning && total_count > 0 {
             ui.add_space(4.0);
             if ui.button("Clear Results").clicked() {
-                state.deep_scan.results = None;
-                state.deep_scan.duration_ms = None;
-                state.deep_scan.selected_result = None;
-                state.deep_scan.filtered_indices.clear();
-                state.deep_scan.clear_highlight();
+                state.deep_scan.clear_results();
             }
         }
     }
 
+    /// Regions outside every carved result: slack space and possible
+    /// deleted-file remnants. Clicking one jumps to it (Alt+Left returns).
+    fn show_gaps(ui: &mut egui::Ui, state: &mut AppState) {
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.strong("Unaccounted regions");
+            if ui.small_button("Find")
+                .on_hover_text(format!(
+                    "List regions of {} bytes or more outside every result with a known size",
+                    GAP_MIN_BYTES
+                ))
+                .clicked()
+            {
+                state.find_carve_gaps();
+            }
+        });
+
+        let Some(gaps) = &state.deep_scan.gaps else { return };
+        if gaps.is_empty() {
+            ui.weak("Every byte is inside a carved result.");
+            return;
+        }
+        let total: u64 = gaps.iter().map(|g| g.size).sum();
+        ui.weak(format!("{} region(s), {} outside carved results", gaps.len(), format_size_short(total)));
+
+        let mut jump = None;
+        egui::ScrollArea::vertical().id_salt("carve_gaps").max_height(160.0).show(ui, |ui| {
+            egui::Grid::new("carve_gaps_grid").striped(true).show(ui, |ui| {
+                for gap in gaps {
+                    if ui.link(RichText::new(format!("0x{:X}", gap.offset)).monospace()).clicked() {
+                        jump = Some(gap.offset);
+                    }
+                    ui.label(format_size_short(gap.size));
+                    ui.colored_label(gap_color(gap.kind), gap.kind.label());
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(offset) = jump {
+            state.jump_to_offset(offset);
+        }
+    }
+
+    /// Select a deep scan result (index into the filtered list), highlight its
+    /// magic bytes and jump to it.
+    fn select_deep_result(state: &mut AppState, filtered_idx: usize) {
+        let Some(offset) = state.deep_scan.get_filtered_signature(filtered_idx).map(|sig| sig.offset) else {
+            return;
+        };
+        state.deep_scan.selected_result = Some(filtered_idx);
+        state.deep_scan.update_highlight();
+        state.select_offset(offset);
+    }
+
     /// Export filtered signatures to a folder.
     fn export_filtered_signatures(state: &AppState) {
         // Collect filtered signatures
@@ -444,7 +605,7 @@ impl SignaturesWindow {
     /// Export all signatures to a folder.
     fn export_all_signatures(state: &AppState, results: &[SignatureHit]) {
         // Pick a folder
-        let folder: PathBuf = match rfd::FileDialog::new().pick_folder() {
+        let folder: PathBuf = match file_dialog::new().pick_folder_remembered() {
             Some(f) => f,
             None => return,
         };
@@ -481,6 +642,50 @@ impl SignaturesWindow {
     }
 }
 
+/// Matched magic bytes as hex and ASCII, truncated to `max_bytes` with an ellipsis.
+fn format_magic(magic: &[u8], max_bytes: usize) -> String {
+    let shown = &magic[..magic.len().min(max_bytes)];
+    let ellipsis = if shown.len() < magic.len() { "\u{2026}" } else { "" };
+    let hex: Vec<String> = shown.iter().map(|b| format!("{:02X}", b)).collect();
+    let ascii: String = shown.iter()
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+        .collect();
+    format!("{}{} |{}{}|", hex.join(" "), ellipsis, ascii, ellipsis)
+}
+
+/// Magic bytes column: truncated preview, full value on hover.
+fn magic_label(ui: &mut egui::Ui, magic: &[u8]) {
+    if magic.is_empty() {
+        return;
+    }
+    let preview = RichText::new(format_magic(magic, MAGIC_PREVIEW_BYTES))
+        .monospace()
+        .small()
+        .color(Color32::from_rgb(140, 140, 140));
+    ui.label(preview).on_hover_text(format!("Matched {} byte(s): {}", magic.len(), format_magic(magic, usize::MAX)));
+}
+
+/// Native executable formats whose header names the architecture.
+const DISASM_SIGNATURES: [&str; 3] = ["ELF", "PE/COFF", "Mach-O"];
+
+/// "Disasm" button for executable hits. Returns true if clicked.
+fn disasm_button(ui: &mut egui::Ui, name: &str) -> bool {
+    DISASM_SIGNATURES.iter().any(|prefix| name.starts_with(prefix))
+        && ui.small_button("Disasm")
+            .on_hover_text("Disassemble from here, with the architecture from the header")
+            .clicked()
+}
+
+/// Carve summary of a hit: "~48 KB, .png" (estimated), "48 KB, .png" (exact)
+/// or ".png" when the size is unknown.
+fn carve_label(carve: &CarveInfo) -> String {
+    match carve.size {
+        Some(size) if carve.size_exact => format!("{}, .{}", format_size_short(size), carve.extension),
+        Some(size) => format!("~{}, .{}", format_size_short(size), carve.extension),
+        None => format!(".{}", carve.extension),
+    }
+}
+
 /// Format bytes into a short human-readable string.
 fn format_size_short(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -498,6 +703,16 @@ fn format_size_short(bytes: u64) -> String {
     }
 }
 
+/// Color of an unaccounted region by content.
+fn gap_color(kind: GapKind) -> Color32 {
+    match kind {
+        GapKind::Zeros => Color32::GRAY,
+        GapKind::HighEntropy => Color32::from_rgb(255, 150, 150),
+        GapKind::Text => Color32::from_rgb(150, 255, 150),
+        GapKind::Binary => Color32::from_rgb(150, 200, 255),
+    }
+}
+
 /// Color-code signatures by type.
 fn signature_color(name: &str) -> Color32 {
     let name_lower = name.to_lowercase();
@@ -518,3 +733,24 @@ fn signature_color(name: &str) -> Color32 {
         Color32::from_rgb(200, 200, 200) // Other - gray
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carve_label_formats() {
+        let mut carve = CarveInfo { size: Some(48 * 1024), extension: "png", size_exact: true };
+        assert_eq!(carve_label(&carve), "48 KB, .png");
+        carve.size_exact = false;
+        assert_eq!(carve_label(&carve), "~48 KB, .png");
+        carve.size = None;
+        assert_eq!(carve_label(&carve), ".png");
+    }
+
+    #[test]
+    fn format_magic_hex_and_ascii() {
+        assert_eq!(format_magic(b"\x7fELF", 8), "7F 45 4C 46 |.ELF|");
+        assert_eq!(format_magic(b"SQLite format 3\x00", 4), "53 51 4C 69\u{2026} |SQLi\u{2026}|");
+    }
+}
@@ -1,9 +1,15 @@
 use std::collections::{HashSet, HashMap};
-use std::path::PathBuf;
-use tv_core::{MappedFile, ViewPort};
+use std::path::{Path, PathBuf};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tv_core::encoding::Encoding;
+use tv_core::signatures::Signature;
+use tv_core::{Architecture, BlockClass, ByteHistogram, CarveInfo, FileDigests, FileMetadata, FileRegion, FileSource, FileStructure, Gap, HashDatabase, HistogramStats, MappedFile, Project, SectionLayout, TextEncoding, ViewPort};
 use std::fs::OpenOptions;
 use std::io::{Seek, SeekFrom, Write};
 use egui::Color32;
+use crate::theme::Theme;
 
 /// Tab selection for signatures window.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,6 +19,241 @@ pub enum SignaturesTab {
     DeepScan,
 }
 
+/// Radix used to display the offset column of the hex view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetRadix {
+    #[default]
+    Hex,
+    Decimal,
+}
+
+/// Default monospace font size of the hex view.
+pub const DEFAULT_HEX_FONT_SIZE: f32 = 13.0;
+/// Smallest selectable hex view font size.
+pub const MIN_HEX_FONT_SIZE: f32 = 8.0;
+/// Largest selectable hex view font size.
+pub const MAX_HEX_FONT_SIZE: f32 = 32.0;
+
+/// Largest selection analyzed for the status bar readout; longer selections
+/// are summarized from their first bytes.
+pub const SELECTION_STATS_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Byte range selected in the hex view (drag, or Shift+click to extend).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Byte where the selection started.
+    pub anchor: u64,
+    /// Byte where the selection ends (may be before the anchor).
+    pub cursor: u64,
+}
+
+impl Selection {
+    pub fn new(anchor: u64, cursor: u64) -> Self {
+        Self { anchor, cursor }
+    }
+
+    /// First selected byte.
+    pub fn start(&self) -> u64 {
+        self.anchor.min(self.cursor)
+    }
+
+    /// Last selected byte (inclusive).
+    pub fn end(&self) -> u64 {
+        self.anchor.max(self.cursor)
+    }
+
+    /// Number of selected bytes (never zero).
+    pub fn length(&self) -> u64 {
+        self.end() - self.start() + 1
+    }
+
+    pub fn contains(&self, offset: u64) -> bool {
+        (self.start()..=self.end()).contains(&offset)
+    }
+
+    /// Selected bytes as a file region.
+    pub fn region(&self) -> FileRegion {
+        FileRegion::new(self.start(), self.length())
+    }
+}
+
+/// How block classification colors are shown in the hex view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClassTint {
+    /// No classification colors.
+    Off,
+    /// Tint only the offset column.
+    #[default]
+    OffsetColumn,
+    /// Tint the offset, hex and ASCII columns of each row.
+    FullRow,
+    /// Tint every hex and ASCII byte by its own block, so class changes
+    /// show at their exact byte even inside a row.
+    Bytes,
+}
+
+impl ClassTint {
+    pub const ALL: [ClassTint; 4] = [ClassTint::Off, ClassTint::OffsetColumn, ClassTint::FullRow, ClassTint::Bytes];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClassTint::Off => "Off",
+            ClassTint::OffsetColumn => "Offset",
+            ClassTint::FullRow => "Row",
+            ClassTint::Bytes => "Bytes",
+        }
+    }
+
+    /// Parse the `Debug` name stored in sessions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| format!("{:?}", t) == name)
+    }
+}
+
+/// Header bytes read to infer the architecture of an executable signature hit
+/// (enough to reach the PE header of typical MZ stubs).
+const DISASM_HEADER_BYTES: u64 = 4096;
+
+/// Files larger than this ask for full or sampled analysis when opened.
+pub const LARGE_FILE_THRESHOLD: u64 = 16 * 1024 * 1024 * 1024;
+
+/// Approximate number of bytes read by a sampled analysis pass.
+pub const SAMPLED_ANALYSIS_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// How entropy and classification cover the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnalysisMode {
+    /// Every block is analyzed.
+    #[default]
+    Full,
+    /// Only every `stride`-th block is analyzed; each sample stands in for the
+    /// blocks up to the next one.
+    Sampled { stride: u64 },
+}
+
+impl AnalysisMode {
+    /// Sampled mode reading about `SAMPLED_ANALYSIS_BYTES` of a `file_len`-byte file.
+    pub fn sampled_for(file_len: u64) -> Self {
+        AnalysisMode::Sampled { stride: file_len.div_ceil(SAMPLED_ANALYSIS_BYTES).max(2) }
+    }
+
+    /// Blocks per analyzed block (1 for full analysis).
+    pub fn stride(&self) -> u64 {
+        match self {
+            AnalysisMode::Full => 1,
+            AnalysisMode::Sampled { stride } => (*stride).max(1),
+        }
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.stride() > 1
+    }
+}
+
+/// Spread sampled block values over the blocks they stand in for.
+///
+/// `samples[i]` is the value of block `first_block + i * stride` and is repeated
+/// up to the next sample, without going past `total_blocks`.
+pub fn expand_samples<T: Copy>(samples: &[T], stride: usize, first_block: usize, total_blocks: usize) -> Vec<T> {
+    let stride = stride.max(1);
+    let end = (first_block + samples.len() * stride).min(total_blocks);
+    (first_block..end).map(|block| samples[(block - first_block) / stride]).collect()
+}
+
+/// Entropy landmark to jump to from the minimap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropyJump {
+    /// Next block whose entropy differs sharply from the previous one.
+    NextBoundary,
+    /// Previous such block.
+    PrevBoundary,
+    /// Highest-entropy block in the file.
+    Maximum,
+}
+
+/// Entropy and classification block size for a `file_len`-byte file.
+///
+/// Grows with the file so the total stays under ~1M blocks:
+/// 256 bytes below 64 MB, 1 KB below 1 GB, 4 KB below 4 GB, then 16 KB.
+pub fn entropy_block_size(file_len: u64) -> u64 {
+    if file_len < 64 * 1024 * 1024 {
+        256
+    } else if file_len < 1024 * 1024 * 1024 {
+        1024
+    } else if file_len < 4 * 1024 * 1024 * 1024 {
+        4096
+    } else {
+        16384
+    }
+}
+
+/// Block sizes offered for recomputing the classification alone.
+pub const CLASSIFICATION_BLOCK_SIZES: [u64; 7] = [64, 128, 256, 512, 1024, 4096, 16384];
+
+/// Most classification blocks a recompute may produce (one byte each).
+pub const MAX_CLASSIFICATION_BLOCKS: u64 = 16 * 1024 * 1024;
+
+/// Classification block sizes usable for a `file_len`-byte file.
+pub fn classification_block_sizes(file_len: u64) -> Vec<u64> {
+    CLASSIFICATION_BLOCK_SIZES.into_iter()
+        .filter(|&size| file_len.div_ceil(size) <= MAX_CLASSIFICATION_BLOCKS)
+        .collect()
+}
+
+/// `classes` resampled to `blocks` blocks: each takes the class of the block
+/// covering its start. For consumers that expect the entropy resolution.
+pub fn resample_classes(classes: &[u8], blocks: usize) -> Vec<u8> {
+    if classes.is_empty() {
+        return vec![PENDING_CLASS; blocks];
+    }
+    (0..blocks).map(|block| classes[block * classes.len() / blocks]).collect()
+}
+
+/// Largest value of `series` in each of `columns` equal slices of the first
+/// `blocks` blocks, for drawing long series in few pixels. Columns narrower
+/// than a block repeat it; columns past the end of `series` are `None`, so
+/// series of different lengths share the same axis.
+pub fn downsample_max(series: &[f32], blocks: usize, columns: usize) -> Vec<Option<f32>> {
+    if blocks == 0 {
+        return vec![];
+    }
+    (0..columns)
+        .map(|col| {
+            let start = (col * blocks / columns).min(blocks - 1);
+            let end = ((col + 1) * blocks / columns).clamp(start + 1, blocks).min(series.len());
+            (start < end).then(|| series[start..end].iter().copied().fold(0.0f32, f32::max))
+        })
+        .collect()
+}
+
+/// Entropy of blocks whose result has not arrived yet.
+pub const PENDING_ENTROPY: f32 = 0.0;
+
+/// Class of blocks whose result has not arrived yet; like `PENDING_ENTROPY`,
+/// they read as empty data until their chunk is merged.
+pub const PENDING_CLASS: u8 = BlockClass::Zeros as u8;
+
+/// Copy a chunk of per-block results into `blocks` starting at `start_block`.
+///
+/// `blocks` grows to `total_blocks` with `fill`. Values past the end are
+/// dropped, so a late, duplicate or out-of-range chunk cannot panic.
+/// Returns true if any value was written.
+pub fn merge_block_chunk<T: Copy>(
+    blocks: &mut Vec<T>,
+    start_block: usize,
+    values: &[T],
+    total_blocks: usize,
+    fill: T,
+) -> bool {
+    if blocks.len() < total_blocks {
+        blocks.resize(total_blocks, fill);
+    }
+    let start = start_block.min(blocks.len());
+    let end = start_block.saturating_add(values.len()).min(blocks.len());
+    blocks[start..end].copy_from_slice(&values[..end - start]);
+    end > start
+}
+
 /// State for binary diff comparison.
 pub struct DiffState {
     /// Second file for comparison.
@@ -39,6 +280,24 @@ pub struct DiffState {
     pub scroll_offset: f32,
     /// Whether diff mode is active (split view in main window).
     pub active: bool,
+    /// Byte selected in either diff pane, mirrored in the other.
+    pub selected_offset: Option<u64>,
+    /// Maximum number of diff offsets to collect.
+    pub max_diffs: usize,
+    /// Only tally differing bytes, without storing offsets.
+    pub count_only: bool,
+    /// Signature comparison between A and B (deep scan of both files).
+    pub signature_diff: Option<SignatureDiff>,
+    /// Whether the signature comparison is requested or in progress.
+    pub signature_diff_computing: bool,
+    /// Whether the signature comparison panel is shown.
+    pub show_signature_diff: bool,
+    /// Per-block entropy of file B, at the block size of A's analysis.
+    pub entropy_b: Option<Vec<f32>>,
+    /// Whether the entropy pass over file B is requested or in progress.
+    pub entropy_b_computing: bool,
+    /// Whether the entropy comparison graph is shown.
+    pub show_entropy_diff: bool,
 }
 
 impl Default for DiffState {
@@ -56,8 +315,36 @@ impl Default for DiffState {
             highlight_viewport: (0, 0),
             scroll_offset: 0.0,
             active: false,
+            selected_offset: None,
+            max_diffs: DEFAULT_MAX_DIFFS,
+            count_only: false,
+            signature_diff: None,
+            signature_diff_computing: false,
+            show_signature_diff: false,
+            entropy_b: None,
+            entropy_b_computing: false,
+            show_entropy_diff: false,
+        }
+    }
+}
+
+/// Default cap on collected diff offsets.
+pub const DEFAULT_MAX_DIFFS: usize = 100_000;
+
+/// Differing bytes at most this far apart belong to the same diff region.
+pub const DIFF_REGION_GAP: u64 = 16;
+
+/// Coalesce sorted diff offsets into `(first, last)` regions, joining offsets
+/// at most `gap` bytes apart.
+pub fn coalesce_diff_regions(offsets: &[u64], gap: u64) -> Vec<(u64, u64)> {
+    let mut regions: Vec<(u64, u64)> = Vec::new();
+    for &offset in offsets {
+        match regions.last_mut() {
+            Some((_, last)) if offset.saturating_sub(*last) <= gap => *last = (*last).max(offset),
+            _ => regions.push((offset, offset)),
         }
     }
+    regions
 }
 
 impl DiffState {
@@ -71,6 +358,28 @@ impl DiffState {
         self.highlight_set.clear();
         self.highlight_viewport = (0, 0);
         self.scroll_offset = 0.0;
+        self.selected_offset = None;
+        self.signature_diff = None;
+        self.signature_diff_computing = false;
+        self.entropy_b = None;
+        self.entropy_b_computing = false;
+    }
+
+    /// Select the first diff of the next (or previous) diff region and return
+    /// the region. Stepping back from inside a region goes to its start.
+    pub fn step_region(&mut self, forward: bool) -> Option<(u64, u64)> {
+        let offsets = self.diff_offsets.as_ref()?;
+        let current = self.selected_diff.and_then(|i| offsets.get(i)).copied();
+        let regions = coalesce_diff_regions(offsets, DIFF_REGION_GAP);
+        let region = *match (current, forward) {
+            (None, true) => regions.first(),
+            (None, false) => regions.last(),
+            (Some(cur), true) => regions.iter().find(|r| r.0 > cur),
+            (Some(cur), false) => regions.iter().rev().find(|r| r.0 < cur),
+        }?;
+        self.selected_diff = Some(offsets.partition_point(|&o| o < region.0));
+        self.selected_offset = Some(region.0);
+        Some(region)
     }
 
     /// Close the comparison file.
@@ -127,11 +436,19 @@ impl DiffState {
     }
 }
 
+/// Fills of more bytes than this ask for confirmation first.
+pub const FILL_CONFIRM_BYTES: u64 = 1024 * 1024;
+
+/// Largest range that can be filled (every byte becomes a pending edit).
+pub const FILL_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
 /// State for hex editing feature.
 /// DANGEROUS: Modifying binary files can corrupt them permanently.
 pub struct EditState {
     /// Whether edit mode is enabled.
     pub enabled: bool,
+    /// File opened read-only: edit mode can't be enabled and nothing is written.
+    pub read_only: bool,
     /// Whether the enable confirmation dialog is open.
     pub confirm_dialog_open: bool,
     /// Pending edits: offset -> new byte value.
@@ -146,12 +463,17 @@ pub struct EditState {
     pub status_message: Option<(String, bool)>,
     /// Original bytes before editing (for undo).
     pub original_bytes: HashMap<u64, u8>,
+    /// Hex value typed in the "Fill with byte" prompt; `Some` while it is open.
+    pub fill_input: Option<String>,
+    /// Fill value of a large fill waiting for confirmation.
+    pub fill_confirm: Option<u8>,
 }
 
 impl Default for EditState {
     fn default() -> Self {
         Self {
             enabled: false,
+            read_only: false,
             confirm_dialog_open: false,
             pending_edits: HashMap::new(),
             selected_offset: None,
@@ -159,6 +481,8 @@ impl Default for EditState {
             save_dialog_open: false,
             status_message: None,
             original_bytes: HashMap::new(),
+            fill_input: None,
+            fill_confirm: None,
         }
     }
 }
@@ -190,6 +514,14 @@ impl EditState {
         self.pending_edits.insert(offset, new_value);
     }
 
+    /// Set every byte from `start` to `value`; `originals` are the file bytes
+    /// of the range.
+    pub fn fill(&mut self, start: u64, originals: &[u8], value: u8) {
+        for (i, &original) in originals.iter().enumerate() {
+            self.set_byte(start + i as u64, original, value);
+        }
+    }
+
     /// Get the edited byte value at offset, or None if not edited.
     pub fn get_edited_byte(&self, offset: u64) -> Option<u8> {
         self.pending_edits.get(&offset).copied()
@@ -205,7 +537,30 @@ impl EditState {
         self.pending_edits.clear();
     }
 
+    /// Ask to enable edit mode (opens the confirmation dialog).
+    /// Returns false for read-only files.
+    pub fn request_enable(&mut self) -> bool {
+        if self.read_only {
+            self.status_message = Some(("File is open read-only".to_string(), true));
+            return false;
+        }
+        self.confirm_dialog_open = true;
+        true
+    }
+
+    /// Lock the file read-only. Refused while edits are pending.
+    pub fn set_read_only(&mut self) -> bool {
+        if self.has_changes() {
+            return false;
+        }
+        self.clear();
+        self.read_only = true;
+        true
+    }
+
     /// Clear all state (when closing file or disabling edit mode).
+    ///
+    /// The read-only flag is kept: it belongs to the opened file.
     pub fn clear(&mut self) {
         self.enabled = false;
         self.confirm_dialog_open = false;
@@ -215,15 +570,57 @@ impl EditState {
         self.save_dialog_open = false;
         self.status_message = None;
         self.original_bytes.clear();
+        self.fill_input = None;
+        self.fill_confirm = None;
     }
 
     /// Save pending edits to file.
     /// Returns (success_count, errors).
     pub fn save_to_file(&mut self, path: &std::path::Path) -> Result<usize, String> {
+        if self.read_only {
+            return Err("File is open read-only".to_string());
+        }
         if self.pending_edits.is_empty() {
             return Ok(0);
         }
 
+        let saved_count = self.write_edits(path)?;
+
+        // Clear pending edits after successful save
+        self.pending_edits.clear();
+        self.original_bytes.clear();
+
+        Ok(saved_count)
+    }
+
+    /// Write a copy of `source` with the pending edits applied to `dest`,
+    /// leaving the original file and the pending edits untouched.
+    pub fn save_as(&self, source: &std::path::Path, dest: &std::path::Path) -> Result<usize, String> {
+        let same_file = std::fs::canonicalize(dest)
+            .is_ok_and(|dest| std::fs::canonicalize(source).is_ok_and(|source| source == dest));
+        if same_file {
+            return Err("Choose a different file than the one being edited".to_string());
+        }
+        std::fs::copy(source, dest).map_err(|e| format!("Failed to copy file: {}", e))?;
+        self.write_edits(dest)
+    }
+
+    /// Pending edits as a text patch, one `offset: old -> new` line per byte
+    /// in offset order. The original file is not touched.
+    pub fn patch_text(&self, file_name: &str) -> String {
+        let mut edits: Vec<_> = self.pending_edits.iter().collect();
+        edits.sort_by_key(|(offset, _)| *offset);
+
+        let mut text = format!("# TitanView patch for {}\n# {} byte(s), offset: old -> new\n", file_name, edits.len());
+        for (&offset, &byte) in edits {
+            let old = self.original_bytes.get(&offset).map_or("??".to_string(), |b| format!("{:02X}", b));
+            text.push_str(&format!("0x{:08X}: {} -> {:02X}\n", offset, old, byte));
+        }
+        text
+    }
+
+    /// Seek-write each pending edit into `path`, in offset order.
+    fn write_edits(&self, path: &std::path::Path) -> Result<usize, String> {
         let mut file = OpenOptions::new()
             .write(true)
             .open(path)
@@ -257,10 +654,6 @@ impl EditState {
                 saved_count, errors.len(), errors.join("; ")));
         }
 
-        // Clear pending edits after successful save
-        self.pending_edits.clear();
-        self.original_bytes.clear();
-
         Ok(saved_count)
     }
 }
@@ -276,29 +669,117 @@ pub struct AppState {
     /// Per-block classification results from GPU (if computed).
     /// Each u8 maps to `BlockClass::from_u8()`.
     pub classification: Option<Vec<u8>>,
+    /// Block size of `classification` when recomputed at a size of its own;
+    /// `None` when it shares the entropy block size.
+    pub classification_block_size: Option<u64>,
+    /// Classification recompute requested at this block size (started by the app).
+    pub classification_request: Option<u64>,
     /// Search state.
     pub search: SearchState,
     /// "Go to offset" dialog state.
     pub goto_open: bool,
     pub goto_text: String,
+    /// Fine offset input of the coarse navigation slider (large files).
+    pub coarse_offset_text: String,
     /// Detected file signatures (quick scan at startup, first 1 MB).
     pub signatures: Option<Vec<SignatureHit>>,
+    /// Carve analysis of each quick-scan hit (parallel to `signatures`).
+    pub signature_carves: Vec<CarveInfo>,
+    /// Section layout when the file is an ELF, PE or Mach-O executable (parsed on open).
+    pub sections: Option<SectionLayout>,
+    /// PNG chunks or ZIP entries when the file starts with one of those
+    /// signatures (parsed on open).
+    pub structure: Option<FileStructure>,
     /// Deep scan state (GPU multi-pattern, full file).
     pub deep_scan: DeepScanState,
     /// Cached entropy stats (avg, computed once when data arrives).
     pub cached_entropy_stats: Option<EntropyStats>,
     /// Cached classification counts (computed once when data arrives).
     pub cached_class_counts: Option<[u32; 5]>,
+    /// Full-file byte histogram (computed once in the background on open).
+    pub byte_histogram: Option<ByteHistogram>,
+    /// MD5, SHA-1 and SHA-256 of the file (computed in one background pass on open).
+    pub file_digests: Option<FileDigests>,
+    /// Fraction of the file hashed while the digests are computed.
+    pub file_hash_progress: Option<f32>,
+    /// Stops the running hash when set; shared with the hashing thread.
+    pub file_hash_cancel: Arc<AtomicBool>,
+    /// Ask the app to hash the file again, e.g. after a cancel (consumed by the app).
+    pub file_hash_request: bool,
+    /// Filesystem timestamps and permissions (read on open).
+    pub file_metadata: Option<FileMetadata>,
+    /// Text encoding found at the start of the file (`None` = binary).
+    pub text_encoding: Option<Encoding>,
+    /// Known-good and known-bad hash lists, shared by all tabs.
+    pub hash_db: HashDatabase,
+    /// Result of the last hash list load (message, is_error).
+    pub hash_db_status: Option<(String, bool)>,
     /// Current tab in signatures window.
     pub signatures_tab: SignaturesTab,
     /// Binary diff state.
     pub diff: DiffState,
     /// Structure inspector highlights (offsets to highlight).
     pub inspector_highlights: HashSet<u64>,
+    /// Byte range of the inspector field under the mouse (or selected),
+    /// drawn in a distinct color over `inspector_highlights`.
+    pub inspector_focus: Option<Range<u64>>,
+    /// Byte range blinking in the hex view after a "locate" action.
+    pub hex_flash: Option<HexFlash>,
     /// Hex editing state (DANGEROUS operation).
     pub edit: EditState,
+    /// Free-form notes on the file, saved with the session.
+    pub notes: String,
     /// Cached minimap pixels (avoid recomputing 16M+ block iterations every frame).
     pub minimap_cache: MinimapCache,
+    /// Per-block CRC-32 strip over the minimap (file A vs diff file B).
+    pub checksums: ChecksumStrip,
+    /// Radix of the hex view offset column.
+    pub offset_radix: OffsetRadix,
+    /// Classification coloring in the hex view.
+    pub class_tint: ClassTint,
+    /// Monospace font size of the hex view.
+    pub hex_font_size: f32,
+    /// Colors for the hex view, highlights and classification.
+    pub theme: Theme,
+    /// Coverage of the entropy and classification pass for the current file.
+    pub analysis_mode: AnalysisMode,
+    /// The file is larger than `LARGE_FILE_THRESHOLD` and the analysis mode
+    /// has not been chosen yet.
+    pub analysis_prompt: bool,
+    /// Analysis mode chosen in the UI; the app (re)starts the analysis with it.
+    pub analysis_request: Option<AnalysisMode>,
+    /// GPU operation currently running (updated by the app every frame).
+    pub gpu_job: Option<GpuJob>,
+    /// Offset to open the disassembly at, with the architecture if known
+    /// (set from a signature hit, consumed by the app).
+    pub disasm_request: Option<(u64, Option<Architecture>)>,
+    /// Ask the app to show the Search window (consumed by the app).
+    pub search_window_request: bool,
+    /// Ask the app to show the Text View in this encoding (consumed by the app).
+    pub text_view_request: Option<TextEncoding>,
+    /// Ask the app to show the Structure window (consumed by the app).
+    pub structure_window_request: bool,
+    /// Ask the XOR window to open and analyze the selection (consumed by it).
+    pub xor_window_request: bool,
+    /// Address of file offset 0 for "Follow as offset" (e.g. an image base);
+    /// pointer values are rebased by subtracting it.
+    pub address_base: u64,
+    /// Read followed pointers as big-endian instead of little-endian.
+    pub follow_big_endian: bool,
+    /// Viewport offsets to return to after following pointers or entropy jumps (latest last).
+    pub nav_back: Vec<u64>,
+    /// Byte range selected in the hex view.
+    pub selection: Option<Selection>,
+    /// Whether the selection is being dragged.
+    pub selecting: bool,
+    /// Byte statistics of the selection, cached for the status bar readout.
+    selection_stats: Option<(Selection, HistogramStats)>,
+    /// Result of comparing the selection to the clipboard: (compared
+    /// selection, message, is mismatch).
+    pub clipboard_compare: Option<(Selection, String, bool)>,
+    /// Repetition period detected in the selection: (examined selection,
+    /// period in bytes if any).
+    pub selection_period: Option<(Selection, Option<usize>)>,
 }
 
 /// Cached entropy statistics to avoid recomputing every frame.
@@ -311,7 +792,8 @@ pub struct EntropyStats {
 /// Cached minimap pixels to avoid recomputing downsampling every frame.
 /// For a 4GB file with 256-byte blocks, there are 16M blocks.
 /// Without caching, we iterate through all blocks every frame (34M+ iterations).
-/// With caching, we only recompute when data or height changes.
+/// The blocks are summarized once at a fixed resolution (`base`) and only
+/// recomputed when the data changes; a new height resamples the summary.
 #[derive(Default)]
 pub struct MinimapCache {
     /// Pre-computed pixel colors for the minimap.
@@ -322,6 +804,17 @@ pub struct MinimapCache {
     pub cached_block_count: usize,
     /// Whether classification was available when cache was computed.
     pub cached_has_classification: bool,
+    /// Block summary at the canonical resolution, resampled to `pixels`.
+    pub base: Vec<MinimapRow>,
+}
+
+/// Summary of the blocks behind one canonical minimap row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapRow {
+    /// Highest block entropy.
+    pub max_entropy: f32,
+    /// Blocks per class (by `BlockClass as u8`), all zero without classification.
+    pub class_counts: [u32; 5],
 }
 
 impl MinimapCache {
@@ -329,6 +822,13 @@ impl MinimapCache {
     pub fn is_valid(&self, height: usize, block_count: usize, has_classification: bool) -> bool {
         !self.pixels.is_empty()
             && self.cached_height == height
+            && self.base_is_valid(block_count, has_classification)
+    }
+
+    /// Whether the block summary matches the current data (only the height
+    /// may have changed).
+    pub fn base_is_valid(&self, block_count: usize, has_classification: bool) -> bool {
+        !self.base.is_empty()
             && self.cached_block_count == block_count
             && self.cached_has_classification == has_classification
     }
@@ -336,11 +836,77 @@ impl MinimapCache {
     /// Invalidate the cache (call when entropy/classification data changes).
     pub fn invalidate(&mut self) {
         self.pixels.clear();
+        self.base.clear();
         self.cached_height = 0;
         self.cached_block_count = 0;
     }
 }
 
+/// Block size of the minimap checksum strip (coarse, so huge images stay fast).
+pub const CHECKSUM_BLOCK_SIZE: usize = 4096;
+
+/// Per-block CRC-32 series of file A and diff file B, drawn as a strip over
+/// the minimap. Blocks whose checksums differ mark changed regions without a
+/// byte-level diff.
+#[derive(Default)]
+pub struct ChecksumStrip {
+    /// Whether the strip is shown (and computed).
+    pub enabled: bool,
+    /// Checksums of file A, with the path they were computed for.
+    pub a: Option<(PathBuf, Vec<u32>)>,
+    /// Checksums of diff file B, with the path they were computed for.
+    pub b: Option<(PathBuf, Vec<u32>)>,
+    /// Whether a computation is in progress.
+    pub computing: bool,
+    /// Cached strip colors, one per minimap pixel row.
+    pub pixels: Vec<Color32>,
+    /// (height, A blocks, B blocks) the pixels were built for.
+    pub pixels_key: (usize, usize, usize),
+}
+
+impl ChecksumStrip {
+    /// Checksums of file A if they were computed for `path`.
+    pub fn checksums_a(&self, path: Option<&Path>) -> Option<&[u32]> {
+        Self::series(&self.a, path)
+    }
+
+    /// Checksums of diff file B if they were computed for `path`.
+    pub fn checksums_b(&self, path: Option<&Path>) -> Option<&[u32]> {
+        Self::series(&self.b, path)
+    }
+
+    fn series<'a>(entry: &'a Option<(PathBuf, Vec<u32>)>, path: Option<&Path>) -> Option<&'a [u32]> {
+        match (entry, path) {
+            (Some((p, sums)), Some(path)) if p == path => Some(sums),
+            _ => None,
+        }
+    }
+
+    /// Store the checksums computed for file A or B.
+    pub fn set(&mut self, is_b: bool, path: PathBuf, sums: Vec<u32>) {
+        let entry = if is_b { &mut self.b } else { &mut self.a };
+        *entry = Some((path, sums));
+        self.pixels.clear();
+    }
+
+    /// Files whose checksums are missing or stale: (is_b, path).
+    pub fn missing(&self, path_a: Option<&Path>, path_b: Option<&Path>) -> Vec<(bool, PathBuf)> {
+        let mut missing = Vec::new();
+        if let Some(path) = path_a.filter(|&p| self.checksums_a(Some(p)).is_none()) {
+            missing.push((false, path.to_path_buf()));
+        }
+        if let Some(path) = path_b.filter(|&p| self.checksums_b(Some(p)).is_none()) {
+            missing.push((true, path.to_path_buf()));
+        }
+        missing
+    }
+}
+
+/// Whether block `index` of A differs from B (a block missing from B differs).
+pub fn checksum_block_differs(a: &[u32], b: &[u32], index: usize) -> bool {
+    a.get(index) != b.get(index)
+}
+
 /// State for the pattern search feature.
 pub struct SearchState {
     /// Hex input string (e.g. "FF D8 FF").
@@ -359,6 +925,81 @@ pub struct SearchState {
     pub highlight_viewport: (u64, u64),
     /// Search duration in milliseconds.
     pub search_duration_ms: Option<f64>,
+    /// Scanner to use for the next search.
+    pub backend: SearchBackend,
+    /// Scanner that produced the current results.
+    pub used_backend: Option<SearchBackend>,
+    /// Number of matches, when larger than the stored results (GPU result buffer full).
+    pub total_matches: Option<u64>,
+    /// Keep only matches at offsets that are a multiple of this (applied after the scan).
+    pub alignment: Option<u64>,
+    /// Match counts per offset bucket (density heatmap), built lazily.
+    pub density: Vec<u32>,
+    /// (file length, bucket count) the density was built for.
+    pub density_key: (u64, usize),
+    /// Search the viewport as the query is typed.
+    pub live: bool,
+    /// Time of the last query edit not yet searched (debounce).
+    pub live_edited_at: Option<f64>,
+    /// Range the results cover when they come from a live viewport search
+    /// (`None` for a whole-file search).
+    pub live_scope: Option<(u64, u64)>,
+    /// Bumped whenever a running search is superseded; results of older
+    /// generations are dropped.
+    pub generation: u64,
+    /// Set to stop the running whole-file search once it is superseded
+    /// (each search gets a fresh flag).
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// GPU operations on the active file. Only one runs at a time; requests made
+/// while another is running wait for it to finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuJob {
+    /// Entropy and classification.
+    Analysis,
+    Search,
+    DeepScan,
+    Hilbert,
+    Diff,
+    SignatureDiff,
+    /// Entropy of diff file B, for the entropy comparison.
+    EntropyDiff,
+}
+
+impl GpuJob {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GpuJob::Analysis => "entropy analysis",
+            GpuJob::Search => "search",
+            GpuJob::DeepScan => "deep scan",
+            GpuJob::Hilbert => "Hilbert curve",
+            GpuJob::Diff => "diff",
+            GpuJob::SignatureDiff => "signature comparison",
+            GpuJob::EntropyDiff => "entropy comparison",
+        }
+    }
+}
+
+/// Scanner used for pattern search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchBackend {
+    /// Parallel SIMD search on all CPU cores.
+    #[default]
+    Cpu,
+    /// GPU compute shader (patterns up to 16 bytes; longer ones run on the CPU).
+    Gpu,
+}
+
+impl SearchBackend {
+    pub const ALL: [SearchBackend; 2] = [SearchBackend::Cpu, SearchBackend::Gpu];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchBackend::Cpu => "CPU",
+            SearchBackend::Gpu => "GPU",
+        }
+    }
 }
 
 /// A detected file signature (magic bytes).
@@ -369,6 +1010,84 @@ pub struct SignatureHit {
     pub magic: Vec<u8>,
 }
 
+/// Offsets closer than this count as the same position when comparing signatures.
+pub const SIGNATURE_MATCH_TOLERANCE: u64 = 16;
+
+/// A signature present in both files, at different offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedSignature {
+    pub name: String,
+    pub offset_a: u64,
+    pub offset_b: u64,
+}
+
+/// Signatures added, removed or moved between diff files A and B.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureDiff {
+    /// Signatures only found in file A.
+    pub only_in_a: Vec<SignatureHit>,
+    /// Signatures found in both files at different offsets.
+    pub moved: Vec<MovedSignature>,
+    /// Signatures only found in file B.
+    pub only_in_b: Vec<SignatureHit>,
+    /// Signatures found at (approximately) the same offset in both files.
+    pub unchanged: usize,
+}
+
+/// Compare the signatures found in two files.
+///
+/// Hits are matched by name: same-name hits within `tolerance` bytes of each
+/// other are unchanged, the remaining ones are paired in offset order as
+/// moved, and whatever is left is only in A or only in B.
+pub fn diff_signatures(a: &[SignatureHit], b: &[SignatureHit], tolerance: u64) -> SignatureDiff {
+    let mut by_name: HashMap<&str, (Vec<&SignatureHit>, Vec<&SignatureHit>)> = HashMap::new();
+    for hit in a {
+        by_name.entry(hit.name.as_str()).or_default().0.push(hit);
+    }
+    for hit in b {
+        by_name.entry(hit.name.as_str()).or_default().1.push(hit);
+    }
+
+    let mut diff = SignatureDiff::default();
+    for (_, (mut hits_a, mut hits_b)) in by_name {
+        hits_a.sort_by_key(|h| h.offset);
+        hits_b.sort_by_key(|h| h.offset);
+
+        // Pair hits at the same position (two-pointer walk over both sorted lists)
+        let mut left_a = Vec::new();
+        let mut left_b = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < hits_a.len() && j < hits_b.len() {
+            let (oa, ob) = (hits_a[i].offset, hits_b[j].offset);
+            if oa.abs_diff(ob) <= tolerance {
+                diff.unchanged += 1;
+                i += 1;
+                j += 1;
+            } else if oa < ob {
+                left_a.push(hits_a[i]);
+                i += 1;
+            } else {
+                left_b.push(hits_b[j]);
+                j += 1;
+            }
+        }
+        left_a.extend(&hits_a[i..]);
+        left_b.extend(&hits_b[j..]);
+
+        let paired = left_a.len().min(left_b.len());
+        for (ha, hb) in left_a.iter().zip(&left_b) {
+            diff.moved.push(MovedSignature { name: ha.name.clone(), offset_a: ha.offset, offset_b: hb.offset });
+        }
+        diff.only_in_a.extend(left_a[paired..].iter().map(|&h| h.clone()));
+        diff.only_in_b.extend(left_b[paired..].iter().map(|&h| h.clone()));
+    }
+
+    diff.only_in_a.sort_by_key(|h| h.offset);
+    diff.only_in_b.sort_by_key(|h| h.offset);
+    diff.moved.sort_by_key(|m| m.offset_a);
+    diff
+}
+
 /// Sort order for deep scan results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SignatureSortOrder {
@@ -438,6 +1157,22 @@ impl SignatureCategory {
         }
     }
 
+    /// All categories, in display order.
+    pub const ALL: [SignatureCategory; 7] = [
+        Self::All,
+        Self::Executables,
+        Self::Archives,
+        Self::Images,
+        Self::Documents,
+        Self::Databases,
+        Self::Other,
+    ];
+
+    /// Known signatures in this category.
+    pub fn signatures(&self) -> Vec<&'static Signature> {
+        tv_core::signatures::SIGNATURES.iter().filter(|s| self.matches(s.name)).collect()
+    }
+
     /// Display name for UI.
     pub fn label(&self) -> &'static str {
         match self {
@@ -452,6 +1187,15 @@ impl SignatureCategory {
     }
 }
 
+/// Default GPU deep scan chunk size in MB.
+pub const DEFAULT_DEEP_SCAN_CHUNK_MB: u64 = 64;
+
+/// Chunk sizes offered for the deep scan (smaller suits constrained GPUs).
+pub const DEEP_SCAN_CHUNK_OPTIONS_MB: [u64; 5] = [16, 32, 64, 128, 256];
+
+/// Smallest unaccounted region listed between carved files (one disk sector).
+pub const GAP_MIN_BYTES: u64 = 512;
+
 /// State for the GPU deep scan feature (multi-pattern signature detection).
 #[derive(Default)]
 pub struct DeepScanState {
@@ -461,12 +1205,23 @@ pub struct DeepScanState {
     pub results: Option<Vec<SignatureHit>>,
     /// Filtered and sorted indices into results (avoids re-sorting original).
     pub filtered_indices: Vec<usize>,
+    /// Sorted indices of every result passing the filters, before grouping
+    /// hides those under collapsed parents.
+    pub matched_indices: Vec<usize>,
     /// Current sort order.
     pub sort_order: SignatureSortOrder,
     /// Current filter category.
     pub filter_category: SignatureCategory,
+    /// Signatures the next scan looks for.
+    pub scan_category: SignatureCategory,
+    /// GPU scan chunk size in MB (`None` = `DEFAULT_DEEP_SCAN_CHUNK_MB`).
+    pub scan_chunk_mb: Option<u64>,
     /// Text filter (search in signature name).
     pub filter_text: String,
+    /// Lowercased text filter `filtered_indices` was built with.
+    pub applied_filter: String,
+    /// Time of the last text filter edit not yet applied (debounce).
+    pub filter_edited_at: Option<f64>,
     /// Scan duration in milliseconds.
     pub duration_ms: Option<f64>,
     /// Currently selected result index (index into filtered_indices).
@@ -477,9 +1232,198 @@ pub struct DeepScanState {
     pub total_bytes: u64,
     /// Pre-computed set of highlighted byte offsets for the selected signature.
     pub highlight_set: HashSet<u64>,
+    /// Exact carved size of each result, when known (parallel to `results`).
+    pub extents: Vec<Option<u64>>,
+    /// Innermost result whose carved range contains each result (parallel to `results`).
+    pub parents: Vec<Option<usize>>,
+    /// Results whose nested hits are shown.
+    pub expanded: HashSet<usize>,
+    /// Nesting depth of each row (parallel to `filtered_indices`).
+    pub filtered_depths: Vec<usize>,
+    /// Number of nested hits directly under each result in the filtered view.
+    pub child_counts: HashMap<usize, usize>,
+    /// Show nested hits as a flat list instead of grouping them under their parent.
+    pub flat_list: bool,
+    /// Output folder of a requested batch carve (picked in the UI, consumed by the app).
+    pub carve_request: Option<PathBuf>,
+    /// Running batch carve: (hits processed, total hits).
+    pub carve_progress: Option<(usize, usize)>,
+    /// Outcome of the last batch carve (message, is_error).
+    pub carve_status: Option<(String, bool)>,
+    /// Regions outside every result with a known extent (found on request).
+    pub gaps: Option<Vec<Gap>>,
 }
 
 impl DeepScanState {
+    /// GPU scan chunk size in bytes.
+    pub fn scan_chunk_bytes(&self) -> u64 {
+        self.scan_chunk_mb.unwrap_or(DEFAULT_DEEP_SCAN_CHUNK_MB) * 1024 * 1024
+    }
+
+    /// Drop all results and the state derived from them.
+    pub fn clear_results(&mut self) {
+        self.results = None;
+        self.duration_ms = None;
+        self.selected_result = None;
+        self.filtered_indices.clear();
+        self.matched_indices.clear();
+        self.extents.clear();
+        self.parents.clear();
+        self.expanded.clear();
+        self.filtered_depths.clear();
+        self.child_counts.clear();
+        self.gaps = None;
+        self.clear_highlight();
+    }
+
+    /// Find the parent of every result: the innermost hit with a known extent
+    /// whose carved range contains it. Call once the scan is complete.
+    pub fn compute_nesting(&mut self) {
+        let results = match &self.results {
+            Some(r) => r,
+            None => return,
+        };
+        self.parents = vec![None; results.len()];
+
+        // Outer hits first at equal offsets, so larger ranges open before smaller ones
+        let extent = |i: usize| self.extents.get(i).copied().flatten();
+        let mut order: Vec<usize> = (0..results.len()).collect();
+        order.sort_by_key(|&i| (results[i].offset, std::cmp::Reverse(extent(i).unwrap_or(0))));
+
+        // Stack of open ranges: (end, result index)
+        let mut open: Vec<(u64, usize)> = Vec::new();
+        for i in order {
+            let offset = results[i].offset;
+            while open.last().is_some_and(|&(end, _)| offset >= end) {
+                open.pop();
+            }
+            // A hit at the very start of a range is the same file seen by another signature
+            self.parents[i] = open.iter().rev()
+                .find(|&&(_, p)| results[p].offset < offset)
+                .map(|&(_, p)| p);
+            if let Some(size) = extent(i).filter(|&s| s > 0) {
+                open.push((offset.saturating_add(size), i));
+            }
+        }
+    }
+
+    /// Expand or collapse the nested hits under a result, keeping the selection.
+    pub fn toggle_expanded(&mut self, result_idx: usize) {
+        if !self.expanded.remove(&result_idx) {
+            self.expanded.insert(result_idx);
+        }
+        self.refresh_filtered_indices();
+    }
+
+    /// Index into `results` of the selected row.
+    fn selected_result_index(&self) -> Option<usize> {
+        self.selected_result.and_then(|sel| self.filtered_indices.get(sel).copied())
+    }
+
+    /// Select the row of result `idx` again after the list changed
+    /// (deselecting if it is no longer listed).
+    fn reselect(&mut self, idx: Option<usize>) {
+        self.selected_result = idx.and_then(|idx| self.filtered_indices.iter().position(|&i| i == idx));
+        self.update_highlight();
+    }
+
+    /// Nested hits are listed under their parent.
+    fn is_grouped(&self) -> bool {
+        !self.flat_list && self.results.as_ref().is_some_and(|r| self.parents.len() == r.len())
+    }
+
+    /// Rebuild filtered_indices like [`Self::rebuild_filtered_indices`],
+    /// keeping the selected result selected if it is still listed.
+    pub fn refresh_filtered_indices(&mut self) {
+        let selected = self.selected_result_index();
+        self.rebuild_filtered_indices();
+        self.reselect(selected);
+    }
+
+    /// The text filter changed at `now` (seconds); [`Self::apply_text_filter`]
+    /// applies it once typing pauses.
+    pub fn filter_edited(&mut self, now: f64) {
+        self.filter_edited_at = Some(now);
+    }
+
+    /// Apply a text filter edit once it has been unchanged for
+    /// `SIGNATURE_FILTER_DEBOUNCE_SECS`, keeping the selection if it still
+    /// matches. A filter that only adds to the applied text can only remove
+    /// rows, so the sorted matches are narrowed in place (and regrouped)
+    /// instead of rebuilt.
+    /// Returns true while an edit is waiting for the debounce.
+    pub fn apply_text_filter(&mut self, now: f64) -> bool {
+        match self.filter_edited_at {
+            Some(edited) if now - edited < SIGNATURE_FILTER_DEBOUNCE_SECS => return true,
+            Some(_) => self.filter_edited_at = None,
+            None => return false,
+        }
+        let filter = self.filter_text.to_lowercase();
+        if filter == self.applied_filter {
+            return false;
+        }
+
+        if !filter.contains(self.applied_filter.as_str()) {
+            self.refresh_filtered_indices();
+            return false;
+        }
+        let selected = self.selected_result_index();
+        if let Some(results) = &self.results {
+            self.matched_indices.retain(|&i| results[i].name.to_lowercase().contains(&filter));
+        }
+        self.filtered_indices.clone_from(&self.matched_indices);
+        self.filtered_depths.clear();
+        self.child_counts.clear();
+        // Grouping depends on which parents pass the filter, so it is redone
+        if self.is_grouped() {
+            self.group_filtered_indices();
+        } else {
+            self.filtered_depths = vec![0; self.filtered_indices.len()];
+        }
+        self.applied_filter = filter;
+        self.reselect(selected);
+        false
+    }
+
+    /// Nearest ancestor of `idx` that is part of `included`.
+    fn included_parent(&self, idx: usize, included: &HashSet<usize>) -> Option<usize> {
+        let mut current = self.parents.get(idx).copied().flatten();
+        while let Some(p) = current {
+            if included.contains(&p) {
+                return Some(p);
+            }
+            current = self.parents.get(p).copied().flatten();
+        }
+        None
+    }
+
+    /// Reorder sorted indices so nested hits follow their parent, hiding those
+    /// under collapsed parents.
+    fn group_filtered_indices(&mut self) {
+        let included: HashSet<usize> = self.filtered_indices.iter().copied().collect();
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut roots = Vec::new();
+        for &i in &self.filtered_indices {
+            match self.included_parent(i, &included) {
+                Some(p) => children.entry(p).or_default().push(i),
+                None => roots.push(i),
+            }
+        }
+
+        self.filtered_indices.clear();
+        let mut stack: Vec<(usize, usize)> = roots.iter().rev().map(|&i| (i, 0)).collect();
+        while let Some((i, depth)) = stack.pop() {
+            self.filtered_indices.push(i);
+            self.filtered_depths.push(depth);
+            if self.expanded.contains(&i) {
+                if let Some(kids) = children.get(&i) {
+                    stack.extend(kids.iter().rev().map(|&k| (k, depth + 1)));
+                }
+            }
+        }
+        self.child_counts = children.into_iter().map(|(p, kids)| (p, kids.len())).collect();
+    }
+
     /// Update highlight set when a signature is selected.
     /// selected_result is an index into filtered_indices.
     pub fn update_highlight(&mut self) {
@@ -509,6 +1453,13 @@ impl DeepScanState {
         }
     }
 
+    /// Highlight the magic bytes of a hit outside the deep scan list (quick scan),
+    /// deselecting any deep scan result.
+    pub fn highlight_hit(&mut self, sig: &SignatureHit) {
+        self.selected_result = None;
+        self.highlight_set = (0..sig.magic.len() as u64).map(|i| sig.offset + i).collect();
+    }
+
     /// Clear highlight when deselecting.
     pub fn clear_highlight(&mut self) {
         self.highlight_set.clear();
@@ -524,15 +1475,22 @@ impl DeepScanState {
     /// Call this after changing filter_category, filter_text, or sort_order.
     pub fn rebuild_filtered_indices(&mut self) {
         self.filtered_indices.clear();
+        self.matched_indices.clear();
+        self.filtered_depths.clear();
+        self.child_counts.clear();
         self.selected_result = None;
+        // Any pending text filter edit is applied here too
+        let filter_text_lower = self.filter_text.to_lowercase();
+        self.applied_filter.clone_from(&filter_text_lower);
+        self.filter_edited_at = None;
 
+        let grouped = self.is_grouped();
         let results = match &self.results {
             Some(r) => r,
             None => return,
         };
 
         // Build list of indices matching the filter
-        let filter_text_lower = self.filter_text.to_lowercase();
 
         for (i, sig) in results.iter().enumerate() {
             // Category filter
@@ -569,6 +1527,13 @@ impl DeepScanState {
                 });
             }
         }
+
+        self.matched_indices.clone_from(&self.filtered_indices);
+        if grouped {
+            self.group_filtered_indices();
+        } else {
+            self.filtered_depths = vec![0; self.filtered_indices.len()];
+        }
     }
 
     /// Get count of filtered results.
@@ -592,28 +1557,153 @@ fn signature_type_order(name: &str) -> u8 {
     else { 5 }
 }
 
-/// A file that has been opened and memory-mapped.
+/// A file that has been opened and memory-mapped, or bytes viewed in memory.
 pub struct LoadedFile {
+    /// Path on disk, or for in-memory bytes a label such as `<clipboard 1>`
+    /// that only names them: nothing is read from or written next to it.
     pub path: PathBuf,
     pub mapped: MappedFile,
+    /// The bytes of an in-memory file; `None` for a file on disk.
+    pub memory: Option<Arc<[u8]>>,
 }
 
-impl Default for SearchState {
-    fn default() -> Self {
-        Self {
-            query_text: String::new(),
-            pattern: None,
-            searching: false,
-            results: None,
-            selected_result: None,
-            highlight_set: HashSet::new(),
-            highlight_viewport: (0, 0),
+impl LoadedFile {
+    /// Open and map the file at `path`.
+    pub fn open(path: PathBuf) -> anyhow::Result<Self> {
+        let mapped = MappedFile::open(&path)?;
+        Ok(Self { path, mapped, memory: None })
+    }
+
+    /// In-memory bytes (e.g. decoded from the clipboard) shown as `label`.
+    pub fn from_memory(label: String, data: Vec<u8>) -> Self {
+        let data: Arc<[u8]> = data.into();
+        Self { path: PathBuf::from(label), mapped: MappedFile::from_memory(Arc::clone(&data)), memory: Some(data) }
+    }
+
+    /// Path of the file on disk; `None` for in-memory bytes, which have no
+    /// place for sessions, projects or saved edits.
+    pub fn disk_path(&self) -> Option<&Path> {
+        self.memory.is_none().then_some(self.path.as_path())
+    }
+
+    /// Source for reopening the file on a background thread.
+    pub fn source(&self) -> FileSource {
+        match &self.memory {
+            Some(data) => FileSource::Memory(Arc::clone(data)),
+            None => FileSource::Path(self.path.clone()),
+        }
+    }
+}
+
+/// Per-file state of an open tab that is not currently shown.
+///
+/// The active tab lives directly in [`AppState`]; switching tabs swaps these
+/// fields in and out so panels and windows keep working on `AppState`.
+pub struct FileTab {
+    pub file: LoadedFile,
+    pub viewport: ViewPort,
+    pub entropy: Option<Vec<f32>>,
+    pub classification: Option<Vec<u8>>,
+    pub classification_block_size: Option<u64>,
+    pub classification_request: Option<u64>,
+    pub cached_entropy_stats: Option<EntropyStats>,
+    pub cached_class_counts: Option<[u32; 5]>,
+    /// Minimap summary of the file, kept for the multi-file overview.
+    pub minimap_cache: MinimapCache,
+    pub analysis_mode: AnalysisMode,
+    pub analysis_prompt: bool,
+    pub analysis_request: Option<AnalysisMode>,
+    pub byte_histogram: Option<ByteHistogram>,
+    pub file_digests: Option<FileDigests>,
+    pub file_hash_progress: Option<f32>,
+    pub file_hash_cancel: Arc<AtomicBool>,
+    pub file_metadata: Option<FileMetadata>,
+    pub text_encoding: Option<Encoding>,
+    pub nav_back: Vec<u64>,
+    pub signatures: Option<Vec<SignatureHit>>,
+    pub signature_carves: Vec<CarveInfo>,
+    pub sections: Option<SectionLayout>,
+    pub structure: Option<FileStructure>,
+    pub deep_scan: DeepScanState,
+    pub search: SearchState,
+    pub edit: EditState,
+    pub notes: String,
+    /// Annotation project of the file (bookmarks, labels, comments).
+    pub project: Option<Project>,
+    pub project_path: Option<PathBuf>,
+    /// Whether the project has unsaved changes.
+    pub project_modified: bool,
+}
+
+impl FileTab {
+    /// File name shown on the tab.
+    pub fn name(&self) -> &str {
+        self.file.path.file_name().and_then(|n| n.to_str()).unwrap_or("?")
+    }
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        Self {
+            query_text: String::new(),
+            pattern: None,
+            searching: false,
+            results: None,
+            selected_result: None,
+            highlight_set: HashSet::new(),
+            highlight_viewport: (0, 0),
             search_duration_ms: None,
+            backend: SearchBackend::default(),
+            used_backend: None,
+            total_matches: None,
+            alignment: None,
+            density: Vec::new(),
+            density_key: (0, 0),
+            live: false,
+            live_edited_at: None,
+            live_scope: None,
+            generation: 0,
+            cancel: Arc::default(),
         }
     }
 }
 
 impl SearchState {
+    /// Start a whole-file search for `pattern`, superseding any running one.
+    pub fn start_search(&mut self, pattern: Vec<u8>) {
+        self.pattern = Some(pattern);
+        self.searching = true;
+        self.generation += 1;
+        self.cancel.store(true, Ordering::Relaxed);
+        self.live_edited_at = None;
+        self.live_scope = None;
+        self.clear_results();
+    }
+
+    /// Drop the results and their statistics.
+    pub fn clear_results(&mut self) {
+        self.results = None;
+        self.selected_result = None;
+        self.search_duration_ms = None;
+        self.used_backend = None;
+        self.total_matches = None;
+        self.rebuild_highlights();
+    }
+
+    /// The query text changed at `now` (seconds). In live mode this restarts
+    /// the debounce and cancels a running search for the previous query.
+    pub fn query_edited(&mut self, now: f64) {
+        if !self.live {
+            return;
+        }
+        self.live_edited_at = Some(now);
+        if self.searching {
+            self.searching = false;
+            self.generation += 1;
+            self.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
     /// Rebuild the highlight set for the given viewport range only.
     /// Uses binary search on the sorted results to find relevant matches.
     /// Call this when results change OR when the viewport moves.
@@ -653,14 +1743,57 @@ impl SearchState {
         }
     }
 
+    /// Drop scan matches that don't satisfy the alignment constraint.
+    pub fn apply_alignment(&self, mut offsets: Vec<u64>) -> Vec<u64> {
+        if let Some(alignment) = self.alignment.filter(|&a| a > 1) {
+            offsets.retain(|&o| o % alignment == 0);
+        }
+        offsets
+    }
+
     /// Force rebuild (e.g., when new results arrive).
     pub fn rebuild_highlights(&mut self) {
         // Reset viewport tracking so next frame rebuilds
         self.highlight_viewport = (u64::MAX, 0);
         self.highlight_set.clear();
+        self.density.clear();
+    }
+
+    /// Rebuild the density heatmap if the file length or bucket count changed.
+    pub fn rebuild_density(&mut self, file_len: u64, buckets: usize) {
+        if !self.density.is_empty() && self.density_key == (file_len, buckets) {
+            return;
+        }
+        self.density_key = (file_len, buckets);
+        self.density = match &self.results {
+            Some(r) => bucket_offsets(r, file_len, buckets),
+            None => Vec::new(),
+        };
     }
 }
 
+/// Count offsets per equal-sized bucket spanning `[0, file_len)`.
+pub fn bucket_offsets(offsets: &[u64], file_len: u64, buckets: usize) -> Vec<u32> {
+    let mut counts = vec![0u32; buckets];
+    if buckets == 0 || file_len == 0 {
+        return counts;
+    }
+    for &offset in offsets {
+        // u128 avoids overflow of offset * buckets on multi-TB files
+        let idx = (offset as u128 * buckets as u128 / file_len as u128) as usize;
+        counts[idx.min(buckets - 1)] += 1;
+    }
+    counts
+}
+
+/// First file offset covered by `bucket` in a `buckets`-wide heatmap.
+pub fn bucket_start(bucket: usize, file_len: u64, buckets: usize) -> u64 {
+    if buckets == 0 {
+        return 0;
+    }
+    (bucket as u128 * file_len as u128 / buckets as u128) as u64
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self {
@@ -668,25 +1801,118 @@ impl Default for AppState {
             viewport: ViewPort::new(0, 0),
             entropy: None,
             classification: None,
+            classification_block_size: None,
+            classification_request: None,
             search: SearchState::default(),
             goto_open: false,
             goto_text: String::new(),
+            coarse_offset_text: String::new(),
             signatures: None,
+            signature_carves: Vec::new(),
+            sections: None,
+            structure: None,
             deep_scan: DeepScanState::default(),
             cached_entropy_stats: None,
             cached_class_counts: None,
+            byte_histogram: None,
+            file_digests: None,
+            file_hash_progress: None,
+            file_hash_cancel: Arc::default(),
+            file_hash_request: false,
+            file_metadata: None,
+            text_encoding: None,
+            hash_db: HashDatabase::default(),
+            hash_db_status: None,
             signatures_tab: SignaturesTab::default(),
             diff: DiffState::default(),
             inspector_highlights: HashSet::new(),
+            inspector_focus: None,
+            hex_flash: None,
             edit: EditState::default(),
+            notes: String::new(),
             minimap_cache: MinimapCache::default(),
+            checksums: ChecksumStrip::default(),
+            offset_radix: OffsetRadix::default(),
+            class_tint: ClassTint::default(),
+            hex_font_size: DEFAULT_HEX_FONT_SIZE,
+            theme: Theme::default(),
+            analysis_mode: AnalysisMode::default(),
+            analysis_prompt: false,
+            analysis_request: None,
+            gpu_job: None,
+            disasm_request: None,
+            search_window_request: false,
+            text_view_request: None,
+            structure_window_request: false,
+            xor_window_request: false,
+            address_base: 0,
+            follow_big_endian: false,
+            nav_back: Vec::new(),
+            selection: None,
+            selecting: false,
+            selection_stats: None,
+            clipboard_compare: None,
+            selection_period: None,
         }
     }
 }
 
-/// Parse a hex string like "FF D8 FF E0" into bytes.
-/// Accepts spaces, commas, or no separator. Also accepts "0x" prefix per byte.
+/// Longest hex search pattern, in bytes.
+pub const MAX_SEARCH_PATTERN_LEN: usize = 1024;
+
+/// Longest pattern the GPU scanner matches (`tv_gpu::MAX_GPU_PATTERN_LEN`);
+/// longer patterns are searched on the CPU.
+pub const GPU_SEARCH_PATTERN_LEN: usize = 16;
+
+/// Quiet time after the last keystroke before a live search runs.
+pub const LIVE_SEARCH_DEBOUNCE_SECS: f64 = 0.3;
+
+/// Quiet time after the last keystroke before the deep scan text filter is applied.
+pub const SIGNATURE_FILTER_DEBOUNCE_SECS: f64 = 0.25;
+
+/// Bytes searched from the viewport start by a live search.
+pub const LIVE_SEARCH_BYTES: u64 = 16 * 1024;
+
+/// Positions kept on the "Follow as offset" back-stack.
+pub const NAV_BACK_LIMIT: usize = 64;
+
+/// How long a located byte range blinks in the hex view (seconds).
+pub const HEX_FLASH_SECS: f64 = 1.2;
+
+/// Length of each on/off phase of the blink (seconds).
+const HEX_FLASH_BLINK_SECS: f64 = 0.2;
+
+/// One-shot highlight of a byte range in the hex view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexFlash {
+    pub range: Range<u64>,
+    /// Time the flash ends; set when the hex view first draws it.
+    pub until: Option<f64>,
+}
+
+/// Alignment choices offered for search results: (bytes, label).
+pub const SEARCH_ALIGNMENTS: [(u64, &str); 6] = [
+    (2, "2 bytes"),
+    (4, "4 bytes"),
+    (8, "8 bytes"),
+    (16, "16 bytes (paragraph)"),
+    (512, "512 bytes (sector)"),
+    (4096, "4 KB (page)"),
+];
+
+/// Parse a hex string like "FF D8 FF E0" into a search pattern of at most
+/// `MAX_SEARCH_PATTERN_LEN` bytes.
 pub fn parse_hex_pattern(input: &str) -> Result<Vec<u8>, String> {
+    let bytes = parse_hex_bytes(input)?;
+    if bytes.len() > MAX_SEARCH_PATTERN_LEN {
+        return Err(format!("Pattern too long ({} bytes, max {})", bytes.len(), MAX_SEARCH_PATTERN_LEN));
+    }
+    Ok(bytes)
+}
+
+/// Parse a hex string like "FF D8 FF E0" into bytes, any length.
+/// Accepts spaces, commas, or no separator. Also accepts "0x" prefix per byte.
+pub fn parse_hex_bytes(input: &str) -> Result<Vec<u8>, String> {
     let cleaned = input.trim();
     if cleaned.is_empty() {
         return Err("Empty pattern".to_string());
@@ -711,12 +1937,16 @@ pub fn parse_hex_pattern(input: &str) -> Result<Vec<u8>, String> {
     if bytes.is_empty() {
         return Err("No valid bytes found".to_string());
     }
-    if bytes.len() > 16 {
-        return Err(format!("Pattern too long ({} bytes, max 16)", bytes.len()));
-    }
     Ok(bytes)
 }
 
+/// Index of the first byte where `actual` and `expected` differ, counting a
+/// length difference as a mismatch at the end of the shorter one.
+pub fn first_difference(actual: &[u8], expected: &[u8]) -> Option<usize> {
+    actual.iter().zip(expected).position(|(a, b)| a != b)
+        .or_else(|| (actual.len() != expected.len()).then(|| actual.len().min(expected.len())))
+}
+
 impl AppState {
     /// Returns the file size in bytes, or 0 if no file is loaded.
     pub fn file_len(&self) -> u64 {
@@ -744,12 +1974,495 @@ impl AppState {
     pub fn has_file(&self) -> bool {
         self.file.is_some()
     }
+
+    /// Move the active file's state out into a tab, leaving no file loaded.
+    ///
+    /// The annotation project is not part of `AppState`; the caller fills it in.
+    pub fn take_tab(&mut self) -> Option<FileTab> {
+        let file = self.file.take()?;
+        self.inspector_highlights.clear();
+        self.inspector_focus = None;
+        self.hex_flash = None;
+        self.clear_selection();
+        Some(FileTab {
+            file,
+            viewport: std::mem::replace(&mut self.viewport, ViewPort::new(0, 4096)),
+            entropy: self.entropy.take(),
+            classification: self.classification.take(),
+            classification_block_size: self.classification_block_size.take(),
+            classification_request: self.classification_request.take(),
+            cached_entropy_stats: self.cached_entropy_stats.take(),
+            cached_class_counts: self.cached_class_counts.take(),
+            minimap_cache: std::mem::take(&mut self.minimap_cache),
+            analysis_mode: std::mem::take(&mut self.analysis_mode),
+            analysis_prompt: std::mem::take(&mut self.analysis_prompt),
+            analysis_request: self.analysis_request.take(),
+            byte_histogram: self.byte_histogram.take(),
+            file_digests: self.file_digests.take(),
+            file_hash_progress: self.file_hash_progress.take(),
+            file_hash_cancel: std::mem::take(&mut self.file_hash_cancel),
+            file_metadata: self.file_metadata.take(),
+            text_encoding: self.text_encoding.take(),
+            nav_back: std::mem::take(&mut self.nav_back),
+            signatures: self.signatures.take(),
+            signature_carves: std::mem::take(&mut self.signature_carves),
+            sections: self.sections.take(),
+            structure: self.structure.take(),
+            deep_scan: std::mem::take(&mut self.deep_scan),
+            search: std::mem::take(&mut self.search),
+            edit: std::mem::take(&mut self.edit),
+            notes: std::mem::take(&mut self.notes),
+            project: None,
+            project_path: None,
+            project_modified: false,
+        })
+    }
+
+    /// Make a tab's file the active one.
+    ///
+    /// The tab's annotation project is dropped; the caller takes it out first.
+    pub fn restore_tab(&mut self, tab: FileTab) {
+        self.file = Some(tab.file);
+        self.viewport = tab.viewport;
+        self.entropy = tab.entropy;
+        self.classification = tab.classification;
+        self.classification_block_size = tab.classification_block_size;
+        self.classification_request = tab.classification_request;
+        self.cached_entropy_stats = tab.cached_entropy_stats;
+        self.cached_class_counts = tab.cached_class_counts;
+        self.analysis_mode = tab.analysis_mode;
+        self.analysis_prompt = tab.analysis_prompt;
+        self.analysis_request = tab.analysis_request;
+        self.byte_histogram = tab.byte_histogram;
+        self.file_digests = tab.file_digests;
+        self.file_hash_progress = tab.file_hash_progress;
+        self.file_hash_cancel = tab.file_hash_cancel;
+        self.file_metadata = tab.file_metadata;
+        self.text_encoding = tab.text_encoding;
+        self.nav_back = tab.nav_back;
+        self.signatures = tab.signatures;
+        self.signature_carves = tab.signature_carves;
+        self.sections = tab.sections;
+        self.structure = tab.structure;
+        self.deep_scan = tab.deep_scan;
+        self.search = tab.search;
+        self.edit = tab.edit;
+        self.notes = tab.notes;
+        // The block summary is still valid; the pixels may predate a theme change
+        self.minimap_cache = tab.minimap_cache;
+        self.minimap_cache.pixels.clear();
+    }
+
+    /// Bytes per classification block.
+    pub fn class_block_size(&self) -> u64 {
+        self.classification_block_size.unwrap_or_else(|| entropy_block_size(self.file_len()))
+    }
+
+    /// Set the hex view font size, clamped to the supported range.
+    pub fn set_hex_font_size(&mut self, size: f32) {
+        self.hex_font_size = size.clamp(MIN_HEX_FONT_SIZE, MAX_HEX_FONT_SIZE);
+    }
+
+    /// Progress text of a requested GPU job: `running` while it runs, otherwise
+    /// which job it is waiting for.
+    pub fn gpu_job_text(&self, job: GpuJob, running: &str) -> String {
+        match self.gpu_job {
+            Some(busy) if busy != job => format!("Waiting for {}...", busy.label()),
+            _ => running.to_string(),
+        }
+    }
+
+    /// Drop the hex view range selection.
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+        self.selecting = false;
+        self.selection_stats = None;
+        self.clipboard_compare = None;
+        self.selection_period = None;
+    }
+
+    /// Look for a repeating record stride in the selection (its first
+    /// `PERIOD_SAMPLE_BYTES`) and keep the result for the status bar.
+    pub fn detect_selection_period(&mut self) {
+        let Some(selection) = self.selection else { return };
+        let Some(file) = &self.file else { return };
+        let start = selection.start().min(file.mapped.len());
+        let len = selection.length().min(tv_core::period::PERIOD_SAMPLE_BYTES as u64).min(file.mapped.len() - start);
+        let period = tv_core::autocorrelate(file.mapped.slice(FileRegion::new(start, len)));
+        self.selection_period = Some((selection, period));
+    }
+
+    /// Compare the selected bytes to `clipboard` parsed as hex and keep the
+    /// verdict for the status bar.
+    pub fn compare_selection_to(&mut self, clipboard: &str) {
+        let Some(selection) = self.selection else { return };
+        let Some(file) = &self.file else { return };
+        let expected = match parse_hex_bytes(clipboard) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.clipboard_compare = Some((selection, format!("Clipboard is not hex: {}", e), true));
+                return;
+            }
+        };
+
+        let start = selection.start().min(file.mapped.len());
+        // One byte past the expected length is enough to tell the lengths differ
+        let len = selection.length().min(expected.len() as u64 + 1).min(file.mapped.len() - start);
+        let actual = file.mapped.slice(FileRegion::new(start, len));
+        let (message, mismatch) = match first_difference(actual, &expected) {
+            None => (format!("Matches clipboard ({} bytes)", expected.len()), false),
+            Some(i) if i < actual.len() && i < expected.len() => (format!(
+                "Differs from clipboard at 0x{:X} (0x{:02X}, expected 0x{:02X})",
+                start + i as u64, actual[i], expected[i]
+            ), true),
+            Some(i) => (format!(
+                "Clipboard has {} bytes, selection {}; equal up to 0x{:X}",
+                expected.len(), selection.length(), start + i as u64
+            ), true),
+        };
+        self.clipboard_compare = Some((selection, message, mismatch));
+    }
+
+    /// Shannon entropy, byte count and most common byte of the selection.
+    ///
+    /// Computed on the CPU from at most `SELECTION_STATS_MAX_BYTES` and cached
+    /// until the selection changes.
+    pub fn selection_stats(&mut self) -> Option<HistogramStats> {
+        let selection = self.selection?;
+        if let Some((cached, stats)) = &self.selection_stats {
+            if *cached == selection {
+                return Some(*stats);
+            }
+        }
+        let file = self.file.as_ref()?;
+        let start = selection.start().min(file.mapped.len());
+        let len = selection.length().min(SELECTION_STATS_MAX_BYTES).min(file.mapped.len() - start);
+        let data = file.mapped.slice(FileRegion::new(start, len));
+        let stats = ByteHistogram::from_data(data).stats();
+        self.selection_stats = Some((selection, stats));
+        Some(stats)
+    }
+
+    /// Ask the app to disassemble from an executable header at `offset`, with
+    /// the architecture read from the header (auto-detect if it names none).
+    pub fn request_disasm_at(&mut self, offset: u64) {
+        let arch = self.file.as_ref().and_then(|f| {
+            let len = DISASM_HEADER_BYTES.min(f.mapped.len().saturating_sub(offset));
+            tv_core::detect_architecture(f.mapped.slice(FileRegion::new(offset, len)))
+        });
+        self.disasm_request = Some((offset, arch));
+        self.select_offset(offset);
+    }
+
+    /// Put the selected bytes (up to `MAX_SEARCH_PATTERN_LEN`) in the search
+    /// box as hex and ask for the Search window. Patterns too long for the
+    /// GPU scanner switch the search to the CPU. Returns the pattern length.
+    pub fn use_selection_as_search(&mut self) -> Option<usize> {
+        let selection = self.selection?;
+        let file = self.file.as_ref()?;
+        let start = selection.start().min(file.mapped.len());
+        let len = selection.length().min(MAX_SEARCH_PATTERN_LEN as u64).min(file.mapped.len() - start);
+        if len == 0 {
+            return None;
+        }
+        let bytes = file.mapped.slice(FileRegion::new(start, len));
+        self.search.query_text = bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ");
+        if bytes.len() > GPU_SEARCH_PATTERN_LEN {
+            self.search.backend = SearchBackend::Cpu;
+        }
+        self.search_window_request = true;
+        Some(bytes.len())
+    }
+
+    /// Run a due live search over the viewport: once the query has been
+    /// unchanged for `LIVE_SEARCH_DEBOUNCE_SECS`, or right away when the
+    /// viewport moves away from the searched range. Returns true while a
+    /// search is waiting for the debounce.
+    pub fn run_live_search(&mut self, now: f64) -> bool {
+        if !self.search.live || self.search.searching {
+            return false;
+        }
+        let Some(file) = &self.file else { return false };
+        let file_len = file.mapped.len();
+        let start = self.viewport.start.min(file_len);
+
+        let moved = self.search.live_scope.is_some_and(|(scope_start, _)| scope_start != start);
+        match self.search.live_edited_at {
+            Some(edited) if now - edited < LIVE_SEARCH_DEBOUNCE_SECS => return true,
+            Some(_) => self.search.live_edited_at = None,
+            None if !moved => return false,
+            None => {}
+        }
+
+        let Ok(pattern) = parse_hex_pattern(&self.search.query_text) else {
+            // Keep whole-file results; stale viewport results would mislead
+            if self.search.live_scope.take().is_some() {
+                self.search.clear_results();
+            }
+            return false;
+        };
+
+        let started = std::time::Instant::now();
+        let end = start.saturating_add(LIVE_SEARCH_BYTES).min(file_len);
+        let data = file.mapped.slice(FileRegion::new(start, end - start));
+        let offsets = tv_core::scan_pattern_cpu(data, &pattern).into_iter().map(|o| start + o).collect();
+        let results = self.search.apply_alignment(offsets);
+
+        self.search.clear_results();
+        self.search.pattern = Some(pattern);
+        self.search.results = Some(results);
+        self.search.search_duration_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+        self.search.used_backend = Some(SearchBackend::Cpu);
+        self.search.live_scope = Some((start, end));
+        false
+    }
+
+    /// Scroll the hex view to `offset` and select its byte.
+    pub fn select_offset(&mut self, offset: u64) {
+        self.viewport.start = (offset / 16) * 16;
+        self.edit.selected_offset = Some(offset);
+        self.edit.input_buffer.clear();
+    }
+
+    /// Pointer value of `width` (4 or 8) bytes at `offset`, in the follow endianness.
+    pub fn pointer_at(&self, offset: u64, width: u64) -> Option<u64> {
+        let file = self.file.as_ref()?;
+        if !matches!(width, 4 | 8) || offset.checked_add(width)? > file.mapped.len() {
+            return None;
+        }
+        let bytes = file.mapped.slice(FileRegion::new(offset, width));
+        let mut buf = [0u8; 8];
+        Some(if self.follow_big_endian {
+            buf[8 - bytes.len()..].copy_from_slice(bytes);
+            u64::from_be_bytes(buf)
+        } else {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u64::from_le_bytes(buf)
+        })
+    }
+
+    /// File offset a pointer value refers to, rebased on `address_base`.
+    /// `None` if it falls outside the file.
+    pub fn pointer_target(&self, value: u64) -> Option<u64> {
+        value.checked_sub(self.address_base).filter(|&target| target < self.file_len())
+    }
+
+    /// Jump to the offset stored in the `width`-byte value at `offset`,
+    /// remembering the current position for [`Self::navigate_back`].
+    pub fn follow_pointer(&mut self, offset: u64, width: u64) -> Option<u64> {
+        let target = self.pointer_target(self.pointer_at(offset, width)?)?;
+        self.push_nav_back();
+        self.select_offset(target);
+        Some(target)
+    }
+
+    /// Find and classify the regions not covered by any deep scan result
+    /// with a known extent (slack space, deleted-file remnants).
+    pub fn find_carve_gaps(&mut self) {
+        let (Some(file), Some(results)) = (&self.file, &self.deep_scan.results) else {
+            return;
+        };
+        let regions: Vec<(u64, u64)> = results.iter()
+            .zip(&self.deep_scan.extents)
+            .filter_map(|(hit, extent)| extent.map(|size| (hit.offset, size)))
+            .collect();
+        let data = file.mapped.slice(FileRegion::new(0, file.mapped.len()));
+        self.deep_scan.gaps = Some(tv_core::find_gaps(data, &regions, GAP_MIN_BYTES));
+    }
+
+    /// Select `offset`, remembering the current position for [`Self::navigate_back`].
+    pub fn jump_to_offset(&mut self, offset: u64) {
+        self.push_nav_back();
+        self.select_offset(offset);
+    }
+
+    /// Scroll the hex view to `range` and make it blink briefly, remembering
+    /// the current position for [`Self::navigate_back`].
+    pub fn locate_range(&mut self, range: Range<u64>) {
+        self.push_nav_back();
+        self.viewport.start = range.start / 16 * 16;
+        self.hex_flash = Some(HexFlash { range, until: None });
+    }
+
+    /// Range of the flash to draw at time `now` (`None` during the off phase
+    /// of the blink). The flash ends [`HEX_FLASH_SECS`] after it is first drawn.
+    pub fn hex_flash_range(&mut self, now: f64) -> Option<Range<u64>> {
+        let flash = self.hex_flash.as_mut()?;
+        let until = *flash.until.get_or_insert(now + HEX_FLASH_SECS);
+        if now >= until {
+            self.hex_flash = None;
+            return None;
+        }
+        // Counted from the first draw, which is always an on phase
+        let elapsed = now + HEX_FLASH_SECS - until;
+        let phase = (elapsed / HEX_FLASH_BLINK_SECS) as u64;
+        phase.is_multiple_of(2).then(|| flash.range.clone())
+    }
+
+    /// Remember the current position for [`Self::navigate_back`].
+    fn push_nav_back(&mut self) {
+        if self.nav_back.len() == NAV_BACK_LIMIT {
+            self.nav_back.remove(0);
+        }
+        self.nav_back.push(self.viewport.start);
+    }
+
+    /// Jump to an entropy landmark relative to the viewport, remembering the
+    /// current position for [`Self::navigate_back`]. Returns the target offset.
+    pub fn jump_to_entropy(&mut self, jump: EntropyJump) -> Option<u64> {
+        use tv_core::entropy::{max_entropy_block, next_entropy_boundary, prev_entropy_boundary, BOUNDARY_THRESHOLD};
+
+        let entropy = self.entropy.as_deref()?;
+        let block_size = entropy_block_size(self.file_len());
+        let current = (self.viewport.start / block_size) as usize;
+        let block = match jump {
+            EntropyJump::NextBoundary => next_entropy_boundary(entropy, current, BOUNDARY_THRESHOLD),
+            EntropyJump::PrevBoundary => prev_entropy_boundary(entropy, current, BOUNDARY_THRESHOLD),
+            EntropyJump::Maximum => max_entropy_block(entropy),
+        }?;
+        let target = block as u64 * block_size;
+        self.push_nav_back();
+        self.viewport.start = target;
+        Some(target)
+    }
+
+    /// Return to the position before the last followed pointer or entropy jump.
+    pub fn navigate_back(&mut self) -> bool {
+        match self.nav_back.pop() {
+            Some(start) => {
+                self.viewport.start = start;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Select a byte for editing, pre-filling the input with its current value.
+    pub fn select_edit_byte(&mut self, offset: u64) {
+        self.edit.selected_offset = Some(offset);
+        self.edit.input_buffer.clear();
+        if let Some(ref file) = self.file {
+            if offset < file.mapped.len() {
+                let current = self.edit.get_edited_byte(offset)
+                    .unwrap_or_else(|| file.mapped.slice(FileRegion::new(offset, 1))[0]);
+                self.edit.input_buffer = format!("{:02X}", current);
+            }
+        }
+    }
+
+    /// Fill the selection with `value`, asking for confirmation first when it
+    /// is longer than `FILL_CONFIRM_BYTES`.
+    pub fn request_fill(&mut self, value: u8) {
+        match self.selection {
+            Some(sel) if sel.length() > FILL_CONFIRM_BYTES => self.edit.fill_confirm = Some(value),
+            Some(_) => self.fill_selection(value),
+            None => {}
+        }
+    }
+
+    /// Stage an edit setting every selected byte to `value`, reading the
+    /// original bytes from the file. Refused above `FILL_MAX_BYTES`.
+    pub fn fill_selection(&mut self, value: u8) {
+        let (Some(sel), Some(file)) = (self.selection, &self.file) else {
+            return;
+        };
+        let file_len = file.mapped.len();
+        let start = sel.start().min(file_len);
+        let len = sel.length().min(file_len - start);
+        if len > FILL_MAX_BYTES {
+            self.edit.status_message = Some((
+                format!("Fill limited to {} MB, select a smaller range", FILL_MAX_BYTES / (1024 * 1024)),
+                true,
+            ));
+            return;
+        }
+
+        let originals = file.mapped.slice(FileRegion::new(start, len));
+        self.edit.fill(start, originals, value);
+        self.edit.status_message = Some((
+            format!("Filled {} byte(s) at 0x{:X} with {:02X}", len, start, value),
+            false,
+        ));
+    }
+
+    /// Move the byte selected for editing by `delta` bytes (±1 wraps across
+    /// rows, ±16 moves a row), staying inside the file. Scrolls so the byte
+    /// stays within the `visible` bytes after the viewport start.
+    pub fn move_edit_byte(&mut self, delta: i64, visible: u64) -> bool {
+        let Some(offset) = self.edit.selected_offset else {
+            return false;
+        };
+        let file_len = self.file_len();
+        let target = offset.saturating_add_signed(delta).min(file_len.saturating_sub(1));
+        if target == offset {
+            return false;
+        }
+        self.select_edit_byte(target);
+
+        let row = target / 16 * 16;
+        if target < self.viewport.start {
+            self.viewport.start = row;
+        } else if target >= self.viewport.start.saturating_add(visible) {
+            self.viewport.start = (row + 16).saturating_sub(visible);
+        }
+        true
+    }
+
+    /// Scroll the hex view so `range` is on screen, assuming `visible` bytes
+    /// are shown. Leaves the viewport alone if the range start is already visible.
+    pub fn reveal_range(&mut self, range: Range<u64>, visible: u64) {
+        let end = self.viewport.start.saturating_add(visible);
+        if range.start < self.viewport.start || range.start >= end {
+            self.viewport.start = range.start / 16 * 16;
+        }
+    }
+
+    /// Open the "Go to offset" dialog, pre-filled from the clipboard if it
+    /// holds an offset.
+    pub fn open_goto(&mut self) {
+        self.goto_open = true;
+        self.goto_text.clear();
+        if let Some(text) = crate::clipboard::read_text() {
+            let text = text.trim();
+            if crate::hex_panel::parse_offset(text).is_some() {
+                self.goto_text = text.to_string();
+            }
+        }
+    }
+
+    /// Offset text to copy to the clipboard (e.g. "0x1A2B").
+    /// Uses the selected byte if there is one, otherwise the viewport start.
+    pub fn offset_clipboard_text(&self) -> String {
+        let offset = self.edit.selected_offset.unwrap_or(self.viewport.start);
+        format!("0x{:X}", offset)
+    }
+
+    /// Byte values at `offset` in file A and file B, `None` past either end.
+    pub fn diff_bytes_at(&self, offset: u64) -> (Option<u8>, Option<u8>) {
+        let byte_at = |file: Option<&LoadedFile>| {
+            file.filter(|f| offset < f.mapped.len())
+                .map(|f| f.mapped.slice(FileRegion::new(offset, 1))[0])
+        };
+        (byte_at(self.file.as_ref()), byte_at(self.diff.file_b.as_ref()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// State with `bytes` open as its file; the file is removed on drop.
+    fn state_with_file(bytes: &[u8]) -> (AppState, tempfile::TempPath) {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, bytes).unwrap();
+        let path = file.into_temp_path();
+        let state = AppState {
+            file: Some(LoadedFile::open(path.to_path_buf()).unwrap()),
+            ..Default::default()
+        };
+        (state, path)
+    }
+
     #[test]
     fn default_state_has_no_file() {
         let state = AppState::default();
@@ -762,6 +2475,712 @@ mod tests {
         assert!(state.search.results.is_none());
     }
 
+    #[test]
+    fn search_alignment_filters_offsets() {
+        let mut search = SearchState::default();
+        let offsets = vec![0, 3, 16, 510, 512, 1024, 1030];
+        assert_eq!(search.apply_alignment(offsets.clone()), offsets);
+
+        search.alignment = Some(512);
+        assert_eq!(search.apply_alignment(offsets.clone()), vec![0, 512, 1024]);
+        search.alignment = Some(16);
+        assert_eq!(search.apply_alignment(offsets.clone()), vec![0, 16, 512, 1024]);
+        // 0 and 1 mean unconstrained
+        search.alignment = Some(0);
+        assert_eq!(search.apply_alignment(offsets.clone()), offsets);
+    }
+
+    #[test]
+    fn classification_block_sizes_and_resampling() {
+        assert_eq!(classification_block_sizes(1 << 20), CLASSIFICATION_BLOCK_SIZES.to_vec());
+        // 4 GB: 64- and 128-byte blocks would be too many
+        assert_eq!(classification_block_sizes(4 << 30).first(), Some(&256));
+
+        let fine = [0, 1, 2, 3, 4, 4, 4, 4];
+        assert_eq!(resample_classes(&fine, 4), vec![0, 2, 4, 4]);
+        assert_eq!(resample_classes(&fine[..2], 4), vec![0, 0, 1, 1]);
+        assert_eq!(resample_classes(&[], 2), vec![PENDING_CLASS; 2]);
+    }
+
+    #[test]
+    fn take_and_restore_tab() {
+        assert!(AppState::default().take_tab().is_none());
+
+        let (mut state, path) = state_with_file(&[0u8; 64]);
+        state.viewport.start = 0x20;
+        state.entropy = Some(vec![1.0]);
+        state.edit.selected_offset = Some(3);
+        state.minimap_cache.base = vec![MinimapRow { max_entropy: 1.0, class_counts: [0; 5] }];
+        state.minimap_cache.cached_block_count = 1;
+        state.minimap_cache.pixels = vec![Color32::RED];
+
+        let tab = state.take_tab().unwrap();
+        assert!(!state.has_file());
+        assert!(state.entropy.is_none());
+        assert!(state.minimap_cache.base.is_empty());
+        // The parked tab keeps its minimap summary for the overview
+        assert!(tab.minimap_cache.base_is_valid(1, false));
+        assert_eq!(state.viewport.start, 0);
+        assert!(state.edit.selected_offset.is_none());
+        assert_eq!(tab.name(), path.file_name().unwrap().to_str().unwrap());
+
+        state.restore_tab(tab);
+        assert_eq!(state.file_len(), 64);
+        assert_eq!(state.viewport.start, 0x20);
+        assert_eq!(state.entropy, Some(vec![1.0]));
+        assert_eq!(state.edit.selected_offset, Some(3));
+        assert!(state.minimap_cache.base_is_valid(1, false));
+        assert!(state.minimap_cache.pixels.is_empty());
+    }
+
+    #[test]
+    fn tabs_keep_their_own_notes() {
+        let (mut state, path) = state_with_file(&[0u8; 16]);
+        state.notes = "first file".to_string();
+        let first = state.take_tab().unwrap();
+        assert!(state.notes.is_empty());
+
+        state.file = Some(LoadedFile::open(path.to_path_buf()).unwrap());
+        state.notes = "second file".to_string();
+        let second = state.take_tab().unwrap();
+
+        state.restore_tab(first);
+        assert_eq!(state.notes, "first file");
+        let first = state.take_tab().unwrap();
+        state.restore_tab(second);
+        assert_eq!(state.notes, "second file");
+        assert_eq!(first.notes, "first file");
+    }
+
+    #[test]
+    fn selection_stats_follow_selection() {
+        let mut data = vec![0u8; 32];
+        data[16..].iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        let (mut state, _file) = state_with_file(&data);
+        assert!(state.selection_stats().is_none());
+
+        // Dragged backwards: anchor after cursor
+        let sel = Selection::new(15, 0);
+        assert_eq!((sel.start(), sel.end(), sel.length()), (0, 15, 16));
+        assert!(sel.contains(7) && !sel.contains(16));
+        state.selection = Some(sel);
+        let stats = state.selection_stats().unwrap();
+        assert_eq!((stats.total, stats.most_common, stats.most_common_count), (16, 0, 16));
+        assert_eq!(stats.entropy, 0.0);
+
+        state.selection = Some(Selection::new(16, 31));
+        assert!((state.selection_stats().unwrap().entropy - 4.0).abs() < 1e-9);
+
+        state.clear_selection();
+        assert!(state.selection_stats().is_none());
+    }
+
+    #[test]
+    fn selection_becomes_search_pattern() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(2048).collect();
+        let (mut state, _file) = state_with_file(&data);
+        state.search.backend = SearchBackend::Gpu;
+        assert_eq!(state.use_selection_as_search(), None);
+
+        // Selected backwards: bytes are still in file order
+        state.selection = Some(Selection::new(0x13, 0x10));
+        assert_eq!(state.use_selection_as_search(), Some(4));
+        assert_eq!(state.search.query_text, "10 11 12 13");
+        assert_eq!(parse_hex_pattern(&state.search.query_text), Ok(vec![0x10, 0x11, 0x12, 0x13]));
+        assert_eq!(state.search.backend, SearchBackend::Gpu);
+        assert!(state.search_window_request);
+
+        // Too long for the GPU: CPU search, capped at the pattern limit
+        state.selection = Some(Selection::new(0, 2047));
+        assert_eq!(state.use_selection_as_search(), Some(MAX_SEARCH_PATTERN_LEN));
+        assert_eq!(state.search.backend, SearchBackend::Cpu);
+        assert!(parse_hex_pattern(&state.search.query_text).is_ok());
+    }
+
+    #[test]
+    fn live_search_is_debounced_and_limited_to_viewport() {
+        let mut data = vec![0u8; LIVE_SEARCH_BYTES as usize * 4];
+        for offset in [0x10, 0x100, LIVE_SEARCH_BYTES as usize + 0x20] {
+            data[offset..offset + 2].copy_from_slice(&[0xCA, 0xFE]);
+        }
+        let (mut state, _file) = state_with_file(&data);
+        state.search.live = true;
+        state.search.query_text = "CA FE".to_string();
+        state.search.query_edited(1.0);
+
+        // Waits for the debounce, then searches the viewport only
+        assert!(state.run_live_search(1.1));
+        assert!(state.search.results.is_none());
+        assert!(!state.run_live_search(1.0 + LIVE_SEARCH_DEBOUNCE_SECS));
+        assert_eq!(state.search.results, Some(vec![0x10, 0x100]));
+        assert_eq!(state.search.live_scope, Some((0, LIVE_SEARCH_BYTES)));
+
+        // Scrolling re-searches the new viewport without waiting
+        state.viewport.start = LIVE_SEARCH_BYTES;
+        assert!(!state.run_live_search(5.0));
+        assert_eq!(state.search.results, Some(vec![LIVE_SEARCH_BYTES + 0x20]));
+
+        // An invalid query drops the viewport results
+        state.search.query_text = "CA F".to_string();
+        state.search.query_edited(6.0);
+        assert!(!state.run_live_search(7.0));
+        assert!(state.search.results.is_none());
+        assert!(state.search.live_scope.is_none());
+
+        // An edit supersedes a running whole-file search
+        state.search.start_search(vec![0xCA, 0xFE]);
+        let generation = state.search.generation;
+        let cancel = Arc::new(AtomicBool::new(false));
+        state.search.cancel = Arc::clone(&cancel);
+        state.search.query_edited(8.0);
+        assert!(!state.search.searching);
+        assert_ne!(state.search.generation, generation);
+        assert!(cancel.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn selection_compares_to_clipboard_hex() {
+        assert_eq!(first_difference(b"abc", b"abc"), None);
+        assert_eq!(first_difference(b"abc", b"abd"), Some(2));
+        assert_eq!(first_difference(b"ab", b"abc"), Some(2));
+
+        let data: Vec<u8> = (0..=255u8).collect();
+        let (mut state, _file) = state_with_file(&data);
+        state.selection = Some(Selection::new(0x10, 0x13));
+        let verdict = |state: &AppState| state.clipboard_compare.as_ref().map(|(_, msg, bad)| (msg.clone(), *bad));
+
+        state.compare_selection_to("10 11 12 13");
+        assert_eq!(verdict(&state), Some(("Matches clipboard (4 bytes)".to_string(), false)));
+        state.compare_selection_to("10 11 FF 13");
+        assert_eq!(verdict(&state).unwrap().0, "Differs from clipboard at 0x12 (0x12, expected 0xFF)");
+        state.compare_selection_to("10 11 12");
+        assert_eq!(verdict(&state).unwrap().0, "Clipboard has 3 bytes, selection 4; equal up to 0x13");
+        state.compare_selection_to("not hex");
+        assert!(verdict(&state).unwrap().1);
+
+        // Longer than a search pattern is fine
+        state.selection = Some(Selection::new(0, 0xFF));
+        state.compare_selection_to(&data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "));
+        assert_eq!(verdict(&state).unwrap().0, "Matches clipboard (256 bytes)");
+        state.clear_selection();
+        assert!(state.clipboard_compare.is_none());
+    }
+
+    #[test]
+    fn disasm_request_reads_architecture_from_hit() {
+        // ELF64 AArch64 header embedded at 0x100
+        let mut data = vec![0u8; 0x200];
+        data[0x100..0x105].copy_from_slice(b"\x7FELF\x02");
+        data[0x112] = 0xB7;
+        let (mut state, _file) = state_with_file(&data);
+        state.request_disasm_at(0x100);
+        assert_eq!(state.disasm_request, Some((0x100, Some(Architecture::Arm64))));
+        assert_eq!(state.edit.selected_offset, Some(0x100));
+        // No recognizable header (or too little data left): auto-detect
+        state.request_disasm_at(0);
+        assert_eq!(state.disasm_request, Some((0, None)));
+        state.request_disasm_at(0x1F0);
+        assert_eq!(state.disasm_request, Some((0x1F0, None)));
+    }
+
+    #[test]
+    fn diff_regions_coalesce_and_step() {
+        let offsets = vec![0x10, 0x11, 0x20, 0x100, 0x104, 0x200];
+        assert_eq!(
+            coalesce_diff_regions(&offsets, DIFF_REGION_GAP),
+            vec![(0x10, 0x20), (0x100, 0x104), (0x200, 0x200)]
+        );
+        assert!(coalesce_diff_regions(&[], DIFF_REGION_GAP).is_empty());
+
+        let mut diff = DiffState { diff_offsets: Some(offsets), ..Default::default() };
+        assert_eq!(diff.step_region(true), Some((0x10, 0x20)));
+        assert_eq!(diff.step_region(true), Some((0x100, 0x104)));
+        assert_eq!((diff.selected_diff, diff.selected_offset), (Some(3), Some(0x100)));
+        assert_eq!(diff.step_region(true), Some((0x200, 0x200)));
+        assert_eq!(diff.step_region(true), None, "no region after the last");
+        assert_eq!(diff.selected_diff, Some(5));
+
+        // Back from the middle of a region goes to its start first
+        diff.selected_diff = Some(4);
+        assert_eq!(diff.step_region(false), Some((0x100, 0x104)));
+        assert_eq!(diff.step_region(false), Some((0x10, 0x20)));
+        assert_eq!(diff.step_region(false), None);
+
+        diff.selected_diff = None;
+        assert_eq!(diff.step_region(false), Some((0x200, 0x200)));
+    }
+
+    #[test]
+    fn diff_signatures_added_removed_moved() {
+        let hit = |name: &str, offset: u64| SignatureHit { offset, name: name.to_string(), magic: vec![] };
+        let a = vec![hit("ZIP", 0x100), hit("PNG", 0x2000), hit("ELF", 0), hit("GZIP", 0x5000)];
+        let b = vec![hit("ELF", 0), hit("ZIP", 0x108), hit("PNG", 0x3000), hit("DER", 0x4000)];
+
+        let diff = diff_signatures(&a, &b, SIGNATURE_MATCH_TOLERANCE);
+        assert_eq!(diff.unchanged, 2, "ELF exact, ZIP within tolerance");
+        assert_eq!(
+            diff.moved,
+            vec![MovedSignature { name: "PNG".to_string(), offset_a: 0x2000, offset_b: 0x3000 }]
+        );
+        assert_eq!(diff.only_in_a.len(), 1);
+        assert_eq!(diff.only_in_a[0].name, "GZIP");
+        assert_eq!(diff.only_in_b.len(), 1);
+        assert_eq!(diff.only_in_b[0].name, "DER");
+
+        // An extra copy in B is reported as added, not moved
+        let b2 = vec![hit("ELF", 0), hit("ELF", 0x800)];
+        let diff = diff_signatures(&a[2..3], &b2, SIGNATURE_MATCH_TOLERANCE);
+        assert!(diff.moved.is_empty());
+        assert_eq!(diff.only_in_b[0].offset, 0x800);
+    }
+
+    #[test]
+    fn deep_scan_nests_hits_inside_carved_ranges() {
+        let hit = |name: &str, offset: u64| SignatureHit { offset, name: name.to_string(), magic: vec![] };
+        let mut scan = DeepScanState {
+            results: Some(vec![
+                hit("ZIP", 0x1000),
+                hit("PNG", 0x1200),
+                hit("JPEG", 0x1400),
+                hit("PNG", 0x1210),
+                hit("ZIP", 0x9000),
+                hit("WAV", 0x1000),
+            ]),
+            // ZIP 0x1000..0x2000 holds a PNG 0x1200..0x1300 holding another PNG
+            extents: vec![Some(0x1000), Some(0x100), None, None, None, Some(0x20)],
+            ..Default::default()
+        };
+        scan.compute_nesting();
+        assert_eq!(scan.parents, vec![None, Some(0), Some(0), Some(1), None, None]);
+
+        // Collapsed by default: only top-level hits
+        scan.rebuild_filtered_indices();
+        assert_eq!(scan.filtered_indices, vec![0, 5, 4]);
+        assert_eq!(scan.child_counts.get(&0), Some(&2));
+
+        scan.toggle_expanded(0);
+        scan.toggle_expanded(1);
+        assert_eq!(scan.filtered_indices, vec![0, 1, 3, 2, 5, 4]);
+        assert_eq!(scan.filtered_depths, vec![0, 1, 2, 1, 0, 0]);
+
+        // Filtering out a parent lifts its children to the nearest visible ancestor
+        scan.filter_text = "png".to_string();
+        scan.rebuild_filtered_indices();
+        assert_eq!(scan.filtered_indices, vec![1, 3]);
+        assert_eq!(scan.filtered_depths, vec![0, 1]);
+
+        scan.flat_list = true;
+        scan.filter_text.clear();
+        scan.rebuild_filtered_indices();
+        assert_eq!(scan.filtered_indices, vec![0, 5, 1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn deep_scan_text_filter_is_debounced_and_keeps_selection() {
+        let hit = |name: &str, offset: u64| SignatureHit { offset, name: name.to_string(), magic: vec![0; 4] };
+        let mut scan = DeepScanState {
+            results: Some(vec![hit("PNG", 0x10), hit("ZIP", 0x20), hit("PNG image", 0x30), hit("JPEG", 0x40)]),
+            flat_list: true,
+            ..Default::default()
+        };
+        scan.rebuild_filtered_indices();
+        scan.selected_result = Some(2);
+        scan.update_highlight();
+
+        // Nothing happens until typing pauses
+        scan.filter_text = "png".to_string();
+        scan.filter_edited(1.0);
+        assert!(scan.apply_text_filter(1.1));
+        assert_eq!(scan.filtered_indices, vec![0, 1, 2, 3]);
+
+        // Narrowed in place; the selected PNG image is still selected
+        assert!(!scan.apply_text_filter(1.0 + SIGNATURE_FILTER_DEBOUNCE_SECS));
+        assert_eq!(scan.filtered_indices, vec![0, 2]);
+        assert_eq!(scan.selected_result, Some(1));
+        assert!(scan.highlight_set.contains(&0x30));
+
+        // A filter the selection no longer matches deselects it
+        scan.filter_text = "png!".to_string();
+        scan.filter_edited(2.0);
+        scan.apply_text_filter(3.0);
+        assert!(scan.filtered_indices.is_empty());
+        assert_eq!(scan.selected_result, None);
+        assert!(scan.highlight_set.is_empty());
+
+        // Widening rebuilds from all results
+        scan.filter_text = "g".to_string();
+        scan.filter_edited(4.0);
+        scan.apply_text_filter(5.0);
+        assert_eq!(scan.filtered_indices, vec![0, 2, 3]);
+        assert_eq!(scan.applied_filter, "g");
+        assert!(!scan.apply_text_filter(6.0));
+    }
+
+    #[test]
+    fn deep_scan_text_filter_narrows_grouped_view() {
+        let hit = |name: &str, offset: u64| SignatureHit { offset, name: name.to_string(), magic: vec![0; 4] };
+        let mut scan = DeepScanState {
+            results: Some(vec![hit("ZIP", 0x1000), hit("PNG", 0x1200), hit("PNG image", 0x1300), hit("JPEG", 0x9000)]),
+            // ZIP 0x1000..0x2000 holds both PNGs
+            extents: vec![Some(0x1000), None, None, None],
+            ..Default::default()
+        };
+        scan.compute_nesting();
+        scan.filter_text = "p".to_string();
+        scan.rebuild_filtered_indices();
+        assert_eq!(scan.filtered_indices, vec![0, 3]);
+        assert_eq!(scan.child_counts.get(&0), Some(&2));
+        scan.selected_result = Some(1);
+
+        // The collapsed ZIP is filtered out, so its PNGs become top-level rows
+        scan.filter_text = "png".to_string();
+        scan.filter_edited(1.0);
+        assert!(!scan.apply_text_filter(2.0));
+        assert_eq!(scan.filtered_indices, vec![1, 2]);
+        assert_eq!(scan.filtered_depths, vec![0, 0]);
+        assert!(scan.child_counts.is_empty());
+        assert_eq!(scan.selected_result, None);
+
+        scan.filter_text = "png i".to_string();
+        scan.filter_edited(3.0);
+        scan.apply_text_filter(4.0);
+        assert_eq!(scan.filtered_indices, vec![2]);
+        assert_eq!(scan.applied_filter, "png i");
+    }
+
+    #[test]
+    fn reveal_range_scrolls_only_when_off_screen() {
+        let mut state = AppState::default();
+        state.viewport.start = 0x100;
+        state.reveal_range(0x180..0x184, 0x200);
+        assert_eq!(state.viewport.start, 0x100);
+        state.reveal_range(0x1234..0x1238, 0x200);
+        assert_eq!(state.viewport.start, 0x1230);
+        state.reveal_range(0x10..0x20, 0x200);
+        assert_eq!(state.viewport.start, 0x10);
+    }
+
+    #[test]
+    fn edits_export_as_patch_and_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let (source, dest) = (dir.path().join("source.bin"), dir.path().join("dest.bin"));
+        std::fs::write(&source, [0x00, 0x11, 0x22, 0x33]).unwrap();
+
+        let mut edit = EditState::default();
+        edit.set_byte(3, 0x33, 0xFF);
+        edit.set_byte(1, 0x11, 0xAB);
+        assert_eq!(
+            edit.patch_text("a.bin"),
+            "# TitanView patch for a.bin\n# 2 byte(s), offset: old -> new\n0x00000001: 11 -> AB\n0x00000003: 33 -> FF\n"
+        );
+
+        assert_eq!(edit.save_as(&source, &dest), Ok(2));
+        assert_eq!(std::fs::read(&dest).unwrap(), [0x00, 0xAB, 0x22, 0xFF]);
+        assert_eq!(std::fs::read(&source).unwrap(), [0x00, 0x11, 0x22, 0x33]);
+        assert_eq!(edit.edit_count(), 2, "edits stay pending for the original");
+        assert!(edit.save_as(&source, &source).is_err());
+    }
+
+    #[test]
+    fn read_only_blocks_edit_mode() {
+        let mut edit = EditState::default();
+        edit.set_byte(0, 0x00, 0x41);
+        assert!(!edit.set_read_only(), "pending edits must not be dropped");
+
+        edit.undo_all();
+        assert!(edit.set_read_only());
+        assert!(!edit.request_enable());
+        assert!(!edit.confirm_dialog_open);
+
+        // Disabling edit mode or switching files keeps the lock
+        edit.clear();
+        assert!(edit.read_only);
+        edit.pending_edits.insert(0, 0x41);
+        assert!(edit.save_to_file(std::path::Path::new("/nonexistent")).unwrap_err().contains("read-only"));
+    }
+
+    #[test]
+    fn checksum_strip_tracks_paths() {
+        let (a, b) = (Path::new("/a.bin"), Path::new("/b.bin"));
+        let mut strip = ChecksumStrip::default();
+        assert_eq!(strip.missing(Some(a), Some(b)), vec![(false, a.to_path_buf()), (true, b.to_path_buf())]);
+
+        strip.set(false, a.to_path_buf(), vec![1, 2, 3]);
+        strip.set(true, b.to_path_buf(), vec![1, 5]);
+        assert!(strip.missing(Some(a), Some(b)).is_empty());
+        assert_eq!(strip.checksums_a(Some(a)), Some(&[1, 2, 3][..]));
+        // Another file in A (e.g. after a tab switch) needs fresh checksums
+        assert_eq!(strip.checksums_a(Some(b)), None);
+        assert_eq!(strip.missing(Some(b), None), vec![(false, b.to_path_buf())]);
+
+        let (sa, sb) = (strip.checksums_a(Some(a)).unwrap(), strip.checksums_b(Some(b)).unwrap());
+        assert!(!checksum_block_differs(sa, sb, 0));
+        assert!(checksum_block_differs(sa, sb, 1));
+        assert!(checksum_block_differs(sa, sb, 2));
+    }
+
+    #[test]
+    fn deep_scan_signature_subset_and_chunk() {
+        let images = SignatureCategory::Images.signatures();
+        assert!(!images.is_empty());
+        assert!(images.iter().all(|s| SignatureCategory::Images.matches(s.name)));
+        assert!(images.len() < SignatureCategory::All.signatures().len());
+        assert_eq!(SignatureCategory::All.signatures().len(), tv_core::signatures::SIGNATURES.len());
+
+        let mut deep_scan = DeepScanState::default();
+        assert_eq!(deep_scan.scan_chunk_bytes(), 64 * 1024 * 1024);
+        deep_scan.scan_chunk_mb = Some(16);
+        assert_eq!(deep_scan.scan_chunk_bytes(), 16 * 1024 * 1024);
+    }
+
+    #[test]
+    fn follow_pointer_and_navigate_back() {
+        // Little-endian 0x40 at 0x10, big-endian 0x1038 at 0x20, out-of-file at 0x30
+        let mut data = vec![0u8; 0x80];
+        data[0x10] = 0x40;
+        data[0x20..0x24].copy_from_slice(&[0x00, 0x00, 0x10, 0x38]);
+        data[0x30..0x38].copy_from_slice(&u64::MAX.to_le_bytes());
+        let (mut state, _file) = state_with_file(&data);
+        assert_eq!(state.pointer_at(0x10, 4), Some(0x40));
+        assert_eq!(state.pointer_at(0x10, 8), Some(0x40));
+        assert_eq!(state.pointer_at(0x10, 2), None);
+        assert_eq!(state.pointer_at(0x7E, 4), None);
+
+        assert_eq!(state.follow_pointer(0x10, 4), Some(0x40));
+        assert_eq!(state.viewport.start, 0x40);
+        assert_eq!(state.follow_pointer(0x30, 8), None);
+        assert_eq!(state.nav_back, vec![0]);
+
+        state.follow_big_endian = true;
+        state.address_base = 0x1000;
+        assert_eq!(state.follow_pointer(0x20, 4), Some(0x38));
+        assert_eq!(state.edit.selected_offset, Some(0x38));
+
+        assert!(state.navigate_back());
+        assert_eq!(state.viewport.start, 0x40);
+        assert!(state.navigate_back());
+        assert_eq!(state.viewport.start, 0);
+        assert!(!state.navigate_back());
+    }
+
+    #[test]
+    fn jump_to_entropy_landmarks() {
+        let (mut state, _file) = state_with_file(&[0u8; 8 * 256]);
+        assert_eq!(state.jump_to_entropy(EntropyJump::Maximum), None);
+
+        state.entropy = Some(vec![4.5, 4.8, 4.6, 7.9, 7.95, 7.9, 0.0, 0.0]);
+        assert_eq!(state.jump_to_entropy(EntropyJump::NextBoundary), Some(3 * 256));
+        assert_eq!(state.jump_to_entropy(EntropyJump::NextBoundary), Some(6 * 256));
+        assert_eq!(state.jump_to_entropy(EntropyJump::NextBoundary), None);
+        assert_eq!(state.jump_to_entropy(EntropyJump::PrevBoundary), Some(3 * 256));
+        assert_eq!(state.jump_to_entropy(EntropyJump::Maximum), Some(4 * 256));
+        assert_eq!(state.viewport.start, 4 * 256);
+
+        assert!(state.navigate_back());
+        assert_eq!(state.viewport.start, 3 * 256);
+    }
+
+    #[test]
+    fn locate_range_scrolls_and_blinks_once() {
+        let mut state = AppState::default();
+        state.locate_range(0x1234..0x1238);
+        assert_eq!(state.viewport.start, 0x1230);
+        assert_eq!(state.nav_back, vec![0]);
+
+        // The timer starts on the first draw, which shows the range
+        assert_eq!(state.hex_flash_range(10.0), Some(0x1234..0x1238));
+        assert_eq!(state.hex_flash.as_ref().and_then(|f| f.until), Some(10.0 + HEX_FLASH_SECS));
+        assert_eq!(state.hex_flash_range(10.3), None, "off phase");
+        assert!(state.hex_flash.is_some());
+        assert_eq!(state.hex_flash_range(10.0 + HEX_FLASH_SECS), None);
+        assert!(state.hex_flash.is_none());
+    }
+
+    #[test]
+    fn select_offset_scrolls_and_selects() {
+        let mut state = AppState::default();
+        state.edit.input_buffer = "4".to_string();
+        state.select_offset(0x1A2B);
+        assert_eq!(state.viewport.start, 0x1A20);
+        assert_eq!(state.edit.selected_offset, Some(0x1A2B));
+        assert!(state.edit.input_buffer.is_empty());
+
+        let hit = SignatureHit { offset: 0x1A2B, name: "PNG".to_string(), magic: vec![0x89, b'P'] };
+        state.deep_scan.selected_result = Some(3);
+        state.deep_scan.highlight_hit(&hit);
+        assert_eq!(state.deep_scan.selected_result, None);
+        assert_eq!(state.deep_scan.highlight_set, HashSet::from([0x1A2B, 0x1A2C]));
+    }
+
+    #[test]
+    fn edit_byte_moves_with_arrows() {
+        let data: Vec<u8> = (0..64u8).collect();
+        let (mut state, _file) = state_with_file(&data);
+        assert!(!state.move_edit_byte(1, 32), "nothing selected");
+
+        state.select_edit_byte(15);
+        assert_eq!(state.edit.input_buffer, "0F");
+        // Right wraps to the next row, Down moves a full row
+        assert!(state.move_edit_byte(1, 32));
+        assert_eq!((state.edit.selected_offset, state.edit.input_buffer.as_str()), (Some(16), "10"));
+        assert!(state.move_edit_byte(16, 32));
+        assert_eq!(state.edit.selected_offset, Some(32));
+        // Leaving the visible rows scrolls the viewport
+        assert_eq!(state.viewport.start, 16);
+
+        // Pending edits are shown instead of the file byte
+        state.edit.set_byte(48, 48, 0xAA);
+        assert!(state.move_edit_byte(16, 32));
+        assert_eq!(state.edit.input_buffer, "AA");
+
+        // Clamped to the file
+        assert!(state.move_edit_byte(16, 32));
+        assert_eq!(state.edit.selected_offset, Some(63));
+        assert!(!state.move_edit_byte(1, 32));
+        state.select_edit_byte(3);
+        assert!(state.move_edit_byte(-16, 32));
+        assert_eq!((state.edit.selected_offset, state.viewport.start), (Some(0), 0));
+    }
+
+    #[test]
+    fn fill_selection_stages_edits() {
+        let data = [0x10u8, 0x00, 0x20, 0x30];
+        let (mut state, _file) = state_with_file(&data);
+        state.request_fill(0x90);
+        assert!(!state.edit.has_changes(), "nothing selected");
+
+        // Bytes already holding the value are not edits
+        state.selection = Some(Selection::new(2, 0));
+        state.request_fill(0);
+        assert_eq!(state.edit.pending_edits, HashMap::from([(0, 0), (2, 0)]));
+        assert_eq!(state.edit.original_bytes.get(&2), Some(&0x20));
+
+        // Refilling keeps the file bytes as originals
+        state.selection = Some(Selection::new(1, 3));
+        state.request_fill(0x90);
+        assert_eq!(state.edit.pending_edits, HashMap::from([(0, 0), (1, 0x90), (2, 0x90), (3, 0x90)]));
+        assert_eq!(state.edit.original_bytes.get(&2), Some(&0x20));
+        assert_eq!(state.edit.fill_confirm, None);
+
+        // Large fills wait for confirmation
+        state.edit.undo_all();
+        state.selection = Some(Selection::new(0, FILL_CONFIRM_BYTES));
+        state.request_fill(0xCC);
+        assert_eq!(state.edit.fill_confirm, Some(0xCC));
+        assert!(!state.edit.has_changes());
+    }
+
+    #[test]
+    fn carve_gaps_skip_results_without_extent() {
+        let mut data = vec![0u8; 4096];
+        data[1024..2048].fill(b'x');
+        let (mut state, _file) = state_with_file(&data);
+        let hit = |offset| SignatureHit { offset, name: "PNG".to_string(), magic: vec![0x89] };
+        state.deep_scan.results = Some(vec![hit(0), hit(2048), hit(3000)]);
+        state.deep_scan.extents = vec![Some(1024), Some(1024), None];
+        state.find_carve_gaps();
+
+        // The hit without an extent does not cover anything
+        let gaps: Vec<_> = state.deep_scan.gaps.iter().flatten().map(|g| (g.offset, g.size, g.kind)).collect();
+        assert_eq!(gaps, [(1024, 1024, tv_core::GapKind::Text), (3072, 1024, tv_core::GapKind::Zeros)]);
+
+        state.deep_scan.clear_results();
+        assert!(state.deep_scan.gaps.is_none());
+    }
+
+    #[test]
+    fn offset_clipboard_text_prefers_selection() {
+        let mut state = AppState::default();
+        state.viewport.start = 0x1000;
+        assert_eq!(state.offset_clipboard_text(), "0x1000");
+
+        state.edit.selected_offset = Some(0x1A2B);
+        assert_eq!(state.offset_clipboard_text(), "0x1A2B");
+    }
+
+    #[test]
+    fn bucket_offsets_clusters() {
+        let offsets = [0, 10, 20, 990, 999];
+        let counts = bucket_offsets(&offsets, 1000, 10);
+        assert_eq!(counts.len(), 10);
+        assert_eq!(counts[0], 3);
+        assert_eq!(counts[9], 2);
+        assert_eq!(counts.iter().sum::<u32>(), 5);
+    }
+
+    #[test]
+    fn bucket_offsets_huge_file() {
+        let file_len = 8u64 << 40;
+        let counts = bucket_offsets(&[file_len - 1, file_len / 2], file_len, 100);
+        assert_eq!(counts[99], 1);
+        assert_eq!(counts[50], 1);
+        assert_eq!(bucket_start(50, file_len, 100), file_len / 2);
+    }
+
+    #[test]
+    fn bucket_offsets_degenerate() {
+        assert!(bucket_offsets(&[1, 2], 100, 0).is_empty());
+        assert_eq!(bucket_offsets(&[1, 2], 0, 4), vec![0; 4]);
+    }
+
+    #[test]
+    fn entropy_series_downsample() {
+        assert_eq!(entropy_block_size(1024), 256);
+        assert_eq!(entropy_block_size(2 * 1024 * 1024 * 1024), 4096);
+
+        let series = [1.0, 7.5, 2.0, 3.0, 0.5, 6.0];
+        assert_eq!(downsample_max(&series, 6, 3), vec![Some(7.5), Some(3.0), Some(6.0)]);
+        // More columns than blocks repeat blocks
+        assert_eq!(downsample_max(&series[..2], 2, 4), vec![Some(1.0), Some(1.0), Some(7.5), Some(7.5)]);
+        // A shorter series ends early on a shared axis
+        assert_eq!(downsample_max(&series[..3], 6, 3), vec![Some(7.5), Some(2.0), None]);
+        assert!(downsample_max(&[], 0, 10).is_empty());
+    }
+
+    #[test]
+    fn sampled_analysis_stride_and_expansion() {
+        assert_eq!(AnalysisMode::Full.stride(), 1);
+        assert!(!AnalysisMode::Full.is_sampled());
+        // 100 GB reads about 1 GB: one block in 100
+        assert_eq!(AnalysisMode::sampled_for(100 << 30).stride(), 100);
+        assert_eq!(AnalysisMode::sampled_for(1 << 20).stride(), 2);
+
+        // Samples of blocks 4, 7 and 10 (stride 3) in a 12-block file
+        assert_eq!(expand_samples(&[1u8, 2, 3], 3, 4, 12), vec![1, 1, 1, 2, 2, 2, 3, 3]);
+        assert_eq!(expand_samples(&[5u8, 6], 1, 0, 10), vec![5, 6]);
+        assert!(expand_samples::<u8>(&[], 4, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn merge_block_chunks_out_of_order() {
+        let mut blocks = Vec::new();
+        // Last chunk first: earlier blocks are pending
+        assert!(merge_block_chunk(&mut blocks, 6, &[4u8, 4], 8, PENDING_CLASS));
+        assert_eq!(blocks, vec![0, 0, 0, 0, 0, 0, 4, 4]);
+        assert!(merge_block_chunk(&mut blocks, 0, &[1, 1, 1], 8, PENDING_CLASS));
+        // Chunk running past the end is truncated
+        assert!(merge_block_chunk(&mut blocks, 5, &[3, 3, 3, 3], 8, PENDING_CLASS));
+        assert_eq!(blocks, vec![1, 1, 1, 0, 0, 3, 3, 3]);
+        // Late chunk from a longer file, or past the end: ignored
+        assert!(!merge_block_chunk(&mut blocks, 8, &[2, 2], 8, PENDING_CLASS));
+        assert!(!merge_block_chunk(&mut blocks, 100, &[2], 4, PENDING_CLASS));
+        assert_eq!(blocks.len(), 8);
+
+        let mut entropy = vec![7.5f32];
+        merge_block_chunk(&mut entropy, 2, &[1.0], 3, PENDING_ENTROPY);
+        assert_eq!(entropy, vec![7.5, PENDING_ENTROPY, 1.0]);
+    }
+
+    #[test]
+    fn gpu_job_text_names_the_running_job() {
+        let mut state = AppState::default();
+        assert_eq!(state.gpu_job_text(GpuJob::DeepScan, "Scanning..."), "Scanning...");
+        state.gpu_job = Some(GpuJob::DeepScan);
+        assert_eq!(state.gpu_job_text(GpuJob::DeepScan, "Scanning..."), "Scanning...");
+        state.gpu_job = Some(GpuJob::Analysis);
+        assert_eq!(state.gpu_job_text(GpuJob::DeepScan, "Scanning..."), "Waiting for entropy analysis...");
+    }
+
     #[test]
     fn parse_hex_basic() {
         assert_eq!(parse_hex_pattern("FF D8 FF").unwrap(), vec![0xFF, 0xD8, 0xFF]);
@@ -791,7 +3210,9 @@ mod tests {
 
     #[test]
     fn parse_hex_too_long() {
-        let long = (0..17).map(|i| format!("{:02X}", i)).collect::<Vec<_>>().join(" ");
-        assert!(parse_hex_pattern(&long).is_err());
+        let hex = |n: usize| (0..n).map(|i| format!("{:02X}", i % 256)).collect::<Vec<_>>().join(" ");
+        assert_eq!(parse_hex_pattern(&hex(17)).unwrap().len(), 17);
+        assert_eq!(parse_hex_pattern(&hex(MAX_SEARCH_PATTERN_LEN)).unwrap().len(), MAX_SEARCH_PATTERN_LEN);
+        assert!(parse_hex_pattern(&hex(MAX_SEARCH_PATTERN_LEN + 1)).is_err());
     }
 }
@@ -2,13 +2,22 @@
 
 use egui::{Context, Color32, RichText, ScrollArea};
 use std::collections::HashSet;
+use std::ops::Range;
 use std::path::PathBuf;
-use crate::state::AppState;
+use crate::file_dialog::{self, RememberDirectory};
+use crate::state::{AppState, SignatureHit};
 use tv_core::{
-    StructTemplate, TemplateResult, FieldValue, apply_template, builtin_templates, FileRegion,
+    StructTemplate, TemplateResult, FieldStatus, FieldValue, apply_template, builtin_templates, FileRegion,
     load_template_from_file, save_template_to_file, example_template_json,
+    template_for_signature,
 };
 
+/// Bytes assumed visible in the hex view when scrolling a hovered field into view.
+const REVEAL_BYTES: u64 = 16 * 32;
+
+/// Signature hits listed in the "Base" menu.
+const MAX_BASE_SIGNATURES: usize = 200;
+
 /// State for the structure inspector window.
 pub struct InspectorState {
     /// Currently selected template index.
@@ -17,10 +26,14 @@ pub struct InspectorState {
     pub offset: u64,
     /// Offset input text.
     pub offset_text: String,
+    /// Why the offset input was rejected (shown next to it).
+    pub offset_error: Option<String>,
     /// Current template result.
     pub result: Option<TemplateResult>,
     /// Selected field index (for highlighting).
     pub selected_field: Option<usize>,
+    /// Field under the mouse (its byte range is highlighted too).
+    pub hovered_field: Option<usize>,
     /// Available templates (builtin + custom).
     pub templates: Vec<StructTemplate>,
     /// Number of builtin templates (first N are builtin).
@@ -35,6 +48,10 @@ pub struct InspectorState {
     pub editor_text: String,
     /// Path to custom templates directory.
     pub custom_templates_dir: Option<PathBuf>,
+    /// Embedded file whose start is the apply offset (template offset 0).
+    pub base_signature: Option<SignatureHit>,
+    /// Distance between records for Prev/Next (0 = template size).
+    pub stride: u64,
 }
 
 impl Default for InspectorState {
@@ -45,8 +62,10 @@ impl Default for InspectorState {
             selected_template: 0,
             offset: 0,
             offset_text: "0".to_string(),
+            offset_error: None,
             result: None,
             selected_field: None,
+            hovered_field: None,
             templates,
             builtin_count,
             auto_detect: true,
@@ -54,6 +73,8 @@ impl Default for InspectorState {
             show_editor: false,
             editor_text: String::new(),
             custom_templates_dir: None,
+            base_signature: None,
+            stride: 0,
         }
     }
 }
@@ -64,36 +85,158 @@ impl InspectorState {
         self.templates.get(self.selected_template)
     }
 
-    /// Get highlight offsets for the hex view.
+    /// Get highlight offsets for the hex view (every parsed field).
     pub fn highlight_offsets(&self) -> HashSet<u64> {
-        let mut set = HashSet::new();
-
-        if let (Some(result), Some(field_idx)) = (&self.result, self.selected_field) {
-            if let Some((field, _)) = result.fields.get(field_idx) {
-                let start = result.base_offset + field.offset as u64;
-                let size = field.field_type.size();
-                for i in 0..size {
-                    set.insert(start + i as u64);
-                }
-            }
+        let field_count = self.result.as_ref().map_or(0, |r| r.fields.len());
+        (0..field_count)
+            .filter_map(|idx| self.field_range(idx))
+            .flatten()
+            .collect()
+    }
+
+    /// Absolute byte range of a parsed field.
+    pub fn field_range(&self, field_idx: usize) -> Option<Range<u64>> {
+        let result = self.result.as_ref()?;
+        let (field, _) = result.fields.get(field_idx)?;
+        let start = result.base_offset + field.offset as u64;
+        Some(start..start + field.field_type.size() as u64)
+    }
+
+    /// Byte range to focus in the hex view: the hovered field, else the selected one.
+    pub fn focused_range(&self) -> Option<Range<u64>> {
+        self.hovered_field.or(self.selected_field).and_then(|idx| self.field_range(idx))
+    }
+
+    /// Bytes between consecutive records: the set stride, else the template size.
+    pub fn record_stride(&self) -> u64 {
+        if self.stride > 0 {
+            self.stride
+        } else {
+            self.current_template().map_or(1, |t| t.size.max(1) as u64)
         }
+    }
 
-        set
+    /// Set the apply offset if it lies inside the file; otherwise keep the
+    /// current one and record the error. Returns true if the offset was set.
+    pub fn set_offset(&mut self, offset: u64, file_len: u64) -> bool {
+        if offset >= file_len {
+            self.offset_error = Some(if file_len == 0 {
+                "File is empty".to_string()
+            } else {
+                format!("Past end of file (last offset 0x{:X})", file_len - 1)
+            });
+            return false;
+        }
+        self.offset = offset;
+        self.offset_error = None;
+        self.base_signature = None;
+        true
+    }
+
+    /// Clamp the apply offset to the last byte of the file, e.g. after a
+    /// session saved for a longer file. Returns false if it had to move.
+    pub fn clamp_offset(&mut self, file_len: u64) -> bool {
+        if self.offset < file_len || (self.offset == 0 && file_len == 0) {
+            return true;
+        }
+        let last = file_len.saturating_sub(1);
+        self.offset_error = Some(format!("Offset 0x{:X} is past the end of the file, moved to 0x{:X}", self.offset, last));
+        self.offset = last;
+        self.offset_text = format!("0x{:X}", last);
+        self.base_signature = None;
+        false
+    }
+
+    /// Move the apply offset to the next (or previous) record inside the
+    /// file. Returns false if there is no such record.
+    pub fn step_record(&mut self, forward: bool, file_len: u64) -> bool {
+        let stride = self.record_stride();
+        let offset = if forward {
+            self.offset.checked_add(stride).filter(|&o| o < file_len)
+        } else {
+            self.offset.checked_sub(stride)
+        };
+        let Some(offset) = offset else { return false };
+        self.offset = offset;
+        self.offset_text = format!("0x{:X}", offset);
+        self.offset_error = None;
+        self.base_signature = None;
+        true
     }
 
     /// Try to auto-detect template from file data.
     pub fn auto_detect_template(&mut self, data: &[u8]) {
+        // Keep the current template if its magic already matches (e.g. ELF32
+        // picked from the file signature, which shares its magic with ELF64)
+        if self.current_template().is_some_and(|t| Self::magic_matches(t, data)) {
+            return;
+        }
+
         // Check magic bytes for each template
-        for (idx, template) in self.templates.iter().enumerate() {
-            if let Some(first_field) = template.fields.first() {
-                if let tv_core::FieldType::Magic(magic) = &first_field.field_type {
-                    if data.len() >= magic.len() && &data[..magic.len()] == magic.as_slice() {
-                        self.selected_template = idx;
-                        return;
-                    }
-                }
+        if let Some(idx) = self.templates.iter().position(|t| Self::magic_matches(t, data)) {
+            self.selected_template = idx;
+        }
+    }
+
+    /// Pre-select the builtin template for the file's signature (quick scan
+    /// results) and point the inspector at offset 0.
+    ///
+    /// Only signatures describing the file itself count: those at offset 0 or
+    /// at their format's fixed offset (e.g. tar). `header` is the start of
+    /// the file. Returns `true` if a template was selected.
+    pub fn auto_select_for_signatures(&mut self, signatures: &[SignatureHit], header: &[u8]) -> bool {
+        if !self.auto_detect {
+            return false;
+        }
+
+        let found = signatures
+            .iter()
+            .filter(|hit| {
+                hit.offset == 0
+                    || tv_core::signatures::SIGNATURES
+                        .iter()
+                        .any(|s| s.name == hit.name && s.fixed_offset == Some(hit.offset))
+            })
+            .find_map(|hit| {
+                let name = template_for_signature(&hit.name, header)?;
+                let idx = self.templates[..self.builtin_count].iter().position(|t| t.name == name)?;
+                Some((idx, hit.name.clone(), name))
+            });
+
+        let Some((idx, signature, name)) = found else {
+            return false;
+        };
+        self.selected_template = idx;
+        self.offset = 0;
+        self.offset_text = "0".to_string();
+        self.clear();
+        self.status_message = Some((format!("Auto-detected {} ({} signature)", name, signature), false));
+        true
+    }
+
+    /// Apply templates relative to an embedded file found by signature
+    /// detection: template offset 0 becomes the start of `hit`, and the
+    /// builtin template for its format is selected if there is one.
+    /// `header` is the data at that start.
+    pub fn set_base_signature(&mut self, hit: &SignatureHit, header: &[u8]) {
+        let start = signature_start(hit);
+        self.offset = start;
+        self.offset_text = format!("0x{:X}", start);
+        self.offset_error = None;
+        if let Some(name) = template_for_signature(&hit.name, header) {
+            if let Some(idx) = self.templates[..self.builtin_count].iter().position(|t| t.name == name) {
+                self.selected_template = idx;
             }
         }
+        self.base_signature = Some(hit.clone());
+        self.clear();
+    }
+
+    fn magic_matches(template: &StructTemplate, data: &[u8]) -> bool {
+        match template.fields.first().map(|f| &f.field_type) {
+            Some(tv_core::FieldType::Magic(magic)) => data.starts_with(magic),
+            _ => false,
+        }
     }
 
     /// Apply current template at current offset.
@@ -113,6 +256,7 @@ impl InspectorState {
     pub fn clear(&mut self) {
         self.result = None;
         self.selected_field = None;
+        self.hovered_field = None;
     }
 
     /// Check if selected template is custom (not builtin).
@@ -366,10 +510,10 @@ impl StructInspector {
         ui.horizontal(|ui| {
             // Load template from file
             if ui.button("Load JSON...").clicked() {
-                if let Some(path) = rfd::FileDialog::new()
+                if let Some(path) = file_dialog::new()
                     .add_filter("JSON", &["json"])
                     .add_filter("All files", &["*"])
-                    .pick_file()
+                    .pick_file_remembered()
                 {
                     match inspector.load_template(&path) {
                         Ok(()) => {}
@@ -382,10 +526,10 @@ impl StructInspector {
 
             // Save current template to file
             if ui.button("Save JSON...").clicked() {
-                if let Some(path) = rfd::FileDialog::new()
+                if let Some(path) = file_dialog::new()
                     .add_filter("JSON", &["json"])
                     .set_file_name("template.json")
-                    .save_file()
+                    .save_file_remembered()
                 {
                     match inspector.save_template(&path) {
                         Ok(()) => {
@@ -429,24 +573,55 @@ impl StructInspector {
                     .font(egui::TextStyle::Monospace)
             );
 
+            let file_len = state.file.as_ref().map_or(0, |f| f.mapped.len());
             if response.changed() {
                 // Parse offset
-                if let Some(offset) = parse_offset(&inspector.offset_text) {
-                    inspector.offset = offset;
+                match parse_offset(&inspector.offset_text) {
+                    Some(offset) => {
+                        inspector.set_offset(offset, file_len);
+                    }
+                    None => inspector.offset_error = Some("Not a valid offset".to_string()),
                 }
             }
 
             // Use current viewport button
-            if ui.button("From Viewport").on_hover_text("Use current hex view offset").clicked() {
-                inspector.offset = state.viewport.start;
+            if ui.button("From Viewport").on_hover_text("Use current hex view offset").clicked()
+                && inspector.set_offset(state.viewport.start, file_len)
+            {
                 inspector.offset_text = format!("0x{:X}", inspector.offset);
             }
 
+            Self::show_base_signature_menu(ui, state, inspector);
+
             // Apply button
             if ui.button("Apply").clicked() {
                 Self::apply_template(state, inspector);
             }
         });
+        if let Some(error) = &inspector.offset_error {
+            ui.label(RichText::new(error).color(Color32::RED).small());
+        }
+
+        // Record table navigation
+        ui.horizontal(|ui| {
+            ui.label("Stride:");
+            ui.add(egui::DragValue::new(&mut inspector.stride).range(0..=u32::MAX as u64))
+                .on_hover_text("Bytes between records (0 = template size)");
+            let file_len = state.file.as_ref().map_or(0, |f| f.mapped.len());
+            let mut step = None;
+            if ui.add_enabled(inspector.offset >= inspector.record_stride(), egui::Button::new("◀ Prev")).clicked() {
+                step = Some(false);
+            }
+            let has_next = inspector.offset.checked_add(inspector.record_stride()).is_some_and(|next| next < file_len);
+            if ui.add_enabled(has_next, egui::Button::new("Next ▶")).clicked() {
+                step = Some(true);
+            }
+            if let Some(forward) = step {
+                if inspector.step_record(forward, file_len) {
+                    Self::apply_template(state, inspector);
+                }
+            }
+        });
 
         // Show template description
         if let Some(template) = inspector.current_template() {
@@ -463,12 +638,26 @@ impl StructInspector {
             ui.horizontal(|ui| {
                 ui.label(RichText::new(&result.template_name).strong());
                 ui.label(format!("@ 0x{:X}", result.base_offset));
+                if let Some(hit) = inspector.base_signature.as_ref().filter(|h| signature_start(h) == result.base_offset) {
+                    ui.label(RichText::new(format!("(embedded {})", hit.name)).weak());
+                }
 
                 if result.magic_ok {
                     ui.label(RichText::new("Magic OK").color(Color32::from_rgb(100, 200, 100)));
                 } else {
                     ui.label(RichText::new("Magic MISMATCH").color(Color32::from_rgb(255, 100, 100)));
                 }
+
+                let (errors, warnings) = (result.error_count(), result.warning_count());
+                if errors > 0 {
+                    ui.label(RichText::new(format!("{} error(s)", errors)).color(Self::status_color(&FieldStatus::Error(String::new()))));
+                }
+                if warnings > 0 {
+                    ui.label(RichText::new(format!("{} warning(s)", warnings)).color(Self::status_color(&FieldStatus::Warning(String::new()))));
+                }
+                if errors == 0 && warnings == 0 {
+                    ui.label(RichText::new("Valid").color(Self::status_color(&FieldStatus::Ok)));
+                }
             });
 
             ui.separator();
@@ -486,9 +675,48 @@ impl StructInspector {
         }
     }
 
+    /// Menu of detected signatures (quick and deep scan) to use as the apply base.
+    fn show_base_signature_menu(ui: &mut egui::Ui, state: &AppState, inspector: &mut InspectorState) {
+        let hits: Vec<&SignatureHit> = state.signatures.iter()
+            .chain(state.deep_scan.results.iter())
+            .flatten()
+            .take(MAX_BASE_SIGNATURES)
+            .collect();
+        let mut chosen: Option<SignatureHit> = None;
+
+        ui.add_enabled_ui(!hits.is_empty(), |ui| {
+            ui.menu_button("From Signature", |ui| {
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for hit in &hits {
+                        if ui.button(format!("{} @ 0x{:X}", hit.name, hit.offset)).clicked() {
+                            chosen = Some((*hit).clone());
+                            ui.close_menu();
+                        }
+                    }
+                });
+            })
+            .response
+            .on_hover_text("Apply relative to an embedded file (template offset 0 = its start)")
+            .on_disabled_hover_text("No signatures detected yet");
+        });
+
+        if let Some(hit) = chosen {
+            if let Some(file) = &state.file {
+                let header = file.mapped.slice_at(signature_start(&hit), 256);
+                inspector.set_base_signature(&hit, header);
+            }
+            Self::apply_template(state, inspector);
+        }
+    }
+
     fn apply_template(state: &AppState, inspector: &mut InspectorState) {
         if let Some(file) = &state.file {
             let file_len = file.mapped.len();
+            if inspector.offset >= file_len {
+                inspector.offset_error = Some(format!("Offset 0x{:X} is past the end of the file", inspector.offset));
+                inspector.result = None;
+                return;
+            }
 
             // Auto-detect if enabled
             if inspector.auto_detect {
@@ -512,22 +740,39 @@ impl StructInspector {
         inspector: &mut InspectorState,
         state: &mut AppState,
     ) {
+        let mut hovered = None;
+
         // Table header
         egui::Grid::new("struct_fields_grid")
-            .num_columns(4)
+            .num_columns(6)
             .spacing([10.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
                 // Header row
+                ui.label(RichText::new("").strong());
                 ui.label(RichText::new("Offset").strong());
                 ui.label(RichText::new("Field").strong());
                 ui.label(RichText::new("Type").strong());
                 ui.label(RichText::new("Value").strong());
+                ui.label("");
                 ui.end_row();
 
                 for (idx, (field, value)) in result.fields.iter().enumerate() {
                     let is_selected = inspector.selected_field == Some(idx);
                     let abs_offset = result.base_offset + field.offset as u64;
+                    let size = field.field_type.size() as u64;
+                    let status = result.status.get(idx).cloned().unwrap_or_default();
+
+                    // Status column
+                    let icon = match status {
+                        FieldStatus::Ok => "✓",
+                        FieldStatus::Warning(_) => "⚠",
+                        FieldStatus::Error(_) => "✗",
+                    };
+                    let status_response = ui.label(RichText::new(icon).color(Self::status_color(&status)));
+                    if let Some(msg) = status.message() {
+                        status_response.on_hover_text(msg);
+                    }
 
                     // Offset column (clickable)
                     let offset_text = format!("0x{:X}", abs_offset);
@@ -537,17 +782,26 @@ impl StructInspector {
                         RichText::new(&offset_text).color(Color32::from_rgb(100, 150, 200))
                     };
 
-                    if ui.add(egui::Label::new(offset_label).sense(egui::Sense::click())).clicked() {
+                    let offset_response = ui.add(egui::Label::new(offset_label).sense(egui::Sense::click()))
+                        .on_hover_text(format!(
+                            "0x{:X}..0x{:X} ({} bytes)",
+                            abs_offset,
+                            abs_offset + size.saturating_sub(1),
+                            size
+                        ));
+                    if offset_response.clicked() {
                         inspector.selected_field = Some(idx);
                         // Navigate hex view to this offset
                         state.viewport.start = (abs_offset / 16) * 16;
                     }
 
-                    // Field name
+                    // Field name (colored by status unless selected)
                     let name_color = if is_selected {
                         Color32::from_rgb(255, 255, 100)
-                    } else {
+                    } else if status.is_ok() {
                         Color32::from_rgb(200, 200, 200)
+                    } else {
+                        Self::status_color(&status)
                     };
                     let name_label = RichText::new(&field.name).color(name_color);
                     let name_response = ui.add(egui::Label::new(name_label).sense(egui::Sense::click()));
@@ -557,6 +811,11 @@ impl StructInspector {
                         state.viewport.start = (abs_offset / 16) * 16;
                     }
 
+                    // Hovering the offset or name highlights the field's bytes
+                    if offset_response.hovered() || name_response.hovered() {
+                        hovered = Some(idx);
+                    }
+
                     // Show tooltip with description
                     if let Some(desc) = &field.description {
                         name_response.on_hover_text(desc);
@@ -569,11 +828,37 @@ impl StructInspector {
                     // Value column
                     let value_str = value.display();
                     let value_color = Self::value_color(value);
-                    ui.label(RichText::new(value_str).color(value_color));
+                    let value_response = ui.label(RichText::new(value_str).color(value_color));
+                    if let Some(msg) = status.message() {
+                        value_response.on_hover_text(msg);
+                    }
+
+                    // Scroll the hex view to the field and flash its bytes
+                    if ui.small_button("⌖").on_hover_text("Locate in hex view").clicked() {
+                        inspector.selected_field = Some(idx);
+                        state.locate_range(abs_offset..abs_offset + size.max(1));
+                    }
 
                     ui.end_row();
                 }
             });
+
+        // Scroll a newly hovered field into view
+        if hovered.is_some() && hovered != inspector.hovered_field {
+            inspector.hovered_field = hovered;
+            if let Some(range) = inspector.focused_range() {
+                state.reveal_range(range, REVEAL_BYTES);
+            }
+        }
+        inspector.hovered_field = hovered;
+    }
+
+    fn status_color(status: &FieldStatus) -> Color32 {
+        match status {
+            FieldStatus::Ok => Color32::from_rgb(100, 200, 100),
+            FieldStatus::Warning(_) => Color32::from_rgb(255, 200, 100),
+            FieldStatus::Error(_) => Color32::from_rgb(255, 100, 100),
+        }
     }
 
     fn type_string(field_type: &tv_core::FieldType) -> String {
@@ -585,6 +870,9 @@ impl StructInspector {
             tv_core::FieldType::Magic(b) => format!("magic[{}]", b.len()),
             tv_core::FieldType::Enum { base, .. } => format!("enum<{}>", base.label()),
             tv_core::FieldType::Flags { base, .. } => format!("flags<{}>", base.label()),
+            tv_core::FieldType::Computed { algo, over } => {
+                format!("{}[0x{:X}..0x{:X}]", algo.label(), over.start, over.end)
+            }
         }
     }
 
@@ -616,11 +904,29 @@ impl StructInspector {
                     Color32::from_rgb(200, 150, 255)
                 }
             }
+            FieldValue::Checksum { stored, computed } => {
+                if computed.as_ref() == Some(stored) {
+                    Color32::from_rgb(100, 255, 100)
+                } else {
+                    Color32::from_rgb(255, 100, 100)
+                }
+            }
             FieldValue::Error(_) => Color32::from_rgb(255, 100, 100),
         }
     }
 }
 
+/// Start of the embedded file of a signature hit: formats with a fixed magic
+/// offset (e.g. tar) start that many bytes before their magic.
+fn signature_start(hit: &SignatureHit) -> u64 {
+    let fixed = tv_core::signatures::SIGNATURES
+        .iter()
+        .find(|s| s.name == hit.name)
+        .and_then(|s| s.fixed_offset)
+        .unwrap_or(0);
+    hit.offset.saturating_sub(fixed)
+}
+
 /// Parse an offset string (hex or decimal).
 fn parse_offset(input: &str) -> Option<u64> {
     let s = input.trim();
@@ -638,3 +944,82 @@ fn parse_offset(input: &str) -> Option<u64> {
         s.parse().ok()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tv_core::{FieldType, PrimitiveType};
+
+    #[test]
+    fn focused_range_prefers_hovered_field() {
+        let template = StructTemplate::builder("Pair")
+            .field("magic", FieldType::Primitive(PrimitiveType::U32))
+            .field("count", FieldType::Primitive(PrimitiveType::U16))
+            .build();
+        let mut inspector = InspectorState {
+            result: Some(apply_template(&template, &[0u8; 6], 0x100)),
+            ..Default::default()
+        };
+
+        assert_eq!(inspector.field_range(0), Some(0x100..0x104));
+        assert_eq!(inspector.field_range(1), Some(0x104..0x106));
+        assert_eq!(inspector.field_range(2), None);
+        assert_eq!(inspector.highlight_offsets().len(), 6);
+
+        assert_eq!(inspector.focused_range(), None);
+        inspector.selected_field = Some(0);
+        assert_eq!(inspector.focused_range(), Some(0x100..0x104));
+        inspector.hovered_field = Some(1);
+        assert_eq!(inspector.focused_range(), Some(0x104..0x106));
+    }
+
+    #[test]
+    fn offset_outside_file_is_rejected_or_clamped() {
+        let mut inspector = InspectorState::default();
+        assert!(inspector.set_offset(0xFF, 0x100));
+        assert_eq!((inspector.offset, inspector.offset_error.as_deref()), (0xFF, None));
+        assert!(!inspector.set_offset(0x100, 0x100));
+        assert_eq!(inspector.offset, 0xFF);
+        assert_eq!(inspector.offset_error.as_deref(), Some("Past end of file (last offset 0xFF)"));
+
+        // A restored offset beyond a shorter file moves to its last byte
+        inspector.offset = 0x5000;
+        assert!(!inspector.clamp_offset(0x20));
+        assert_eq!((inspector.offset, inspector.offset_text.as_str()), (0x1F, "0x1F"));
+        assert!(inspector.offset_error.is_some());
+        assert!(inspector.clamp_offset(0x20));
+    }
+
+    #[test]
+    fn step_record_uses_stride_or_template_size() {
+        let mut inspector = InspectorState::default();
+        let template_size = inspector.current_template().unwrap().size as u64;
+        assert_eq!(inspector.record_stride(), template_size.max(1));
+
+        inspector.stride = 24;
+        inspector.offset = 0x10;
+        assert!(inspector.step_record(true, 0x100));
+        assert_eq!((inspector.offset, inspector.offset_text.as_str()), (0x28, "0x28"));
+        assert!(inspector.step_record(false, 0x100));
+        assert_eq!(inspector.offset, 0x10);
+        // No record before the start or past the end of the file
+        assert!(!inspector.step_record(false, 0x100));
+        assert!(!inspector.step_record(true, 0x20));
+        assert_eq!(inspector.offset, 0x10);
+    }
+
+    #[test]
+    fn base_signature_sets_offset_and_template() {
+        let mut inspector = InspectorState::default();
+        let png = SignatureHit { offset: 0x2000, name: "PNG".to_string(), magic: vec![0x89, b'P', b'N', b'G'] };
+        inspector.set_base_signature(&png, b"\x89PNG\r\n\x1a\n");
+        assert_eq!(inspector.offset, 0x2000);
+        assert_eq!(inspector.offset_text, "0x2000");
+        assert_eq!(inspector.current_template().map(|t| t.name.as_str()), Some("PNG Header"));
+        assert!(inspector.base_signature.is_some());
+
+        // tar magic sits 257 bytes into the header
+        let tar = SignatureHit { offset: 0x1000 + 257, name: "tar (ustar)".to_string(), magic: b"ustar".to_vec() };
+        assert_eq!(signature_start(&tar), 0x1000);
+    }
+}
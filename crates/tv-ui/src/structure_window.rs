@@ -0,0 +1,77 @@
+//! Structure window for PNG and ZIP files.
+//!
+//! Lists the chunks or archive members found on open with their offsets
+//! and sizes; clicking one scrolls the hex view to it and flashes its bytes
+//! (Alt+Left returns).
+
+use egui::{Context, RichText, ScrollArea, Ui};
+use crate::state::AppState;
+
+/// Quick structure window.
+pub struct StructureWindow;
+
+impl StructureWindow {
+    pub fn show(ctx: &Context, state: &mut AppState, visible: &mut bool) {
+        if !*visible {
+            return;
+        }
+
+        egui::Window::new("Structure")
+            .open(visible)
+            .default_size([480.0, 380.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                Self::show_contents(ui, state);
+            });
+    }
+
+    fn show_contents(ui: &mut Ui, state: &mut AppState) {
+        if !state.has_file() {
+            ui.label("Open a file to see its structure.");
+            return;
+        }
+        let Some(structure) = &state.structure else {
+            ui.label("Not a PNG or ZIP file.");
+            return;
+        };
+
+        let file_len = state.file_len();
+        let current = state.viewport.start;
+        let mut jump = None;
+
+        ui.label(format!(
+            "{} file, {} {}(s)",
+            structure.format.label(),
+            structure.entries.len(),
+            structure.format.entry_label()
+        ));
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("structure_grid").striped(true).num_columns(3).show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Offset");
+                ui.strong("Size");
+                ui.end_row();
+
+                for entry in &structure.entries {
+                    let in_file = entry.offset < file_len;
+                    let end = entry.offset.saturating_add(entry.size.max(1));
+                    let selected = in_file && (entry.offset / 16 * 16..end).contains(&current);
+                    let name = RichText::new(&entry.name).monospace();
+                    let response = ui.add_enabled(in_file, egui::SelectableLabel::new(selected, name));
+                    if response.on_hover_text("Show in the hex view").clicked() {
+                        jump = Some(entry.offset..end.min(file_len));
+                    }
+                    ui.monospace(format!("0x{:X}", entry.offset));
+                    ui.monospace(format!("0x{:X}", entry.size));
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(range) = jump {
+            state.locate_range(range);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Text view window.
+//!
+//! Renders the bytes at the top of the hex view as text in a chosen encoding,
+//! for reading strings and text-heavy regions more than 16 bytes at a time.
+//! Lines wrap at a fixed width or at words to fit the window; an optional
+//! gutter shows the file offset of each line, and clicking a character
+//! selects its byte in the hex view.
+
+use std::ops::Range;
+
+use egui::{Context, FontId, Sense, ScrollArea, Ui};
+use tv_core::{decode_text, decode_text_offsets, FileRegion, TextEncoding};
+use crate::state::AppState;
+
+/// Bytes decoded from the viewport start.
+pub const TEXT_VIEW_BYTES: u64 = 4096;
+
+const FONT_SIZE: f32 = 13.0;
+
+/// How decoded lines are broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextWrap {
+    /// Break at word boundaries to fit the window width.
+    #[default]
+    Window,
+    /// Break every `wrap_width` characters.
+    Fixed,
+}
+
+/// State for the text view window.
+pub struct TextViewState {
+    pub encoding: TextEncoding,
+    pub wrap: TextWrap,
+    /// Line length in characters for [`TextWrap::Fixed`].
+    pub wrap_width: usize,
+    /// Show the file offset of each line.
+    pub show_offsets: bool,
+    /// Start of the decoded region in the last frame.
+    start: u64,
+    /// Region start kept while the hex view stays at the given viewport
+    /// start (after a click moved it), so the text does not jump.
+    held: Option<(u64, u64)>,
+}
+
+impl Default for TextViewState {
+    fn default() -> Self {
+        Self {
+            encoding: TextEncoding::default(),
+            wrap: TextWrap::default(),
+            wrap_width: 80,
+            show_offsets: true,
+            start: 0,
+            held: None,
+        }
+    }
+}
+
+impl TextViewState {
+    /// Keep decoding from the current region while the hex view stays at
+    /// `viewport_start` (call after navigating from a click).
+    pub fn hold(&mut self, viewport_start: u64) {
+        self.held = Some((self.start, viewport_start));
+    }
+}
+
+/// Break `chars` into display lines of at most `width` characters, as
+/// ranges into `chars`. Newlines end a line and are not part of it. With
+/// `word_wrap`, long lines break after the last space that fits.
+fn wrap_lines(chars: &[(usize, char)], width: usize, word_wrap: bool) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    // Index just past the last space on the current line
+    let mut after_space: Option<usize> = None;
+    for (i, &(_, c)) in chars.iter().enumerate() {
+        if c == '\n' {
+            lines.push(line_start..i);
+            line_start = i + 1;
+            after_space = None;
+            continue;
+        }
+        if i - line_start == width {
+            let end = match after_space {
+                Some(end) if word_wrap && end > line_start => end,
+                _ => i,
+            };
+            lines.push(line_start..end);
+            line_start = end;
+            after_space = chars[end..i].iter().rposition(|&(_, c)| c == ' ').map(|p| end + p + 1);
+        }
+        if c == ' ' {
+            after_space = Some(i + 1);
+        }
+    }
+    lines.push(line_start..chars.len());
+    lines
+}
+
+/// Decoded text view of the current viewport region.
+pub struct TextViewWindow;
+
+impl TextViewWindow {
+    /// Show the text view. Returns the file offset of a clicked character.
+    pub fn show(ctx: &Context, state: &AppState, text_view: &mut TextViewState, visible: &mut bool) -> Option<u64> {
+        if !*visible {
+            return None;
+        }
+
+        let mut clicked = None;
+        egui::Window::new("Text View")
+            .open(visible)
+            .default_size([480.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                clicked = Self::show_contents(ui, state, text_view);
+            });
+        clicked
+    }
+
+    fn show_contents(ui: &mut Ui, state: &AppState, text_view: &mut TextViewState) -> Option<u64> {
+        let Some(file) = &state.file else {
+            ui.label("Open a file to view it as text.");
+            return None;
+        };
+
+        // Only the region at the top of the hex view is decoded, every frame
+        let file_len = file.mapped.len();
+        let start = match text_view.held {
+            Some((held_start, viewport)) if viewport == state.viewport.start => held_start,
+            _ => {
+                text_view.held = None;
+                state.viewport.start
+            }
+        }.min(file_len);
+        text_view.start = start;
+        let len = TEXT_VIEW_BYTES.min(file_len - start);
+        let data = file.mapped.slice(FileRegion::new(start, len));
+
+        ui.horizontal(|ui| {
+            ui.label("Encoding:");
+            egui::ComboBox::from_id_salt("text_view_encoding")
+                .selected_text(text_view.encoding.label())
+                .show_ui(ui, |ui| {
+                    for encoding in TextEncoding::ALL {
+                        ui.selectable_value(&mut text_view.encoding, encoding, encoding.label());
+                    }
+                });
+            ui.weak(format!("0x{:X} - 0x{:X}", start, start + len));
+            if ui.button("Copy").clicked() {
+                ui.ctx().copy_text(decode_text(data, text_view.encoding));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Wrap:");
+            ui.selectable_value(&mut text_view.wrap, TextWrap::Window, "Words")
+                .on_hover_text("Break at spaces to fit the window");
+            ui.selectable_value(&mut text_view.wrap, TextWrap::Fixed, "Fixed");
+            if text_view.wrap == TextWrap::Fixed {
+                ui.add(egui::DragValue::new(&mut text_view.wrap_width).range(8..=512).suffix(" chars"));
+            }
+            ui.separator();
+            ui.checkbox(&mut text_view.show_offsets, "Offsets");
+        });
+        ui.separator();
+
+        let chars = decode_text_offsets(data, text_view.encoding);
+        let font = FontId::monospace(FONT_SIZE);
+        let char_width = ui.fonts(|f| f.glyph_width(&font, '0'));
+        let row_height = ui.fonts(|f| f.row_height(&font));
+        let gutter_width = if text_view.show_offsets { char_width * 10.0 } else { 0.0 };
+        let width = match text_view.wrap {
+            TextWrap::Window => ((ui.available_width() - gutter_width - 16.0) / char_width) as usize,
+            TextWrap::Fixed => text_view.wrap_width,
+        };
+        let lines = wrap_lines(&chars, width.max(8), text_view.wrap == TextWrap::Window);
+
+        // Character holding the byte selected in the hex view
+        let selected = state.edit.selected_offset
+            .filter(|&offset| offset >= start && offset < start + len)
+            .map(|offset| chars.partition_point(|&(o, _)| o as u64 <= offset - start))
+            .and_then(|after| after.checked_sub(1));
+
+        let text_color = ui.visuals().text_color();
+        let weak_color = ui.visuals().weak_text_color();
+        let highlight = ui.visuals().selection.bg_fill;
+
+        let mut clicked = None;
+        ScrollArea::both().auto_shrink([false, false]).show(ui, |ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            for line in &lines {
+                let text: String = chars[line.clone()].iter()
+                    .map(|&(_, c)| if c == '\t' { ' ' } else { c })
+                    .collect();
+                let size = egui::vec2(gutter_width + line.len().max(1) as f32 * char_width, row_height);
+                let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+                if !ui.is_rect_visible(rect) {
+                    continue;
+                }
+                let painter = ui.painter();
+                let text_left = rect.left() + gutter_width;
+
+                if text_view.show_offsets {
+                    let offset = chars.get(line.start).filter(|_| !line.is_empty())
+                        .map(|&(o, _)| format!("{:08X}", start + o as u64))
+                        .unwrap_or_default();
+                    painter.text(rect.left_top(), egui::Align2::LEFT_TOP, offset, font.clone(), weak_color);
+                }
+                if let Some(index) = selected.filter(|i| line.contains(i)) {
+                    let x = text_left + (index - line.start) as f32 * char_width;
+                    let cell = egui::Rect::from_min_size(egui::pos2(x, rect.top()), egui::vec2(char_width, row_height));
+                    painter.rect_filled(cell, 0.0, highlight);
+                }
+                painter.text(egui::pos2(text_left, rect.top()), egui::Align2::LEFT_TOP, text, font.clone(), text_color);
+
+                if response.clicked() {
+                    let column = response.interact_pointer_pos()
+                        .map(|pos| pos.x - text_left)
+                        .filter(|&x| x >= 0.0)
+                        .map(|x| (x / char_width) as usize);
+                    if let Some(&(offset, _)) = column.and_then(|col| chars[line.clone()].get(col)) {
+                        clicked = Some(start + offset as u64);
+                    }
+                }
+                if response.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
+            }
+        });
+        clicked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str, width: usize, word_wrap: bool) -> Vec<String> {
+        let chars: Vec<(usize, char)> = text.chars().enumerate().collect();
+        wrap_lines(&chars, width, word_wrap)
+            .into_iter()
+            .map(|range| chars[range].iter().map(|&(_, c)| c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wraps_at_fixed_width_or_words() {
+        assert_eq!(lines("abcdefgh", 3, false), ["abc", "def", "gh"]);
+        assert_eq!(lines("hello big world", 8, false), ["hello bi", "g world"]);
+        assert_eq!(lines("hello big world", 8, true), ["hello ", "big ", "world"]);
+        // A word longer than the line is cut
+        assert_eq!(lines("abcdefghij klm", 4, true), ["abcd", "efgh", "ij ", "klm"]);
+        assert_eq!(lines("ab\ncd\n", 8, true), ["ab", "cd", ""]);
+        assert_eq!(lines("", 8, true), [""]);
+    }
+}
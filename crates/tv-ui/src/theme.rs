@@ -0,0 +1,240 @@
+//! Color theme for the hex view, highlights and block classification.
+
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use tv_core::BlockClass;
+
+/// Built-in theme presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    /// Okabe-Ito palette, distinguishable with common color vision deficiencies.
+    Colorblind,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 2] = [ThemePreset::Dark, ThemePreset::Colorblind];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Dark",
+            ThemePreset::Colorblind => "Colorblind-friendly",
+        }
+    }
+}
+
+/// Foreground/background pair for highlighted bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HighlightColors {
+    #[serde(with = "color_hex")]
+    pub fg: Color32,
+    #[serde(with = "color_hex")]
+    pub bg: Color32,
+}
+
+impl HighlightColors {
+    pub const fn new(fg: Color32, bg: Color32) -> Self {
+        Self { fg, bg }
+    }
+
+    /// (foreground, background) tuple, as used by the hex view renderers.
+    pub fn pair(&self) -> (Color32, Color32) {
+        (self.fg, self.bg)
+    }
+}
+
+/// Colors used by the hex view and minimap.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Preset this theme was derived from.
+    pub preset: ThemePreset,
+    /// Offset column text.
+    #[serde(with = "color_hex")]
+    pub offset_text: Color32,
+    /// Hex column text.
+    #[serde(with = "color_hex")]
+    pub hex_text: Color32,
+    /// ASCII column text.
+    #[serde(with = "color_hex")]
+    pub ascii_text: Color32,
+    /// Search results.
+    pub search: HighlightColors,
+    /// Deep scan signature matches.
+    pub deep_scan: HighlightColors,
+    /// Struct inspector fields.
+    pub inspector: HighlightColors,
+    /// Struct inspector field under the mouse (or selected).
+    pub inspector_focus: HighlightColors,
+    /// Differing bytes in diff mode.
+    pub diff: HighlightColors,
+    /// Modified bytes in edit mode.
+    pub edit: HighlightColors,
+    /// Selected byte in edit mode.
+    pub selected: HighlightColors,
+    /// Minimap hue per block class (indexed by `BlockClass as u8`).
+    #[serde(with = "color_hex_list")]
+    pub class_colors: [Color32; 5],
+    /// Subtle hex view background per block class.
+    #[serde(with = "color_hex_list")]
+    pub class_backgrounds: [Color32; 5],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Default dark theme.
+    pub fn dark() -> Self {
+        Self {
+            preset: ThemePreset::Dark,
+            offset_text: Color32::from_rgb(100, 140, 180),
+            hex_text: Color32::from_rgb(220, 220, 220),
+            ascii_text: Color32::from_rgb(160, 200, 140),
+            search: HighlightColors::new(Color32::from_rgb(255, 255, 80), Color32::from_rgb(50, 50, 0)),
+            deep_scan: HighlightColors::new(Color32::from_rgb(80, 255, 255), Color32::from_rgb(0, 50, 50)),
+            inspector: HighlightColors::new(Color32::from_rgb(255, 150, 255), Color32::from_rgb(50, 0, 50)),
+            inspector_focus: HighlightColors::new(Color32::WHITE, Color32::from_rgb(140, 40, 140)),
+            diff: HighlightColors::new(Color32::from_rgb(255, 100, 100), Color32::from_rgb(80, 0, 0)),
+            edit: HighlightColors::new(Color32::from_rgb(255, 100, 100), Color32::from_rgb(80, 0, 0)),
+            selected: HighlightColors::new(Color32::from_rgb(255, 255, 100), Color32::from_rgb(80, 80, 0)),
+            class_colors: [
+                Color32::from_rgb(77, 89, 128),  // Zeros: grey-blue
+                Color32::from_rgb(51, 217, 77),  // ASCII: green
+                Color32::from_rgb(77, 128, 242), // UTF-8: blue
+                Color32::from_rgb(230, 166, 38), // Binary: amber
+                Color32::from_rgb(242, 38, 26),  // High entropy: red
+            ],
+            class_backgrounds: [
+                Color32::from_rgba_premultiplied(40, 50, 70, 60),
+                Color32::from_rgba_premultiplied(30, 80, 40, 60),
+                Color32::from_rgba_premultiplied(40, 60, 100, 60),
+                Color32::from_rgba_premultiplied(90, 65, 20, 60),
+                Color32::from_rgba_premultiplied(100, 25, 15, 60),
+            ],
+        }
+    }
+
+    /// Colorblind-friendly theme based on the Okabe-Ito palette.
+    pub fn colorblind() -> Self {
+        Self {
+            preset: ThemePreset::Colorblind,
+            offset_text: Color32::from_rgb(86, 180, 233),
+            hex_text: Color32::from_rgb(220, 220, 220),
+            ascii_text: Color32::from_rgb(200, 200, 200),
+            search: HighlightColors::new(Color32::from_rgb(240, 228, 66), Color32::from_rgb(60, 56, 0)),
+            deep_scan: HighlightColors::new(Color32::from_rgb(86, 180, 233), Color32::from_rgb(0, 40, 70)),
+            inspector: HighlightColors::new(Color32::from_rgb(204, 121, 167), Color32::from_rgb(60, 20, 45)),
+            inspector_focus: HighlightColors::new(Color32::WHITE, Color32::from_rgb(150, 70, 115)),
+            diff: HighlightColors::new(Color32::from_rgb(230, 159, 0), Color32::from_rgb(70, 45, 0)),
+            edit: HighlightColors::new(Color32::from_rgb(213, 94, 0), Color32::from_rgb(70, 30, 0)),
+            selected: HighlightColors::new(Color32::WHITE, Color32::from_rgb(0, 90, 140)),
+            class_colors: [
+                Color32::from_rgb(120, 120, 120), // Zeros: grey
+                Color32::from_rgb(0, 158, 115),   // ASCII: bluish green
+                Color32::from_rgb(86, 180, 233),  // UTF-8: sky blue
+                Color32::from_rgb(230, 159, 0),   // Binary: orange
+                Color32::from_rgb(213, 94, 0),    // High entropy: vermillion
+            ],
+            class_backgrounds: [
+                Color32::from_rgba_premultiplied(45, 45, 45, 60),
+                Color32::from_rgba_premultiplied(0, 70, 50, 60),
+                Color32::from_rgba_premultiplied(30, 65, 85, 60),
+                Color32::from_rgba_premultiplied(85, 60, 0, 60),
+                Color32::from_rgba_premultiplied(90, 40, 0, 60),
+            ],
+        }
+    }
+
+    /// Theme for a preset.
+    pub fn from_preset(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark => Self::dark(),
+            ThemePreset::Colorblind => Self::colorblind(),
+        }
+    }
+
+    /// Minimap hue for a block class.
+    pub fn class_color(&self, class: BlockClass) -> Color32 {
+        self.class_colors[class as usize]
+    }
+
+    /// Hex view background for a raw block class id.
+    pub fn class_bg(&self, class_id: u8) -> Color32 {
+        self.class_backgrounds[BlockClass::from_u8(class_id) as usize]
+    }
+}
+
+/// Serialize colors as `#rrggbbaa` strings.
+mod color_hex {
+    use egui::Color32;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color32, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&color.to_hex())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Color32, D::Error> {
+        let text = String::deserialize(d)?;
+        Color32::from_hex(&text).map_err(|e| serde::de::Error::custom(format!("invalid color {}: {:?}", text, e)))
+    }
+}
+
+/// Serialize a fixed list of colors as `#rrggbbaa` strings.
+mod color_hex_list {
+    use egui::Color32;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(colors: &[Color32; N], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(colors.iter().map(|c| c.to_hex()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[Color32; N], D::Error> {
+        let texts = Vec::<String>::deserialize(d)?;
+        if texts.len() != N {
+            return Err(serde::de::Error::invalid_length(texts.len(), &"one color per block class"));
+        }
+        let mut colors = [Color32::TRANSPARENT; N];
+        for (color, text) in colors.iter_mut().zip(&texts) {
+            *color = Color32::from_hex(text)
+                .map_err(|e| serde::de::Error::custom(format!("invalid color {}: {:?}", text, e)))?;
+        }
+        Ok(colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_roundtrips_through_json() {
+        let theme = Theme::colorblind();
+        let json = serde_json::to_string(&theme).unwrap();
+        assert!(json.contains("\"#"));
+        let back: Theme = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, theme);
+    }
+
+    #[test]
+    fn theme_missing_fields_use_dark_defaults() {
+        let theme: Theme = serde_json::from_str(r##"{"hex_text": "#ffffffff"}"##).unwrap();
+        assert_eq!(theme.hex_text, Color32::WHITE);
+        assert_eq!(theme.search, Theme::dark().search);
+        assert!(serde_json::from_str::<Theme>(r#"{"hex_text": "white"}"#).is_err());
+    }
+
+    #[test]
+    fn theme_class_lookup() {
+        let theme = Theme::dark();
+        assert_eq!(theme.class_color(BlockClass::Ascii), theme.class_colors[1]);
+        assert_eq!(theme.class_bg(4), theme.class_backgrounds[4]);
+        // Presets differ where it matters
+        assert_ne!(Theme::colorblind().diff, theme.diff);
+        assert_eq!(Theme::from_preset(ThemePreset::Colorblind).preset, ThemePreset::Colorblind);
+    }
+}
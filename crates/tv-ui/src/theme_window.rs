@@ -0,0 +1,123 @@
+//! Theme editor window.
+//!
+//! Edits the colors of the hex view, highlights and block classification,
+//! starting from one of the built-in presets.
+
+use egui::{Color32, Context, RichText, Ui};
+use crate::state::AppState;
+use crate::theme::{HighlightColors, Theme, ThemePreset};
+
+/// Block class names, in `BlockClass` order.
+const CLASS_NAMES: [&str; 5] = ["Zeros", "ASCII", "UTF-8", "Binary", "High entropy"];
+
+/// Theme editor window.
+pub struct ThemeWindow;
+
+impl ThemeWindow {
+    /// Show the theme editor. Returns true if the theme was changed.
+    pub fn show(ctx: &Context, state: &mut AppState, visible: &mut bool) -> bool {
+        if !*visible {
+            return false;
+        }
+
+        let mut changed = false;
+        egui::Window::new("Theme")
+            .open(visible)
+            .default_size([360.0, 480.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                changed = Self::show_contents(ui, &mut state.theme);
+            });
+
+        if changed {
+            // Minimap pixels are cached with the old class colors; the block
+            // summary doesn't depend on the theme
+            state.minimap_cache.pixels.clear();
+        }
+        changed
+    }
+
+    fn show_contents(ui: &mut Ui, theme: &mut Theme) -> bool {
+        let before = theme.clone();
+
+        ui.horizontal(|ui| {
+            ui.label("Preset:");
+            for preset in ThemePreset::ALL {
+                if ui.selectable_label(theme.preset == preset, preset.label()).clicked() {
+                    *theme = Theme::from_preset(preset);
+                }
+            }
+        });
+        if ui.button("Reset to preset").clicked() {
+            *theme = Theme::from_preset(theme.preset);
+        }
+
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.label(RichText::new("Text").strong());
+            egui::Grid::new("theme_text_grid")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    color_row(ui, "Offset", &mut theme.offset_text);
+                    color_row(ui, "Hex", &mut theme.hex_text);
+                    color_row(ui, "ASCII", &mut theme.ascii_text);
+                });
+
+            ui.add_space(8.0);
+            ui.label(RichText::new("Highlights").strong());
+            egui::Grid::new("theme_highlight_grid")
+                .num_columns(4)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.weak("");
+                    ui.weak("Text");
+                    ui.weak("Background");
+                    ui.weak("Preview");
+                    ui.end_row();
+                    highlight_row(ui, "Search", &mut theme.search);
+                    highlight_row(ui, "Deep scan", &mut theme.deep_scan);
+                    highlight_row(ui, "Inspector", &mut theme.inspector);
+                    highlight_row(ui, "Inspector focus", &mut theme.inspector_focus);
+                    highlight_row(ui, "Diff", &mut theme.diff);
+                    highlight_row(ui, "Modified", &mut theme.edit);
+                    highlight_row(ui, "Selected", &mut theme.selected);
+                });
+
+            ui.add_space(8.0);
+            ui.label(RichText::new("Block classes").strong());
+            egui::Grid::new("theme_class_grid")
+                .num_columns(3)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.weak("");
+                    ui.weak("Minimap");
+                    ui.weak("Hex tint");
+                    ui.end_row();
+                    for (i, name) in CLASS_NAMES.iter().enumerate() {
+                        ui.label(*name);
+                        ui.color_edit_button_srgba(&mut theme.class_colors[i]);
+                        ui.color_edit_button_srgba(&mut theme.class_backgrounds[i]);
+                        ui.end_row();
+                    }
+                });
+        });
+
+        *theme != before
+    }
+}
+
+fn color_row(ui: &mut Ui, label: &str, color: &mut Color32) {
+    ui.label(label);
+    ui.color_edit_button_srgba(color);
+    ui.end_row();
+}
+
+fn highlight_row(ui: &mut Ui, label: &str, colors: &mut HighlightColors) {
+    ui.label(label);
+    ui.color_edit_button_srgba(&mut colors.fg);
+    ui.color_edit_button_srgba(&mut colors.bg);
+    ui.label(RichText::new(" 4D 5A 90 ").monospace().color(colors.fg).background_color(colors.bg));
+    ui.end_row();
+}
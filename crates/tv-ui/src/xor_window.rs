@@ -0,0 +1,165 @@
+//! Single-byte XOR brute-force window.
+//!
+//! Scores all 256 XOR keys on the selected bytes and previews the decoded
+//! data for a chosen key.
+
+use egui::{Color32, Context, RichText, ScrollArea, Ui};
+use tv_core::{brute_single_byte, xor_decode, FileRegion};
+use crate::state::AppState;
+
+/// Largest region analyzed (longer selections are truncated).
+pub const XOR_MAX_BYTES: u64 = 64 * 1024;
+
+/// Bytes analyzed from the viewport start when nothing is selected.
+const XOR_VIEWPORT_BYTES: u64 = 256;
+
+/// Number of best keys listed.
+const XOR_TOP_CANDIDATES: usize = 16;
+
+/// Decoded bytes shown in the preview and the candidate list.
+const PREVIEW_BYTES: usize = 512;
+const CANDIDATE_PREVIEW_CHARS: usize = 40;
+
+/// State for the XOR window.
+#[derive(Default)]
+pub struct XorState {
+    /// Analyzed region (start offset, bytes copied from the file).
+    pub region: Option<(u64, Vec<u8>)>,
+    /// Keys sorted by score, best first.
+    pub candidates: Vec<(u8, f32)>,
+    /// Key whose decoded bytes are previewed.
+    pub selected_key: Option<u8>,
+}
+
+impl XorState {
+    /// Brute-force the selection (or the start of the viewport if nothing
+    /// is selected), truncated to `XOR_MAX_BYTES`.
+    pub fn analyze(&mut self, state: &AppState) {
+        let Some(file) = &state.file else { return };
+        let file_len = file.mapped.len();
+        let (start, len) = match state.selection {
+            Some(sel) => (sel.start(), sel.length().min(XOR_MAX_BYTES)),
+            None => (state.viewport.start, XOR_VIEWPORT_BYTES),
+        };
+        let start = start.min(file_len);
+        let data = file.mapped.slice(FileRegion::new(start, len.min(file_len - start))).to_vec();
+
+        self.candidates = brute_single_byte(&data);
+        self.selected_key = self.candidates.first().map(|&(key, _)| key);
+        self.region = Some((start, data));
+    }
+
+    /// Drop the analysis (e.g. when the file changes).
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Decoded bytes as text, non-printable bytes shown as '.'.
+fn preview_text(data: &[u8]) -> String {
+    data.iter()
+        .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+        .collect()
+}
+
+/// Single-byte XOR brute-force window.
+pub struct XorWindow;
+
+impl XorWindow {
+    pub fn show(ctx: &Context, state: &mut AppState, xor: &mut XorState, visible: &mut bool) {
+        if std::mem::take(&mut state.xor_window_request) {
+            xor.analyze(state);
+            *visible = true;
+        }
+        if !*visible {
+            return;
+        }
+
+        egui::Window::new("XOR Brute Force")
+            .open(visible)
+            .default_size([520.0, 460.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                Self::show_contents(ui, state, xor);
+            });
+    }
+
+    fn show_contents(ui: &mut Ui, state: &mut AppState, xor: &mut XorState) {
+        if !state.has_file() {
+            ui.label("Open a file to analyze.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            let label = if state.selection.is_some() { "Analyze Selection" } else { "Analyze View" };
+            if ui.button(label)
+                .on_hover_text(format!("Try all 256 keys (up to {} KB)", XOR_MAX_BYTES / 1024))
+                .clicked()
+            {
+                xor.analyze(state);
+            }
+            if let Some((start, data)) = &xor.region {
+                ui.weak(format!("0x{:X}, {} byte(s)", start, data.len()));
+            }
+        });
+
+        let Some((start, data)) = &xor.region else {
+            ui.weak("Select bytes in the hex view, then analyze.");
+            return;
+        };
+        let start = *start;
+
+        ui.separator();
+        ui.label(RichText::new("Best keys").strong());
+        ScrollArea::vertical().id_salt("xor_candidates").max_height(220.0).show(ui, |ui| {
+            egui::Grid::new("xor_candidate_grid").striped(true).show(ui, |ui| {
+                for &(key, score) in xor.candidates.iter().take(XOR_TOP_CANDIDATES) {
+                    let selected = xor.selected_key == Some(key);
+                    if ui.selectable_label(selected, RichText::new(format!("0x{:02X}", key)).monospace()).clicked() {
+                        xor.selected_key = Some(key);
+                    }
+                    let color = if score > 0.9 { Color32::from_rgb(100, 220, 100) } else { Color32::GRAY };
+                    ui.label(RichText::new(format!("{:.0}%", score * 100.0)).color(color));
+                    let head = &data[..data.len().min(CANDIDATE_PREVIEW_CHARS)];
+                    ui.label(RichText::new(preview_text(&xor_decode(head, key))).monospace());
+                    ui.end_row();
+                }
+            });
+        });
+
+        let Some(key) = xor.selected_key else { return };
+        let decoded = xor_decode(&data[..data.len().min(PREVIEW_BYTES)], key);
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Decoded with 0x{:02X}", key)).strong());
+            if ui.button("Go to region").clicked() {
+                state.viewport.start = start / 16 * 16;
+            }
+            if ui.button("Copy text").clicked() {
+                ui.ctx().copy_text(preview_text(&xor_decode(data, key)));
+            }
+        });
+        ScrollArea::vertical().id_salt("xor_preview").show(ui, |ui| {
+            for (i, row) in decoded.chunks(16).enumerate() {
+                let hex: String = row.iter().map(|b| format!("{:02X} ", b)).collect();
+                ui.label(RichText::new(format!(
+                    "{:08X}  {:<48} {}",
+                    start + (i * 16) as u64,
+                    hex,
+                    preview_text(row)
+                )).monospace());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_masks_non_printable_bytes() {
+        assert_eq!(preview_text(b"Hi\x00\x7F there\n"), "Hi.. there.");
+    }
+}
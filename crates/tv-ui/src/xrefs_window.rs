@@ -18,6 +18,10 @@ pub struct XRefsState {
     pub filter_calls: bool,
     pub filter_jumps: bool,
     pub filter_data: bool,
+    /// Set when the user asks for the string analysis pass (handled by the app).
+    pub analyze_strings_requested: bool,
+    /// Result of the last string analysis pass.
+    pub analysis_status: Option<String>,
     /// Cached file size for invalidation.
     cached_file_size: u64,
     /// Cached disasm offset for invalidation.
@@ -33,6 +37,8 @@ impl Default for XRefsState {
             filter_calls: true,
             filter_jumps: true,
             filter_data: true,
+            analyze_strings_requested: false,
+            analysis_status: None,
             cached_file_size: 0,
             cached_disasm_offset: u64::MAX,
         }
@@ -44,6 +50,7 @@ impl XRefsState {
     pub fn clear(&mut self) {
         self.table = None;
         self.selected_target = None;
+        self.analysis_status = None;
         self.cached_file_size = 0;
         self.cached_disasm_offset = u64::MAX;
     }
@@ -148,6 +155,16 @@ impl XRefsWindow {
             ui.label(format!("{} total references", table.total_refs));
             ui.separator();
             ui.label(format!("{} unique targets", table.refs_to.len()));
+            ui.separator();
+            if ui.button("Analyze strings")
+                .on_hover_text("Label strings referenced by data xrefs (str_...)")
+                .clicked()
+            {
+                xrefs.analyze_strings_requested = true;
+            }
+            if let Some(ref status) = xrefs.analysis_status {
+                ui.weak(status);
+            }
         });
 
         ui.separator();
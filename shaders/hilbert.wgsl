@@ -184,10 +184,9 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     switch (params.mode) {
         case 0u: {
-            // Entropy mode - use precomputed entropy data
-            let block_idx = file_offset / 256u;
-            if (block_idx < arrayLength(&entropy_data)) {
-                let entropy = entropy_data[block_idx];
+            // Entropy mode - one precomputed value per Hilbert cell
+            if (hilbert_index < arrayLength(&entropy_data)) {
+                let entropy = entropy_data[hilbert_index];
                 color = entropy_to_color(entropy);
             } else {
                 color = vec4<f32>(0.1, 0.1, 0.1, 1.0);
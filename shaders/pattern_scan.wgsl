@@ -47,7 +47,8 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
     }
 
     if matched {
-        // Atomically increment hit counter (stored at results[0])
+        // Atomically increment hit counter (stored at results[0]). It keeps
+        // counting once the buffer is full so the caller gets the true total.
         let idx = atomicAdd(&results[0], 1u);
         // Store the offset (results[1..] holds the offsets)
         if idx < params.max_results {